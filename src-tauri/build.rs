@@ -1,3 +1,15 @@
 fn main() {
+    // Commit the binary was built from, reported by get_app_info
+    let hash = std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=PIXNIB_BUILD_HASH={hash}");
+    println!("cargo:rerun-if-changed=../.git/HEAD");
+
     tauri_build::build()
 }
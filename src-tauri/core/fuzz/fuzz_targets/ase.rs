@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pixnib_core::import;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = import::parse_ase(data, "fuzz");
+});
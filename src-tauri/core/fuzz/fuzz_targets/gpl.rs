@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pixnib_core::import;
+
+fuzz_target!(|text: &str| {
+    let _ = import::parse_gpl(text, "fuzz");
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pixnib_core::color_math;
+
+fuzz_target!(|text: &str| {
+    if let Ok(rgb) = color_math::parse_hex(text) {
+        // Whatever was accepted must survive a trip through the canonical form
+        assert_eq!(color_math::parse_hex(&color_math::to_hex(rgb)), Ok(rgb));
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pixnib_core::{import, share};
+
+// Links pasted into the import box or opened through the URL scheme
+fuzz_target!(|text: &str| {
+    let _ = share::parse_share_url(text);
+    let _ = import::parse_coolors_url(text);
+    let _ = import::lospec_slug(text);
+    let _ = import::parse_lospec(text);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pixnib_core::palette_file;
+
+fuzz_target!(|text: &str| {
+    let _ = palette_file::parse(text);
+});
@@ -0,0 +1,48 @@
+use crate::color_math;
+use serde::Serialize;
+
+/// Mean of several picks and how far apart they were.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AverageStats {
+    pub count: usize,
+    pub hex: String,
+    pub rgb: [u8; 3],
+    /// Population standard deviation of each sRGB channel, 0–255
+    pub std_dev: [f64; 3],
+    /// CIEDE2000 distance of the samples from the mean
+    pub mean_delta_e: f64,
+    pub max_delta_e: f64,
+}
+
+/// Average `samples` per sRGB channel, the same way area picks are averaged.
+/// `None` without samples.
+pub fn average(samples: &[[u8; 3]]) -> Option<AverageStats> {
+    if samples.is_empty() {
+        return None;
+    }
+    let n = samples.len() as f64;
+    let mean: [f64; 3] =
+        std::array::from_fn(|i| samples.iter().map(|s| s[i] as f64).sum::<f64>() / n);
+    let std_dev = std::array::from_fn(|i| {
+        let variance = samples
+            .iter()
+            .map(|s| (s[i] as f64 - mean[i]).powi(2))
+            .sum::<f64>()
+            / n;
+        variance.sqrt()
+    });
+    let rgb = mean.map(|c| c.round() as u8);
+    let distances: Vec<f64> = samples
+        .iter()
+        .map(|&s| color_math::delta_e(rgb, s))
+        .collect();
+    Some(AverageStats {
+        count: samples.len(),
+        hex: color_math::to_hex(rgb),
+        rgb,
+        std_dev,
+        mean_delta_e: distances.iter().sum::<f64>() / n,
+        max_delta_e: distances.iter().copied().fold(0.0, f64::max),
+    })
+}
@@ -0,0 +1,403 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Hsv {
+    pub h: f64,
+    pub s: f64,
+    pub v: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Cmyk {
+    pub c: f64,
+    pub m: f64,
+    pub y: f64,
+    pub k: f64,
+}
+
+/// CIE L*a*b* (D65)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Lab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Oklch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+}
+
+/// Parse `#RRGGBB`, `RRGGBB` or the `#RGB` shorthand.
+pub fn parse_hex(hex: &str) -> Result<[u8; 3], String> {
+    let h = hex.trim().trim_start_matches('#');
+    if !h.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid hex color: {hex}"));
+    }
+    let full = match h.len() {
+        3 => h.chars().flat_map(|c| [c, c]).collect::<String>(),
+        6 => h.to_string(),
+        _ => return Err(format!("Invalid hex color: {hex}")),
+    };
+    let channel = |i: usize| {
+        u8::from_str_radix(&full[i..i + 2], 16).map_err(|_| format!("Invalid hex color: {hex}"))
+    };
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+pub fn to_hex(rgb: [u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2])
+}
+
+/// Hue in degrees, saturation and lightness in percent.
+pub fn rgb_to_hsl(rgb: [u8; 3]) -> Hsl {
+    let [r, g, b] = rgb.map(|v| v as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    let s = if d == 0.0 {
+        0.0
+    } else if l > 0.5 {
+        d / (2.0 - max - min)
+    } else {
+        d / (max + min)
+    };
+    Hsl {
+        h: hue(r, g, b, max, d),
+        s: s * 100.0,
+        l: l * 100.0,
+    }
+}
+
+/// Hue in degrees, saturation and value in percent.
+pub fn rgb_to_hsv(rgb: [u8; 3]) -> Hsv {
+    let [r, g, b] = rgb.map(|v| v as f64 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let d = max - min;
+    Hsv {
+        h: hue(r, g, b, max, d),
+        s: if max == 0.0 { 0.0 } else { d / max * 100.0 },
+        v: max * 100.0,
+    }
+}
+
+/// Naive (profile-free) CMYK, all channels in percent.
+pub fn rgb_to_cmyk(rgb: [u8; 3]) -> Cmyk {
+    let [r, g, b] = rgb.map(|v| v as f64 / 255.0);
+    let k = 1.0 - r.max(g).max(b);
+    if k >= 1.0 {
+        return Cmyk {
+            c: 0.0,
+            m: 0.0,
+            y: 0.0,
+            k: 100.0,
+        };
+    }
+    Cmyk {
+        c: (1.0 - r - k) / (1.0 - k) * 100.0,
+        m: (1.0 - g - k) / (1.0 - k) * 100.0,
+        y: (1.0 - b - k) / (1.0 - k) * 100.0,
+        k: k * 100.0,
+    }
+}
+
+pub fn cmyk_to_rgb(cmyk: Cmyk) -> [u8; 3] {
+    let k = 1.0 - cmyk.k / 100.0;
+    [cmyk.c, cmyk.m, cmyk.y].map(|v| unit_to_u8((1.0 - v / 100.0) * k))
+}
+
+fn hue(r: f64, g: f64, b: f64, max: f64, d: f64) -> f64 {
+    if d == 0.0 {
+        return 0.0;
+    }
+    let h = if max == r {
+        (g - b) / d + if g < b { 6.0 } else { 0.0 }
+    } else if max == g {
+        (b - r) / d + 2.0
+    } else {
+        (r - g) / d + 4.0
+    };
+    h * 60.0
+}
+
+/// sRGB channel to linear light in 0–1.
+pub fn srgb_to_linear(v: u8) -> f64 {
+    let c = v as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f64) -> f64 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn unit_to_u8(v: f64) -> u8 {
+    (v.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Outcome of converting into 8-bit sRGB from a space that can describe
+/// colors sRGB can't show.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GamutMapped {
+    /// Displayable color, each channel clamped into range
+    pub rgb: [u8; 3],
+    /// Channels before clamping on the same 0–255 scale; out of gamut when
+    /// any is below 0 or above 255
+    pub unclipped: [f64; 3],
+    pub clipped: bool,
+}
+
+/// Encode linear-light sRGB, noting channels that don't round into 0–255.
+fn map_linear(linear: [f64; 3]) -> GamutMapped {
+    let unclipped = linear.map(|c| linear_to_srgb(c) * 255.0);
+    GamutMapped {
+        rgb: linear.map(|c| unit_to_u8(linear_to_srgb(c))),
+        unclipped,
+        clipped: unclipped.iter().any(|c| !(-0.5..255.5).contains(c)),
+    }
+}
+
+/// Encode linear-light sRGB, clamping out-of-gamut channels.
+pub fn linear_to_rgb(linear: [f64; 3]) -> [u8; 3] {
+    map_linear(linear).rgb
+}
+
+/// WCAG 2.x relative luminance.
+pub fn relative_luminance(rgb: [u8; 3]) -> f64 {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    0.2126 * r + 0.7152 * g + 0.0722 * b
+}
+
+pub fn contrast_ratio(a: [u8; 3], b: [u8; 3]) -> f64 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    let (hi, lo) = if la >= lb { (la, lb) } else { (lb, la) };
+    (hi + 0.05) / (lo + 0.05)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WcagLevels {
+    pub aa_normal: bool,
+    pub aaa_normal: bool,
+    pub aa_large: bool,
+    pub aaa_large: bool,
+}
+
+pub fn wcag_levels(ratio: f64) -> WcagLevels {
+    WcagLevels {
+        aa_normal: ratio >= 4.5,
+        aaa_normal: ratio >= 7.0,
+        aa_large: ratio >= 3.0,
+        aaa_large: ratio >= 4.5,
+    }
+}
+
+const NEAR_BLACK: [u8; 3] = [0x11, 0x18, 0x27];
+const NEAR_WHITE: [u8; 3] = [0xF9, 0xFA, 0xFB];
+
+/// Black or white text, whichever contrasts more with `bg`. With `tuned`,
+/// the softer near-black/near-white is used when it still passes AA (4.5:1).
+pub fn readable_text_color(bg: [u8; 3], tuned: bool) -> [u8; 3] {
+    let dark = contrast_ratio(bg, [0, 0, 0]) >= contrast_ratio(bg, [255, 255, 255]);
+    let (pure, soft) = if dark {
+        ([0, 0, 0], NEAR_BLACK)
+    } else {
+        ([255, 255, 255], NEAR_WHITE)
+    };
+    if tuned && contrast_ratio(bg, soft) >= 4.5 {
+        soft
+    } else {
+        pure
+    }
+}
+
+pub fn rgb_to_lab(rgb: [u8; 3]) -> Lab {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    // sRGB -> XYZ, normalized to the D65 white point
+    let x = (0.4124564 * r + 0.3575761 * g + 0.1804375 * b) / 0.95047;
+    let y = 0.2126729 * r + 0.7151522 * g + 0.0721750 * b;
+    let z = (0.0193339 * r + 0.119192 * g + 0.9503041 * b) / 1.08883;
+    let f = |t: f64| {
+        if t > 0.008856 {
+            t.cbrt()
+        } else {
+            7.787 * t + 16.0 / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    Lab {
+        l: 116.0 * fy - 16.0,
+        a: 500.0 * (fx - fy),
+        b: 200.0 * (fy - fz),
+    }
+}
+
+/// Convert back to 8-bit sRGB, clamping anything outside the gamut.
+pub fn lab_to_rgb(lab: Lab) -> [u8; 3] {
+    lab_to_rgb_mapped(lab).rgb
+}
+
+/// [`lab_to_rgb`], reporting whether the color had to be clipped.
+pub fn lab_to_rgb_mapped(lab: Lab) -> GamutMapped {
+    let fy = (lab.l + 16.0) / 116.0;
+    let fx = fy + lab.a / 500.0;
+    let fz = fy - lab.b / 200.0;
+    let finv = |t: f64| {
+        if t.powi(3) > 0.008856 {
+            t.powi(3)
+        } else {
+            (t - 16.0 / 116.0) / 7.787
+        }
+    };
+    let (x, y, z) = (finv(fx) * 0.95047, finv(fy), finv(fz) * 1.08883);
+    map_linear([
+        3.2404542 * x - 1.5371385 * y - 0.4985314 * z,
+        -0.969266 * x + 1.8760108 * y + 0.041556 * z,
+        0.0556434 * x - 0.2040259 * y + 1.0572252 * z,
+    ])
+}
+
+pub fn rgb_to_oklab(rgb: [u8; 3]) -> Oklab {
+    let [r, g, b] = rgb.map(srgb_to_linear);
+    let l = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    Oklab {
+        l: 0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        a: 1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        b: 0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    }
+}
+
+/// Convert back to 8-bit sRGB, clamping anything outside the gamut.
+pub fn oklab_to_rgb(lab: Oklab) -> [u8; 3] {
+    oklab_to_rgb_mapped(lab).rgb
+}
+
+/// [`oklab_to_rgb`], reporting whether the color had to be clipped.
+pub fn oklab_to_rgb_mapped(lab: Oklab) -> GamutMapped {
+    let l = (lab.l + 0.3963377774 * lab.a + 0.2158037573 * lab.b).powi(3);
+    let m = (lab.l - 0.1055613458 * lab.a - 0.0638541728 * lab.b).powi(3);
+    let s = (lab.l - 0.0894841775 * lab.a - 1.2914855480 * lab.b).powi(3);
+    map_linear([
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s,
+    ])
+}
+
+pub fn oklab_to_oklch(lab: Oklab) -> Oklch {
+    let c = (lab.a * lab.a + lab.b * lab.b).sqrt();
+    let h = lab.b.atan2(lab.a).to_degrees().rem_euclid(360.0);
+    Oklch { l: lab.l, c, h }
+}
+
+pub fn rgb_to_oklch(rgb: [u8; 3]) -> Oklch {
+    oklab_to_oklch(rgb_to_oklab(rgb))
+}
+
+pub fn oklch_to_oklab(lch: Oklch) -> Oklab {
+    let h = lch.h.to_radians();
+    Oklab {
+        l: lch.l,
+        a: lch.c * h.cos(),
+        b: lch.c * h.sin(),
+    }
+}
+
+pub fn oklch_to_rgb(lch: Oklch) -> [u8; 3] {
+    oklab_to_rgb(oklch_to_oklab(lch))
+}
+
+pub fn oklch_to_rgb_mapped(lch: Oklch) -> GamutMapped {
+    oklab_to_rgb_mapped(oklch_to_oklab(lch))
+}
+
+/// CIEDE2000 color difference between two sRGB colors.
+pub fn delta_e(a: [u8; 3], b: [u8; 3]) -> f64 {
+    ciede2000(rgb_to_lab(a), rgb_to_lab(b))
+}
+
+pub fn ciede2000(l1: Lab, l2: Lab) -> f64 {
+    let c1 = l1.a.hypot(l1.b);
+    let c2 = l2.a.hypot(l2.b);
+    let c_bar = (c1 + c2) / 2.0;
+    let g = 0.5 * (1.0 - (c_bar.powi(7) / (c_bar.powi(7) + 25f64.powi(7))).sqrt());
+    let a1 = l1.a * (1.0 + g);
+    let a2 = l2.a * (1.0 + g);
+    let c1p = a1.hypot(l1.b);
+    let c2p = a2.hypot(l2.b);
+    let hp = |b: f64, a: f64| {
+        if b == 0.0 && a == 0.0 {
+            0.0
+        } else {
+            b.atan2(a).to_degrees().rem_euclid(360.0)
+        }
+    };
+    let h1p = hp(l1.b, a1);
+    let h2p = hp(l2.b, a2);
+
+    let dl = l2.l - l1.l;
+    let dc = c2p - c1p;
+    let dh_angle = if c1p * c2p == 0.0 {
+        0.0
+    } else if (h2p - h1p).abs() <= 180.0 {
+        h2p - h1p
+    } else if h2p - h1p > 180.0 {
+        h2p - h1p - 360.0
+    } else {
+        h2p - h1p + 360.0
+    };
+    let dh = 2.0 * (c1p * c2p).sqrt() * (dh_angle / 2.0).to_radians().sin();
+
+    let l_bar = (l1.l + l2.l) / 2.0;
+    let c_bar_p = (c1p + c2p) / 2.0;
+    let h_bar = if c1p * c2p == 0.0 {
+        h1p + h2p
+    } else if (h1p - h2p).abs() <= 180.0 {
+        (h1p + h2p) / 2.0
+    } else if h1p + h2p < 360.0 {
+        (h1p + h2p + 360.0) / 2.0
+    } else {
+        (h1p + h2p - 360.0) / 2.0
+    };
+    let t = 1.0 - 0.17 * (h_bar - 30.0).to_radians().cos()
+        + 0.24 * (2.0 * h_bar).to_radians().cos()
+        + 0.32 * (3.0 * h_bar + 6.0).to_radians().cos()
+        - 0.20 * (4.0 * h_bar - 63.0).to_radians().cos();
+    let sl = 1.0 + 0.015 * (l_bar - 50.0).powi(2) / (20.0 + (l_bar - 50.0).powi(2)).sqrt();
+    let sc = 1.0 + 0.045 * c_bar_p;
+    let sh = 1.0 + 0.015 * c_bar_p * t;
+    let rt = -2.0
+        * (c_bar_p.powi(7) / (c_bar_p.powi(7) + 25f64.powi(7))).sqrt()
+        * (60.0 * (-((h_bar - 275.0) / 25.0).powi(2)).exp())
+            .to_radians()
+            .sin();
+    ((dl / sl).powi(2) + (dc / sc).powi(2) + (dh / sh).powi(2) + rt * (dc / sc) * (dh / sh)).sqrt()
+}
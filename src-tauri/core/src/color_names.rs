@@ -0,0 +1,1000 @@
+use crate::color_math::{rgb_to_oklab, Oklab};
+use serde::{Deserialize, Serialize};
+
+/// Language color names are given in. English uses the CSS names; the others
+/// use natural color words of the language, including traditional names
+/// that have no CSS equivalent, backed by the CSS names translated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NameLocale {
+    #[default]
+    En,
+    De,
+    Fr,
+    Es,
+    Ja,
+}
+
+impl NameLocale {
+    /// Dataset for a BCP 47 tag such as `de-AT`, chosen by its language.
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        match language.as_str() {
+            "en" => Some(NameLocale::En),
+            "de" => Some(NameLocale::De),
+            "fr" => Some(NameLocale::Fr),
+            "es" => Some(NameLocale::Es),
+            "ja" => Some(NameLocale::Ja),
+            _ => None,
+        }
+    }
+
+    pub fn tag(self) -> &'static str {
+        match self {
+            NameLocale::En => "en",
+            NameLocale::De => "de",
+            NameLocale::Fr => "fr",
+            NameLocale::Es => "es",
+            NameLocale::Ja => "ja",
+        }
+    }
+
+    /// Names searched for this language: its own color words first, then
+    /// the CSS names translated.
+    fn names(self) -> [&'static [(&'static str, [u8; 3])]; 2] {
+        match self {
+            NameLocale::En => [CSS_NAMES, &[]],
+            NameLocale::De => [DE_NAMES, DE_CSS_NAMES],
+            NameLocale::Fr => [FR_NAMES, FR_CSS_NAMES],
+            NameLocale::Es => [ES_NAMES, ES_CSS_NAMES],
+            NameLocale::Ja => [JA_NAMES, JA_CSS_NAMES],
+        }
+    }
+}
+
+/// CSS Color Module Level 4 named colors (grey/cyan/magenta aliases omitted).
+const CSS_NAMES: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [0xF0, 0xF8, 0xFF]),
+    ("antiquewhite", [0xFA, 0xEB, 0xD7]),
+    ("aqua", [0x00, 0xFF, 0xFF]),
+    ("aquamarine", [0x7F, 0xFF, 0xD4]),
+    ("azure", [0xF0, 0xFF, 0xFF]),
+    ("beige", [0xF5, 0xF5, 0xDC]),
+    ("bisque", [0xFF, 0xE4, 0xC4]),
+    ("black", [0x00, 0x00, 0x00]),
+    ("blanchedalmond", [0xFF, 0xEB, 0xCD]),
+    ("blue", [0x00, 0x00, 0xFF]),
+    ("blueviolet", [0x8A, 0x2B, 0xE2]),
+    ("brown", [0xA5, 0x2A, 0x2A]),
+    ("burlywood", [0xDE, 0xB8, 0x87]),
+    ("cadetblue", [0x5F, 0x9E, 0xA0]),
+    ("chartreuse", [0x7F, 0xFF, 0x00]),
+    ("chocolate", [0xD2, 0x69, 0x1E]),
+    ("coral", [0xFF, 0x7F, 0x50]),
+    ("cornflowerblue", [0x64, 0x95, 0xED]),
+    ("cornsilk", [0xFF, 0xF8, 0xDC]),
+    ("crimson", [0xDC, 0x14, 0x3C]),
+    ("darkblue", [0x00, 0x00, 0x8B]),
+    ("darkcyan", [0x00, 0x8B, 0x8B]),
+    ("darkgoldenrod", [0xB8, 0x86, 0x0B]),
+    ("darkgray", [0xA9, 0xA9, 0xA9]),
+    ("darkgreen", [0x00, 0x64, 0x00]),
+    ("darkkhaki", [0xBD, 0xB7, 0x6B]),
+    ("darkmagenta", [0x8B, 0x00, 0x8B]),
+    ("darkolivegreen", [0x55, 0x6B, 0x2F]),
+    ("darkorange", [0xFF, 0x8C, 0x00]),
+    ("darkorchid", [0x99, 0x32, 0xCC]),
+    ("darkred", [0x8B, 0x00, 0x00]),
+    ("darksalmon", [0xE9, 0x96, 0x7A]),
+    ("darkseagreen", [0x8F, 0xBC, 0x8F]),
+    ("darkslateblue", [0x48, 0x3D, 0x8B]),
+    ("darkslategray", [0x2F, 0x4F, 0x4F]),
+    ("darkturquoise", [0x00, 0xCE, 0xD1]),
+    ("darkviolet", [0x94, 0x00, 0xD3]),
+    ("deeppink", [0xFF, 0x14, 0x93]),
+    ("deepskyblue", [0x00, 0xBF, 0xFF]),
+    ("dimgray", [0x69, 0x69, 0x69]),
+    ("dodgerblue", [0x1E, 0x90, 0xFF]),
+    ("firebrick", [0xB2, 0x22, 0x22]),
+    ("floralwhite", [0xFF, 0xFA, 0xF0]),
+    ("forestgreen", [0x22, 0x8B, 0x22]),
+    ("fuchsia", [0xFF, 0x00, 0xFF]),
+    ("gainsboro", [0xDC, 0xDC, 0xDC]),
+    ("ghostwhite", [0xF8, 0xF8, 0xFF]),
+    ("gold", [0xFF, 0xD7, 0x00]),
+    ("goldenrod", [0xDA, 0xA5, 0x20]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("greenyellow", [0xAD, 0xFF, 0x2F]),
+    ("honeydew", [0xF0, 0xFF, 0xF0]),
+    ("hotpink", [0xFF, 0x69, 0xB4]),
+    ("indianred", [0xCD, 0x5C, 0x5C]),
+    ("indigo", [0x4B, 0x00, 0x82]),
+    ("ivory", [0xFF, 0xFF, 0xF0]),
+    ("khaki", [0xF0, 0xE6, 0x8C]),
+    ("lavender", [0xE6, 0xE6, 0xFA]),
+    ("lavenderblush", [0xFF, 0xF0, 0xF5]),
+    ("lawngreen", [0x7C, 0xFC, 0x00]),
+    ("lemonchiffon", [0xFF, 0xFA, 0xCD]),
+    ("lightblue", [0xAD, 0xD8, 0xE6]),
+    ("lightcoral", [0xF0, 0x80, 0x80]),
+    ("lightcyan", [0xE0, 0xFF, 0xFF]),
+    ("lightgoldenrodyellow", [0xFA, 0xFA, 0xD2]),
+    ("lightgray", [0xD3, 0xD3, 0xD3]),
+    ("lightgreen", [0x90, 0xEE, 0x90]),
+    ("lightpink", [0xFF, 0xB6, 0xC1]),
+    ("lightsalmon", [0xFF, 0xA0, 0x7A]),
+    ("lightseagreen", [0x20, 0xB2, 0xAA]),
+    ("lightskyblue", [0x87, 0xCE, 0xFA]),
+    ("lightslategray", [0x77, 0x88, 0x99]),
+    ("lightsteelblue", [0xB0, 0xC4, 0xDE]),
+    ("lightyellow", [0xFF, 0xFF, 0xE0]),
+    ("lime", [0x00, 0xFF, 0x00]),
+    ("limegreen", [0x32, 0xCD, 0x32]),
+    ("linen", [0xFA, 0xF0, 0xE6]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("mediumaquamarine", [0x66, 0xCD, 0xAA]),
+    ("mediumblue", [0x00, 0x00, 0xCD]),
+    ("mediumorchid", [0xBA, 0x55, 0xD3]),
+    ("mediumpurple", [0x93, 0x70, 0xDB]),
+    ("mediumseagreen", [0x3C, 0xB3, 0x71]),
+    ("mediumslateblue", [0x7B, 0x68, 0xEE]),
+    ("mediumspringgreen", [0x00, 0xFA, 0x9A]),
+    ("mediumturquoise", [0x48, 0xD1, 0xCC]),
+    ("mediumvioletred", [0xC7, 0x15, 0x85]),
+    ("midnightblue", [0x19, 0x19, 0x70]),
+    ("mintcream", [0xF5, 0xFF, 0xFA]),
+    ("mistyrose", [0xFF, 0xE4, 0xE1]),
+    ("moccasin", [0xFF, 0xE4, 0xB5]),
+    ("navajowhite", [0xFF, 0xDE, 0xAD]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("oldlace", [0xFD, 0xF5, 0xE6]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("olivedrab", [0x6B, 0x8E, 0x23]),
+    ("orange", [0xFF, 0xA5, 0x00]),
+    ("orangered", [0xFF, 0x45, 0x00]),
+    ("orchid", [0xDA, 0x70, 0xD6]),
+    ("palegoldenrod", [0xEE, 0xE8, 0xAA]),
+    ("palegreen", [0x98, 0xFB, 0x98]),
+    ("paleturquoise", [0xAF, 0xEE, 0xEE]),
+    ("palevioletred", [0xDB, 0x70, 0x93]),
+    ("papayawhip", [0xFF, 0xEF, 0xD5]),
+    ("peachpuff", [0xFF, 0xDA, 0xB9]),
+    ("peru", [0xCD, 0x85, 0x3F]),
+    ("pink", [0xFF, 0xC0, 0xCB]),
+    ("plum", [0xDD, 0xA0, 0xDD]),
+    ("powderblue", [0xB0, 0xE0, 0xE6]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("rebeccapurple", [0x66, 0x33, 0x99]),
+    ("red", [0xFF, 0x00, 0x00]),
+    ("rosybrown", [0xBC, 0x8F, 0x8F]),
+    ("royalblue", [0x41, 0x69, 0xE1]),
+    ("saddlebrown", [0x8B, 0x45, 0x13]),
+    ("salmon", [0xFA, 0x80, 0x72]),
+    ("sandybrown", [0xF4, 0xA4, 0x60]),
+    ("seagreen", [0x2E, 0x8B, 0x57]),
+    ("seashell", [0xFF, 0xF5, 0xEE]),
+    ("sienna", [0xA0, 0x52, 0x2D]),
+    ("silver", [0xC0, 0xC0, 0xC0]),
+    ("skyblue", [0x87, 0xCE, 0xEB]),
+    ("slateblue", [0x6A, 0x5A, 0xCD]),
+    ("slategray", [0x70, 0x80, 0x90]),
+    ("snow", [0xFF, 0xFA, 0xFA]),
+    ("springgreen", [0x00, 0xFF, 0x7F]),
+    ("steelblue", [0x46, 0x82, 0xB4]),
+    ("tan", [0xD2, 0xB4, 0x8C]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("thistle", [0xD8, 0xBF, 0xD8]),
+    ("tomato", [0xFF, 0x63, 0x47]),
+    ("turquoise", [0x40, 0xE0, 0xD0]),
+    ("violet", [0xEE, 0x82, 0xEE]),
+    ("wheat", [0xF5, 0xDE, 0xB3]),
+    ("white", [0xFF, 0xFF, 0xFF]),
+    ("whitesmoke", [0xF5, 0xF5, 0xF5]),
+    ("yellow", [0xFF, 0xFF, 0x00]),
+    ("yellowgreen", [0x9A, 0xCD, 0x32]),
+];
+
+const DE_NAMES: &[(&str, [u8; 3])] = &[
+    ("Schwarz", [0x00, 0x00, 0x00]),
+    ("Weiß", [0xFF, 0xFF, 0xFF]),
+    ("Grau", [0x80, 0x80, 0x80]),
+    ("Hellgrau", [0xD3, 0xD3, 0xD3]),
+    ("Silber", [0xC0, 0xC0, 0xC0]),
+    ("Anthrazit", [0x29, 0x31, 0x33]),
+    ("Rot", [0xFF, 0x00, 0x00]),
+    ("Dunkelrot", [0x8B, 0x00, 0x00]),
+    ("Bordeauxrot", [0x5E, 0x21, 0x29]),
+    ("Karminrot", [0x96, 0x00, 0x18]),
+    ("Rosa", [0xFF, 0xC0, 0xCB]),
+    ("Pink", [0xFF, 0x69, 0xB4]),
+    ("Lachs", [0xFA, 0x80, 0x72]),
+    ("Koralle", [0xFF, 0x7F, 0x50]),
+    ("Orange", [0xFF, 0xA5, 0x00]),
+    ("Dunkelorange", [0xFF, 0x8C, 0x00]),
+    ("Braun", [0x8B, 0x45, 0x13]),
+    ("Schokoladenbraun", [0xD2, 0x69, 0x1E]),
+    ("Ocker", [0xCC, 0x77, 0x22]),
+    ("Beige", [0xF5, 0xF5, 0xDC]),
+    ("Sand", [0xC2, 0xB2, 0x80]),
+    ("Creme", [0xFF, 0xFD, 0xD0]),
+    ("Elfenbein", [0xFF, 0xFF, 0xF0]),
+    ("Gelb", [0xFF, 0xFF, 0x00]),
+    ("Gold", [0xFF, 0xD7, 0x00]),
+    ("Senfgelb", [0xFF, 0xDB, 0x58]),
+    ("Khaki", [0xF0, 0xE6, 0x8C]),
+    ("Oliv", [0x80, 0x80, 0x00]),
+    ("Grün", [0x00, 0x80, 0x00]),
+    ("Dunkelgrün", [0x00, 0x64, 0x00]),
+    ("Hellgrün", [0x90, 0xEE, 0x90]),
+    ("Limettengrün", [0x32, 0xCD, 0x32]),
+    ("Mintgrün", [0x98, 0xFF, 0x98]),
+    ("Tannengrün", [0x1F, 0x3A, 0x3D]),
+    ("Türkis", [0x40, 0xE0, 0xD0]),
+    ("Petrol", [0x00, 0x5F, 0x6A]),
+    ("Cyan", [0x00, 0xFF, 0xFF]),
+    ("Himmelblau", [0x87, 0xCE, 0xEB]),
+    ("Hellblau", [0xAD, 0xD8, 0xE6]),
+    ("Stahlblau", [0x46, 0x82, 0xB4]),
+    ("Blau", [0x00, 0x00, 0xFF]),
+    ("Königsblau", [0x41, 0x69, 0xE1]),
+    ("Dunkelblau", [0x00, 0x00, 0x8B]),
+    ("Marineblau", [0x00, 0x00, 0x80]),
+    ("Indigo", [0x4B, 0x00, 0x82]),
+    ("Violett", [0x8F, 0x00, 0xFF]),
+    ("Purpur", [0x80, 0x00, 0x80]),
+    ("Flieder", [0xC8, 0xA2, 0xC8]),
+    ("Lavendel", [0xE6, 0xE6, 0xFA]),
+    ("Magenta", [0xFF, 0x00, 0xFF]),
+];
+
+const FR_NAMES: &[(&str, [u8; 3])] = &[
+    ("noir", [0x00, 0x00, 0x00]),
+    ("blanc", [0xFF, 0xFF, 0xFF]),
+    ("gris", [0x80, 0x80, 0x80]),
+    ("gris clair", [0xD3, 0xD3, 0xD3]),
+    ("gris anthracite", [0x30, 0x30, 0x30]),
+    ("argent", [0xC0, 0xC0, 0xC0]),
+    ("rouge", [0xFF, 0x00, 0x00]),
+    ("rouge foncé", [0x8B, 0x00, 0x00]),
+    ("bordeaux", [0x6D, 0x07, 0x1A]),
+    ("carmin", [0x96, 0x00, 0x18]),
+    ("rose", [0xFF, 0xC0, 0xCB]),
+    ("rose vif", [0xFF, 0x69, 0xB4]),
+    ("saumon", [0xFA, 0x80, 0x72]),
+    ("corail", [0xFF, 0x7F, 0x50]),
+    ("orange", [0xFF, 0xA5, 0x00]),
+    ("orange foncé", [0xFF, 0x8C, 0x00]),
+    ("marron", [0x88, 0x42, 0x1D]),
+    ("chocolat", [0xD2, 0x69, 0x1E]),
+    ("ocre", [0xDF, 0xAF, 0x2C]),
+    ("beige", [0xF5, 0xF5, 0xDC]),
+    ("crème", [0xFD, 0xF1, 0xB8]),
+    ("ivoire", [0xFF, 0xFF, 0xF0]),
+    ("jaune", [0xFF, 0xFF, 0x00]),
+    ("or", [0xFF, 0xD7, 0x00]),
+    ("moutarde", [0xC7, 0xB0, 0x09]),
+    ("kaki", [0x94, 0x81, 0x2B]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("vert", [0x00, 0x80, 0x00]),
+    ("vert foncé", [0x00, 0x64, 0x00]),
+    ("vert clair", [0x90, 0xEE, 0x90]),
+    ("vert citron", [0x32, 0xCD, 0x32]),
+    ("vert menthe", [0x98, 0xFF, 0x98]),
+    ("vert sapin", [0x09, 0x52, 0x28]),
+    ("turquoise", [0x40, 0xE0, 0xD0]),
+    ("bleu canard", [0x04, 0x8B, 0x9A]),
+    ("cyan", [0x00, 0xFF, 0xFF]),
+    ("bleu ciel", [0x87, 0xCE, 0xEB]),
+    ("bleu clair", [0xAD, 0xD8, 0xE6]),
+    ("bleu de France", [0x31, 0x8C, 0xE7]),
+    ("bleu", [0x00, 0x00, 0xFF]),
+    ("bleu roi", [0x41, 0x69, 0xE1]),
+    ("bleu marine", [0x00, 0x00, 0x80]),
+    ("bleu nuit", [0x0F, 0x05, 0x6B]),
+    ("indigo", [0x4B, 0x00, 0x82]),
+    ("violet", [0x7F, 0x00, 0xFF]),
+    ("prune", [0x81, 0x14, 0x53]),
+    ("mauve", [0xD4, 0x73, 0xD4]),
+    ("lilas", [0xB6, 0x66, 0xD2]),
+    ("lavande", [0xE6, 0xE6, 0xFA]),
+    ("magenta", [0xFF, 0x00, 0xFF]),
+];
+
+const ES_NAMES: &[(&str, [u8; 3])] = &[
+    ("negro", [0x00, 0x00, 0x00]),
+    ("blanco", [0xFF, 0xFF, 0xFF]),
+    ("gris", [0x80, 0x80, 0x80]),
+    ("gris claro", [0xD3, 0xD3, 0xD3]),
+    ("gris oscuro", [0x40, 0x40, 0x40]),
+    ("plata", [0xC0, 0xC0, 0xC0]),
+    ("rojo", [0xFF, 0x00, 0x00]),
+    ("rojo oscuro", [0x8B, 0x00, 0x00]),
+    ("granate", [0x80, 0x00, 0x00]),
+    ("carmesí", [0xDC, 0x14, 0x3C]),
+    ("rosa", [0xFF, 0xC0, 0xCB]),
+    ("rosa intenso", [0xFF, 0x69, 0xB4]),
+    ("salmón", [0xFA, 0x80, 0x72]),
+    ("coral", [0xFF, 0x7F, 0x50]),
+    ("naranja", [0xFF, 0xA5, 0x00]),
+    ("naranja oscuro", [0xFF, 0x8C, 0x00]),
+    ("marrón", [0x8B, 0x45, 0x13]),
+    ("chocolate", [0xD2, 0x69, 0x1E]),
+    ("café", [0x6F, 0x4E, 0x37]),
+    ("ocre", [0xCC, 0x77, 0x22]),
+    ("beige", [0xF5, 0xF5, 0xDC]),
+    ("crema", [0xFF, 0xFD, 0xD0]),
+    ("marfil", [0xFF, 0xFF, 0xF0]),
+    ("amarillo", [0xFF, 0xFF, 0x00]),
+    ("dorado", [0xFF, 0xD7, 0x00]),
+    ("mostaza", [0xFF, 0xDB, 0x58]),
+    ("caqui", [0xF0, 0xE6, 0x8C]),
+    ("oliva", [0x80, 0x80, 0x00]),
+    ("verde", [0x00, 0x80, 0x00]),
+    ("verde oscuro", [0x00, 0x64, 0x00]),
+    ("verde claro", [0x90, 0xEE, 0x90]),
+    ("verde lima", [0x32, 0xCD, 0x32]),
+    ("verde menta", [0x98, 0xFF, 0x98]),
+    ("verde botella", [0x00, 0x6A, 0x4E]),
+    ("turquesa", [0x40, 0xE0, 0xD0]),
+    ("verde azulado", [0x00, 0x80, 0x80]),
+    ("cian", [0x00, 0xFF, 0xFF]),
+    ("celeste", [0x87, 0xCE, 0xEB]),
+    ("azul claro", [0xAD, 0xD8, 0xE6]),
+    ("azul", [0x00, 0x00, 0xFF]),
+    ("azul rey", [0x41, 0x69, 0xE1]),
+    ("azul marino", [0x00, 0x00, 0x80]),
+    ("añil", [0x4B, 0x00, 0x82]),
+    ("violeta", [0x8F, 0x00, 0xFF]),
+    ("morado", [0x80, 0x00, 0x80]),
+    ("lila", [0xC8, 0xA2, 0xC8]),
+    ("lavanda", [0xE6, 0xE6, 0xFA]),
+    ("fucsia", [0xFF, 0x00, 0xFF]),
+];
+
+/// Basic color words plus traditional Japanese colors (伝統色).
+const JA_NAMES: &[(&str, [u8; 3])] = &[
+    ("漆黒", [0x0D, 0x00, 0x15]),
+    ("黒", [0x2B, 0x2B, 0x2B]),
+    ("墨", [0x59, 0x58, 0x57]),
+    ("灰色", [0x7D, 0x7D, 0x7D]),
+    ("鼠色", [0x94, 0x94, 0x95]),
+    ("銀色", [0xC0, 0xC0, 0xC0]),
+    ("白", [0xFF, 0xFF, 0xFF]),
+    ("生成り色", [0xFB, 0xFA, 0xF5]),
+    ("象牙色", [0xF8, 0xF4, 0xE6]),
+    ("赤", [0xE6, 0x00, 0x33]),
+    ("紅色", [0xD7, 0x00, 0x3A]),
+    ("臙脂", [0xB9, 0x40, 0x47]),
+    ("小豆色", [0x96, 0x51, 0x4D]),
+    ("桃色", [0xF0, 0x91, 0x99]),
+    ("撫子色", [0xEE, 0xBB, 0xCB]),
+    ("鴇色", [0xF4, 0xB3, 0xC2]),
+    ("牡丹色", [0xE7, 0x60, 0x9E]),
+    ("珊瑚色", [0xF5, 0xB1, 0x99]),
+    ("朱色", [0xEB, 0x61, 0x01]),
+    ("柿色", [0xED, 0x6D, 0x3D]),
+    ("橙色", [0xEE, 0x78, 0x00]),
+    ("茶色", [0x96, 0x50, 0x42]),
+    ("焦茶", [0x6F, 0x4B, 0x3E]),
+    ("黄土色", [0xC3, 0x91, 0x43]),
+    ("山吹色", [0xF8, 0xB5, 0x00]),
+    ("黄色", [0xFF, 0xD9, 0x00]),
+    ("金色", [0xE6, 0xB4, 0x22]),
+    ("鶯色", [0x92, 0x8C, 0x36]),
+    ("抹茶色", [0xC5, 0xC5, 0x6A]),
+    ("若草色", [0xC3, 0xD8, 0x25]),
+    ("萌黄", [0xAA, 0xCF, 0x53]),
+    ("緑", [0x3E, 0xB3, 0x70]),
+    ("常磐色", [0x00, 0x7B, 0x43]),
+    ("深緑", [0x00, 0x52, 0x43]),
+    ("青緑", [0x00, 0xA4, 0x97]),
+    ("浅葱色", [0x00, 0xA3, 0xAF]),
+    ("水色", [0xBC, 0xE2, 0xE8]),
+    ("空色", [0xA0, 0xD8, 0xEF]),
+    ("青", [0x00, 0x95, 0xD9]),
+    ("瑠璃色", [0x1E, 0x50, 0xA2]),
+    ("群青色", [0x4C, 0x6C, 0xB3]),
+    ("藍色", [0x16, 0x5E, 0x83]),
+    ("紺色", [0x22, 0x3A, 0x70]),
+    ("桔梗色", [0x56, 0x54, 0xA2]),
+    ("菫色", [0x70, 0x58, 0xA3]),
+    ("江戸紫", [0x74, 0x53, 0x99]),
+    ("紫", [0x88, 0x48, 0x98]),
+    ("藤色", [0xBB, 0xBC, 0xDE]),
+];
+
+/// The CSS names in German; [`DE_NAMES`] wins where both fit equally.
+const DE_CSS_NAMES: &[(&str, [u8; 3])] = &[
+    ("Aliceblau", [0xF0, 0xF8, 0xFF]),
+    ("Antikweiß", [0xFA, 0xEB, 0xD7]),
+    ("Aqua", [0x00, 0xFF, 0xFF]),
+    ("Aquamarin", [0x7F, 0xFF, 0xD4]),
+    ("Azurblau", [0xF0, 0xFF, 0xFF]),
+    ("Beige", [0xF5, 0xF5, 0xDC]),
+    ("Biskuit", [0xFF, 0xE4, 0xC4]),
+    ("Schwarz", [0x00, 0x00, 0x00]),
+    ("Mandelweiß", [0xFF, 0xEB, 0xCD]),
+    ("Blau", [0x00, 0x00, 0xFF]),
+    ("Blauviolett", [0x8A, 0x2B, 0xE2]),
+    ("Braun", [0xA5, 0x2A, 0x2A]),
+    ("Grobholz", [0xDE, 0xB8, 0x87]),
+    ("Kadettblau", [0x5F, 0x9E, 0xA0]),
+    ("Chartreuse", [0x7F, 0xFF, 0x00]),
+    ("Schokolade", [0xD2, 0x69, 0x1E]),
+    ("Koralle", [0xFF, 0x7F, 0x50]),
+    ("Kornblumenblau", [0x64, 0x95, 0xED]),
+    ("Maisseide", [0xFF, 0xF8, 0xDC]),
+    ("Karmesinrot", [0xDC, 0x14, 0x3C]),
+    ("Dunkelblau", [0x00, 0x00, 0x8B]),
+    ("Dunkelcyan", [0x00, 0x8B, 0x8B]),
+    ("Dunkelgoldrute", [0xB8, 0x86, 0x0B]),
+    ("Dunkelgrau", [0xA9, 0xA9, 0xA9]),
+    ("Dunkelgrün", [0x00, 0x64, 0x00]),
+    ("Dunkelkhaki", [0xBD, 0xB7, 0x6B]),
+    ("Dunkelmagenta", [0x8B, 0x00, 0x8B]),
+    ("Dunkelolivgrün", [0x55, 0x6B, 0x2F]),
+    ("Dunkelorange", [0xFF, 0x8C, 0x00]),
+    ("Dunkelorchidee", [0x99, 0x32, 0xCC]),
+    ("Dunkelrot", [0x8B, 0x00, 0x00]),
+    ("Dunkellachs", [0xE9, 0x96, 0x7A]),
+    ("Dunkelseegrün", [0x8F, 0xBC, 0x8F]),
+    ("Dunkelschieferblau", [0x48, 0x3D, 0x8B]),
+    ("Dunkelschiefergrau", [0x2F, 0x4F, 0x4F]),
+    ("Dunkeltürkis", [0x00, 0xCE, 0xD1]),
+    ("Dunkelviolett", [0x94, 0x00, 0xD3]),
+    ("Tiefrosa", [0xFF, 0x14, 0x93]),
+    ("Tiefhimmelblau", [0x00, 0xBF, 0xFF]),
+    ("Trübgrau", [0x69, 0x69, 0x69]),
+    ("Dodgerblau", [0x1E, 0x90, 0xFF]),
+    ("Ziegelrot", [0xB2, 0x22, 0x22]),
+    ("Blütenweiß", [0xFF, 0xFA, 0xF0]),
+    ("Waldgrün", [0x22, 0x8B, 0x22]),
+    ("Fuchsie", [0xFF, 0x00, 0xFF]),
+    ("Gainsboro", [0xDC, 0xDC, 0xDC]),
+    ("Geisterweiß", [0xF8, 0xF8, 0xFF]),
+    ("Gold", [0xFF, 0xD7, 0x00]),
+    ("Goldrute", [0xDA, 0xA5, 0x20]),
+    ("Grau", [0x80, 0x80, 0x80]),
+    ("Grün", [0x00, 0x80, 0x00]),
+    ("Grüngelb", [0xAD, 0xFF, 0x2F]),
+    ("Honigmelone", [0xF0, 0xFF, 0xF0]),
+    ("Leuchtrosa", [0xFF, 0x69, 0xB4]),
+    ("Indischrot", [0xCD, 0x5C, 0x5C]),
+    ("Indigo", [0x4B, 0x00, 0x82]),
+    ("Elfenbein", [0xFF, 0xFF, 0xF0]),
+    ("Khaki", [0xF0, 0xE6, 0x8C]),
+    ("Lavendel", [0xE6, 0xE6, 0xFA]),
+    ("Lavendelrosa", [0xFF, 0xF0, 0xF5]),
+    ("Rasengrün", [0x7C, 0xFC, 0x00]),
+    ("Zitronenchiffon", [0xFF, 0xFA, 0xCD]),
+    ("Hellblau", [0xAD, 0xD8, 0xE6]),
+    ("Hellkoralle", [0xF0, 0x80, 0x80]),
+    ("Hellcyan", [0xE0, 0xFF, 0xFF]),
+    ("Hellgoldrutengelb", [0xFA, 0xFA, 0xD2]),
+    ("Hellgrau", [0xD3, 0xD3, 0xD3]),
+    ("Hellgrün", [0x90, 0xEE, 0x90]),
+    ("Hellrosa", [0xFF, 0xB6, 0xC1]),
+    ("Helllachs", [0xFF, 0xA0, 0x7A]),
+    ("Hellseegrün", [0x20, 0xB2, 0xAA]),
+    ("Hellhimmelblau", [0x87, 0xCE, 0xFA]),
+    ("Hellschiefergrau", [0x77, 0x88, 0x99]),
+    ("Hellstahlblau", [0xB0, 0xC4, 0xDE]),
+    ("Hellgelb", [0xFF, 0xFF, 0xE0]),
+    ("Limette", [0x00, 0xFF, 0x00]),
+    ("Limettengrün", [0x32, 0xCD, 0x32]),
+    ("Leinen", [0xFA, 0xF0, 0xE6]),
+    ("Kastanienbraun", [0x80, 0x00, 0x00]),
+    ("Mittelaquamarin", [0x66, 0xCD, 0xAA]),
+    ("Mittelblau", [0x00, 0x00, 0xCD]),
+    ("Mittelorchidee", [0xBA, 0x55, 0xD3]),
+    ("Mittelpurpur", [0x93, 0x70, 0xDB]),
+    ("Mittelseegrün", [0x3C, 0xB3, 0x71]),
+    ("Mittelschieferblau", [0x7B, 0x68, 0xEE]),
+    ("Mittelfrühlingsgrün", [0x00, 0xFA, 0x9A]),
+    ("Mitteltürkis", [0x48, 0xD1, 0xCC]),
+    ("Mittelviolettrot", [0xC7, 0x15, 0x85]),
+    ("Mitternachtsblau", [0x19, 0x19, 0x70]),
+    ("Minzcreme", [0xF5, 0xFF, 0xFA]),
+    ("Nebelrose", [0xFF, 0xE4, 0xE1]),
+    ("Mokassin", [0xFF, 0xE4, 0xB5]),
+    ("Navajoweiß", [0xFF, 0xDE, 0xAD]),
+    ("Marineblau", [0x00, 0x00, 0x80]),
+    ("Spitzenweiß", [0xFD, 0xF5, 0xE6]),
+    ("Oliv", [0x80, 0x80, 0x00]),
+    ("Olivgrau", [0x6B, 0x8E, 0x23]),
+    ("Orange", [0xFF, 0xA5, 0x00]),
+    ("Orangerot", [0xFF, 0x45, 0x00]),
+    ("Orchidee", [0xDA, 0x70, 0xD6]),
+    ("Blassgoldrute", [0xEE, 0xE8, 0xAA]),
+    ("Blassgrün", [0x98, 0xFB, 0x98]),
+    ("Blasstürkis", [0xAF, 0xEE, 0xEE]),
+    ("Blassviolettrot", [0xDB, 0x70, 0x93]),
+    ("Papayacreme", [0xFF, 0xEF, 0xD5]),
+    ("Pfirsich", [0xFF, 0xDA, 0xB9]),
+    ("Peru", [0xCD, 0x85, 0x3F]),
+    ("Rosa", [0xFF, 0xC0, 0xCB]),
+    ("Pflaume", [0xDD, 0xA0, 0xDD]),
+    ("Puderblau", [0xB0, 0xE0, 0xE6]),
+    ("Purpur", [0x80, 0x00, 0x80]),
+    ("Rebeccapurpur", [0x66, 0x33, 0x99]),
+    ("Rot", [0xFF, 0x00, 0x00]),
+    ("Rosenholz", [0xBC, 0x8F, 0x8F]),
+    ("Königsblau", [0x41, 0x69, 0xE1]),
+    ("Sattelbraun", [0x8B, 0x45, 0x13]),
+    ("Lachs", [0xFA, 0x80, 0x72]),
+    ("Sandbraun", [0xF4, 0xA4, 0x60]),
+    ("Seegrün", [0x2E, 0x8B, 0x57]),
+    ("Muschel", [0xFF, 0xF5, 0xEE]),
+    ("Siena", [0xA0, 0x52, 0x2D]),
+    ("Silber", [0xC0, 0xC0, 0xC0]),
+    ("Himmelblau", [0x87, 0xCE, 0xEB]),
+    ("Schieferblau", [0x6A, 0x5A, 0xCD]),
+    ("Schiefergrau", [0x70, 0x80, 0x90]),
+    ("Schneeweiß", [0xFF, 0xFA, 0xFA]),
+    ("Frühlingsgrün", [0x00, 0xFF, 0x7F]),
+    ("Stahlblau", [0x46, 0x82, 0xB4]),
+    ("Hautfarbe", [0xD2, 0xB4, 0x8C]),
+    ("Blaugrün", [0x00, 0x80, 0x80]),
+    ("Distel", [0xD8, 0xBF, 0xD8]),
+    ("Tomate", [0xFF, 0x63, 0x47]),
+    ("Türkis", [0x40, 0xE0, 0xD0]),
+    ("Violett", [0xEE, 0x82, 0xEE]),
+    ("Weizen", [0xF5, 0xDE, 0xB3]),
+    ("Weiß", [0xFF, 0xFF, 0xFF]),
+    ("Rauchweiß", [0xF5, 0xF5, 0xF5]),
+    ("Gelb", [0xFF, 0xFF, 0x00]),
+    ("Gelbgrün", [0x9A, 0xCD, 0x32]),
+];
+
+/// The CSS names in French; [`FR_NAMES`] wins where both fit equally.
+const FR_CSS_NAMES: &[(&str, [u8; 3])] = &[
+    ("bleu alice", [0xF0, 0xF8, 0xFF]),
+    ("blanc antique", [0xFA, 0xEB, 0xD7]),
+    ("aqua", [0x00, 0xFF, 0xFF]),
+    ("aigue-marine", [0x7F, 0xFF, 0xD4]),
+    ("azur", [0xF0, 0xFF, 0xFF]),
+    ("beige", [0xF5, 0xF5, 0xDC]),
+    ("bisque", [0xFF, 0xE4, 0xC4]),
+    ("noir", [0x00, 0x00, 0x00]),
+    ("amande blanchie", [0xFF, 0xEB, 0xCD]),
+    ("bleu", [0x00, 0x00, 0xFF]),
+    ("bleu violet", [0x8A, 0x2B, 0xE2]),
+    ("brun", [0xA5, 0x2A, 0x2A]),
+    ("bois dur", [0xDE, 0xB8, 0x87]),
+    ("bleu cadet", [0x5F, 0x9E, 0xA0]),
+    ("chartreuse", [0x7F, 0xFF, 0x00]),
+    ("chocolat", [0xD2, 0x69, 0x1E]),
+    ("corail", [0xFF, 0x7F, 0x50]),
+    ("bleu bleuet", [0x64, 0x95, 0xED]),
+    ("soie de maïs", [0xFF, 0xF8, 0xDC]),
+    ("cramoisi", [0xDC, 0x14, 0x3C]),
+    ("bleu foncé", [0x00, 0x00, 0x8B]),
+    ("cyan foncé", [0x00, 0x8B, 0x8B]),
+    ("verge d'or foncé", [0xB8, 0x86, 0x0B]),
+    ("gris foncé", [0xA9, 0xA9, 0xA9]),
+    ("vert foncé", [0x00, 0x64, 0x00]),
+    ("kaki foncé", [0xBD, 0xB7, 0x6B]),
+    ("magenta foncé", [0x8B, 0x00, 0x8B]),
+    ("vert olive foncé", [0x55, 0x6B, 0x2F]),
+    ("orange foncé", [0xFF, 0x8C, 0x00]),
+    ("orchidée foncé", [0x99, 0x32, 0xCC]),
+    ("rouge foncé", [0x8B, 0x00, 0x00]),
+    ("saumon foncé", [0xE9, 0x96, 0x7A]),
+    ("vert d'eau foncé", [0x8F, 0xBC, 0x8F]),
+    ("bleu ardoise foncé", [0x48, 0x3D, 0x8B]),
+    ("gris ardoise foncé", [0x2F, 0x4F, 0x4F]),
+    ("turquoise foncé", [0x00, 0xCE, 0xD1]),
+    ("violet foncé", [0x94, 0x00, 0xD3]),
+    ("rose profond", [0xFF, 0x14, 0x93]),
+    ("bleu ciel profond", [0x00, 0xBF, 0xFF]),
+    ("gris sombre", [0x69, 0x69, 0x69]),
+    ("bleu dodger", [0x1E, 0x90, 0xFF]),
+    ("rouge brique", [0xB2, 0x22, 0x22]),
+    ("blanc floral", [0xFF, 0xFA, 0xF0]),
+    ("vert forêt", [0x22, 0x8B, 0x22]),
+    ("fuchsia", [0xFF, 0x00, 0xFF]),
+    ("gainsboro", [0xDC, 0xDC, 0xDC]),
+    ("blanc spectral", [0xF8, 0xF8, 0xFF]),
+    ("or", [0xFF, 0xD7, 0x00]),
+    ("verge d'or", [0xDA, 0xA5, 0x20]),
+    ("gris", [0x80, 0x80, 0x80]),
+    ("vert", [0x00, 0x80, 0x00]),
+    ("vert-jaune", [0xAD, 0xFF, 0x2F]),
+    ("miellat", [0xF0, 0xFF, 0xF0]),
+    ("rose vif", [0xFF, 0x69, 0xB4]),
+    ("rouge indien", [0xCD, 0x5C, 0x5C]),
+    ("indigo", [0x4B, 0x00, 0x82]),
+    ("ivoire", [0xFF, 0xFF, 0xF0]),
+    ("kaki", [0xF0, 0xE6, 0x8C]),
+    ("lavande", [0xE6, 0xE6, 0xFA]),
+    ("lavande rosée", [0xFF, 0xF0, 0xF5]),
+    ("vert pelouse", [0x7C, 0xFC, 0x00]),
+    ("mousseline citron", [0xFF, 0xFA, 0xCD]),
+    ("bleu clair", [0xAD, 0xD8, 0xE6]),
+    ("corail clair", [0xF0, 0x80, 0x80]),
+    ("cyan clair", [0xE0, 0xFF, 0xFF]),
+    ("jaune verge d'or clair", [0xFA, 0xFA, 0xD2]),
+    ("gris clair", [0xD3, 0xD3, 0xD3]),
+    ("vert clair", [0x90, 0xEE, 0x90]),
+    ("rose clair", [0xFF, 0xB6, 0xC1]),
+    ("saumon clair", [0xFF, 0xA0, 0x7A]),
+    ("vert d'eau clair", [0x20, 0xB2, 0xAA]),
+    ("bleu ciel clair", [0x87, 0xCE, 0xFA]),
+    ("gris ardoise clair", [0x77, 0x88, 0x99]),
+    ("bleu acier clair", [0xB0, 0xC4, 0xDE]),
+    ("jaune clair", [0xFF, 0xFF, 0xE0]),
+    ("citron vert", [0x00, 0xFF, 0x00]),
+    ("vert citron", [0x32, 0xCD, 0x32]),
+    ("lin", [0xFA, 0xF0, 0xE6]),
+    ("marron", [0x80, 0x00, 0x00]),
+    ("aigue-marine moyen", [0x66, 0xCD, 0xAA]),
+    ("bleu moyen", [0x00, 0x00, 0xCD]),
+    ("orchidée moyen", [0xBA, 0x55, 0xD3]),
+    ("pourpre moyen", [0x93, 0x70, 0xDB]),
+    ("vert d'eau moyen", [0x3C, 0xB3, 0x71]),
+    ("bleu ardoise moyen", [0x7B, 0x68, 0xEE]),
+    ("vert printemps moyen", [0x00, 0xFA, 0x9A]),
+    ("turquoise moyen", [0x48, 0xD1, 0xCC]),
+    ("rouge violacé moyen", [0xC7, 0x15, 0x85]),
+    ("bleu nuit", [0x19, 0x19, 0x70]),
+    ("crème menthe", [0xF5, 0xFF, 0xFA]),
+    ("rose brumeux", [0xFF, 0xE4, 0xE1]),
+    ("mocassin", [0xFF, 0xE4, 0xB5]),
+    ("blanc navajo", [0xFF, 0xDE, 0xAD]),
+    ("bleu marine", [0x00, 0x00, 0x80]),
+    ("dentelle ancienne", [0xFD, 0xF5, 0xE6]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("vert olive terne", [0x6B, 0x8E, 0x23]),
+    ("orange", [0xFF, 0xA5, 0x00]),
+    ("rouge orangé", [0xFF, 0x45, 0x00]),
+    ("orchidée", [0xDA, 0x70, 0xD6]),
+    ("verge d'or pâle", [0xEE, 0xE8, 0xAA]),
+    ("vert pâle", [0x98, 0xFB, 0x98]),
+    ("turquoise pâle", [0xAF, 0xEE, 0xEE]),
+    ("rouge violacé pâle", [0xDB, 0x70, 0x93]),
+    ("crème de papaye", [0xFF, 0xEF, 0xD5]),
+    ("pêche", [0xFF, 0xDA, 0xB9]),
+    ("pérou", [0xCD, 0x85, 0x3F]),
+    ("rose", [0xFF, 0xC0, 0xCB]),
+    ("prune", [0xDD, 0xA0, 0xDD]),
+    ("bleu poudre", [0xB0, 0xE0, 0xE6]),
+    ("pourpre", [0x80, 0x00, 0x80]),
+    ("pourpre rebecca", [0x66, 0x33, 0x99]),
+    ("rouge", [0xFF, 0x00, 0x00]),
+    ("brun rosé", [0xBC, 0x8F, 0x8F]),
+    ("bleu roi", [0x41, 0x69, 0xE1]),
+    ("brun cuir", [0x8B, 0x45, 0x13]),
+    ("saumon", [0xFA, 0x80, 0x72]),
+    ("brun sable", [0xF4, 0xA4, 0x60]),
+    ("vert d'eau", [0x2E, 0x8B, 0x57]),
+    ("coquillage", [0xFF, 0xF5, 0xEE]),
+    ("terre de sienne", [0xA0, 0x52, 0x2D]),
+    ("argent", [0xC0, 0xC0, 0xC0]),
+    ("bleu ciel", [0x87, 0xCE, 0xEB]),
+    ("bleu ardoise", [0x6A, 0x5A, 0xCD]),
+    ("gris ardoise", [0x70, 0x80, 0x90]),
+    ("neige", [0xFF, 0xFA, 0xFA]),
+    ("vert printemps", [0x00, 0xFF, 0x7F]),
+    ("bleu acier", [0x46, 0x82, 0xB4]),
+    ("tanné", [0xD2, 0xB4, 0x8C]),
+    ("sarcelle", [0x00, 0x80, 0x80]),
+    ("chardon", [0xD8, 0xBF, 0xD8]),
+    ("tomate", [0xFF, 0x63, 0x47]),
+    ("turquoise", [0x40, 0xE0, 0xD0]),
+    ("violet", [0xEE, 0x82, 0xEE]),
+    ("blé", [0xF5, 0xDE, 0xB3]),
+    ("blanc", [0xFF, 0xFF, 0xFF]),
+    ("fumée blanche", [0xF5, 0xF5, 0xF5]),
+    ("jaune", [0xFF, 0xFF, 0x00]),
+    ("jaune-vert", [0x9A, 0xCD, 0x32]),
+];
+
+/// The CSS names in Spanish; [`ES_NAMES`] wins where both fit equally.
+const ES_CSS_NAMES: &[(&str, [u8; 3])] = &[
+    ("azul alicia", [0xF0, 0xF8, 0xFF]),
+    ("blanco antiguo", [0xFA, 0xEB, 0xD7]),
+    ("agua", [0x00, 0xFF, 0xFF]),
+    ("aguamarina", [0x7F, 0xFF, 0xD4]),
+    ("azur", [0xF0, 0xFF, 0xFF]),
+    ("beis", [0xF5, 0xF5, 0xDC]),
+    ("bizcocho", [0xFF, 0xE4, 0xC4]),
+    ("negro", [0x00, 0x00, 0x00]),
+    ("almendra blanqueada", [0xFF, 0xEB, 0xCD]),
+    ("azul", [0x00, 0x00, 0xFF]),
+    ("azul violeta", [0x8A, 0x2B, 0xE2]),
+    ("marrón", [0xA5, 0x2A, 0x2A]),
+    ("madera", [0xDE, 0xB8, 0x87]),
+    ("azul cadete", [0x5F, 0x9E, 0xA0]),
+    ("chartreuse", [0x7F, 0xFF, 0x00]),
+    ("chocolate", [0xD2, 0x69, 0x1E]),
+    ("coral", [0xFF, 0x7F, 0x50]),
+    ("azul aciano", [0x64, 0x95, 0xED]),
+    ("seda de maíz", [0xFF, 0xF8, 0xDC]),
+    ("carmesí", [0xDC, 0x14, 0x3C]),
+    ("azul oscuro", [0x00, 0x00, 0x8B]),
+    ("cian oscuro", [0x00, 0x8B, 0x8B]),
+    ("vara de oro oscuro", [0xB8, 0x86, 0x0B]),
+    ("gris oscuro", [0xA9, 0xA9, 0xA9]),
+    ("verde oscuro", [0x00, 0x64, 0x00]),
+    ("caqui oscuro", [0xBD, 0xB7, 0x6B]),
+    ("magenta oscuro", [0x8B, 0x00, 0x8B]),
+    ("verde oliva oscuro", [0x55, 0x6B, 0x2F]),
+    ("naranja oscuro", [0xFF, 0x8C, 0x00]),
+    ("orquídea oscuro", [0x99, 0x32, 0xCC]),
+    ("rojo oscuro", [0x8B, 0x00, 0x00]),
+    ("salmón oscuro", [0xE9, 0x96, 0x7A]),
+    ("verde mar oscuro", [0x8F, 0xBC, 0x8F]),
+    ("azul pizarra oscuro", [0x48, 0x3D, 0x8B]),
+    ("gris pizarra oscuro", [0x2F, 0x4F, 0x4F]),
+    ("turquesa oscuro", [0x00, 0xCE, 0xD1]),
+    ("violeta oscuro", [0x94, 0x00, 0xD3]),
+    ("rosa intenso", [0xFF, 0x14, 0x93]),
+    ("azul cielo intenso", [0x00, 0xBF, 0xFF]),
+    ("gris tenue", [0x69, 0x69, 0x69]),
+    ("azul dodger", [0x1E, 0x90, 0xFF]),
+    ("rojo ladrillo", [0xB2, 0x22, 0x22]),
+    ("blanco floral", [0xFF, 0xFA, 0xF0]),
+    ("verde bosque", [0x22, 0x8B, 0x22]),
+    ("fucsia", [0xFF, 0x00, 0xFF]),
+    ("gainsboro", [0xDC, 0xDC, 0xDC]),
+    ("blanco fantasma", [0xF8, 0xF8, 0xFF]),
+    ("oro", [0xFF, 0xD7, 0x00]),
+    ("vara de oro", [0xDA, 0xA5, 0x20]),
+    ("gris", [0x80, 0x80, 0x80]),
+    ("verde", [0x00, 0x80, 0x00]),
+    ("verde amarillo", [0xAD, 0xFF, 0x2F]),
+    ("melón", [0xF0, 0xFF, 0xF0]),
+    ("rosa fuerte", [0xFF, 0x69, 0xB4]),
+    ("rojo indio", [0xCD, 0x5C, 0x5C]),
+    ("índigo", [0x4B, 0x00, 0x82]),
+    ("marfil", [0xFF, 0xFF, 0xF0]),
+    ("caqui", [0xF0, 0xE6, 0x8C]),
+    ("lavanda", [0xE6, 0xE6, 0xFA]),
+    ("lavanda rubor", [0xFF, 0xF0, 0xF5]),
+    ("verde césped", [0x7C, 0xFC, 0x00]),
+    ("gasa limón", [0xFF, 0xFA, 0xCD]),
+    ("azul claro", [0xAD, 0xD8, 0xE6]),
+    ("coral claro", [0xF0, 0x80, 0x80]),
+    ("cian claro", [0xE0, 0xFF, 0xFF]),
+    ("amarillo vara de oro claro", [0xFA, 0xFA, 0xD2]),
+    ("gris claro", [0xD3, 0xD3, 0xD3]),
+    ("verde claro", [0x90, 0xEE, 0x90]),
+    ("rosa claro", [0xFF, 0xB6, 0xC1]),
+    ("salmón claro", [0xFF, 0xA0, 0x7A]),
+    ("verde mar claro", [0x20, 0xB2, 0xAA]),
+    ("azul cielo claro", [0x87, 0xCE, 0xFA]),
+    ("gris pizarra claro", [0x77, 0x88, 0x99]),
+    ("azul acero claro", [0xB0, 0xC4, 0xDE]),
+    ("amarillo claro", [0xFF, 0xFF, 0xE0]),
+    ("lima", [0x00, 0xFF, 0x00]),
+    ("verde lima", [0x32, 0xCD, 0x32]),
+    ("lino", [0xFA, 0xF0, 0xE6]),
+    ("granate", [0x80, 0x00, 0x00]),
+    ("aguamarina medio", [0x66, 0xCD, 0xAA]),
+    ("azul medio", [0x00, 0x00, 0xCD]),
+    ("orquídea medio", [0xBA, 0x55, 0xD3]),
+    ("púrpura medio", [0x93, 0x70, 0xDB]),
+    ("verde mar medio", [0x3C, 0xB3, 0x71]),
+    ("azul pizarra medio", [0x7B, 0x68, 0xEE]),
+    ("verde primavera medio", [0x00, 0xFA, 0x9A]),
+    ("turquesa medio", [0x48, 0xD1, 0xCC]),
+    ("rojo violáceo medio", [0xC7, 0x15, 0x85]),
+    ("azul medianoche", [0x19, 0x19, 0x70]),
+    ("crema de menta", [0xF5, 0xFF, 0xFA]),
+    ("rosa brumoso", [0xFF, 0xE4, 0xE1]),
+    ("mocasín", [0xFF, 0xE4, 0xB5]),
+    ("blanco navajo", [0xFF, 0xDE, 0xAD]),
+    ("azul marino", [0x00, 0x00, 0x80]),
+    ("encaje antiguo", [0xFD, 0xF5, 0xE6]),
+    ("oliva", [0x80, 0x80, 0x00]),
+    ("verde oliva apagado", [0x6B, 0x8E, 0x23]),
+    ("naranja", [0xFF, 0xA5, 0x00]),
+    ("rojo anaranjado", [0xFF, 0x45, 0x00]),
+    ("orquídea", [0xDA, 0x70, 0xD6]),
+    ("vara de oro pálido", [0xEE, 0xE8, 0xAA]),
+    ("verde pálido", [0x98, 0xFB, 0x98]),
+    ("turquesa pálido", [0xAF, 0xEE, 0xEE]),
+    ("rojo violáceo pálido", [0xDB, 0x70, 0x93]),
+    ("crema de papaya", [0xFF, 0xEF, 0xD5]),
+    ("melocotón", [0xFF, 0xDA, 0xB9]),
+    ("perú", [0xCD, 0x85, 0x3F]),
+    ("rosa", [0xFF, 0xC0, 0xCB]),
+    ("ciruela", [0xDD, 0xA0, 0xDD]),
+    ("azul polvo", [0xB0, 0xE0, 0xE6]),
+    ("púrpura", [0x80, 0x00, 0x80]),
+    ("púrpura rebeca", [0x66, 0x33, 0x99]),
+    ("rojo", [0xFF, 0x00, 0x00]),
+    ("marrón rosado", [0xBC, 0x8F, 0x8F]),
+    ("azul real", [0x41, 0x69, 0xE1]),
+    ("marrón cuero", [0x8B, 0x45, 0x13]),
+    ("salmón", [0xFA, 0x80, 0x72]),
+    ("marrón arena", [0xF4, 0xA4, 0x60]),
+    ("verde mar", [0x2E, 0x8B, 0x57]),
+    ("concha", [0xFF, 0xF5, 0xEE]),
+    ("siena", [0xA0, 0x52, 0x2D]),
+    ("plata", [0xC0, 0xC0, 0xC0]),
+    ("azul cielo", [0x87, 0xCE, 0xEB]),
+    ("azul pizarra", [0x6A, 0x5A, 0xCD]),
+    ("gris pizarra", [0x70, 0x80, 0x90]),
+    ("nieve", [0xFF, 0xFA, 0xFA]),
+    ("verde primavera", [0x00, 0xFF, 0x7F]),
+    ("azul acero", [0x46, 0x82, 0xB4]),
+    ("bronceado", [0xD2, 0xB4, 0x8C]),
+    ("verde azulado", [0x00, 0x80, 0x80]),
+    ("cardo", [0xD8, 0xBF, 0xD8]),
+    ("tomate", [0xFF, 0x63, 0x47]),
+    ("turquesa", [0x40, 0xE0, 0xD0]),
+    ("violeta", [0xEE, 0x82, 0xEE]),
+    ("trigo", [0xF5, 0xDE, 0xB3]),
+    ("blanco", [0xFF, 0xFF, 0xFF]),
+    ("humo blanco", [0xF5, 0xF5, 0xF5]),
+    ("amarillo", [0xFF, 0xFF, 0x00]),
+    ("amarillo verdoso", [0x9A, 0xCD, 0x32]),
+];
+
+/// The CSS names in Japanese; [`JA_NAMES`] wins where both fit equally.
+const JA_CSS_NAMES: &[(&str, [u8; 3])] = &[
+    ("アリスブルー", [0xF0, 0xF8, 0xFF]),
+    ("アンティークホワイト", [0xFA, 0xEB, 0xD7]),
+    ("アクア", [0x00, 0xFF, 0xFF]),
+    ("アクアマリン", [0x7F, 0xFF, 0xD4]),
+    ("アジュール", [0xF0, 0xFF, 0xFF]),
+    ("ベージュ", [0xF5, 0xF5, 0xDC]),
+    ("ビスク", [0xFF, 0xE4, 0xC4]),
+    ("ブラック", [0x00, 0x00, 0x00]),
+    ("ブランチドアーモンド", [0xFF, 0xEB, 0xCD]),
+    ("ブルー", [0x00, 0x00, 0xFF]),
+    ("ブルーバイオレット", [0x8A, 0x2B, 0xE2]),
+    ("ブラウン", [0xA5, 0x2A, 0x2A]),
+    ("バーリーウッド", [0xDE, 0xB8, 0x87]),
+    ("カデットブルー", [0x5F, 0x9E, 0xA0]),
+    ("シャルトルーズ", [0x7F, 0xFF, 0x00]),
+    ("チョコレート", [0xD2, 0x69, 0x1E]),
+    ("コーラル", [0xFF, 0x7F, 0x50]),
+    ("コーンフラワーブルー", [0x64, 0x95, 0xED]),
+    ("コーンシルク", [0xFF, 0xF8, 0xDC]),
+    ("クリムゾン", [0xDC, 0x14, 0x3C]),
+    ("ダークブルー", [0x00, 0x00, 0x8B]),
+    ("ダークシアン", [0x00, 0x8B, 0x8B]),
+    ("ダークゴールデンロッド", [0xB8, 0x86, 0x0B]),
+    ("ダークグレー", [0xA9, 0xA9, 0xA9]),
+    ("ダークグリーン", [0x00, 0x64, 0x00]),
+    ("ダークカーキ", [0xBD, 0xB7, 0x6B]),
+    ("ダークマゼンタ", [0x8B, 0x00, 0x8B]),
+    ("ダークオリーブグリーン", [0x55, 0x6B, 0x2F]),
+    ("ダークオレンジ", [0xFF, 0x8C, 0x00]),
+    ("ダークオーキッド", [0x99, 0x32, 0xCC]),
+    ("ダークレッド", [0x8B, 0x00, 0x00]),
+    ("ダークサーモン", [0xE9, 0x96, 0x7A]),
+    ("ダークシーグリーン", [0x8F, 0xBC, 0x8F]),
+    ("ダークスレートブルー", [0x48, 0x3D, 0x8B]),
+    ("ダークスレートグレー", [0x2F, 0x4F, 0x4F]),
+    ("ダークターコイズ", [0x00, 0xCE, 0xD1]),
+    ("ダークバイオレット", [0x94, 0x00, 0xD3]),
+    ("ディープピンク", [0xFF, 0x14, 0x93]),
+    ("ディープスカイブルー", [0x00, 0xBF, 0xFF]),
+    ("ディムグレー", [0x69, 0x69, 0x69]),
+    ("ドジャーブルー", [0x1E, 0x90, 0xFF]),
+    ("ファイアブリック", [0xB2, 0x22, 0x22]),
+    ("フローラルホワイト", [0xFF, 0xFA, 0xF0]),
+    ("フォレストグリーン", [0x22, 0x8B, 0x22]),
+    ("フクシア", [0xFF, 0x00, 0xFF]),
+    ("ゲインズボロ", [0xDC, 0xDC, 0xDC]),
+    ("ゴーストホワイト", [0xF8, 0xF8, 0xFF]),
+    ("ゴールド", [0xFF, 0xD7, 0x00]),
+    ("ゴールデンロッド", [0xDA, 0xA5, 0x20]),
+    ("グレー", [0x80, 0x80, 0x80]),
+    ("グリーン", [0x00, 0x80, 0x00]),
+    ("グリーンイエロー", [0xAD, 0xFF, 0x2F]),
+    ("ハニーデュー", [0xF0, 0xFF, 0xF0]),
+    ("ホットピンク", [0xFF, 0x69, 0xB4]),
+    ("インディアンレッド", [0xCD, 0x5C, 0x5C]),
+    ("インディゴ", [0x4B, 0x00, 0x82]),
+    ("アイボリー", [0xFF, 0xFF, 0xF0]),
+    ("カーキ", [0xF0, 0xE6, 0x8C]),
+    ("ラベンダー", [0xE6, 0xE6, 0xFA]),
+    ("ラベンダーブラッシュ", [0xFF, 0xF0, 0xF5]),
+    ("ローングリーン", [0x7C, 0xFC, 0x00]),
+    ("レモンシフォン", [0xFF, 0xFA, 0xCD]),
+    ("ライトブルー", [0xAD, 0xD8, 0xE6]),
+    ("ライトコーラル", [0xF0, 0x80, 0x80]),
+    ("ライトシアン", [0xE0, 0xFF, 0xFF]),
+    ("ライトゴールデンロッドイエロー", [0xFA, 0xFA, 0xD2]),
+    ("ライトグレー", [0xD3, 0xD3, 0xD3]),
+    ("ライトグリーン", [0x90, 0xEE, 0x90]),
+    ("ライトピンク", [0xFF, 0xB6, 0xC1]),
+    ("ライトサーモン", [0xFF, 0xA0, 0x7A]),
+    ("ライトシーグリーン", [0x20, 0xB2, 0xAA]),
+    ("ライトスカイブルー", [0x87, 0xCE, 0xFA]),
+    ("ライトスレートグレー", [0x77, 0x88, 0x99]),
+    ("ライトスチールブルー", [0xB0, 0xC4, 0xDE]),
+    ("ライトイエロー", [0xFF, 0xFF, 0xE0]),
+    ("ライム", [0x00, 0xFF, 0x00]),
+    ("ライムグリーン", [0x32, 0xCD, 0x32]),
+    ("リネン", [0xFA, 0xF0, 0xE6]),
+    ("マルーン", [0x80, 0x00, 0x00]),
+    ("ミディアムアクアマリン", [0x66, 0xCD, 0xAA]),
+    ("ミディアムブルー", [0x00, 0x00, 0xCD]),
+    ("ミディアムオーキッド", [0xBA, 0x55, 0xD3]),
+    ("ミディアムパープル", [0x93, 0x70, 0xDB]),
+    ("ミディアムシーグリーン", [0x3C, 0xB3, 0x71]),
+    ("ミディアムスレートブルー", [0x7B, 0x68, 0xEE]),
+    ("ミディアムスプリンググリーン", [0x00, 0xFA, 0x9A]),
+    ("ミディアムターコイズ", [0x48, 0xD1, 0xCC]),
+    ("ミディアムバイオレットレッド", [0xC7, 0x15, 0x85]),
+    ("ミッドナイトブルー", [0x19, 0x19, 0x70]),
+    ("ミントクリーム", [0xF5, 0xFF, 0xFA]),
+    ("ミスティローズ", [0xFF, 0xE4, 0xE1]),
+    ("モカシン", [0xFF, 0xE4, 0xB5]),
+    ("ナバホホワイト", [0xFF, 0xDE, 0xAD]),
+    ("ネイビー", [0x00, 0x00, 0x80]),
+    ("オールドレース", [0xFD, 0xF5, 0xE6]),
+    ("オリーブ", [0x80, 0x80, 0x00]),
+    ("オリーブドラブ", [0x6B, 0x8E, 0x23]),
+    ("オレンジ", [0xFF, 0xA5, 0x00]),
+    ("オレンジレッド", [0xFF, 0x45, 0x00]),
+    ("オーキッド", [0xDA, 0x70, 0xD6]),
+    ("ペールゴールデンロッド", [0xEE, 0xE8, 0xAA]),
+    ("ペールグリーン", [0x98, 0xFB, 0x98]),
+    ("ペールターコイズ", [0xAF, 0xEE, 0xEE]),
+    ("ペールバイオレットレッド", [0xDB, 0x70, 0x93]),
+    ("パパイヤホイップ", [0xFF, 0xEF, 0xD5]),
+    ("ピーチパフ", [0xFF, 0xDA, 0xB9]),
+    ("ペルー", [0xCD, 0x85, 0x3F]),
+    ("ピンク", [0xFF, 0xC0, 0xCB]),
+    ("プラム", [0xDD, 0xA0, 0xDD]),
+    ("パウダーブルー", [0xB0, 0xE0, 0xE6]),
+    ("パープル", [0x80, 0x00, 0x80]),
+    ("レベッカパープル", [0x66, 0x33, 0x99]),
+    ("レッド", [0xFF, 0x00, 0x00]),
+    ("ロージーブラウン", [0xBC, 0x8F, 0x8F]),
+    ("ロイヤルブルー", [0x41, 0x69, 0xE1]),
+    ("サドルブラウン", [0x8B, 0x45, 0x13]),
+    ("サーモン", [0xFA, 0x80, 0x72]),
+    ("サンディブラウン", [0xF4, 0xA4, 0x60]),
+    ("シーグリーン", [0x2E, 0x8B, 0x57]),
+    ("シーシェル", [0xFF, 0xF5, 0xEE]),
+    ("シエナ", [0xA0, 0x52, 0x2D]),
+    ("シルバー", [0xC0, 0xC0, 0xC0]),
+    ("スカイブルー", [0x87, 0xCE, 0xEB]),
+    ("スレートブルー", [0x6A, 0x5A, 0xCD]),
+    ("スレートグレー", [0x70, 0x80, 0x90]),
+    ("スノー", [0xFF, 0xFA, 0xFA]),
+    ("スプリンググリーン", [0x00, 0xFF, 0x7F]),
+    ("スチールブルー", [0x46, 0x82, 0xB4]),
+    ("タン", [0xD2, 0xB4, 0x8C]),
+    ("ティール", [0x00, 0x80, 0x80]),
+    ("シスル", [0xD8, 0xBF, 0xD8]),
+    ("トマト", [0xFF, 0x63, 0x47]),
+    ("ターコイズ", [0x40, 0xE0, 0xD0]),
+    ("バイオレット", [0xEE, 0x82, 0xEE]),
+    ("ウィート", [0xF5, 0xDE, 0xB3]),
+    ("ホワイト", [0xFF, 0xFF, 0xFF]),
+    ("ホワイトスモーク", [0xF5, 0xF5, 0xF5]),
+    ("イエロー", [0xFF, 0xFF, 0x00]),
+    ("イエローグリーン", [0x9A, 0xCD, 0x32]),
+];
+
+/// Closest CSS color name, measured in OKLab so the match looks right.
+pub fn nearest_name(rgb: [u8; 3]) -> &'static str {
+    nearest_name_in(rgb, NameLocale::En)
+}
+
+/// Closest color name in `locale`'s dataset, measured in OKLab.
+pub fn nearest_name_in(rgb: [u8; 3], locale: NameLocale) -> &'static str {
+    let target = rgb_to_oklab(rgb);
+    locale
+        .names()
+        .into_iter()
+        .flatten()
+        .min_by(|(_, a), (_, b)| {
+            distance(target, rgb_to_oklab(*a)).total_cmp(&distance(target, rgb_to_oklab(*b)))
+        })
+        .map(|(name, _)| *name)
+        .unwrap_or("black")
+}
+
+fn distance(a: Oklab, b: Oklab) -> f64 {
+    (a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)
+}
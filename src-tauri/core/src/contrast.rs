@@ -0,0 +1,58 @@
+use crate::color_math::{self, WcagLevels};
+use serde::Serialize;
+
+pub const WHITE: [u8; 3] = [255, 255, 255];
+pub const BLACK: [u8; 3] = [0, 0, 0];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContrastCell {
+    /// Rounded to two decimals, as displayed
+    pub ratio: f64,
+    #[serde(flatten)]
+    pub levels: WcagLevels,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContrastRow {
+    pub color: String,
+    /// One cell per background, in the table's background order
+    pub cells: Vec<ContrastCell>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContrastTable {
+    pub backgrounds: Vec<String>,
+    pub rows: Vec<ContrastRow>,
+}
+
+pub fn cell(color: [u8; 3], bg: [u8; 3]) -> ContrastCell {
+    let ratio = color_math::contrast_ratio(color, bg);
+    ContrastCell {
+        ratio: (ratio * 100.0).round() / 100.0,
+        levels: color_math::wcag_levels(ratio),
+    }
+}
+
+/// Check every color against white, black and any extra backgrounds
+/// (duplicates dropped), producing one row per color.
+pub fn contrast_table(colors: &[[u8; 3]], extra_backgrounds: &[[u8; 3]]) -> ContrastTable {
+    let mut backgrounds = vec![WHITE, BLACK];
+    for bg in extra_backgrounds {
+        if !backgrounds.contains(bg) {
+            backgrounds.push(*bg);
+        }
+    }
+    ContrastTable {
+        backgrounds: backgrounds
+            .iter()
+            .map(|bg| color_math::to_hex(*bg))
+            .collect(),
+        rows: colors
+            .iter()
+            .map(|&color| ContrastRow {
+                color: color_math::to_hex(color),
+                cells: backgrounds.iter().map(|&bg| cell(color, bg)).collect(),
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,258 @@
+use crate::export::{self, escape_html};
+use crate::png_encoder::{self, PngOptions};
+use crate::{color_math, contrast, Palette};
+
+/// Pixel size of one grid cell in the PNG rendering.
+const CELL_WIDTH: u32 = 120;
+const CELL_HEIGHT: u32 = 72;
+
+/// Glyph scale for ratios and hex labels, and for badge labels.
+const TEXT_SCALE: u32 = 3;
+const BADGE_SCALE: u32 = 2;
+
+/// Largest palette rendered as PNG; the image grows with the square of the
+/// color count, and 48 colors already make a 5880×3528 image.
+const MAX_PNG_COLORS: usize = 48;
+
+const PAPER: [u8; 3] = [0xFA, 0xFA, 0xF8];
+
+#[derive(Debug, Clone, Copy)]
+pub enum GridFormat {
+    Html,
+    Png,
+}
+
+/// WCAG badge for a pair: label plus (background, ink) colors, matching the
+/// HTML report's badge styling.
+fn badge(levels: color_math::WcagLevels) -> (&'static str, [u8; 3], [u8; 3]) {
+    const PASS: ([u8; 3], [u8; 3]) = ([0xDC, 0xFC, 0xE7], [0x16, 0x65, 0x34]);
+    const LARGE: ([u8; 3], [u8; 3]) = ([0xFE, 0xF9, 0xC3], [0x85, 0x4D, 0x0E]);
+    const FAIL: ([u8; 3], [u8; 3]) = ([0xFE, 0xE2, 0xE2], [0x99, 0x1B, 0x1B]);
+    let (label, (bg, ink)) = if levels.aaa_normal {
+        ("AAA", PASS)
+    } else if levels.aa_normal {
+        ("AA", PASS)
+    } else if levels.aa_large {
+        ("AA18", LARGE)
+    } else {
+        ("FAIL", FAIL)
+    };
+    (label, bg, ink)
+}
+
+/// Every color as text on every color as background, rows being text colors
+/// and columns backgrounds, each cell with its ratio and WCAG badge.
+pub fn render(
+    palette: &Palette,
+    format: GridFormat,
+    png_options: &PngOptions,
+) -> Result<Vec<u8>, String> {
+    let colors = export::palette_rgb(palette)?;
+    if colors.is_empty() {
+        return Err("Palette has no colors".to_string());
+    }
+    match format {
+        GridFormat::Html => Ok(html(&palette.name, &colors).into_bytes()),
+        GridFormat::Png => png(&colors, png_options),
+    }
+}
+
+fn html(name: &str, colors: &[[u8; 3]]) -> String {
+    let name = escape_html(name);
+    let header = colors
+        .iter()
+        .map(|&bg| {
+            let hex = color_math::to_hex(bg);
+            format!(r#"<th><div class="chip" style="background:{hex}"></div>{hex}</th>"#)
+        })
+        .collect::<String>();
+    let rows = colors
+        .iter()
+        .map(|&fg| {
+            let fg_hex = color_math::to_hex(fg);
+            let cells = colors
+                .iter()
+                .map(|&bg| {
+                    if bg == fg {
+                        return r#"<td class="same"></td>"#.to_string();
+                    }
+                    format!(
+                        r#"<td style="background:{bg};color:{fg_hex}"><span class="sample">Aa</span><span class="result">{result}</span></td>"#,
+                        bg = color_math::to_hex(bg),
+                        result = export::contrast_cell(fg, bg),
+                    )
+                })
+                .collect::<String>();
+            format!(
+                r#"
+      <tr><th class="row"><div class="chip" style="background:{fg_hex}"></div>{fg_hex}</th>{cells}</tr>"#
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        r#"<!doctype html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{name} — Contrast Grid</title>
+<style>
+  * {{ box-sizing: border-box; margin: 0; padding: 0; }}
+  body {{ font-family: system-ui, sans-serif; color: #1a1a1a; background: #fafaf8; padding: 48px 40px; }}
+  h1 {{ font-size: 36px; margin-bottom: 6px; }}
+  .sub {{ color: #777; font-size: 14px; margin-bottom: 24px; }}
+  table {{ border-collapse: collapse; background: #fff; border: 1px solid #e6e6e0; }}
+  th {{ font-family: ui-monospace, monospace; font-size: 11px; font-weight: 600; color: #555; padding: 8px; text-align: left; }}
+  th.row {{ white-space: nowrap; }}
+  td {{ width: 120px; height: 72px; padding: 8px; border: 1px solid #f0f0ea; vertical-align: top; }}
+  td.same {{ background: repeating-linear-gradient(45deg, #f4f4f0, #f4f4f0 6px, #fff 6px, #fff 12px); }}
+  .chip {{ width: 24px; height: 24px; border-radius: 6px; border: 1px solid rgba(0,0,0,.08); margin-bottom: 4px; }}
+  .sample {{ display: block; font-size: 22px; font-weight: 700; }}
+  .result {{ font-family: ui-monospace, monospace; font-size: 11px; }}
+  .badge {{ display: inline-block; font-size: 10px; font-weight: 700; padding: 2px 6px; border-radius: 4px; }}
+  .pass {{ background: #dcfce7; color: #166534; }}
+  .large {{ background: #fef9c3; color: #854d0e; }}
+  .fail {{ background: #fee2e2; color: #991b1b; }}
+  footer {{ margin-top: 48px; font-size: 11px; color: #bbb; }}
+</style>
+</head>
+<body>
+  <h1>{name}</h1>
+  <div class="sub">Text color (rows) on background (columns), WCAG 2 contrast</div>
+  <table>
+    <tr><th></th>{header}</tr>{rows}
+  </table>
+  <footer>Made with Pixnib</footer>
+</body>
+</html>"#
+    )
+}
+
+/// 3x5 bitmap glyphs for the handful of characters the PNG grid needs; each
+/// row is three bits, most significant on the left.
+fn glyph(c: char) -> [u8; 5] {
+    match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b111, 0b100, 0b100, 0b100, 0b111],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b111, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        _ => [0; 5],
+    }
+}
+
+fn text_width(text: &str, scale: u32) -> u32 {
+    (text.chars().count() as u32 * 4).saturating_sub(1) * scale
+}
+
+fn draw_text(img: &mut image::RgbImage, text: &str, x: u32, y: u32, scale: u32, ink: [u8; 3]) {
+    for (i, c) in text.chars().enumerate() {
+        let left = x + i as u32 * 4 * scale;
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                fill(
+                    img,
+                    left + col * scale,
+                    y + row as u32 * scale,
+                    scale,
+                    scale,
+                    ink,
+                );
+            }
+        }
+    }
+}
+
+fn fill(img: &mut image::RgbImage, x: u32, y: u32, width: u32, height: u32, color: [u8; 3]) {
+    for py in y..(y + height).min(img.height()) {
+        for px in x..(x + width).min(img.width()) {
+            img.put_pixel(px, py, image::Rgb(color));
+        }
+    }
+}
+
+fn png(colors: &[[u8; 3]], options: &PngOptions) -> Result<Vec<u8>, String> {
+    if colors.len() > MAX_PNG_COLORS {
+        return Err(format!(
+            "Contrast grid PNGs are limited to {} colors, got {}",
+            MAX_PNG_COLORS,
+            colors.len()
+        ));
+    }
+    let n = colors.len() as u32;
+    let mut img = image::RgbImage::from_pixel(
+        (n + 1) * CELL_WIDTH,
+        (n + 1) * CELL_HEIGHT,
+        image::Rgb(PAPER),
+    );
+    let padding = 10;
+
+    // Header swatches: backgrounds across the top, text colors down the side
+    for (i, &color) in colors.iter().enumerate() {
+        let offset = (i as u32 + 1) * CELL_WIDTH;
+        let hex = color_math::to_hex(color);
+        let ink = color_math::readable_text_color(color, true);
+        fill(&mut img, offset, 0, CELL_WIDTH, CELL_HEIGHT, color);
+        draw_text(&mut img, &hex, offset + padding, padding, BADGE_SCALE, ink);
+        let offset = (i as u32 + 1) * CELL_HEIGHT;
+        fill(&mut img, 0, offset, CELL_WIDTH, CELL_HEIGHT, color);
+        draw_text(&mut img, &hex, padding, offset + padding, BADGE_SCALE, ink);
+    }
+
+    for (row, &fg) in colors.iter().enumerate() {
+        for (col, &bg) in colors.iter().enumerate() {
+            if fg == bg {
+                continue;
+            }
+            let x = (col as u32 + 1) * CELL_WIDTH;
+            let y = (row as u32 + 1) * CELL_HEIGHT;
+            fill(&mut img, x + 1, y + 1, CELL_WIDTH - 2, CELL_HEIGHT - 2, bg);
+
+            let contrast::ContrastCell { ratio, levels } = contrast::cell(fg, bg);
+            let ratio = format!("{ratio:.2}:1");
+            draw_text(&mut img, &ratio, x + padding, y + padding, TEXT_SCALE, fg);
+
+            let (label, badge_bg, badge_ink) = badge(levels);
+            let badge_y = y + CELL_HEIGHT - padding - 5 * BADGE_SCALE - 8;
+            let badge_width = text_width(label, BADGE_SCALE) + 8;
+            fill(
+                &mut img,
+                x + padding,
+                badge_y,
+                badge_width,
+                5 * BADGE_SCALE + 8,
+                badge_bg,
+            );
+            draw_text(
+                &mut img,
+                label,
+                x + padding + 4,
+                badge_y + 4,
+                BADGE_SCALE,
+                badge_ink,
+            );
+        }
+    }
+
+    png_encoder::encode_rgb(&img, options)
+}
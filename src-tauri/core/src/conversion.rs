@@ -0,0 +1,114 @@
+//! The representations reported alongside hex and RGB wherever a color is
+//! handed to the frontend, so it can show every format without redoing the
+//! math in JS, and conversion of CSS colors between spaces.
+
+use crate::color_math::{self, Cmyk, Hsl, Hsv, Oklab, Oklch};
+use crate::import;
+use serde::{Deserialize, Serialize};
+
+/// HSL and HSV hue in degrees, their other channels and CMYK in percent;
+/// OKLCH lightness from 0 to 1.
+/// Missing fields default to zero so structs flattening this still load
+/// from before it existed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Representations {
+    pub hsl: Hsl,
+    pub hsv: Hsv,
+    /// Naive (profile-free); see `print_gamut` for press profiles
+    pub cmyk: Cmyk,
+    pub oklch: Oklch,
+}
+
+pub fn representations(rgb: [u8; 3]) -> Representations {
+    Representations {
+        hsl: color_math::rgb_to_hsl(rgb),
+        hsv: color_math::rgb_to_hsv(rgb),
+        cmyk: color_math::rgb_to_cmyk(rgb),
+        oklch: color_math::rgb_to_oklch(rgb),
+    }
+}
+
+/// A color in every supported space.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Conversion {
+    pub hex: String,
+    pub rgb: [u8; 3],
+    #[serde(flatten)]
+    pub representations: Representations,
+    pub oklab: Oklab,
+    /// The input lies outside sRGB; `hex` and `rgb` are clipped into it
+    pub clipped: bool,
+}
+
+/// Convert a CSS color (hex, `rgb()`, `oklab()` or `oklch()`) into every
+/// supported space. OKLab and OKLCH input is reported back as given rather
+/// than from the rounded sRGB color.
+pub fn convert(color: &str) -> Result<Conversion, String> {
+    let (rgb, oklab, clipped) = match parse_oklab(color) {
+        Some(oklab) => {
+            let oklab = oklab?;
+            let mapped = color_math::oklab_to_rgb_mapped(oklab);
+            (mapped.rgb, oklab, mapped.clipped)
+        }
+        None => {
+            let rgb = import::parse_css_color(color)?;
+            (rgb, color_math::rgb_to_oklab(rgb), false)
+        }
+    };
+    let mut representations = representations(rgb);
+    representations.oklch = color_math::oklab_to_oklch(oklab);
+    Ok(Conversion {
+        hex: color_math::to_hex(rgb),
+        rgb,
+        representations,
+        oklab,
+        clipped,
+    })
+}
+
+/// Parse CSS `oklab(L a b)` or `oklch(L C H)`, `None` for any other
+/// notation. Lightness is a number from 0 to 1 or a percentage; chroma and
+/// a/b are numbers or percentages of 0.4; hue is in degrees. Alpha is
+/// dropped.
+pub fn parse_oklab(value: &str) -> Option<Result<Oklab, String>> {
+    let lower = value.trim().to_ascii_lowercase();
+    let (polar, args) = match lower.strip_prefix("oklch(") {
+        Some(args) => (true, args),
+        None => (false, lower.strip_prefix("oklab(")?),
+    };
+    Some(components(args, polar).ok_or_else(|| format!("Invalid CSS color: {}", value.trim())))
+}
+
+fn components(args: &str, polar: bool) -> Option<Oklab> {
+    let channels = args.strip_suffix(')')?.split('/').next()?;
+    let [l, second, third] = channels.split_whitespace().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    let l = component(l, 1.0)?;
+    if polar {
+        let c = component(second, 0.4)?.max(0.0);
+        // Hue takes no percentages
+        let h = third.strip_suffix("deg").unwrap_or(third);
+        let h = component(h, f64::NAN)?;
+        return Some(color_math::oklch_to_oklab(Oklch { l, c, h }));
+    }
+    Some(Oklab {
+        l,
+        a: component(second, 0.4)?,
+        b: component(third, 0.4)?,
+    })
+}
+
+/// A number, `none` (zero) or a percentage of `full`.
+fn component(part: &str, full: f64) -> Option<f64> {
+    if part == "none" {
+        return Some(0.0);
+    }
+    let v = match part.strip_suffix('%') {
+        Some(percent) => percent.parse::<f64>().ok()? / 100.0 * full,
+        None => part.parse::<f64>().ok()?,
+    };
+    v.is_finite().then_some(v)
+}
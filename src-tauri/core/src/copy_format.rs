@@ -0,0 +1,65 @@
+use crate::color_math;
+use serde::{Deserialize, Serialize};
+
+/// Text format used when the backend copies a color (copy-last, format
+/// cycling). All but custom match the frontend's formats of the same name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CopyFormat {
+    #[default]
+    Hex,
+    Rgb,
+    Hsl,
+    /// CSS `oklch()`
+    Oklch,
+    /// The user's `copy_template`
+    Custom,
+}
+
+impl CopyFormat {
+    /// HEX → RGB → HSL → OKLCH → custom → HEX; custom is skipped without a
+    /// template.
+    pub fn next(self, has_template: bool) -> CopyFormat {
+        match self {
+            CopyFormat::Hex => CopyFormat::Rgb,
+            CopyFormat::Rgb => CopyFormat::Hsl,
+            CopyFormat::Hsl => CopyFormat::Oklch,
+            CopyFormat::Oklch if has_template => CopyFormat::Custom,
+            CopyFormat::Oklch | CopyFormat::Custom => CopyFormat::Hex,
+        }
+    }
+}
+
+/// Render `rgb` in `format`. Custom templates may use `{hex}`, `{r}`, `{g}`,
+/// `{b}`, `{h}`, `{s}` and `{l}`; without a template custom falls back to hex.
+pub fn format(rgb: [u8; 3], format: CopyFormat, template: Option<&str>) -> String {
+    let hex = color_math::to_hex(rgb);
+    let hsl = color_math::rgb_to_hsl(rgb);
+    let (h, s, l) = (
+        hsl.h.round() as i32,
+        hsl.s.round() as i32,
+        hsl.l.round() as i32,
+    );
+    match (format, template) {
+        (CopyFormat::Hex, _) | (CopyFormat::Custom, None) => hex,
+        (CopyFormat::Rgb, _) => format!("rgb({}, {}, {})", rgb[0], rgb[1], rgb[2]),
+        (CopyFormat::Hsl, _) => format!("hsl({h}, {s}%, {l}%)"),
+        (CopyFormat::Oklch, _) => oklch(rgb),
+        (CopyFormat::Custom, Some(template)) => template
+            .replace("{hex}", &hex)
+            .replace("{r}", &rgb[0].to_string())
+            .replace("{g}", &rgb[1].to_string())
+            .replace("{b}", &rgb[2].to_string())
+            .replace("{h}", &h.to_string())
+            .replace("{s}", &s.to_string())
+            .replace("{l}", &l.to_string()),
+    }
+}
+
+/// `oklch(L% C H)` with lightness in percent; grays get hue 0, which CSS
+/// ignores at zero chroma anyway.
+fn oklch(rgb: [u8; 3]) -> String {
+    let lch = color_math::rgb_to_oklch(rgb);
+    let h = if lch.c < 0.0005 { 0.0 } else { lch.h };
+    format!("oklch({:.1}% {:.3} {:.1})", lch.l * 100.0, lch.c, h)
+}
@@ -0,0 +1,270 @@
+use crate::color_math::{self, Oklch};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// CIEDE2000 distance two colors need under simulation to count as
+/// distinguishable, unless the caller asks for another.
+pub const DEFAULT_MIN_DISTANCE: f64 = 10.0;
+/// Largest palette adjusted; the work grows with the square of the count,
+/// and no larger palette can stay distinguishable with a deficiency anyway.
+const MAX_COLORS: usize = 32;
+
+/// Adjustments tried around each original color: OKLCH lightness in steps
+/// of `LIGHTNESS_STEP` and hue in steps of `HUE_STEP` degrees, up to
+/// `MAX_STEPS` either way.
+const LIGHTNESS_STEP: f64 = 0.02;
+const HUE_STEP: f64 = 5.0;
+const MAX_STEPS: i32 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CvdType {
+    /// No working L cones (red-blind)
+    Protanopia,
+    /// No working M cones (green-blind), the most common
+    Deuteranopia,
+    /// No working S cones (blue-blind)
+    Tritanopia,
+}
+
+impl CvdType {
+    /// Machado, Oliveira & Fernandes (2009) simulation at full severity, on
+    /// linear sRGB.
+    fn matrix(self) -> [[f64; 3]; 3] {
+        match self {
+            CvdType::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            CvdType::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            CvdType::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+}
+
+/// How `rgb` looks to someone with `cvd`.
+pub fn simulate(rgb: [u8; 3], cvd: CvdType) -> [u8; 3] {
+    let linear = rgb.map(color_math::srgb_to_linear);
+    let m = cvd.matrix();
+    color_math::linear_to_rgb(std::array::from_fn(|i| {
+        m[i][0] * linear[0] + m[i][1] * linear[1] + m[i][2] * linear[2]
+    }))
+}
+
+/// One color moved by [`adjust`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorChange {
+    pub index: usize,
+    pub from: String,
+    pub to: String,
+    /// OKLCH lightness change, -1–1
+    pub lightness: f64,
+    /// Hue rotation in degrees
+    pub hue: f64,
+    /// CIEDE2000 between the original and the adjusted color
+    pub delta_e: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CvdReport {
+    pub cvd: CvdType,
+    pub min_distance: f64,
+    /// Adjusted palette, as `#RRGGBB`
+    pub colors: Vec<String>,
+    pub changes: Vec<ColorChange>,
+    /// Index pairs still too close under simulation that no allowed
+    /// adjustment could separate
+    pub unresolved: Vec<[usize; 2]>,
+    /// Smallest simulated distance among the pairs that must stay apart,
+    /// before and after; `None` when there are no such pairs
+    pub closest_before: Option<f64>,
+    pub closest_after: Option<f64>,
+}
+
+/// Pairs that must stay apart: those distinguishable with normal vision.
+/// Shades that were meant to be close are left alone.
+fn guarded_pairs(colors: &[[u8; 3]], min_distance: f64) -> Vec<[usize; 2]> {
+    let mut pairs = Vec::new();
+    for i in 0..colors.len() {
+        for j in i + 1..colors.len() {
+            if color_math::delta_e(colors[i], colors[j]) >= min_distance {
+                pairs.push([i, j]);
+            }
+        }
+    }
+    pairs
+}
+
+/// Indices of the colors each color must stay apart from.
+fn partners(count: usize, pairs: &[[usize; 2]]) -> Vec<Vec<usize>> {
+    let mut partners = vec![Vec::new(); count];
+    for &[i, j] in pairs {
+        partners[i].push(j);
+        partners[j].push(i);
+    }
+    partners
+}
+
+fn simulate_all(colors: &[[u8; 3]], cvd: CvdType) -> Vec<[u8; 3]> {
+    colors.iter().map(|&rgb| simulate(rgb, cvd)).collect()
+}
+
+fn closest(simulated: &[[u8; 3]], pairs: &[[usize; 2]]) -> Option<f64> {
+    pairs
+        .iter()
+        .map(|&[i, j]| color_math::delta_e(simulated[i], simulated[j]))
+        .min_by(f64::total_cmp)
+}
+
+/// Smallest simulated distance from a color, simulated as `simulated_rgb`,
+/// to its `partners`.
+fn separation(simulated: &[[u8; 3]], partners: &[usize], simulated_rgb: [u8; 3]) -> f64 {
+    partners
+        .iter()
+        .map(|&other| color_math::delta_e(simulated_rgb, simulated[other]))
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Best replacement for color `index`: the one closest to its original that
+/// clears `min_distance` from its guarded partners, otherwise the one that
+/// separates it most. `None` when nothing beats the current color.
+fn best_candidate(
+    original: [u8; 3],
+    simulated: &[[u8; 3]],
+    partners: &[usize],
+    index: usize,
+    cvd: CvdType,
+    min_distance: f64,
+) -> Option<([u8; 3], f64)> {
+    let base = color_math::rgb_to_oklch(original);
+    let current = separation(simulated, partners, simulated[index]);
+    let mut best_fit: Option<([u8; 3], f64)> = None;
+    let mut best_spread: Option<([u8; 3], f64)> = None;
+    for dl in -MAX_STEPS..=MAX_STEPS {
+        for dh in -MAX_STEPS..=MAX_STEPS {
+            let rgb = color_math::oklch_to_rgb(Oklch {
+                l: (base.l + dl as f64 * LIGHTNESS_STEP).clamp(0.0, 1.0),
+                c: base.c,
+                h: (base.h + dh as f64 * HUE_STEP).rem_euclid(360.0),
+            });
+            let spread = separation(simulated, partners, simulate(rgb, cvd));
+            let cost = color_math::delta_e(original, rgb);
+            if spread >= min_distance {
+                if !matches!(best_fit, Some((_, c)) if c <= cost) {
+                    best_fit = Some((rgb, cost));
+                }
+            } else if spread > current && !matches!(best_spread, Some((_, s)) if s >= spread) {
+                best_spread = Some((rgb, spread));
+            }
+        }
+    }
+    best_fit.or(best_spread.map(|(rgb, _)| (rgb, color_math::delta_e(original, rgb))))
+}
+
+/// Propose the smallest hue and lightness changes that keep every pair of
+/// `colors` that is distinguishable with normal vision at least
+/// `min_distance` apart (CIEDE2000) as seen with `cvd`. Colors are moved one
+/// at a time, always fixing the closest pair by moving whichever of its two
+/// colors needs the smaller change. Palettes over [`MAX_COLORS`] colors are
+/// refused.
+pub fn adjust(colors: &[[u8; 3]], cvd: CvdType, min_distance: f64) -> Result<CvdReport, String> {
+    if !min_distance.is_finite() || min_distance <= 0.0 {
+        return Err("Minimum distance must be a positive delta-E".to_string());
+    }
+    if colors.len() > MAX_COLORS {
+        return Err(format!(
+            "Palettes of up to {} colors can be adjusted, got {}",
+            MAX_COLORS,
+            colors.len()
+        ));
+    }
+    let pairs = guarded_pairs(colors, min_distance);
+    let partners = partners(colors.len(), &pairs);
+    let mut adjusted = colors.to_vec();
+    let mut simulated = simulate_all(colors, cvd);
+    let closest_before = closest(&simulated, &pairs);
+    let mut stuck: HashSet<[usize; 2]> = HashSet::new();
+
+    // Each round settles one pair for good or gives up on it
+    for _ in 0..=pairs.len() {
+        let worst = pairs
+            .iter()
+            .filter(|pair| !stuck.contains(*pair))
+            .map(|&[i, j]| ([i, j], color_math::delta_e(simulated[i], simulated[j])))
+            .filter(|(_, d)| *d < min_distance)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        let Some(([i, j], _)) = worst else {
+            break;
+        };
+        let moves = [i, j].map(|index| {
+            best_candidate(
+                colors[index],
+                &simulated,
+                &partners[index],
+                index,
+                cvd,
+                min_distance,
+            )
+            .map(|(rgb, cost)| (index, rgb, cost))
+        });
+        match moves
+            .into_iter()
+            .flatten()
+            .min_by(|a, b| a.2.total_cmp(&b.2))
+        {
+            Some((index, rgb, _)) => {
+                adjusted[index] = rgb;
+                simulated[index] = simulate(rgb, cvd);
+            }
+            None => {
+                stuck.insert([i, j]);
+            }
+        }
+    }
+
+    let changes = (0..colors.len())
+        .filter(|&i| adjusted[i] != colors[i])
+        .map(|i| {
+            let (from, to) = (
+                color_math::rgb_to_oklch(colors[i]),
+                color_math::rgb_to_oklch(adjusted[i]),
+            );
+            ColorChange {
+                index: i,
+                from: color_math::to_hex(colors[i]),
+                to: color_math::to_hex(adjusted[i]),
+                lightness: to.l - from.l,
+                hue: (to.h - from.h + 180.0).rem_euclid(360.0) - 180.0,
+                delta_e: color_math::delta_e(colors[i], adjusted[i]),
+            }
+        })
+        .collect();
+    let unresolved = pairs
+        .iter()
+        .copied()
+        .filter(|&[i, j]| color_math::delta_e(simulated[i], simulated[j]) < min_distance)
+        .collect();
+    Ok(CvdReport {
+        cvd,
+        min_distance,
+        colors: adjusted
+            .iter()
+            .map(|rgb| color_math::to_hex(*rgb))
+            .collect(),
+        changes,
+        unresolved,
+        closest_before,
+        closest_after: closest(&simulated, &pairs),
+    })
+}
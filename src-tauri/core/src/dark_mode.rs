@@ -0,0 +1,44 @@
+use crate::color_math::{self, GamutMapped, Oklch};
+use crate::contrast::{BLACK, WHITE};
+
+/// Lightness step used when nudging a derived color to restore contrast.
+const NUDGE: f64 = 0.01;
+
+/// Dark-theme counterpart of a light-theme color: OKLCH lightness is mirrored
+/// around mid-gray while hue and chroma are kept. A color that read well on
+/// white must read at least as well on black afterwards, so if gamut clipping
+/// cost contrast the lightness is nudged up until it is restored.
+pub fn dark_counterpart(rgb: [u8; 3]) -> [u8; 3] {
+    dark_counterpart_mapped(rgb).rgb
+}
+
+/// [`dark_counterpart`], reporting whether the result was clipped to sRGB.
+pub fn dark_counterpart_mapped(rgb: [u8; 3]) -> GamutMapped {
+    let lch = color_math::rgb_to_oklch(rgb);
+    let target = color_math::contrast_ratio(rgb, WHITE);
+    let mut dark = Oklch {
+        l: 1.0 - lch.l,
+        ..lch
+    };
+    let mut out = color_math::oklch_to_rgb_mapped(dark);
+    while color_math::contrast_ratio(out.rgb, BLACK) < target && dark.l < 1.0 {
+        dark.l = (dark.l + NUDGE).min(1.0);
+        out = color_math::oklch_to_rgb_mapped(dark);
+    }
+    out
+}
+
+pub fn dark_palette(colors: &[[u8; 3]]) -> Vec<String> {
+    colors
+        .iter()
+        .map(|rgb| color_math::to_hex(dark_counterpart(*rgb)))
+        .collect()
+}
+
+/// [`dark_palette`] with the gamut outcome of each color, in order.
+pub fn dark_palette_mapped(colors: &[[u8; 3]]) -> Vec<GamutMapped> {
+    colors
+        .iter()
+        .map(|rgb| dark_counterpart_mapped(*rgb))
+        .collect()
+}
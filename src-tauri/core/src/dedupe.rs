@@ -0,0 +1,54 @@
+use crate::color_math;
+use serde::Serialize;
+
+/// Colors folded into one kept color.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergedColors {
+    pub kept: String,
+    pub merged: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DedupReport {
+    /// CIEDE2000 distance at or under which colors were merged
+    pub threshold: f64,
+    pub before: usize,
+    pub after: usize,
+    /// Only kept colors that absorbed at least one other
+    pub merged: Vec<MergedColors>,
+}
+
+/// Collapse near-identical colors: each color within `threshold`
+/// (CIEDE2000) of an earlier kept color is dropped in its favour. Order of
+/// the kept colors is preserved. Colors that don't parse are kept as they are.
+pub fn dedupe(colors: &[String], threshold: f64) -> (Vec<String>, DedupReport) {
+    let mut kept: Vec<(String, Option<[u8; 3]>, Vec<String>)> = Vec::new();
+    for hex in colors {
+        let rgb = color_math::parse_hex(hex).ok();
+        let target = rgb.and_then(|rgb| {
+            kept.iter_mut().find(|(_, other, _)| {
+                other.is_some_and(|other| color_math::delta_e(rgb, other) <= threshold)
+            })
+        });
+        match target {
+            Some((_, _, merged)) => merged.push(hex.clone()),
+            None => kept.push((hex.clone(), rgb, Vec::new())),
+        }
+    }
+    let report = DedupReport {
+        threshold,
+        before: colors.len(),
+        after: kept.len(),
+        merged: kept
+            .iter()
+            .filter(|(_, _, merged)| !merged.is_empty())
+            .map(|(hex, _, merged)| MergedColors {
+                kept: hex.clone(),
+                merged: merged.clone(),
+            })
+            .collect(),
+    };
+    (kept.into_iter().map(|(hex, _, _)| hex).collect(), report)
+}
@@ -0,0 +1,836 @@
+use crate::color_math::{self, parse_hex};
+use crate::color_names;
+use crate::contrast::{self, BLACK, WHITE};
+use crate::Palette;
+use serde::{Deserialize, Serialize};
+
+/// Lowercase, dash-separated identifier, matching the frontend's export naming.
+pub fn slug(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c);
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    let out = out.trim_end_matches('-');
+    if out.is_empty() {
+        "color".to_string()
+    } else {
+        out.to_string()
+    }
+}
+
+/// How the file name is derived from the palette name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NamingConvention {
+    /// `brand-colors`
+    #[default]
+    Kebab,
+    /// `brand_colors`
+    Snake,
+    /// `brandColors`
+    Camel,
+    /// The name as typed, minus characters file names can't hold
+    Original,
+}
+
+/// A palette's own export settings, so repeat exports need no dialog.
+/// Unset fields fall back to the workspace's export defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteExport {
+    /// Registry id of the exporter
+    pub exporter: Option<String>,
+    pub directory: Option<String>,
+    #[serde(default)]
+    pub naming: NamingConvention,
+}
+
+/// File name (without extension) for a palette called `name`.
+pub fn file_stem(name: &str, naming: NamingConvention) -> String {
+    let slug = slug(name);
+    match naming {
+        NamingConvention::Kebab => slug,
+        NamingConvention::Snake => slug.replace('-', "_"),
+        NamingConvention::Camel => slug
+            .split('-')
+            .enumerate()
+            .map(|(i, word)| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) if i > 0 => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                    _ => word.to_string(),
+                }
+            })
+            .collect(),
+        NamingConvention::Original => {
+            let clean: String = name
+                .chars()
+                .filter(|c| !c.is_control() && !r#"<>:"/\|?*"#.contains(*c))
+                .collect();
+            let clean = clean.trim().trim_end_matches('.');
+            if clean.is_empty() {
+                slug
+            } else {
+                clean.to_string()
+            }
+        }
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Parse every palette color, failing on the first malformed hex value.
+pub fn palette_rgb(palette: &Palette) -> Result<Vec<[u8; 3]>, String> {
+    palette.colors.iter().map(|hex| parse_hex(hex)).collect()
+}
+
+/// Ratio plus WCAG badge markup for one text/background pair.
+pub fn contrast_cell(rgb: [u8; 3], bg: [u8; 3]) -> String {
+    let contrast::ContrastCell { ratio, levels } = contrast::cell(rgb, bg);
+    let badge = if levels.aaa_normal {
+        r#"<span class="badge pass">AAA</span>"#
+    } else if levels.aa_normal {
+        r#"<span class="badge pass">AA</span>"#
+    } else if levels.aa_large {
+        r#"<span class="badge large">AA Large</span>"#
+    } else {
+        r#"<span class="badge fail">Fail</span>"#
+    };
+    format!("{ratio:.2}:1 {badge}")
+}
+
+/// A standalone, shareable HTML report: a thumbnail strip of the whole palette,
+/// then one row per color with its name (in `names`' language), every
+/// color-space value and WCAG contrast against white and black.
+pub fn palette_html_report(
+    palette: &Palette,
+    names: color_names::NameLocale,
+) -> Result<String, String> {
+    let colors = palette_rgb(palette)?;
+    let name = escape_html(&palette.name);
+
+    let strip_width = colors.len().max(1) * 40;
+    let strip = colors
+        .iter()
+        .enumerate()
+        .map(|(i, rgb)| {
+            format!(
+                r#"<rect x="{}" y="0" width="40" height="48" fill="{}"/>"#,
+                i * 40,
+                color_math::to_hex(*rgb)
+            )
+        })
+        .collect::<String>();
+
+    let rows = colors
+        .iter()
+        .map(|&rgb| {
+            let hex = color_math::to_hex(rgb);
+            let hsl = color_math::rgb_to_hsl(rgb);
+            let hsv = color_math::rgb_to_hsv(rgb);
+            let cmyk = color_math::rgb_to_cmyk(rgb);
+            let lab = color_math::rgb_to_lab(rgb);
+            let oklch = color_math::rgb_to_oklch(rgb);
+            format!(
+                r#"
+      <tr>
+        <td><div class="chip" style="background:{hex}"></div></td>
+        <td class="name" lang="{lang}">{name}</td>
+        <td class="mono">{hex}</td>
+        <td class="mono">rgb({r}, {g}, {b})<br>hsl({hh:.0}, {hs:.0}%, {hl:.0}%)<br>hsv({vh:.0}, {vs:.0}%, {vv:.0}%)</td>
+        <td class="mono">cmyk({c:.0}%, {m:.0}%, {y:.0}%, {k:.0}%)<br>lab({ll:.1} {la:.1} {lb:.1})<br>oklch({ol:.3} {oc:.3} {oh:.1})</td>
+        <td>{on_white}</td>
+        <td>{on_black}</td>
+      </tr>"#,
+                name = color_names::nearest_name_in(rgb, names),
+                lang = names.tag(),
+                r = rgb[0],
+                g = rgb[1],
+                b = rgb[2],
+                hh = hsl.h,
+                hs = hsl.s,
+                hl = hsl.l,
+                vh = hsv.h,
+                vs = hsv.s,
+                vv = hsv.v,
+                c = cmyk.c,
+                m = cmyk.m,
+                y = cmyk.y,
+                k = cmyk.k,
+                ll = lab.l,
+                la = lab.a,
+                lb = lab.b,
+                ol = oklch.l,
+                oc = oklch.c,
+                oh = oklch.h,
+                on_white = contrast_cell(rgb, WHITE),
+                on_black = contrast_cell(rgb, BLACK),
+            )
+        })
+        .collect::<String>();
+
+    Ok(format!(
+        r#"<!doctype html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{name} — Palette Report</title>
+<style>
+  * {{ box-sizing: border-box; margin: 0; padding: 0; }}
+  body {{ font-family: system-ui, sans-serif; color: #1a1a1a; background: #fafaf8; padding: 48px 40px; }}
+  .wrap {{ max-width: 980px; margin: 0 auto; }}
+  h1 {{ font-size: 36px; margin-bottom: 6px; }}
+  .sub {{ color: #777; font-size: 14px; margin-bottom: 24px; }}
+  .strip {{ display: block; width: 100%; height: 48px; border-radius: 12px; margin-bottom: 32px; }}
+  table {{ width: 100%; border-collapse: collapse; background: #fff; border: 1px solid #e6e6e0; }}
+  th {{ text-align: left; font-size: 11px; text-transform: uppercase; letter-spacing: .05em; color: #999; padding: 10px 12px; border-bottom: 1px solid #e6e6e0; }}
+  td {{ padding: 10px 12px; border-bottom: 1px solid #f0f0ea; font-size: 13px; vertical-align: middle; }}
+  .chip {{ width: 48px; height: 48px; border-radius: 8px; border: 1px solid rgba(0,0,0,.08); }}
+  .name {{ text-transform: capitalize; font-weight: 600; }}
+  .mono {{ font-family: ui-monospace, monospace; font-size: 11px; line-height: 1.6; }}
+  .badge {{ display: inline-block; font-size: 10px; font-weight: 700; padding: 2px 6px; border-radius: 4px; }}
+  .pass {{ background: #dcfce7; color: #166534; }}
+  .large {{ background: #fef9c3; color: #854d0e; }}
+  .fail {{ background: #fee2e2; color: #991b1b; }}
+  footer {{ margin-top: 48px; font-size: 11px; color: #bbb; }}
+</style>
+</head>
+<body>
+  <div class="wrap">
+    <h1>{name}</h1>
+    <div class="sub">{count} colors</div>
+    <svg class="strip" viewBox="0 0 {strip_width} 48" preserveAspectRatio="none">{strip}</svg>
+    <table>
+      <tr><th></th><th>Name</th><th>Hex</th><th>RGB / HSL / HSV</th><th>CMYK / Lab / OKLCH</th><th>On white</th><th>On black</th></tr>{rows}
+    </table>
+    <footer>Made with Pixnib</footer>
+  </div>
+</body>
+</html>"#,
+        count = colors.len(),
+    ))
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TerminalFormat {
+    WindowsTerminal,
+    Alacritty,
+    Kitty,
+}
+
+const ANSI_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Terminal roles taken from a 16+ color palette: the first 16 colors are the
+/// ANSI slots (normal 0-7, bright 8-15); optional 17th and 18th colors override
+/// the background and foreground, which otherwise default to ANSI black/white.
+struct TerminalScheme {
+    ansi: [String; 16],
+    background: String,
+    foreground: String,
+    selection: String,
+}
+
+impl TerminalScheme {
+    fn from_palette(palette: &Palette) -> Result<Self, String> {
+        let colors: Vec<String> = palette_rgb(palette)?
+            .into_iter()
+            .map(color_math::to_hex)
+            .collect();
+        if colors.len() < 16 {
+            return Err(format!(
+                "Terminal themes need at least 16 colors, palette has {}",
+                colors.len()
+            ));
+        }
+        let ansi: [String; 16] = std::array::from_fn(|i| colors[i].clone());
+        Ok(TerminalScheme {
+            background: colors.get(16).unwrap_or(&ansi[0]).clone(),
+            foreground: colors.get(17).unwrap_or(&ansi[7]).clone(),
+            selection: ansi[8].clone(),
+            ansi,
+        })
+    }
+}
+
+fn windows_terminal_theme(name: &str, scheme: &TerminalScheme) -> String {
+    let mut fields = vec![
+        ("name".to_string(), name.to_string()),
+        ("background".to_string(), scheme.background.clone()),
+        ("foreground".to_string(), scheme.foreground.clone()),
+        ("cursorColor".to_string(), scheme.foreground.clone()),
+        ("selectionBackground".to_string(), scheme.selection.clone()),
+    ];
+    for (i, hex) in scheme.ansi.iter().enumerate() {
+        let base = ANSI_NAMES[i % 8];
+        let key = if i < 8 {
+            base.to_string()
+        } else {
+            // brightBlack, brightRed, …
+            format!("bright{}{}", base[..1].to_uppercase(), &base[1..])
+        };
+        fields.push((key, hex.clone()));
+    }
+    let map: serde_json::Map<String, serde_json::Value> = fields
+        .into_iter()
+        .map(|(k, v)| (k, serde_json::Value::String(v)))
+        .collect();
+    serde_json::to_string_pretty(&map).unwrap_or_default()
+}
+
+fn alacritty_theme(name: &str, scheme: &TerminalScheme) -> String {
+    let mut out = format!(
+        "# {name}\n\n[colors.primary]\nbackground = \"{}\"\nforeground = \"{}\"\n\n\
+         [colors.cursor]\ntext = \"{}\"\ncursor = \"{}\"\n\n\
+         [colors.selection]\ntext = \"CellForeground\"\nbackground = \"{}\"\n",
+        scheme.background,
+        scheme.foreground,
+        scheme.background,
+        scheme.foreground,
+        scheme.selection,
+    );
+    for (section, offset) in [("normal", 0), ("bright", 8)] {
+        out.push_str(&format!("\n[colors.{section}]\n"));
+        for (i, color) in ANSI_NAMES.iter().enumerate() {
+            out.push_str(&format!("{color} = \"{}\"\n", scheme.ansi[offset + i]));
+        }
+    }
+    out
+}
+
+fn kitty_theme(name: &str, scheme: &TerminalScheme) -> String {
+    let mut out = format!(
+        "# {name}\n\nbackground {}\nforeground {}\ncursor {}\n\
+         selection_background {}\nselection_foreground {}\n\n",
+        scheme.background,
+        scheme.foreground,
+        scheme.foreground,
+        scheme.selection,
+        scheme.foreground,
+    );
+    for (i, hex) in scheme.ansi.iter().enumerate() {
+        out.push_str(&format!("color{i} {hex}\n"));
+    }
+    out
+}
+
+pub fn terminal_theme(palette: &Palette, format: TerminalFormat) -> Result<String, String> {
+    let scheme = TerminalScheme::from_palette(palette)?;
+    Ok(match format {
+        TerminalFormat::WindowsTerminal => windows_terminal_theme(&palette.name, &scheme),
+        TerminalFormat::Alacritty => alacritty_theme(&palette.name, &scheme),
+        TerminalFormat::Kitty => kitty_theme(&palette.name, &scheme),
+    })
+}
+
+/// Minimal VS Code color theme. Roles are assigned by OKLCH lightness: the
+/// darkest colors become editor and sidebar backgrounds, the lightest the
+/// foreground, and the remaining colors (most saturated first) syntax accents.
+/// Mostly light palettes are flipped into a light theme.
+pub fn vscode_theme(palette: &Palette) -> Result<String, String> {
+    let mut colors = palette_rgb(palette)?;
+    if colors.len() < 3 {
+        return Err("A VS Code theme needs at least 3 colors".to_string());
+    }
+    colors.sort_by(|a, b| {
+        color_math::rgb_to_oklch(*a)
+            .l
+            .total_cmp(&color_math::rgb_to_oklch(*b).l)
+    });
+
+    // Light means dark text reads better on it
+    let is_light = |c: &[u8; 3]| color_math::readable_text_color(*c, false) == [0, 0, 0];
+    if colors.iter().filter(|c| is_light(c)).count() * 2 > colors.len() {
+        colors.reverse();
+    }
+    let kind = if is_light(&colors[0]) {
+        "light"
+    } else {
+        "dark"
+    };
+    let bg = color_math::to_hex(colors[0]);
+    let bg_alt = color_math::to_hex(colors[1]);
+    let fg = color_math::to_hex(colors[colors.len() - 1]);
+
+    let mut middle: Vec<[u8; 3]> = colors[2..colors.len() - 1].to_vec();
+    middle.sort_by(|a, b| {
+        color_math::rgb_to_oklch(*b)
+            .c
+            .total_cmp(&color_math::rgb_to_oklch(*a).c)
+    });
+    // The least saturated mid color reads best as a comment
+    let comment = middle
+        .pop()
+        .map(color_math::to_hex)
+        .unwrap_or_else(|| bg_alt.clone());
+    let accents: Vec<String> = middle.into_iter().map(color_math::to_hex).collect();
+    let accent = |i: usize| {
+        if accents.is_empty() {
+            fg.clone()
+        } else {
+            accents[i % accents.len()].clone()
+        }
+    };
+
+    let token = |scope: serde_json::Value, color: String| serde_json::json!({ "scope": scope, "settings": { "foreground": color } });
+
+    let theme = serde_json::json!({
+        "name": palette.name,
+        "type": kind,
+        "colors": {
+            "editor.background": bg,
+            "editor.foreground": fg,
+            "editorCursor.foreground": accent(0),
+            "editor.lineHighlightBackground": bg_alt,
+            "editor.selectionBackground": comment,
+            "editorLineNumber.foreground": comment,
+            "sideBar.background": bg_alt,
+            "sideBar.foreground": fg,
+            "activityBar.background": bg_alt,
+            "activityBar.foreground": fg,
+            "statusBar.background": accent(0),
+            "statusBar.foreground": bg,
+            "titleBar.activeBackground": bg_alt,
+            "titleBar.activeForeground": fg,
+            "tab.activeBackground": bg,
+            "tab.inactiveBackground": bg_alt,
+            "focusBorder": accent(0),
+        },
+        "tokenColors": [
+            token(serde_json::json!(["comment", "punctuation.definition.comment"]), comment.clone()),
+            token(serde_json::json!(["keyword", "storage", "storage.type"]), accent(0)),
+            token(serde_json::json!(["string", "string.quoted"]), accent(1)),
+            token(serde_json::json!(["entity.name.function", "support.function"]), accent(2)),
+            token(serde_json::json!(["constant.numeric", "constant.language"]), accent(3)),
+            token(serde_json::json!(["entity.name.type", "support.type", "entity.name.class"]), accent(4)),
+            token(serde_json::json!(["variable", "meta.definition.variable"]), fg.clone()),
+        ],
+    });
+    serde_json::to_string_pretty(&theme).map_err(|e| format!("Failed to serialize theme: {}", e))
+}
+
+/// Tailwind config extending the theme colors. Tailwind shades run 100–900,
+/// so every run of nine colors becomes one family: `brand` for a palette of
+/// up to nine colors, `brand-1`, `brand-2`, … beyond that (e.g. after
+/// expanding each color into a 9-step scale).
+pub fn tailwind_config(palette: &Palette) -> Result<String, String> {
+    let colors = palette_rgb(palette)?;
+    let base = slug(&palette.name);
+    let families: Vec<&[[u8; 3]]> = colors.chunks(9).collect();
+    let mut out = String::from(
+        "// tailwind.config.js\nmodule.exports = {\n  theme: {\n    extend: {\n      colors: {\n",
+    );
+    for (i, family) in families.iter().enumerate() {
+        let name = if families.len() == 1 {
+            base.clone()
+        } else {
+            format!("{base}-{}", i + 1)
+        };
+        out.push_str(&format!("        '{name}': {{\n"));
+        for (j, rgb) in family.iter().enumerate() {
+            out.push_str(&format!(
+                "          {}: '{}',\n",
+                (j + 1) * 100,
+                color_math::to_hex(*rgb)
+            ));
+        }
+        out.push_str("        },\n");
+    }
+    out.push_str("      },\n    },\n  },\n};\n");
+    Ok(out)
+}
+
+/// Office theme color slots, in the order a 12-color palette maps onto them.
+const OFFICE_SLOTS: [&str; 12] = [
+    "dk1", "lt1", "dk2", "lt2", "accent1", "accent2", "accent3", "accent4", "accent5", "accent6",
+    "hlink", "folHlink",
+];
+
+/// Office's own dark 2, light 2, hyperlink and followed hyperlink colors.
+const OFFICE_DEFAULTS: [[u8; 3]; 4] = [
+    [0x44, 0x54, 0x6A],
+    [0xE7, 0xE6, 0xE6],
+    [0x05, 0x63, 0xC1],
+    [0x95, 0x4F, 0x72],
+];
+
+/// Office theme colors (`clrScheme`), as saved under "Document Themes/Theme
+/// Colors" and picked up by PowerPoint, Word and Excel. A palette of 12 or
+/// more colors maps in slot order: dark 1, light 1, dark 2, light 2,
+/// accents 1–6, hyperlink, followed hyperlink. Smaller palettes get their
+/// darkest and lightest colors as dark 1 and light 1 and the rest, most
+/// saturated first, as accents; the other slots keep Office's defaults.
+pub fn office_theme(palette: &Palette) -> Result<String, String> {
+    let colors = palette_rgb(palette)?;
+    let slots: Vec<[u8; 3]> = if colors.len() >= OFFICE_SLOTS.len() {
+        colors[..OFFICE_SLOTS.len()].to_vec()
+    } else {
+        if colors.len() < 3 {
+            return Err("An Office theme needs at least 3 colors".to_string());
+        }
+        let mut by_lightness = colors;
+        by_lightness.sort_by(|a, b| {
+            color_math::rgb_to_oklch(*a)
+                .l
+                .total_cmp(&color_math::rgb_to_oklch(*b).l)
+        });
+        let dark = by_lightness[0];
+        let light = by_lightness[by_lightness.len() - 1];
+        let mut accents = by_lightness[1..by_lightness.len() - 1].to_vec();
+        accents.sort_by(|a, b| {
+            color_math::rgb_to_oklch(*b)
+                .c
+                .total_cmp(&color_math::rgb_to_oklch(*a).c)
+        });
+        let [dark2, light2, link, followed] = OFFICE_DEFAULTS;
+        let mut slots = vec![dark, light, dark2, light2];
+        slots.extend((0..6).map(|i| accents[i % accents.len()]));
+        slots.extend([link, followed]);
+        slots
+    };
+
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+         <a:clrScheme xmlns:a=\"http://schemas.openxmlformats.org/drawingml/2006/main\" name=\"{}\">\n",
+        escape_html(&palette.name)
+    );
+    for (slot, rgb) in OFFICE_SLOTS.iter().zip(slots) {
+        xml.push_str(&format!(
+            "  <a:{slot}><a:srgbClr val=\"{}\"/></a:{slot}>\n",
+            &color_math::to_hex(rgb)[1..]
+        ));
+    }
+    xml.push_str("</a:clrScheme>\n");
+    Ok(xml)
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EngineFormat {
+    /// Unity `.colors` swatch library, gamma-space like the color picker
+    UnityPresets,
+    /// Unreal `FLinearColor` text, one per line, for pasting into any Linear
+    /// Color property
+    UnrealColors,
+    /// Unreal curve table JSON with R, G, B and A rows, the colors keyed
+    /// evenly from 0 to 1; import it and reference it from a Curve Linear
+    /// Color or material
+    UnrealCurve,
+}
+
+/// Swatches for game engines, so picked colors don't have to be re-entered
+/// by hand. Unreal values are linear, as the engine stores them.
+pub fn engine_swatches(palette: &Palette, format: EngineFormat) -> Result<String, String> {
+    let colors = palette_rgb(palette)?;
+    if colors.is_empty() {
+        return Err("The palette has no colors".to_string());
+    }
+    let linear = |rgb: [u8; 3]| rgb.map(color_math::srgb_to_linear);
+    Ok(match format {
+        EngineFormat::UnityPresets => {
+            let mut text = String::from(
+                "%YAML 1.1\n%TAG !u! tag:unity3d.com,2011:\n--- !u!114 &1\nMonoBehaviour:\n  \
+                 m_ObjectHideFlags: 52\n  m_CorrespondingSourceObject: {fileID: 0}\n  \
+                 m_PrefabInstance: {fileID: 0}\n  m_PrefabAsset: {fileID: 0}\n  \
+                 m_GameObject: {fileID: 0}\n  m_Enabled: 1\n  m_EditorHideFlags: 0\n  \
+                 m_Script: {fileID: 12323, guid: 0000000000000000e000000000000000, type: 0}\n",
+            );
+            text.push_str(&format!(
+                "  m_Name: {}\n  m_EditorClassIdentifier:\n  m_Presets:\n",
+                slug(&palette.name)
+            ));
+            for [r, g, b] in &colors {
+                let [r, g, b] = [r, g, b].map(|c| *c as f32 / 255.0);
+                text.push_str(&format!(
+                    "  - m_Name:\n    m_Color: {{r: {r}, g: {g}, b: {b}, a: 1}}\n"
+                ));
+            }
+            text
+        }
+        EngineFormat::UnrealColors => colors
+            .iter()
+            .map(|&rgb| {
+                let [r, g, b] = linear(rgb);
+                format!("(R={r:.6},G={g:.6},B={b:.6},A=1.000000)\n")
+            })
+            .collect(),
+        EngineFormat::UnrealCurve => {
+            let last = (colors.len() - 1).max(1) as f64;
+            let rows: Vec<serde_json::Value> = ["R", "G", "B", "A"]
+                .iter()
+                .enumerate()
+                .map(|(channel, name)| {
+                    let mut row = serde_json::Map::new();
+                    row.insert("Name".to_string(), (*name).into());
+                    for (i, &rgb) in colors.iter().enumerate() {
+                        let value = linear(rgb).get(channel).copied().unwrap_or(1.0);
+                        row.insert(format!("{}", i as f64 / last), value.into());
+                    }
+                    row.into()
+                })
+                .collect();
+            serde_json::to_string_pretty(&rows)
+                .map_err(|e| format!("Failed to serialize curve table: {}", e))?
+        }
+    })
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DesktopFormat {
+    /// Registry file setting the Windows accent color and its shades
+    WindowsAccent,
+    /// Libadwaita named colors for `~/.config/gtk-4.0/gtk.css`
+    Gnome,
+    /// KDE Plasma color scheme for `~/.local/share/color-schemes`
+    Kde,
+}
+
+/// Desktop roles picked from a palette: the mean lightness decides between a
+/// dark and a light scheme, the extremes become background and foreground,
+/// and the most saturated remaining color the accent. The alternate
+/// background is derived from the background rather than picked.
+struct DesktopScheme {
+    background: [u8; 3],
+    background_alt: [u8; 3],
+    foreground: [u8; 3],
+    accent: [u8; 3],
+    /// Text on the accent
+    accent_foreground: [u8; 3],
+}
+
+impl DesktopScheme {
+    fn from_palette(palette: &Palette) -> Result<Self, String> {
+        let mut colors = palette_rgb(palette)?;
+        if colors.len() < 3 {
+            return Err("A desktop theme needs at least 3 colors".to_string());
+        }
+        let lightness = |rgb: &[u8; 3]| color_math::rgb_to_oklch(*rgb).l;
+        colors.sort_by(|a, b| lightness(a).total_cmp(&lightness(b)));
+        let mean = colors.iter().map(lightness).sum::<f64>() / colors.len() as f64;
+        if mean >= 0.5 {
+            colors.reverse();
+        }
+        let background = colors[0];
+        let foreground = colors[colors.len() - 1];
+        let middle = &colors[1..colors.len() - 1];
+        let accent = middle
+            .iter()
+            .copied()
+            .max_by(|a, b| {
+                color_math::rgb_to_oklch(*a)
+                    .c
+                    .total_cmp(&color_math::rgb_to_oklch(*b).c)
+            })
+            .unwrap_or(foreground);
+        // Headers and cards sit a step from the background towards the text
+        let base = color_math::rgb_to_oklch(background);
+        let step = if mean >= 0.5 { -0.05 } else { 0.05 };
+        let background_alt = color_math::oklch_to_rgb(color_math::Oklch {
+            l: (base.l + step).clamp(0.0, 1.0),
+            ..base
+        });
+        Ok(DesktopScheme {
+            background,
+            background_alt,
+            foreground,
+            accent,
+            accent_foreground: color_math::readable_text_color(accent, false),
+        })
+    }
+}
+
+/// Eight shades of the accent, light to dark, as Windows' `AccentPalette`
+/// expects them; the fourth is the accent itself.
+fn accent_shades(accent: [u8; 3]) -> [[u8; 3]; 8] {
+    const STEPS: [f64; 8] = [0.3, 0.2, 0.1, 0.0, -0.1, -0.2, -0.3, -0.4];
+    let base = color_math::rgb_to_oklch(accent);
+    STEPS.map(|step| {
+        if step == 0.0 {
+            accent
+        } else {
+            color_math::oklch_to_rgb(color_math::Oklch {
+                l: (base.l + step).clamp(0.0, 1.0),
+                ..base
+            })
+        }
+    })
+}
+
+fn windows_accent_reg(scheme: &DesktopScheme) -> String {
+    let [r, g, b] = scheme.accent;
+    // DWORDs are 0xAABBGGRR, except ColorizationColor which is 0xAARRGGBB
+    let abgr = format!("ff{b:02x}{g:02x}{r:02x}");
+    let shades = accent_shades(scheme.accent)
+        .iter()
+        .map(|[r, g, b]| format!("{r:02x},{g:02x},{b:02x},00"))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!(
+        "Windows Registry Editor Version 5.00\r\n\r\n\
+         [HKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Accent]\r\n\
+         \"AccentPalette\"=hex:{shades}\r\n\
+         \"AccentColorMenu\"=dword:{abgr}\r\n\
+         \"StartColorMenu\"=dword:{abgr}\r\n\r\n\
+         [HKEY_CURRENT_USER\\Software\\Microsoft\\Windows\\DWM]\r\n\
+         \"AccentColor\"=dword:{abgr}\r\n\
+         \"ColorizationColor\"=dword:c4{r:02x}{g:02x}{b:02x}\r\n\
+         \"ColorizationAfterglow\"=dword:c4{r:02x}{g:02x}{b:02x}\r\n"
+    )
+}
+
+/// Palette name safe inside a CSS comment or an INI value: control
+/// characters (including line breaks) are dropped and `*/` can't close the
+/// comment early.
+fn theme_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>()
+        .replace("*/", "* /")
+}
+
+fn gnome_css(name: &str, scheme: &DesktopScheme) -> String {
+    let hex = color_math::to_hex;
+    let mut out = format!("/* {} */\n", theme_name(name));
+    for (key, rgb) in [
+        ("accent_color", scheme.accent),
+        ("accent_bg_color", scheme.accent),
+        ("accent_fg_color", scheme.accent_foreground),
+        ("window_bg_color", scheme.background),
+        ("window_fg_color", scheme.foreground),
+        ("view_bg_color", scheme.background),
+        ("view_fg_color", scheme.foreground),
+        ("headerbar_bg_color", scheme.background_alt),
+        ("headerbar_fg_color", scheme.foreground),
+        ("card_bg_color", scheme.background_alt),
+        ("card_fg_color", scheme.foreground),
+        ("popover_bg_color", scheme.background_alt),
+        ("popover_fg_color", scheme.foreground),
+        ("dialog_bg_color", scheme.background_alt),
+        ("dialog_fg_color", scheme.foreground),
+    ] {
+        out.push_str(&format!("@define-color {key} {};\n", hex(rgb)));
+    }
+    out
+}
+
+fn kde_colors(name: &str, scheme: &DesktopScheme) -> String {
+    let rgb = |[r, g, b]: [u8; 3]| format!("{r},{g},{b}");
+    let group = |background: [u8; 3], alternate: [u8; 3]| {
+        format!(
+            "BackgroundNormal={}\nBackgroundAlternate={}\nForegroundNormal={}\n\
+             ForegroundActive={}\nForegroundLink={}\nDecorationFocus={}\nDecorationHover={}\n",
+            rgb(background),
+            rgb(alternate),
+            rgb(scheme.foreground),
+            rgb(scheme.accent),
+            rgb(scheme.accent),
+            rgb(scheme.accent),
+            rgb(scheme.accent),
+        )
+    };
+    let mut out = String::new();
+    for section in [
+        "Window",
+        "View",
+        "Button",
+        "Header",
+        "Tooltip",
+        "Complementary",
+    ] {
+        let (background, alternate) = match section {
+            "View" => (scheme.background, scheme.background_alt),
+            _ => (scheme.background_alt, scheme.background),
+        };
+        out.push_str(&format!(
+            "[Colors:{section}]\n{}\n",
+            group(background, alternate)
+        ));
+    }
+    out.push_str(&format!(
+        "[Colors:Selection]\nBackgroundNormal={}\nBackgroundAlternate={}\nForegroundNormal={}\n\n",
+        rgb(scheme.accent),
+        rgb(scheme.accent),
+        rgb(scheme.accent_foreground),
+    ));
+    out.push_str(&format!(
+        "[General]\nColorScheme={}\nName={}\n\n[WM]\nactiveBackground={}\n\
+         activeForeground={}\ninactiveBackground={}\ninactiveForeground={}\n",
+        slug(name),
+        theme_name(name),
+        rgb(scheme.background_alt),
+        rgb(scheme.foreground),
+        rgb(scheme.background),
+        rgb(scheme.foreground),
+    ));
+    out
+}
+
+/// Desktop theme from a palette: a Windows accent color, or a GNOME or KDE
+/// color scheme.
+pub fn desktop_theme(palette: &Palette, format: DesktopFormat) -> Result<String, String> {
+    let scheme = DesktopScheme::from_palette(palette)?;
+    Ok(match format {
+        DesktopFormat::WindowsAccent => windows_accent_reg(&scheme),
+        DesktopFormat::Gnome => gnome_css(&palette.name, &scheme),
+        DesktopFormat::Kde => kde_colors(&palette.name, &scheme),
+    })
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DarkSelector {
+    /// `@media (prefers-color-scheme: dark)`
+    Media,
+    /// `[data-theme="dark"]`
+    Attribute,
+}
+
+/// Paired CSS variables: the light palette under `:root`, its dark
+/// counterpart under the chosen dark-mode selector. Variable names follow the
+/// single-palette CSS export (`--<name>-100`, `--<name>-200`, …).
+pub fn dual_css(light: &Palette, dark: &Palette, selector: DarkSelector) -> Result<String, String> {
+    let base = slug(&light.name);
+    let vars = |colors: Vec<[u8; 3]>, indent: &str| {
+        colors
+            .iter()
+            .enumerate()
+            .map(|(i, rgb)| {
+                format!(
+                    "{indent}--{base}-{}: {};\n",
+                    (i + 1) * 100,
+                    color_math::to_hex(*rgb)
+                )
+            })
+            .collect::<String>()
+    };
+    let light_colors = palette_rgb(light)?;
+    let mut dark_colors = palette_rgb(dark)?;
+    dark_colors.truncate(light_colors.len());
+
+    let dark_block = match selector {
+        DarkSelector::Media => format!(
+            "@media (prefers-color-scheme: dark) {{\n  :root {{\n{}  }}\n}}\n",
+            vars(dark_colors, "    ")
+        ),
+        DarkSelector::Attribute => {
+            format!("[data-theme=\"dark\"] {{\n{}}}\n", vars(dark_colors, "  "))
+        }
+    };
+    Ok(format!(
+        ":root {{\n{}}}\n\n{dark_block}",
+        vars(light_colors, "  ")
+    ))
+}
@@ -0,0 +1,373 @@
+use crate::contrast_grid::{self, GridFormat};
+use crate::export::{self, DesktopFormat, EngineFormat, TerminalFormat};
+use crate::png_encoder::PngOptions;
+use crate::{color_math, color_names, pdf, Palette};
+use serde::{Deserialize, Serialize};
+
+/// A palette export format. Built-in formats and user templates share this
+/// interface so every export path (dialogs, defaults, pipelines) can look
+/// them up by id.
+pub trait Exporter {
+    fn id(&self) -> &str;
+    fn name(&self) -> &str;
+    fn extension(&self) -> &str;
+    fn render(&self, palette: &Palette) -> Result<Vec<u8>, String>;
+
+    fn builtin(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExporterInfo {
+    pub id: String,
+    pub name: String,
+    pub extension: String,
+    pub builtin: bool,
+}
+
+/// Color names are given in the wrapped language.
+pub struct HtmlReport(pub color_names::NameLocale);
+
+impl Exporter for HtmlReport {
+    fn id(&self) -> &str {
+        "html-report"
+    }
+    fn name(&self) -> &str {
+        "HTML report"
+    }
+    fn extension(&self) -> &str {
+        "html"
+    }
+    fn render(&self, palette: &Palette) -> Result<Vec<u8>, String> {
+        export::palette_html_report(palette, self.0).map(String::into_bytes)
+    }
+}
+
+pub struct ContrastGrid(pub GridFormat, pub PngOptions);
+
+impl Exporter for ContrastGrid {
+    fn id(&self) -> &str {
+        match self.0 {
+            GridFormat::Html => "contrast-grid-html",
+            GridFormat::Png => "contrast-grid-png",
+        }
+    }
+    fn name(&self) -> &str {
+        match self.0 {
+            GridFormat::Html => "Contrast grid (HTML)",
+            GridFormat::Png => "Contrast grid (PNG)",
+        }
+    }
+    fn extension(&self) -> &str {
+        match self.0 {
+            GridFormat::Html => "html",
+            GridFormat::Png => "png",
+        }
+    }
+    fn render(&self, palette: &Palette) -> Result<Vec<u8>, String> {
+        contrast_grid::render(palette, self.0, &self.1)
+    }
+}
+
+pub struct PdfSheet(pub pdf::PageSize);
+
+impl Exporter for PdfSheet {
+    fn id(&self) -> &str {
+        match self.0 {
+            pdf::PageSize::A4 => "pdf-a4",
+            pdf::PageSize::Letter => "pdf-letter",
+        }
+    }
+    fn name(&self) -> &str {
+        match self.0 {
+            pdf::PageSize::A4 => "PDF swatch sheet (A4)",
+            pdf::PageSize::Letter => "PDF swatch sheet (Letter)",
+        }
+    }
+    fn extension(&self) -> &str {
+        "pdf"
+    }
+    fn render(&self, palette: &Palette) -> Result<Vec<u8>, String> {
+        let colors = export::palette_rgb(palette)?;
+        Ok(pdf::palette_sheet(&palette.name, &colors, self.0))
+    }
+}
+
+pub struct Terminal(pub TerminalFormat);
+
+impl Exporter for Terminal {
+    fn id(&self) -> &str {
+        match self.0 {
+            TerminalFormat::WindowsTerminal => "windows-terminal",
+            TerminalFormat::Alacritty => "alacritty",
+            TerminalFormat::Kitty => "kitty",
+        }
+    }
+    fn name(&self) -> &str {
+        match self.0 {
+            TerminalFormat::WindowsTerminal => "Windows Terminal scheme",
+            TerminalFormat::Alacritty => "Alacritty theme",
+            TerminalFormat::Kitty => "kitty theme",
+        }
+    }
+    fn extension(&self) -> &str {
+        match self.0 {
+            TerminalFormat::WindowsTerminal => "json",
+            TerminalFormat::Alacritty => "toml",
+            TerminalFormat::Kitty => "conf",
+        }
+    }
+    fn render(&self, palette: &Palette) -> Result<Vec<u8>, String> {
+        export::terminal_theme(palette, self.0).map(String::into_bytes)
+    }
+}
+
+pub struct VsCode;
+
+impl Exporter for VsCode {
+    fn id(&self) -> &str {
+        "vscode"
+    }
+    fn name(&self) -> &str {
+        "VS Code color theme"
+    }
+    fn extension(&self) -> &str {
+        "json"
+    }
+    fn render(&self, palette: &Palette) -> Result<Vec<u8>, String> {
+        export::vscode_theme(palette).map(String::into_bytes)
+    }
+}
+
+pub struct Engine(pub EngineFormat);
+
+impl Exporter for Engine {
+    fn id(&self) -> &str {
+        match self.0 {
+            EngineFormat::UnityPresets => "unity-colors",
+            EngineFormat::UnrealColors => "unreal-colors",
+            EngineFormat::UnrealCurve => "unreal-curve",
+        }
+    }
+    fn name(&self) -> &str {
+        match self.0 {
+            EngineFormat::UnityPresets => "Unity color presets",
+            EngineFormat::UnrealColors => "Unreal linear colors",
+            EngineFormat::UnrealCurve => "Unreal color curve table",
+        }
+    }
+    fn extension(&self) -> &str {
+        match self.0 {
+            EngineFormat::UnityPresets => "colors",
+            EngineFormat::UnrealColors => "txt",
+            EngineFormat::UnrealCurve => "json",
+        }
+    }
+    fn render(&self, palette: &Palette) -> Result<Vec<u8>, String> {
+        export::engine_swatches(palette, self.0).map(String::into_bytes)
+    }
+}
+
+pub struct Desktop(pub DesktopFormat);
+
+impl Exporter for Desktop {
+    fn id(&self) -> &str {
+        match self.0 {
+            DesktopFormat::WindowsAccent => "windows-accent",
+            DesktopFormat::Gnome => "gnome-colors",
+            DesktopFormat::Kde => "kde-colors",
+        }
+    }
+    fn name(&self) -> &str {
+        match self.0 {
+            DesktopFormat::WindowsAccent => "Windows accent color",
+            DesktopFormat::Gnome => "GNOME color scheme",
+            DesktopFormat::Kde => "KDE color scheme",
+        }
+    }
+    fn extension(&self) -> &str {
+        match self.0 {
+            DesktopFormat::WindowsAccent => "reg",
+            DesktopFormat::Gnome => "css",
+            DesktopFormat::Kde => "colors",
+        }
+    }
+    fn render(&self, palette: &Palette) -> Result<Vec<u8>, String> {
+        export::desktop_theme(palette, self.0).map(String::into_bytes)
+    }
+}
+
+pub struct Tailwind;
+
+impl Exporter for Tailwind {
+    fn id(&self) -> &str {
+        "tailwind"
+    }
+    fn name(&self) -> &str {
+        "Tailwind config"
+    }
+    fn extension(&self) -> &str {
+        "js"
+    }
+    fn render(&self, palette: &Palette) -> Result<Vec<u8>, String> {
+        export::tailwind_config(palette).map(String::into_bytes)
+    }
+}
+
+pub struct OfficeTheme;
+
+impl Exporter for OfficeTheme {
+    fn id(&self) -> &str {
+        "office-theme"
+    }
+    fn name(&self) -> &str {
+        "Office theme colors"
+    }
+    fn extension(&self) -> &str {
+        "xml"
+    }
+    fn render(&self, palette: &Palette) -> Result<Vec<u8>, String> {
+        export::office_theme(palette).map(String::into_bytes)
+    }
+}
+
+/// A user-defined text exporter from `exporters.json`.
+///
+/// Every template may use `{palette}`, `{slug}` and `{count}`; `color` is
+/// rendered once per color and may also use `{index}`, `{hex}`, `{hex_bare}`,
+/// `{r}`, `{g}`, `{b}`, `{name}` (nearest CSS name) and `{local_name}`
+/// (nearest name in the chosen language).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateExporter {
+    pub id: String,
+    pub name: String,
+    pub extension: String,
+    #[serde(default)]
+    pub header: String,
+    pub color: String,
+    #[serde(default = "default_separator")]
+    pub separator: String,
+    #[serde(default)]
+    pub footer: String,
+    /// Language of `{local_name}`, set by the registry
+    #[serde(skip)]
+    pub names: color_names::NameLocale,
+}
+
+fn default_separator() -> String {
+    "\n".to_string()
+}
+
+impl TemplateExporter {
+    fn fill_palette(&self, template: &str, palette: &Palette, count: usize) -> String {
+        template
+            .replace("{palette}", &palette.name)
+            .replace("{slug}", &export::slug(&palette.name))
+            .replace("{count}", &count.to_string())
+    }
+
+    fn fill_color(&self, palette: &Palette, count: usize, index: usize, rgb: [u8; 3]) -> String {
+        let hex = color_math::to_hex(rgb);
+        self.fill_palette(&self.color, palette, count)
+            .replace("{index}", &(index + 1).to_string())
+            .replace("{hex_bare}", &hex[1..])
+            .replace("{hex}", &hex)
+            .replace("{r}", &rgb[0].to_string())
+            .replace("{g}", &rgb[1].to_string())
+            .replace("{b}", &rgb[2].to_string())
+            .replace("{name}", color_names::nearest_name(rgb))
+            .replace(
+                "{local_name}",
+                color_names::nearest_name_in(rgb, self.names),
+            )
+    }
+}
+
+impl Exporter for TemplateExporter {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn extension(&self) -> &str {
+        &self.extension
+    }
+    fn builtin(&self) -> bool {
+        false
+    }
+    fn render(&self, palette: &Palette) -> Result<Vec<u8>, String> {
+        let colors = export::palette_rgb(palette)?;
+        let body = colors
+            .iter()
+            .enumerate()
+            .map(|(i, rgb)| self.fill_color(palette, colors.len(), i, *rgb))
+            .collect::<Vec<_>>()
+            .join(&self.separator);
+        Ok(format!(
+            "{}{body}{}",
+            self.fill_palette(&self.header, palette, colors.len()),
+            self.fill_palette(&self.footer, palette, colors.len())
+        )
+        .into_bytes())
+    }
+}
+
+/// All exporters: built-ins first, then user templates. A template whose id
+/// collides with a built-in is ignored so built-ins can't be shadowed. Image
+/// formats are written with `png`.
+pub fn registry(
+    user: Vec<TemplateExporter>,
+    png: PngOptions,
+    names: color_names::NameLocale,
+) -> Vec<Box<dyn Exporter>> {
+    let mut exporters: Vec<Box<dyn Exporter>> = vec![
+        Box::new(HtmlReport(names)),
+        Box::new(ContrastGrid(GridFormat::Html, png)),
+        Box::new(ContrastGrid(GridFormat::Png, png)),
+        Box::new(PdfSheet(pdf::PageSize::A4)),
+        Box::new(PdfSheet(pdf::PageSize::Letter)),
+        Box::new(Terminal(TerminalFormat::WindowsTerminal)),
+        Box::new(Terminal(TerminalFormat::Alacritty)),
+        Box::new(Terminal(TerminalFormat::Kitty)),
+        Box::new(VsCode),
+        Box::new(Tailwind),
+        Box::new(OfficeTheme),
+        Box::new(Desktop(DesktopFormat::WindowsAccent)),
+        Box::new(Desktop(DesktopFormat::Gnome)),
+        Box::new(Desktop(DesktopFormat::Kde)),
+        Box::new(Engine(EngineFormat::UnityPresets)),
+        Box::new(Engine(EngineFormat::UnrealColors)),
+        Box::new(Engine(EngineFormat::UnrealCurve)),
+    ];
+    for mut template in user {
+        template.names = names;
+        if !exporters.iter().any(|e| e.id() == template.id) {
+            exporters.push(Box::new(template));
+        }
+    }
+    exporters
+}
+
+pub fn info(exporters: &[Box<dyn Exporter>]) -> Vec<ExporterInfo> {
+    exporters
+        .iter()
+        .map(|e| ExporterInfo {
+            id: e.id().to_string(),
+            name: e.name().to_string(),
+            extension: e.extension().to_string(),
+            builtin: e.builtin(),
+        })
+        .collect()
+}
+
+pub fn find<'a>(exporters: &'a [Box<dyn Exporter>], id: &str) -> Result<&'a dyn Exporter, String> {
+    exporters
+        .iter()
+        .find(|e| e.id() == id)
+        .map(|e| e.as_ref())
+        .ok_or_else(|| format!("Unknown exporter: {id}"))
+}
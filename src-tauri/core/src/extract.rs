@@ -0,0 +1,161 @@
+use crate::{color_math, histogram};
+use image::RgbImage;
+use serde::Serialize;
+
+/// Longest edge images are downscaled to before extraction; plenty for
+/// finding dominant colors and keeps large photos fast.
+const SAMPLE_EDGE: u32 = 128;
+
+/// An extracted color plus where a pixel representing it sits in the
+/// original image, so the UI can mark it.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct ExtractedColor {
+    pub rgb: [u8; 3],
+    pub x: u32,
+    pub y: u32,
+}
+
+/// Whole-image summary colors, e.g. for placeholder or backdrop colors.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageAverage {
+    pub mean: String,
+    /// Per-channel median, less skewed by small bright or dark areas
+    pub median: String,
+    /// Mean OKLab lightness, 0-100
+    pub brightness: f64,
+    /// OKLCH hue in degrees of the most common hue range, if any
+    pub dominant_hue: Option<f64>,
+}
+
+/// A thumbnail pixel and its position.
+#[derive(Clone, Copy)]
+struct Sample {
+    rgb: [u8; 3],
+    x: u32,
+    y: u32,
+}
+
+/// Dominant colors of an image via median cut, most common first.
+pub fn dominant_colors(img: &RgbImage, count: usize) -> Vec<ExtractedColor> {
+    let small = image::imageops::thumbnail(img, SAMPLE_EDGE, SAMPLE_EDGE);
+    let pixels: Vec<Sample> = small
+        .enumerate_pixels()
+        .map(|(x, y, p)| Sample { rgb: p.0, x, y })
+        .collect();
+    if pixels.is_empty() || count == 0 {
+        return Vec::new();
+    }
+
+    let mut boxes = vec![pixels];
+    while boxes.len() < count {
+        // Split the box with the widest channel range at its median
+        let Some((index, channel, range)) = boxes
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                let (channel, range) = widest_channel(b);
+                (i, channel, range)
+            })
+            .max_by_key(|(_, _, range)| *range)
+        else {
+            break;
+        };
+        if range == 0 {
+            break;
+        }
+        let mut target = boxes.swap_remove(index);
+        target.sort_unstable_by_key(|p| p.rgb[channel]);
+        let upper = target.split_off(target.len() / 2);
+        boxes.push(target);
+        boxes.push(upper);
+    }
+
+    boxes.sort_by_key(|b| std::cmp::Reverse(b.len()));
+    // Flat regions split into several boxes with the same average; keep one
+    let (width, height) = img.dimensions();
+    let scale = |v: u32, small: u32, full: u32| {
+        ((2 * v as u64 + 1) * full as u64 / (2 * small.max(1) as u64)) as u32
+    };
+    let mut colors: Vec<ExtractedColor> = Vec::new();
+    for b in &boxes {
+        let color = average(b);
+        if colors
+            .iter()
+            .all(|c| distance_sq(c.rgb, color) > MIN_DISTANCE_SQ)
+        {
+            // The box member closest to the average stands in for the cluster
+            let Some(nearest) = b.iter().min_by_key(|p| distance_sq(p.rgb, color)) else {
+                continue;
+            };
+            colors.push(ExtractedColor {
+                rgb: color,
+                x: scale(nearest.x, small.width(), width).min(width - 1),
+                y: scale(nearest.y, small.height(), height).min(height - 1),
+            });
+        }
+    }
+    colors
+}
+
+/// Mean and median color of every pixel, plus brightness and dominant hue.
+pub fn image_average(img: &RgbImage) -> ImageAverage {
+    let mut sum = [0u64; 3];
+    let mut counts = [[0u64; 256]; 3];
+    let mut lightness = 0.0;
+    for p in img.pixels() {
+        for c in 0..3 {
+            sum[c] += p.0[c] as u64;
+            counts[c][p.0[c] as usize] += 1;
+        }
+        lightness += color_math::rgb_to_oklab(p.0).l;
+    }
+    let n = img.pixels().len().max(1) as u64;
+    let median = counts.map(|channel| {
+        let mut seen = 0;
+        channel
+            .iter()
+            .position(|&count| {
+                seen += count;
+                seen * 2 >= n
+            })
+            .unwrap_or(0) as u8
+    });
+    ImageAverage {
+        mean: color_math::to_hex(sum.map(|s| (s / n) as u8)),
+        median: color_math::to_hex(median),
+        brightness: (lightness / n as f64 * 1000.0).round() / 10.0,
+        dominant_hue: histogram::histogram(img, histogram::DEFAULT_HUE_BUCKETS, 1).dominant_hue,
+    }
+}
+
+/// Squared RGB distance below which two extracted colors count as the same.
+const MIN_DISTANCE_SQ: u32 = 12 * 12;
+
+fn distance_sq(a: [u8; 3], b: [u8; 3]) -> u32 {
+    (0..3)
+        .map(|c| (a[c] as i32 - b[c] as i32).pow(2) as u32)
+        .sum()
+}
+
+fn widest_channel(pixels: &[Sample]) -> (usize, u8) {
+    (0..3)
+        .map(|c| {
+            let min = pixels.iter().map(|p| p.rgb[c]).min().unwrap_or(0);
+            let max = pixels.iter().map(|p| p.rgb[c]).max().unwrap_or(0);
+            (c, max - min)
+        })
+        .max_by_key(|(_, range)| *range)
+        .unwrap_or((0, 0))
+}
+
+fn average(pixels: &[Sample]) -> [u8; 3] {
+    let n = pixels.len().max(1) as u64;
+    let mut sum = [0u64; 3];
+    for p in pixels {
+        for (s, v) in sum.iter_mut().zip(p.rgb) {
+            *s += v as u64;
+        }
+    }
+    sum.map(|s| (s / n) as u8)
+}
@@ -0,0 +1,133 @@
+use crate::color_math;
+use serde::Serialize;
+
+/// Upper bound on pixels examined; larger captures are sampled on a grid.
+const MAX_SAMPLES: u64 = 500_000;
+
+/// OKLCH chroma below which a pixel counts as neutral (hue is meaningless).
+const NEUTRAL_CHROMA: f64 = 0.03;
+
+pub const DEFAULT_HUE_BUCKETS: usize = 12;
+pub const DEFAULT_LIGHTNESS_BUCKETS: usize = 10;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HueBucket {
+    /// Hue range in OKLCH degrees, `start` inclusive
+    pub start: f64,
+    pub end: f64,
+    /// Percent of all sampled pixels
+    pub share: f64,
+    /// Representative color: the bucket's mid hue at its average L and C
+    pub swatch: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LightnessBucket {
+    /// OKLCH lightness range in percent, `start` inclusive
+    pub start: f64,
+    pub end: f64,
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Histogram {
+    pub width: u32,
+    pub height: u32,
+    pub sampled: u64,
+    pub hues: Vec<HueBucket>,
+    /// Percent of pixels too gray to have a meaningful hue
+    pub neutral_share: f64,
+    pub lightness: Vec<LightnessBucket>,
+    /// Mid hue of the largest hue bucket, if any pixel was chromatic
+    pub dominant_hue: Option<f64>,
+}
+
+fn percent(count: u64, total: u64) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (count as f64 * 10000.0 / total as f64).round() / 100.0
+}
+
+/// Bucket every sampled pixel by OKLCH hue (chromatic pixels only) and
+/// lightness (all pixels).
+pub fn histogram(img: &image::RgbImage, hue_buckets: usize, lightness_buckets: usize) -> Histogram {
+    let hue_buckets = hue_buckets.clamp(1, 360);
+    let lightness_buckets = lightness_buckets.clamp(1, 100);
+    let (width, height) = img.dimensions();
+    let pixels = width as u64 * height as u64;
+    let stride = ((pixels as f64 / MAX_SAMPLES as f64).sqrt().ceil() as u32).max(1);
+
+    let mut hue_counts = vec![0u64; hue_buckets];
+    let mut hue_lc = vec![(0.0, 0.0); hue_buckets];
+    let mut lightness_counts = vec![0u64; lightness_buckets];
+    let mut neutral = 0u64;
+    let mut sampled = 0u64;
+    for y in (0..height).step_by(stride as usize) {
+        for x in (0..width).step_by(stride as usize) {
+            let lch = color_math::rgb_to_oklch(img.get_pixel(x, y).0);
+            sampled += 1;
+            let l = ((lch.l * lightness_buckets as f64) as usize).min(lightness_buckets - 1);
+            lightness_counts[l] += 1;
+            if lch.c < NEUTRAL_CHROMA {
+                neutral += 1;
+                continue;
+            }
+            let h = ((lch.h / 360.0 * hue_buckets as f64) as usize).min(hue_buckets - 1);
+            hue_counts[h] += 1;
+            hue_lc[h].0 += lch.l;
+            hue_lc[h].1 += lch.c;
+        }
+    }
+
+    let hue_width = 360.0 / hue_buckets as f64;
+    let hues: Vec<HueBucket> = hue_counts
+        .iter()
+        .zip(&hue_lc)
+        .enumerate()
+        .map(|(i, (&count, &(l, c)))| {
+            let start = i as f64 * hue_width;
+            let n = count.max(1) as f64;
+            HueBucket {
+                start,
+                end: start + hue_width,
+                share: percent(count, sampled),
+                swatch: color_math::to_hex(color_math::oklch_to_rgb(color_math::Oklch {
+                    l: if count == 0 { 0.7 } else { l / n },
+                    c: if count == 0 { 0.12 } else { c / n },
+                    h: start + hue_width / 2.0,
+                })),
+            }
+        })
+        .collect();
+    let dominant_hue = hue_counts
+        .iter()
+        .enumerate()
+        .filter(|(_, &count)| count > 0)
+        .max_by_key(|(_, &count)| count)
+        .map(|(i, _)| (i as f64 + 0.5) * hue_width);
+
+    let lightness_width = 100.0 / lightness_buckets as f64;
+    let lightness = lightness_counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| LightnessBucket {
+            start: i as f64 * lightness_width,
+            end: (i + 1) as f64 * lightness_width,
+            share: percent(count, sampled),
+        })
+        .collect();
+
+    Histogram {
+        width,
+        height,
+        sampled,
+        hues,
+        neutral_share: percent(neutral, sampled),
+        lightness,
+        dominant_hue,
+    }
+}
@@ -0,0 +1,272 @@
+use crate::{color_math, conversion};
+use serde::{Deserialize, Serialize};
+
+pub const LOSPEC_API: &str = "https://lospec.com/palette-list/";
+
+/// A palette parsed from an external source, before it gets an id.
+#[derive(Debug, Clone)]
+pub struct ImportedPalette {
+    pub name: String,
+    pub colors: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct LospecPalette {
+    name: String,
+    colors: Vec<String>,
+}
+
+/// Normalize a list of hex strings to `#RRGGBB`, rejecting malformed ones.
+fn normalize(colors: &[&str]) -> Result<Vec<String>, String> {
+    colors
+        .iter()
+        .map(|hex| color_math::parse_hex(hex).map(color_math::to_hex))
+        .collect()
+}
+
+/// Parse a coolors.co URL such as `https://coolors.co/ff8800-112233` or
+/// `https://coolors.co/palette/ff8800-112233`. The colors live in the last
+/// path segment, so no network request is needed.
+pub fn parse_coolors_url(url: &str) -> Result<ImportedPalette, String> {
+    let path = url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches('/');
+    let segment = path.rsplit('/').next().unwrap_or_default();
+    let hexes: Vec<&str> = segment.split('-').filter(|s| !s.is_empty()).collect();
+    if hexes.is_empty() {
+        return Err(format!("No colors found in coolors URL: {url}"));
+    }
+    Ok(ImportedPalette {
+        name: "Coolors palette".to_string(),
+        colors: normalize(&hexes)?,
+    })
+}
+
+/// Accept either a bare Lospec slug (`endesga-32`) or a palette page URL.
+pub fn lospec_slug(source: &str) -> Result<String, String> {
+    let slug = source
+        .trim()
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(".json");
+    if slug.is_empty()
+        || !slug
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(format!("Invalid Lospec palette: {source}"));
+    }
+    Ok(slug.to_lowercase())
+}
+
+/// Parse a Lospec palette-list API response.
+pub fn parse_lospec(body: &str) -> Result<ImportedPalette, String> {
+    let palette: LospecPalette =
+        serde_json::from_str(body).map_err(|e| format!("Failed to parse Lospec palette: {}", e))?;
+    let hexes: Vec<&str> = palette.colors.iter().map(String::as_str).collect();
+    Ok(ImportedPalette {
+        name: palette.name,
+        colors: normalize(&hexes)?,
+    })
+}
+
+/// Parse a GIMP palette (`.gpl`): a `GIMP Palette` header, optional
+/// `Name:`/`Columns:` lines and `#` comments, then `R G B [name]` rows.
+pub fn parse_gpl(text: &str, fallback_name: &str) -> Result<ImportedPalette, String> {
+    let mut lines = text.lines();
+    if lines.next().map(str::trim) != Some("GIMP Palette") {
+        return Err("Not a GIMP palette file".to_string());
+    }
+    let mut name = fallback_name.to_string();
+    let mut colors = Vec::new();
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("Columns:") {
+            continue;
+        }
+        if let Some(n) = line.strip_prefix("Name:") {
+            name = n.trim().to_string();
+            continue;
+        }
+        let channels: Vec<u8> = line
+            .split_whitespace()
+            .take(3)
+            .map(|v| v.parse::<u8>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("Invalid GIMP palette row: {line}"))?;
+        if let [r, g, b] = channels[..] {
+            colors.push(color_math::to_hex([r, g, b]));
+        } else {
+            return Err(format!("Invalid GIMP palette row: {line}"));
+        }
+    }
+    if colors.is_empty() {
+        return Err("GIMP palette contains no colors".to_string());
+    }
+    Ok(ImportedPalette { name, colors })
+}
+
+/// A custom property read from a web page, e.g. `--brand-500: #3b82f6`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CssVariable {
+    pub name: String,
+    pub value: String,
+}
+
+/// Parse a CSS color as browsers serialize computed values: hex,
+/// `rgb()`/`rgba()` with comma- or space-separated channels (numbers or
+/// percentages), or `oklab()`/`oklch()` clipped into sRGB. Alpha is dropped.
+pub fn parse_css_color(value: &str) -> Result<[u8; 3], String> {
+    let value = value.trim();
+    if let Some(oklab) = conversion::parse_oklab(value) {
+        return oklab.map(color_math::oklab_to_rgb);
+    }
+    let lower = value.to_ascii_lowercase();
+    let Some(args) = lower
+        .strip_prefix("rgba(")
+        .or_else(|| lower.strip_prefix("rgb("))
+        .and_then(|rest| rest.strip_suffix(')'))
+    else {
+        return color_math::parse_hex(value);
+    };
+    let channel = |part: &str| -> Option<u8> {
+        let (number, scale) = match part.strip_suffix('%') {
+            Some(percent) => (percent, 255.0 / 100.0),
+            None => (part, 1.0),
+        };
+        let v = number.parse::<f64>().ok().filter(|v| v.is_finite())? * scale;
+        Some(v.round().clamp(0.0, 255.0) as u8)
+    };
+    let channels: Vec<u8> = args
+        .split([',', ' ', '/'])
+        .filter(|part| !part.is_empty())
+        .take(3)
+        .map(channel)
+        .collect::<Option<_>>()
+        .ok_or_else(|| format!("Invalid CSS color: {value}"))?;
+    match channels[..] {
+        [r, g, b] => Ok([r, g, b]),
+        _ => Err(format!("Invalid CSS color: {value}")),
+    }
+}
+
+/// Palette of a page's color variables in page order, repeats dropped.
+/// Variables that don't hold a color (spacing, fonts) are skipped.
+pub fn from_css_variables(
+    name: &str,
+    variables: &[CssVariable],
+) -> Result<ImportedPalette, String> {
+    let mut colors: Vec<String> = Vec::new();
+    for variable in variables {
+        if let Ok(rgb) = parse_css_color(&variable.value) {
+            let hex = color_math::to_hex(rgb);
+            if !colors.contains(&hex) {
+                colors.push(hex);
+            }
+        }
+    }
+    if colors.is_empty() {
+        return Err("No color variables found".to_string());
+    }
+    Ok(ImportedPalette {
+        name: name.to_string(),
+        colors,
+    })
+}
+
+/// Big-endian reader over an `.ase` buffer that errors instead of panicking
+/// on truncated files.
+struct AseReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> AseReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|end| *end <= self.data.len())
+            .ok_or("Truncated swatch file")?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn f32(&mut self) -> Result<f64, String> {
+        Ok(f32::from_bits(self.u32()?) as f64)
+    }
+}
+
+const ASE_COLOR_ENTRY: u16 = 0x0001;
+
+/// Parse an Adobe Swatch Exchange (`.ase`) file. RGB, CMYK, Lab and Gray
+/// swatches are converted to sRGB; groups are flattened.
+pub fn parse_ase(data: &[u8], name: &str) -> Result<ImportedPalette, String> {
+    let mut r = AseReader { data, pos: 0 };
+    if r.take(4)? != b"ASEF" {
+        return Err("Not an Adobe Swatch Exchange file".to_string());
+    }
+    r.take(4)?; // version
+    let blocks = r.u32()?;
+
+    let mut colors = Vec::new();
+    for _ in 0..blocks {
+        let kind = r.u16()?;
+        let len = r.u32()? as usize;
+        let block = r.take(len)?;
+        if kind != ASE_COLOR_ENTRY {
+            continue; // group start/end
+        }
+        let mut b = AseReader {
+            data: block,
+            pos: 0,
+        };
+        let name_len = b.u16()? as usize;
+        b.take(name_len * 2)?; // UTF-16 swatch name
+        let model = b.take(4)?;
+        let rgb = match model {
+            b"RGB " => {
+                [b.f32()?, b.f32()?, b.f32()?].map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+            }
+            b"CMYK" => color_math::cmyk_to_rgb(color_math::Cmyk {
+                c: b.f32()? * 100.0,
+                m: b.f32()? * 100.0,
+                y: b.f32()? * 100.0,
+                k: b.f32()? * 100.0,
+            }),
+            b"LAB " => color_math::lab_to_rgb(color_math::Lab {
+                l: b.f32()? * 100.0,
+                a: b.f32()?,
+                b: b.f32()?,
+            }),
+            b"Gray" => {
+                let v = (b.f32()?.clamp(0.0, 1.0) * 255.0).round() as u8;
+                [v, v, v]
+            }
+            _ => continue,
+        };
+        colors.push(color_math::to_hex(rgb));
+    }
+    if colors.is_empty() {
+        return Err("Swatch file contains no colors".to_string());
+    }
+    Ok(ImportedPalette {
+        name: name.to_string(),
+        colors,
+    })
+}
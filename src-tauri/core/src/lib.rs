@@ -0,0 +1,51 @@
+//! Pure-Rust core of Pixnib: color math and naming, contrast, palette
+//! parsing, formatting and export. Nothing here touches Tauri or the OS, so
+//! it builds and runs headless.
+
+use serde::{Deserialize, Serialize};
+
+pub mod average;
+pub mod color_math;
+pub mod color_names;
+pub mod contrast;
+pub mod contrast_grid;
+pub mod conversion;
+pub mod copy_format;
+pub mod cvd;
+pub mod dark_mode;
+pub mod dedupe;
+pub mod export;
+pub mod exporters;
+pub mod extract;
+pub mod histogram;
+pub mod import;
+pub mod palette_diff;
+pub mod palette_file;
+pub mod palette_lock;
+pub mod pdf;
+pub mod pipeline;
+pub mod png_encoder;
+pub mod print_gamut;
+pub mod quantize;
+pub mod share;
+pub mod workspace;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Palette {
+    pub id: String,
+    pub name: String,
+    pub colors: Vec<String>, // hex values
+    /// Set on derived dark palettes: id of the light palette they came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dark_variant_of: Option<String>,
+    /// Owning workspace; `None` is the default workspace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace_id: Option<String>,
+    /// Locked palettes can be used and exported but not edited or deleted
+    #[serde(default)]
+    pub locked: bool,
+    /// Default exporter, directory and file naming for one-shot exports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export: Option<export::PaletteExport>,
+}
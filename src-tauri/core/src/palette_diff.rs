@@ -0,0 +1,111 @@
+use crate::color_math;
+use serde::Serialize;
+
+/// A color of the old palette that moved to a nearby color in the new one.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangedColor {
+    pub from: String,
+    pub to: String,
+    /// CIEDE2000 distance between the two
+    pub delta_e: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteDiff {
+    /// Largest CIEDE2000 distance still reported as a change rather than a
+    /// removal plus an addition
+    pub threshold: f64,
+    /// Colors only in the new palette, in its order
+    pub added: Vec<String>,
+    /// Colors only in the old palette, in its order
+    pub removed: Vec<String>,
+    /// In the order of the old palette
+    pub changed: Vec<ChangedColor>,
+    /// Colors present in both, ignoring hex case
+    pub unchanged: usize,
+}
+
+/// Compare an old and a new color list. Identical colors pair up first; the
+/// rest are paired closest-first while within `threshold`, so a tweaked
+/// brand color shows as one change instead of a removal and an addition.
+/// Colors that don't parse only ever match themselves.
+pub fn diff(old: &[String], new: &[String], threshold: f64) -> PaletteDiff {
+    let key = |hex: &String| {
+        color_math::parse_hex(hex)
+            .map(color_math::to_hex)
+            .unwrap_or_else(|_| hex.clone())
+    };
+    let new_keys: Vec<String> = new.iter().map(key).collect();
+    let mut old_match: Vec<Option<usize>> = vec![None; old.len()];
+    let mut new_taken = vec![false; new.len()];
+
+    let mut unchanged = 0;
+    for (i, hex) in old.iter().enumerate() {
+        let hex = key(hex);
+        if let Some(j) = (0..new.len()).find(|&j| !new_taken[j] && new_keys[j] == hex) {
+            old_match[i] = Some(j);
+            new_taken[j] = true;
+            unchanged += 1;
+        }
+    }
+
+    let parsed = |colors: &[String]| -> Vec<Option<[u8; 3]>> {
+        colors
+            .iter()
+            .map(|hex| color_math::parse_hex(hex).ok())
+            .collect()
+    };
+    let (old_rgb, new_rgb) = (parsed(old), parsed(new));
+    let mut candidates = Vec::new();
+    for (i, a) in old_rgb.iter().enumerate() {
+        let Some(a) = a.filter(|_| old_match[i].is_none()) else {
+            continue;
+        };
+        for (j, b) in new_rgb.iter().enumerate() {
+            let Some(b) = b.filter(|_| !new_taken[j]) else {
+                continue;
+            };
+            let distance = color_math::delta_e(a, b);
+            if distance <= threshold {
+                candidates.push((distance, i, j));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut distances = vec![0.0; old.len()];
+    for (distance, i, j) in candidates {
+        if old_match[i].is_none() && !new_taken[j] {
+            old_match[i] = Some(j);
+            new_taken[j] = true;
+            distances[i] = distance;
+        }
+    }
+
+    let mut changed = Vec::new();
+    let mut removed = Vec::new();
+    for (i, hex) in old.iter().enumerate() {
+        match old_match[i] {
+            None => removed.push(hex.clone()),
+            Some(j) if new_keys[j] != key(hex) => changed.push(ChangedColor {
+                from: hex.clone(),
+                to: new[j].clone(),
+                delta_e: distances[i],
+            }),
+            Some(_) => {}
+        }
+    }
+    PaletteDiff {
+        threshold,
+        added: new
+            .iter()
+            .zip(&new_taken)
+            .filter(|(_, taken)| !**taken)
+            .map(|(hex, _)| hex.clone())
+            .collect(),
+        removed,
+        changed,
+        unchanged,
+    }
+}
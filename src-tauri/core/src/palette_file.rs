@@ -0,0 +1,112 @@
+use crate::color_math;
+use crate::import::ImportedPalette;
+use crate::png_encoder::{self, PngOptions};
+use crate::Palette;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+pub const EXTENSION: &str = "colorsnap";
+
+const FORMAT_TAG: &str = "colorsnap";
+/// Bump when the layout changes; files newer than this are rejected.
+const CURRENT_VERSION: u32 = 1;
+const THUMB_SWATCH: u32 = 32;
+
+/// On-disk `.colorsnap` document: a versioned palette plus metadata and a
+/// PNG preview strip so file browsers and the import dialog can show it.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PaletteFile {
+    format: String,
+    version: u32,
+    palette: FilePalette,
+    #[serde(default)]
+    metadata: FileMetadata,
+    /// `data:image/png;base64,…` preview of the whole palette
+    #[serde(default)]
+    thumbnail: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FilePalette {
+    name: String,
+    colors: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileMetadata {
+    #[serde(default)]
+    exported_at: u64,
+    #[serde(default)]
+    app_version: String,
+}
+
+/// Render the palette as a row of square swatches, PNG-encoded as a data URL.
+fn thumbnail(colors: &[[u8; 3]], options: &PngOptions) -> Result<Option<String>, String> {
+    if colors.is_empty() {
+        return Ok(None);
+    }
+    let img = image::RgbImage::from_fn(THUMB_SWATCH * colors.len() as u32, THUMB_SWATCH, |x, _| {
+        image::Rgb(colors[(x / THUMB_SWATCH) as usize])
+    });
+    let png = png_encoder::encode_rgb(&img, options)?;
+    Ok(Some(format!(
+        "data:image/png;base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(png)
+    )))
+}
+
+/// `png` controls how the embedded thumbnail is encoded.
+pub fn to_json(
+    palette: &Palette,
+    app_version: &str,
+    exported_at: u64,
+    png: &PngOptions,
+) -> Result<String, String> {
+    let colors = crate::export::palette_rgb(palette)?;
+    let file = PaletteFile {
+        format: FORMAT_TAG.to_string(),
+        version: CURRENT_VERSION,
+        palette: FilePalette {
+            name: palette.name.clone(),
+            colors: colors.iter().map(|rgb| color_math::to_hex(*rgb)).collect(),
+        },
+        metadata: FileMetadata {
+            exported_at,
+            app_version: app_version.to_string(),
+        },
+        thumbnail: thumbnail(&colors, png)?,
+    };
+    serde_json::to_string_pretty(&file).map_err(|e| format!("Failed to serialize palette: {}", e))
+}
+
+pub fn parse(json: &str) -> Result<ImportedPalette, String> {
+    let file: PaletteFile =
+        serde_json::from_str(json).map_err(|e| format!("Not a valid .colorsnap file: {}", e))?;
+    if file.format != FORMAT_TAG {
+        return Err("Not a .colorsnap palette file".to_string());
+    }
+    if file.version > CURRENT_VERSION {
+        return Err(format!(
+            "This palette was saved by a newer version (format v{}); please update",
+            file.version
+        ));
+    }
+    let colors = file
+        .palette
+        .colors
+        .iter()
+        .map(|hex| color_math::parse_hex(hex).map(color_math::to_hex))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ImportedPalette {
+        name: file.palette.name,
+        colors,
+    })
+}
+
+pub fn is_palette_file(path: &str) -> bool {
+    std::path::Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(EXTENSION))
+}
@@ -0,0 +1,70 @@
+use crate::{workspace, Palette};
+use serde::Serialize;
+
+/// Error returned by palette mutation commands. Serialized with a `kind` tag
+/// so the frontend can tell a locked palette apart from other failures.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum PaletteError {
+    /// The palette is locked and the change would have edited or removed it
+    Locked {
+        id: String,
+        name: String,
+    },
+    Other {
+        message: String,
+    },
+}
+
+impl From<String> for PaletteError {
+    fn from(message: String) -> Self {
+        PaletteError::Other { message }
+    }
+}
+
+impl std::fmt::Display for PaletteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaletteError::Locked { name, .. } => write!(f, "Palette \"{name}\" is locked"),
+            PaletteError::Other { message } => f.write_str(message),
+        }
+    }
+}
+
+pub fn ensure_unlocked(palette: &Palette) -> Result<(), PaletteError> {
+    if palette.locked {
+        return Err(PaletteError::Locked {
+            id: palette.id.clone(),
+            name: palette.name.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Refuse a save of `workspace`'s palettes that would rename, recolor,
+/// unlock or drop any palette that is locked in `stored`. Locking is only
+/// undone through the dedicated command.
+pub fn check_save(
+    stored: &[Palette],
+    incoming: &[Palette],
+    workspace: Option<&str>,
+) -> Result<(), PaletteError> {
+    for locked in stored
+        .iter()
+        .filter(|p| p.locked && workspace::in_workspace(p, workspace))
+    {
+        let unchanged = incoming
+            .iter()
+            .find(|p| p.id == locked.id)
+            .is_some_and(|p| {
+                p.locked
+                    && p.name == locked.name
+                    && p.colors == locked.colors
+                    && p.dark_variant_of == locked.dark_variant_of
+            });
+        if !unchanged {
+            ensure_unlocked(locked)?;
+        }
+    }
+    Ok(())
+}
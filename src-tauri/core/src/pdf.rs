@@ -0,0 +1,197 @@
+use crate::color_math;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PageSize {
+    A4,
+    Letter,
+}
+
+impl PageSize {
+    /// Width and height in PDF points (1/72 inch).
+    fn dimensions(self) -> (f64, f64) {
+        match self {
+            PageSize::A4 => (595.28, 841.89),
+            PageSize::Letter => (612.0, 792.0),
+        }
+    }
+}
+
+const MARGIN: f64 = 40.0;
+const GAP: f64 = 16.0;
+const COLUMNS: usize = 3;
+const SWATCH_HEIGHT: f64 = 90.0;
+const LABEL_HEIGHT: f64 = 52.0;
+const HEADER_HEIGHT: f64 = 56.0;
+
+/// Escape a string for a PDF literal. Helvetica only covers WinAnsi, so
+/// anything outside ASCII is replaced rather than rendered as garbage.
+fn pdf_text(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\\' | '(' | ')' => format!("\\{c}"),
+            c if c.is_ascii() && !c.is_ascii_control() => c.to_string(),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
+
+fn fill(rgb: [u8; 3]) -> String {
+    format!(
+        "{:.3} {:.3} {:.3} rg",
+        rgb[0] as f64 / 255.0,
+        rgb[1] as f64 / 255.0,
+        rgb[2] as f64 / 255.0
+    )
+}
+
+fn text(out: &mut String, x: f64, y: f64, size: f64, s: &str) {
+    out.push_str(&format!(
+        "BT /F1 {size} Tf {x:.2} {y:.2} Td ({}) Tj ET\n",
+        pdf_text(s)
+    ));
+}
+
+/// Build the content stream of every page: a title on the first page, then
+/// swatch cards with hex, RGB and CMYK values in a fixed grid.
+fn page_streams(title: &str, colors: &[[u8; 3]], size: PageSize) -> Vec<String> {
+    let (width, height) = size.dimensions();
+    let card_w = (width - 2.0 * MARGIN - GAP * (COLUMNS - 1) as f64) / COLUMNS as f64;
+    let card_h = SWATCH_HEIGHT + LABEL_HEIGHT;
+    let usable = height - 2.0 * MARGIN - HEADER_HEIGHT;
+    let rows = (((usable + GAP) / (card_h + GAP)).floor() as usize).max(1);
+    let per_page = rows * COLUMNS;
+
+    let chunks: Vec<&[[u8; 3]]> = if colors.is_empty() {
+        vec![&[]]
+    } else {
+        colors.chunks(per_page).collect()
+    };
+
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(page, chunk)| {
+            let mut out = String::new();
+            let top = height - MARGIN;
+            out.push_str("0.1 0.1 0.1 rg\n");
+            if page == 0 {
+                text(&mut out, MARGIN, top - 24.0, 22.0, title);
+            }
+            out.push_str("0.55 0.55 0.55 rg\n");
+            text(
+                &mut out,
+                MARGIN,
+                top - 42.0,
+                9.0,
+                &format!(
+                    "{} colors - page {} of {}",
+                    colors.len(),
+                    page + 1,
+                    chunks.len()
+                ),
+            );
+
+            for (i, &rgb) in chunk.iter().enumerate() {
+                let (col, row) = (i % COLUMNS, i / COLUMNS);
+                let x = MARGIN + col as f64 * (card_w + GAP);
+                let y = top - HEADER_HEIGHT - (row + 1) as f64 * card_h - row as f64 * GAP;
+
+                // Card outline, then the swatch filling its upper part
+                out.push_str(&format!(
+                    "0.85 0.85 0.82 RG 0.5 w {x:.2} {y:.2} {card_w:.2} {card_h:.2} re S\n"
+                ));
+                out.push_str(&format!(
+                    "{} {x:.2} {:.2} {card_w:.2} {SWATCH_HEIGHT:.2} re f\n",
+                    fill(rgb),
+                    y + LABEL_HEIGHT
+                ));
+
+                let cmyk = color_math::rgb_to_cmyk(rgb);
+                out.push_str("0.1 0.1 0.1 rg\n");
+                text(
+                    &mut out,
+                    x + 8.0,
+                    y + LABEL_HEIGHT - 16.0,
+                    11.0,
+                    &color_math::to_hex(rgb),
+                );
+                out.push_str("0.4 0.4 0.4 rg\n");
+                text(
+                    &mut out,
+                    x + 8.0,
+                    y + LABEL_HEIGHT - 30.0,
+                    8.0,
+                    &format!("RGB {} {} {}", rgb[0], rgb[1], rgb[2]),
+                );
+                text(
+                    &mut out,
+                    x + 8.0,
+                    y + LABEL_HEIGHT - 42.0,
+                    8.0,
+                    &format!(
+                        "CMYK {:.0} {:.0} {:.0} {:.0}",
+                        cmyk.c, cmyk.m, cmyk.y, cmyk.k
+                    ),
+                );
+            }
+            out
+        })
+        .collect()
+}
+
+/// Render a printable swatch sheet as a complete PDF document.
+pub fn palette_sheet(title: &str, colors: &[[u8; 3]], size: PageSize) -> Vec<u8> {
+    let (width, height) = size.dimensions();
+    let streams = page_streams(title, colors, size);
+
+    // Object layout: 1 catalog, 2 page tree, 3 font, then a page + content
+    // stream pair for every page.
+    let page_ids: Vec<usize> = (0..streams.len()).map(|i| 4 + i * 2).collect();
+    let mut objects: Vec<String> = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{id} 0 R"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            page_ids.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    ];
+    for (id, stream) in page_ids.iter().zip(&streams) {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {width:.2} {height:.2}] \
+             /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            id + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{stream}endstream",
+            stream.len()
+        ));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, body) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{body}\nendobj\n", i + 1));
+    }
+    let xref = pdf.len();
+    pdf.push_str(&format!(
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    ));
+    for offset in offsets {
+        pdf.push_str(&format!("{offset:010} 00000 n \n"));
+    }
+    pdf.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1
+    ));
+    pdf.into_bytes()
+}
@@ -0,0 +1,139 @@
+use crate::color_math::{self, Oklch};
+use crate::{dedupe, export, Palette};
+use serde::{Deserialize, Serialize};
+
+/// Largest palette a pipeline may produce; scales multiply the color count.
+const MAX_COLORS: usize = 2000;
+/// Largest delta-E a dedupe step accepts, as for imports.
+const MAX_DEDUPE_THRESHOLD: f64 = 20.0;
+/// OKLCH lightness of the lightest and darkest step of an expanded scale.
+const SCALE_LIGHTEST: f64 = 0.95;
+const SCALE_DARKEST: f64 = 0.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortKey {
+    /// Each color followed by the nearest remaining one, starting from the
+    /// darkest, so neighbors blend into each other
+    Smooth,
+    Lightness,
+    Hue,
+}
+
+/// One step of a pipeline, applied to the colors left by the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum Transform {
+    Sort {
+        by: SortKey,
+    },
+    /// Drop colors within `threshold` (CIEDE2000) of an earlier one
+    Dedupe {
+        threshold: f64,
+    },
+    /// Replace each color with a light-to-dark scale of `steps` colors of
+    /// its hue and chroma
+    Scales {
+        steps: usize,
+    },
+}
+
+/// Transforms to run in order, then the exporter (by registry id) that
+/// writes the result, e.g. sort smooth → dedupe ΔE<3 → 9-step scales →
+/// Tailwind.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Pipeline {
+    #[serde(default)]
+    pub steps: Vec<Transform>,
+    pub exporter: String,
+}
+
+fn sort(colors: &mut Vec<[u8; 3]>, by: SortKey) {
+    let lch = |rgb: &[u8; 3]| color_math::rgb_to_oklch(*rgb);
+    match by {
+        SortKey::Lightness => colors.sort_by(|a, b| lch(a).l.total_cmp(&lch(b).l)),
+        SortKey::Hue => colors.sort_by(|a, b| lch(a).h.total_cmp(&lch(b).h)),
+        SortKey::Smooth => {
+            let mut rest = std::mem::take(colors);
+            rest.sort_by(|a, b| lch(a).l.total_cmp(&lch(b).l));
+            let distance = |a: [u8; 3], b: [u8; 3]| {
+                let (a, b) = (color_math::rgb_to_oklab(a), color_math::rgb_to_oklab(b));
+                (a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)
+            };
+            if !rest.is_empty() {
+                colors.push(rest.remove(0));
+            }
+            while let Some(&last) = colors.last() {
+                let Some(next) = (0..rest.len())
+                    .min_by(|&i, &j| distance(last, rest[i]).total_cmp(&distance(last, rest[j])))
+                else {
+                    break;
+                };
+                colors.push(rest.remove(next));
+            }
+        }
+    }
+}
+
+/// Light-to-dark steps of one hue. Chroma is reduced where sRGB can't show
+/// it at that lightness, instead of clipping channels and shifting the hue.
+fn scale(rgb: [u8; 3], steps: usize) -> impl Iterator<Item = [u8; 3]> {
+    let base = color_math::rgb_to_oklch(rgb);
+    (0..steps).map(move |i| {
+        let t = i as f64 / (steps - 1) as f64;
+        let mut step = Oklch {
+            l: SCALE_LIGHTEST + (SCALE_DARKEST - SCALE_LIGHTEST) * t,
+            ..base
+        };
+        loop {
+            let mapped = color_math::oklch_to_rgb_mapped(step);
+            if !mapped.clipped || step.c < 0.001 {
+                return mapped.rgb;
+            }
+            step.c *= 0.9;
+        }
+    })
+}
+
+/// Run `steps` over the palette's colors. The result keeps the palette's id
+/// and name so exporters name their output as usual.
+pub fn apply(palette: &Palette, steps: &[Transform]) -> Result<Palette, String> {
+    let mut colors = export::palette_rgb(palette)?;
+    for step in steps {
+        match *step {
+            Transform::Sort { by } => sort(&mut colors, by),
+            Transform::Dedupe { threshold } => {
+                if !(0.0..=MAX_DEDUPE_THRESHOLD).contains(&threshold) {
+                    return Err(format!(
+                        "Delta-E threshold must be between 0 and {}",
+                        MAX_DEDUPE_THRESHOLD
+                    ));
+                }
+                let hexes: Vec<String> = colors.iter().map(|&c| color_math::to_hex(c)).collect();
+                let (kept, _) = dedupe::dedupe(&hexes, threshold);
+                colors = kept
+                    .iter()
+                    .map(|hex| color_math::parse_hex(hex))
+                    .collect::<Result<_, _>>()?;
+            }
+            Transform::Scales { steps } => {
+                if !(3..=11).contains(&steps) {
+                    return Err("Scales need between 3 and 11 steps".to_string());
+                }
+                if colors.len() * steps > MAX_COLORS {
+                    return Err(format!(
+                        "Scales would make {} colors, more than {}",
+                        colors.len() * steps,
+                        MAX_COLORS
+                    ));
+                }
+                colors = colors.iter().flat_map(|&c| scale(c, steps)).collect();
+            }
+        }
+    }
+    Ok(Palette {
+        colors: colors.into_iter().map(color_math::to_hex).collect(),
+        ..palette.clone()
+    })
+}
@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PngCompression {
+    /// Quickest to write; files come out somewhat larger
+    Fast,
+    #[default]
+    Balanced,
+    /// Smallest files, noticeably slower on large images
+    High,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PngEncoder {
+    /// The `png` crate, single-threaded
+    #[default]
+    Standard,
+    /// mtpng, which compresses on every core; needs the `mtpng` feature and
+    /// falls back to `Standard` without it
+    Mtpng,
+}
+
+/// How rendered images (contrast grids, QR codes, palette thumbnails) are
+/// written. Every option is lossless.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PngOptions {
+    pub compression: PngCompression,
+    /// Write a palette (indexed) PNG when the image has at most 256 colors,
+    /// as swatch sheets do; much smaller and quicker to compress
+    pub indexed: bool,
+    pub encoder: PngEncoder,
+}
+
+/// Whether the mtpng encoder was compiled in.
+pub fn mtpng_available() -> bool {
+    cfg!(feature = "mtpng")
+}
+
+enum Pixels<'a> {
+    Rgb(&'a [u8]),
+    Gray(&'a [u8]),
+    Indexed { palette: Vec<u8>, indices: Vec<u8> },
+}
+
+pub fn encode_rgb(img: &image::RgbImage, options: &PngOptions) -> Result<Vec<u8>, String> {
+    let pixels = match options.indexed.then(|| index(img)).flatten() {
+        Some((palette, indices)) => Pixels::Indexed { palette, indices },
+        None => Pixels::Rgb(img.as_raw()),
+    };
+    encode(img.width(), img.height(), pixels, options)
+}
+
+pub fn encode_gray(img: &image::GrayImage, options: &PngOptions) -> Result<Vec<u8>, String> {
+    encode(
+        img.width(),
+        img.height(),
+        Pixels::Gray(img.as_raw()),
+        options,
+    )
+}
+
+/// Palette and per-pixel indices, or `None` with more than 256 colors.
+fn index(img: &image::RgbImage) -> Option<(Vec<u8>, Vec<u8>)> {
+    let mut slots: HashMap<[u8; 3], u8> = HashMap::new();
+    let mut palette = Vec::new();
+    let mut indices = Vec::with_capacity((img.width() * img.height()) as usize);
+    for pixel in img.pixels() {
+        let slot = match slots.get(&pixel.0) {
+            Some(&slot) => slot,
+            None => {
+                let slot = u8::try_from(slots.len()).ok()?;
+                slots.insert(pixel.0, slot);
+                palette.extend(pixel.0);
+                slot
+            }
+        };
+        indices.push(slot);
+    }
+    Some((palette, indices))
+}
+
+fn encode(
+    width: u32,
+    height: u32,
+    pixels: Pixels,
+    options: &PngOptions,
+) -> Result<Vec<u8>, String> {
+    #[cfg(feature = "mtpng")]
+    if options.encoder == PngEncoder::Mtpng {
+        return encode_mtpng(width, height, pixels, options);
+    }
+
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_compression(match options.compression {
+        PngCompression::Fast => png::Compression::Fast,
+        PngCompression::Balanced => png::Compression::Balanced,
+        PngCompression::High => png::Compression::High,
+    });
+    let data = match pixels {
+        Pixels::Rgb(data) => {
+            encoder.set_color(png::ColorType::Rgb);
+            data
+        }
+        Pixels::Gray(data) => {
+            encoder.set_color(png::ColorType::Grayscale);
+            data
+        }
+        Pixels::Indexed {
+            palette,
+            ref indices,
+        } => {
+            encoder.set_color(png::ColorType::Indexed);
+            encoder.set_palette(palette);
+            indices
+        }
+    };
+    let mut writer = encoder
+        .write_header()
+        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    writer
+        .write_image_data(data)
+        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    writer
+        .finish()
+        .map_err(|e| format!("Failed to encode PNG: {}", e))?;
+    Ok(out)
+}
+
+#[cfg(feature = "mtpng")]
+fn encode_mtpng(
+    width: u32,
+    height: u32,
+    pixels: Pixels,
+    options: &PngOptions,
+) -> Result<Vec<u8>, String> {
+    use mtpng::encoder::{Encoder, Options};
+    use mtpng::{ColorType, CompressionLevel, Header};
+
+    let fail = |e: std::io::Error| format!("Failed to encode PNG: {}", e);
+    let mut mt_options = Options::new();
+    mt_options
+        .set_compression_level(match options.compression {
+            PngCompression::Fast => CompressionLevel::Fast,
+            PngCompression::Balanced => CompressionLevel::Default,
+            PngCompression::High => CompressionLevel::High,
+        })
+        .map_err(fail)?;
+    let mut header = Header::new();
+    header.set_size(width, height).map_err(fail)?;
+    let (color, data, palette) = match &pixels {
+        Pixels::Rgb(data) => (ColorType::Truecolor, *data, None),
+        Pixels::Gray(data) => (ColorType::Greyscale, *data, None),
+        Pixels::Indexed { palette, indices } => {
+            (ColorType::IndexedColor, indices.as_slice(), Some(palette))
+        }
+    };
+    header.set_color(color, 8).map_err(fail)?;
+
+    let mut encoder = Encoder::new(Vec::new(), &mt_options);
+    encoder.write_header(&header).map_err(fail)?;
+    if let Some(palette) = palette {
+        encoder.write_palette(palette).map_err(fail)?;
+    }
+    encoder.write_image_rows(data).map_err(fail)?;
+    encoder.finish().map_err(fail)
+}
@@ -0,0 +1,211 @@
+use crate::color_math::{self, Cmyk, Lab};
+use serde::{Deserialize, Serialize};
+
+/// CIEDE2000 distance beyond which a color is reported as not print-safe.
+/// Looser than a proofing tolerance, so only colors clearly outside the
+/// press gamut are flagged, not paper white or process primaries.
+const PRINT_TOLERANCE: f64 = 5.0;
+
+/// Coarse search step in ink percent, then refined down to `FINE_STEP`.
+const COARSE_STEP: f64 = 10.0;
+const FINE_STEP: f64 = 0.5;
+
+/// Yule-Nielsen factor: halftone dots print darker than their area suggests.
+const YULE_NIELSEN: f64 = 2.0;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PrintProfile {
+    /// Offset on coated stock (FOGRA39-like)
+    #[default]
+    Coated,
+    /// Offset on uncoated stock (FOGRA29-like)
+    Uncoated,
+}
+
+/// Solid-ink measurements a profile is built from. Values approximate the
+/// published characterization data; good enough to flag colors, not to proof.
+struct Characterization {
+    paper: [f64; 3],
+    c: [f64; 3],
+    m: [f64; 3],
+    y: [f64; 3],
+    cm: [f64; 3],
+    cy: [f64; 3],
+    my: [f64; 3],
+    cmy: [f64; 3],
+    k: [f64; 3],
+    /// Total area coverage limit, in percent
+    max_ink: f64,
+}
+
+const COATED: Characterization = Characterization {
+    paper: [95.0, 0.0, -2.0],
+    c: [55.0, -37.0, -50.0],
+    m: [48.0, 74.0, -3.0],
+    y: [89.0, -5.0, 93.0],
+    cm: [24.0, 22.0, -46.0],
+    cy: [50.0, -65.0, 27.0],
+    my: [47.0, 68.0, 48.0],
+    cmy: [24.0, 1.0, 0.0],
+    k: [16.0, 0.0, 0.0],
+    max_ink: 330.0,
+};
+
+const UNCOATED: Characterization = Characterization {
+    paper: [95.0, 0.0, -2.0],
+    c: [60.0, -26.0, -44.0],
+    m: [56.0, 61.0, -2.0],
+    y: [89.0, -4.0, 78.0],
+    cm: [39.0, 12.0, -29.0],
+    cy: [54.0, -44.0, 14.0],
+    my: [54.0, 58.0, 26.0],
+    cmy: [38.0, 1.0, 2.0],
+    k: [31.0, 1.0, 1.0],
+    max_ink: 300.0,
+};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintSafety {
+    pub hex: String,
+    pub profile: PrintProfile,
+    /// Closest printable ink mix, channels in percent
+    pub cmyk: Cmyk,
+    /// CIEDE2000 between the screen color and its printed simulation
+    pub delta_e: f64,
+    pub print_safe: bool,
+    /// How the color will actually print, when that differs noticeably
+    pub alternative: Option<String>,
+}
+
+/// Lab to white-relative XYZ, using the same white point as `color_math`.
+fn lab_to_xyz([l, a, b]: [f64; 3]) -> [f64; 3] {
+    let fy = (l + 16.0) / 116.0;
+    let finv = |t: f64| {
+        if t.powi(3) > 0.008856 {
+            t.powi(3)
+        } else {
+            (t - 16.0 / 116.0) / 7.787
+        }
+    };
+    [finv(fy + a / 500.0), finv(fy), finv(fy - b / 200.0)]
+}
+
+fn xyz_to_lab([x, y, z]: [f64; 3]) -> Lab {
+    let f = |t: f64| {
+        if t > 0.008856 {
+            t.cbrt()
+        } else {
+            7.787 * t + 16.0 / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    Lab {
+        l: 116.0 * fy - 16.0,
+        a: 500.0 * (fx - fy),
+        b: 200.0 * (fy - fz),
+    }
+}
+
+/// Printed appearance of an ink mix: Demichel-weighted Neugebauer model for
+/// the chromatic inks, with black laid over as a multiplicative screen.
+fn simulate(profile: &Characterization, cmyk: [f64; 4]) -> Lab {
+    let [c, m, y, k] = cmyk.map(|v| v / 100.0);
+    let primaries = [
+        (profile.paper, (1.0 - c) * (1.0 - m) * (1.0 - y)),
+        (profile.c, c * (1.0 - m) * (1.0 - y)),
+        (profile.m, (1.0 - c) * m * (1.0 - y)),
+        (profile.y, (1.0 - c) * (1.0 - m) * y),
+        (profile.cm, c * m * (1.0 - y)),
+        (profile.cy, c * (1.0 - m) * y),
+        (profile.my, (1.0 - c) * m * y),
+        (profile.cmy, c * m * y),
+    ];
+    let n = 1.0 / YULE_NIELSEN;
+    let mut mix = [0.0; 3];
+    for (lab, weight) in primaries {
+        for (acc, v) in mix.iter_mut().zip(lab_to_xyz(lab)) {
+            *acc += weight * v.powf(n);
+        }
+    }
+    let paper = lab_to_xyz(profile.paper);
+    let black = lab_to_xyz(profile.k);
+    let xyz: [f64; 3] = std::array::from_fn(|i| {
+        let chromatic = mix[i].powf(YULE_NIELSEN);
+        let screen = (1.0 - k) + k * (black[i] / paper[i]).powf(n);
+        chromatic * screen.powf(YULE_NIELSEN)
+    });
+    xyz_to_lab(xyz)
+}
+
+/// Closest printable ink mix to `target`: a coarse grid over all four inks
+/// within the coverage limit, refined by shrinking coordinate steps.
+fn nearest_ink(profile: &Characterization, target: Lab) -> ([f64; 4], f64) {
+    let within_limit = |inks: &[f64; 4]| inks.iter().sum::<f64>() <= profile.max_ink;
+    let distance = |inks: &[f64; 4]| color_math::ciede2000(simulate(profile, *inks), target);
+
+    let levels = (100.0 / COARSE_STEP) as usize + 1;
+    let mut best = ([0.0; 4], f64::MAX);
+    for i in 0..levels.pow(4) {
+        let mut rest = i;
+        let inks: [f64; 4] = std::array::from_fn(|_| {
+            let v = (rest % levels) as f64 * COARSE_STEP;
+            rest /= levels;
+            v
+        });
+        if !within_limit(&inks) {
+            continue;
+        }
+        let d = distance(&inks);
+        if d < best.1 {
+            best = (inks, d);
+        }
+    }
+
+    let mut step = COARSE_STEP / 2.0;
+    while step >= FINE_STEP {
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for channel in 0..4 {
+                for delta in [-step, step] {
+                    let mut inks = best.0;
+                    inks[channel] = (inks[channel] + delta).clamp(0.0, 100.0);
+                    if inks == best.0 || !within_limit(&inks) {
+                        continue;
+                    }
+                    let d = distance(&inks);
+                    if d < best.1 {
+                        best = (inks, d);
+                        improved = true;
+                    }
+                }
+            }
+        }
+        step /= 2.0;
+    }
+    best
+}
+
+/// Check how faithfully a screen color survives conversion to print, and
+/// suggest the printable color to design with instead when it does not.
+pub fn check(rgb: [u8; 3], profile: PrintProfile) -> PrintSafety {
+    let characterization = match profile {
+        PrintProfile::Coated => &COATED,
+        PrintProfile::Uncoated => &UNCOATED,
+    };
+    let (inks, delta_e) = nearest_ink(characterization, color_math::rgb_to_lab(rgb));
+    let print_safe = delta_e <= PRINT_TOLERANCE;
+    let alternative = (!print_safe)
+        .then(|| color_math::to_hex(color_math::lab_to_rgb(simulate(characterization, inks))));
+    let [c, m, y, k] = inks.map(|v| (v * 10.0).round() / 10.0);
+    PrintSafety {
+        hex: color_math::to_hex(rgb),
+        profile,
+        cmyk: Cmyk { c, m, y, k },
+        delta_e: (delta_e * 100.0).round() / 100.0,
+        print_safe,
+        alternative,
+    }
+}
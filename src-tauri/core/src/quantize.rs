@@ -0,0 +1,103 @@
+use crate::color_math::{self, Oklab};
+
+const MAX_ITERATIONS: usize = 32;
+
+fn distance_sq(a: &Oklab, b: &Oklab) -> f64 {
+    (a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)
+}
+
+/// Reduce `colors` to at most `k` representatives with k-means in OKLab.
+///
+/// Seeds are chosen farthest-point first (starting from the most chromatic
+/// color), so small clusters of distinct hues survive instead of being
+/// absorbed by large runs of similar colors. Each cluster is represented by
+/// its member closest to the centroid, so results are real captured colors.
+pub fn kmeans_oklab(colors: &[[u8; 3]], k: usize) -> Vec<[u8; 3]> {
+    let mut unique: Vec<[u8; 3]> = Vec::new();
+    for c in colors {
+        if !unique.contains(c) {
+            unique.push(*c);
+        }
+    }
+    if k == 0 || unique.len() <= k {
+        return unique;
+    }
+    let points: Vec<Oklab> = unique
+        .iter()
+        .map(|c| color_math::rgb_to_oklab(*c))
+        .collect();
+
+    let first = (0..points.len())
+        .max_by(|&a, &b| {
+            let chroma = |p: &Oklab| p.a.hypot(p.b);
+            chroma(&points[a]).total_cmp(&chroma(&points[b]))
+        })
+        .unwrap_or(0);
+    let mut centroids = vec![points[first]];
+    while centroids.len() < k {
+        let next = (0..points.len())
+            .max_by(|&a, &b| {
+                let nearest = |p: &Oklab| {
+                    centroids
+                        .iter()
+                        .map(|c| distance_sq(p, c))
+                        .fold(f64::INFINITY, f64::min)
+                };
+                nearest(&points[a]).total_cmp(&nearest(&points[b]))
+            })
+            .unwrap_or(0);
+        centroids.push(points[next]);
+    }
+
+    let mut assignment = vec![0usize; points.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (i, p) in points.iter().enumerate() {
+            let best = nearest_index(p, &centroids);
+            if assignment[i] != best {
+                assignment[i] = best;
+                changed = true;
+            }
+        }
+        for (ci, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&Oklab> = points
+                .iter()
+                .zip(&assignment)
+                .filter(|(_, a)| **a == ci)
+                .map(|(p, _)| p)
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+            let n = members.len() as f64;
+            *centroid = Oklab {
+                l: members.iter().map(|p| p.l).sum::<f64>() / n,
+                a: members.iter().map(|p| p.a).sum::<f64>() / n,
+                b: members.iter().map(|p| p.b).sum::<f64>() / n,
+            };
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    centroids
+        .iter()
+        .filter_map(|c| {
+            (0..points.len())
+                .min_by(|&a, &b| distance_sq(&points[a], c).total_cmp(&distance_sq(&points[b], c)))
+                .map(|i| unique[i])
+        })
+        .fold(Vec::new(), |mut out, c| {
+            if !out.contains(&c) {
+                out.push(c);
+            }
+            out
+        })
+}
+
+fn nearest_index(p: &Oklab, centroids: &[Oklab]) -> usize {
+    (0..centroids.len())
+        .min_by(|&a, &b| distance_sq(p, &centroids[a]).total_cmp(&distance_sq(p, &centroids[b])))
+        .unwrap_or(0)
+}
@@ -0,0 +1,133 @@
+use crate::color_math;
+use crate::import::ImportedPalette;
+use crate::png_encoder::{self, PngOptions};
+use crate::Palette;
+use serde::Serialize;
+
+const SHARE_SCHEME: &str = "colorsnap://palette";
+const COOLORS_BASE: &str = "https://coolors.co/";
+
+/// Pixels per QR module and quiet-zone width (in modules) of rendered codes.
+const QR_SCALE: u32 = 8;
+const QR_QUIET_ZONE: u32 = 4;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedPalette {
+    pub url: String,
+    pub coolors_url: String,
+    pub qr_path: Option<String>,
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters.
+pub fn encode_component(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Colors as dash-separated six-digit hex, the form coolors.co uses in its paths.
+fn hex_list(palette: &Palette) -> Result<String, String> {
+    Ok(palette
+        .colors
+        .iter()
+        .map(|hex| color_math::parse_hex(hex).map(|rgb| color_math::to_hex(rgb)[1..].to_string()))
+        .collect::<Result<Vec<_>, _>>()?
+        .join("-"))
+}
+
+/// `colorsnap://palette?name=…&colors=FF8800-112233`
+pub fn share_url(palette: &Palette) -> Result<String, String> {
+    Ok(format!(
+        "{SHARE_SCHEME}?name={}&colors={}",
+        encode_component(&palette.name),
+        hex_list(palette)?
+    ))
+}
+
+/// `https://coolors.co/ff8800-112233`
+pub fn coolors_url(palette: &Palette) -> Result<String, String> {
+    Ok(format!(
+        "{COOLORS_BASE}{}",
+        hex_list(palette)?.to_lowercase()
+    ))
+}
+
+/// Render `data` as a black-on-white QR code PNG.
+pub fn write_qr_png(data: &str, path: &str, png: &PngOptions) -> Result<(), String> {
+    let code = qrcode::QrCode::new(data.as_bytes())
+        .map_err(|e| format!("Failed to encode QR code: {}", e))?;
+    let modules = code.width() as u32;
+    let colors = code.to_colors();
+    let size = (modules + QR_QUIET_ZONE * 2) * QR_SCALE;
+
+    let img = image::GrayImage::from_fn(size, size, |x, y| {
+        let (mx, my) = (x / QR_SCALE, y / QR_SCALE);
+        let dark = mx >= QR_QUIET_ZONE
+            && my >= QR_QUIET_ZONE
+            && mx < modules + QR_QUIET_ZONE
+            && my < modules + QR_QUIET_ZONE
+            && colors[((my - QR_QUIET_ZONE) * modules + mx - QR_QUIET_ZONE) as usize]
+                == qrcode::Color::Dark;
+        image::Luma([if dark { 0 } else { 255 }])
+    });
+    std::fs::write(path, png_encoder::encode_gray(&img, png)?)
+        .map_err(|e| format!("Failed to write QR code: {}", e))
+}
+
+fn decode_component(text: &str) -> String {
+    let hex = |b: u8| (b as char).to_digit(16).map(|d| d as u8);
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let decoded = match bytes[i] {
+            b'%' if i + 2 < bytes.len() => hex(bytes[i + 1])
+                .zip(hex(bytes[i + 2]))
+                .map(|(hi, lo)| (hi << 4) | lo),
+            _ => None,
+        };
+        match decoded {
+            Some(b) => {
+                out.push(b);
+                i += 3;
+            }
+            None => {
+                out.push(if bytes[i] == b'+' { b' ' } else { bytes[i] });
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Parse a link produced by [`share_url`] back into a palette.
+pub fn parse_share_url(url: &str) -> Result<ImportedPalette, String> {
+    let query = url
+        .strip_prefix(SHARE_SCHEME)
+        .and_then(|rest| rest.strip_prefix('?'))
+        .ok_or_else(|| format!("Not a palette link: {url}"))?;
+    let (mut name, mut colors) = (None, None);
+    for pair in query.split('&') {
+        match pair.split_once('=') {
+            Some(("name", value)) => name = Some(decode_component(value)),
+            Some(("colors", value)) => colors = Some(value),
+            _ => {}
+        }
+    }
+    let colors = colors
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| format!("Palette link has no colors: {url}"))?
+        .split('-')
+        .map(|hex| color_math::parse_hex(hex).map(color_math::to_hex))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ImportedPalette {
+        name: name.unwrap_or_else(|| "Shared palette".to_string()),
+        colors,
+    })
+}
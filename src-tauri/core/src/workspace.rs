@@ -0,0 +1,43 @@
+use crate::Palette;
+use serde::{Deserialize, Serialize};
+
+/// A client/project grouping above palettes. Palettes belong to exactly one
+/// workspace; `None` on a palette means the built-in default workspace.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Workspace {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub export_defaults: ExportDefaults,
+}
+
+/// Per-workspace defaults the export dialogs start from.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportDefaults {
+    pub format: Option<String>,
+    pub directory: Option<String>,
+}
+
+pub fn in_workspace(palette: &Palette, workspace: Option<&str>) -> bool {
+    palette.workspace_id.as_deref() == workspace
+}
+
+/// Replace the palettes of one workspace, leaving every other workspace's
+/// palettes untouched. Incoming palettes are stamped with the workspace id.
+pub fn replace_workspace_palettes(
+    all: Vec<Palette>,
+    workspace: Option<&str>,
+    incoming: Vec<Palette>,
+) -> Vec<Palette> {
+    let mut result: Vec<Palette> = incoming
+        .into_iter()
+        .map(|mut p| {
+            p.workspace_id = workspace.map(str::to_string);
+            p
+        })
+        .collect();
+    result.extend(all.into_iter().filter(|p| !in_workspace(p, workspace)));
+    result
+}
@@ -0,0 +1,164 @@
+//! Property tests for everything that parses user-supplied palettes: files
+//! dropped on the window, pasted links and typed hex values. Each parser must
+//! reject garbage with an error rather than panic, and read back what the
+//! matching writer produced.
+
+use pixnib_core::{color_math, import, palette_file, share, Palette};
+use proptest::prelude::*;
+
+fn rgb() -> impl Strategy<Value = [u8; 3]> {
+    any::<[u8; 3]>()
+}
+
+fn colors() -> impl Strategy<Value = Vec<[u8; 3]>> {
+    prop::collection::vec(rgb(), 1..24)
+}
+
+/// Palette names as users type them: no line breaks, not blank.
+fn name() -> impl Strategy<Value = String> {
+    "[^\r\n]{0,40}".prop_filter("non-blank", |s| !s.trim().is_empty())
+}
+
+fn hexes(colors: &[[u8; 3]]) -> Vec<String> {
+    colors.iter().map(|&c| color_math::to_hex(c)).collect()
+}
+
+fn palette(name: &str, colors: &[[u8; 3]]) -> Palette {
+    Palette {
+        id: "p".to_string(),
+        name: name.to_string(),
+        colors: hexes(colors),
+        dark_variant_of: None,
+        workspace_id: None,
+        locked: false,
+        export: None,
+    }
+}
+
+fn gpl(name: &str, colors: &[[u8; 3]]) -> String {
+    let mut text = format!("GIMP Palette\nName: {name}\nColumns: 4\n# comment\n");
+    for [r, g, b] in colors {
+        text.push_str(&format!("{r:3} {g:3} {b:3}\tswatch\n"));
+    }
+    text
+}
+
+fn ase(colors: &[[u8; 3]]) -> Vec<u8> {
+    let mut data = b"ASEF".to_vec();
+    data.extend([0, 1, 0, 0]);
+    data.extend((colors.len() as u32).to_be_bytes());
+    for rgb in colors {
+        let mut block = Vec::new();
+        block.extend(2u16.to_be_bytes());
+        block.extend([0, b'a', 0, 0]);
+        block.extend(b"RGB ");
+        for c in rgb {
+            block.extend((*c as f32 / 255.0).to_bits().to_be_bytes());
+        }
+        block.extend(0u16.to_be_bytes());
+        data.extend(1u16.to_be_bytes());
+        data.extend((block.len() as u32).to_be_bytes());
+        data.extend(block);
+    }
+    data
+}
+
+proptest! {
+    #[test]
+    fn hex_never_panics(text in "\\PC*") {
+        let _ = color_math::parse_hex(&text);
+    }
+
+    #[test]
+    fn hex_round_trips(c in rgb(), lower in any::<bool>(), hash in any::<bool>()) {
+        let hex = color_math::to_hex(c);
+        let hex = if lower { hex.to_lowercase() } else { hex };
+        let hex = if hash { hex } else { hex[1..].to_string() };
+        prop_assert_eq!(color_math::parse_hex(&hex), Ok(c));
+    }
+
+    #[test]
+    fn hex_shorthand_doubles_digits(d in prop::array::uniform3(0u8..16)) {
+        let short: String = d.iter().map(|v| format!("{v:x}")).collect();
+        prop_assert_eq!(color_math::parse_hex(&short), Ok(d.map(|v| v * 17)));
+    }
+
+    #[test]
+    fn gpl_never_panics(text in "(GIMP Palette\n)?[ -~\n\t]*") {
+        let _ = import::parse_gpl(&text, "fallback");
+    }
+
+    #[test]
+    fn gpl_round_trips(name in name(), colors in colors()) {
+        let parsed = import::parse_gpl(&gpl(&name, &colors), "fallback").unwrap();
+        prop_assert_eq!(parsed.name, name.trim());
+        prop_assert_eq!(parsed.colors, hexes(&colors));
+    }
+
+    #[test]
+    fn ase_never_panics(mut data in prop::collection::vec(any::<u8>(), 0..512), magic in any::<bool>()) {
+        if magic {
+            data.splice(0..0, *b"ASEF");
+        }
+        let _ = import::parse_ase(&data, "swatches");
+    }
+
+    #[test]
+    fn ase_survives_truncation(colors in colors(), cut in any::<prop::sample::Index>()) {
+        let data = ase(&colors);
+        let _ = import::parse_ase(&data[..cut.index(data.len())], "swatches");
+    }
+
+    #[test]
+    fn ase_round_trips(colors in colors()) {
+        let parsed = import::parse_ase(&ase(&colors), "swatches").unwrap();
+        prop_assert_eq!(parsed.colors, hexes(&colors));
+    }
+
+    #[test]
+    fn palette_file_never_panics(text in "\\PC*") {
+        let _ = palette_file::parse(&text);
+    }
+
+    #[test]
+    fn palette_file_round_trips(name in name(), colors in colors()) {
+        let json = palette_file::to_json(&palette(&name, &colors), "0.0.0", 0, &Default::default()).unwrap();
+        let parsed = palette_file::parse(&json).unwrap();
+        prop_assert_eq!(parsed.name, name);
+        prop_assert_eq!(parsed.colors, hexes(&colors));
+    }
+
+    #[test]
+    fn share_url_never_panics(text in "(colorsnap://palette\\?)?\\PC*") {
+        let _ = share::parse_share_url(&text);
+    }
+
+    #[test]
+    fn share_url_round_trips(name in name(), colors in colors()) {
+        let url = share::share_url(&palette(&name, &colors)).unwrap();
+        let parsed = share::parse_share_url(&url).unwrap();
+        prop_assert_eq!(parsed.name, name);
+        prop_assert_eq!(parsed.colors, hexes(&colors));
+    }
+
+    #[test]
+    fn coolors_url_never_panics(text in "\\PC*") {
+        let _ = import::parse_coolors_url(&text);
+    }
+
+    #[test]
+    fn css_color_never_panics(text in "(rgba?\\()?\\PC*") {
+        let _ = import::parse_css_color(&text);
+    }
+
+    #[test]
+    fn css_rgb_round_trips(c in rgb(), modern in any::<bool>()) {
+        let [r, g, b] = c;
+        let text = if modern {
+            format!("rgb({r} {g} {b} / 0.5)")
+        } else {
+            format!("rgba({r}, {g}, {b}, 1)")
+        };
+        prop_assert_eq!(import::parse_css_color(&text), Ok(c));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+
+const MAX_RESULTS: usize = 1000;
+/// How long after a logged pick the frontend may take to save it to history
+const PICK_MATCH_WINDOW_MS: u64 = 60_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActivityKind {
+    Pick,
+    Import,
+    Export,
+}
+
+impl ActivityKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ActivityKind::Pick => "pick",
+            ActivityKind::Import => "import",
+            ActivityKind::Export => "export",
+        }
+    }
+}
+
+/// One line of the append-only activity log. Unlike history, entries are
+/// never edited or removed by the app.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityEntry {
+    pub at: u64,
+    pub kind: ActivityKind,
+    pub colors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub palette: Option<String>,
+    /// App a color was picked from, or the file/URL imported from or
+    /// exported to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityQuery {
+    pub kind: Option<ActivityKind>,
+    /// Inclusive range in Unix milliseconds
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+    /// Case-insensitive match on palette name, source or color
+    pub text: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// Append an entry, logging rather than failing the action on I/O errors.
+pub fn record(
+    app: &tauri::AppHandle,
+    kind: ActivityKind,
+    colors: Vec<String>,
+    palette: Option<String>,
+    source: Option<String>,
+) {
+    let entry = ActivityEntry {
+        at: storage::now_millis(),
+        kind,
+        colors,
+        palette,
+        source,
+    };
+    if let Err(e) = storage::append_activity(app, &entry) {
+        eprintln!("Failed to record activity: {}", e);
+    }
+}
+
+fn matches(entry: &ActivityEntry, query: &ActivityQuery, text: Option<&str>) -> bool {
+    query.kind.is_none_or(|k| k == entry.kind)
+        && query.since.is_none_or(|t| entry.at >= t)
+        && query.until.is_none_or(|t| entry.at <= t)
+        && text.is_none_or(|text| {
+            entry
+                .palette
+                .iter()
+                .chain(entry.source.iter())
+                .chain(entry.colors.iter())
+                .any(|field| field.to_lowercase().contains(text))
+        })
+}
+
+/// Entries matching `query`, newest first.
+pub fn query(entries: Vec<ActivityEntry>, query: &ActivityQuery) -> Vec<ActivityEntry> {
+    let text = query.text.as_deref().map(str::to_lowercase);
+    let limit = query.limit.unwrap_or(MAX_RESULTS).min(MAX_RESULTS);
+    entries
+        .into_iter()
+        .rev()
+        .filter(|e| matches(e, query, text.as_deref()))
+        .take(limit)
+        .collect()
+}
+
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// CSV with one row per entry; colors are space-separated in one column.
+pub fn to_csv(entries: &[ActivityEntry]) -> String {
+    let mut out = String::from("at,kind,palette,source,colors\n");
+    for e in entries {
+        let row = [
+            e.at.to_string(),
+            e.kind.as_str().to_string(),
+            e.palette.clone().unwrap_or_default(),
+            e.source.clone().unwrap_or_default(),
+            e.colors.join(" "),
+        ];
+        let row: Vec<String> = row.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// App the pick of `hex` at `at` (Unix milliseconds) came from: the source of
+/// the latest matching pick logged up to a minute before it.
+pub fn pick_source<'a>(entries: &'a [ActivityEntry], hex: &str, at: u64) -> Option<&'a str> {
+    entries
+        .iter()
+        .rev()
+        .filter(|e| e.kind == ActivityKind::Pick && e.at <= at && at - e.at <= PICK_MATCH_WINDOW_MS)
+        .find(|e| e.colors.iter().any(|c| c.eq_ignore_ascii_case(hex)))
+        .and_then(|e| e.source.as_deref())
+}
@@ -0,0 +1,79 @@
+//! Low-frequency stream of the average color of the monitor under the
+//! cursor, for driving smart lights from what is on screen. While the
+//! `ambient_interval_ms` setting is set, an `ambient-color` event
+//! (`{hex, rgb}`) is emitted that often; integrations subscribe to it the
+//! same way they subscribe to `color-picked`.
+
+use crate::{color_math, color_picker};
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+/// Whole-monitor captures are not cheap, so ticks are at least this far apart.
+pub const MIN_INTERVAL_MS: u64 = 250;
+
+/// Bumped by every start and stop; a stream thread ends once it is stale.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AmbientColor {
+    pub hex: String,
+    pub rgb: [u8; 3],
+}
+
+/// Start emitting every `interval_ms`, replacing a running stream.
+pub fn start(app: &tauri::AppHandle, interval_ms: u64) -> Result<(), String> {
+    if interval_ms < MIN_INTERVAL_MS {
+        return Err(format!(
+            "The interval must be at least {} ms",
+            MIN_INTERVAL_MS
+        ));
+    }
+    // Every portal screenshot asks the user
+    if color_picker::uses_system_picker() {
+        return Err("The ambient color stream is not available on Wayland".to_string());
+    }
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    let interval = Duration::from_millis(interval_ms);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let mut last_error = None;
+        while GENERATION.load(Ordering::SeqCst) == generation {
+            let started = Instant::now();
+            match average() {
+                Ok(rgb) => {
+                    last_error = None;
+                    let hex = color_math::to_hex(rgb);
+                    let _ = app.emit("ambient-color", AmbientColor { hex, rgb });
+                }
+                // Once per failure streak, not every tick
+                Err(e) => {
+                    if last_error.as_ref() != Some(&e) {
+                        eprintln!("Failed to sample ambient color: {}", e);
+                    }
+                    last_error = Some(e);
+                }
+            }
+            std::thread::sleep(interval.saturating_sub(started.elapsed()));
+        }
+    });
+    Ok(())
+}
+
+pub fn stop() {
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Mean color of the monitor under the cursor.
+fn average() -> Result<[u8; 3], String> {
+    let img = color_picker::capture_active_monitor()?;
+    let mut sum = [0u64; 3];
+    for p in img.pixels() {
+        for (s, v) in sum.iter_mut().zip(p.0) {
+            *s += v as u64;
+        }
+    }
+    let n = img.pixels().len().max(1) as u64;
+    Ok(sum.map(|s| (s / n) as u8))
+}
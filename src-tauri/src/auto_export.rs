@@ -0,0 +1,132 @@
+//! Palettes published to files on every change, e.g. `tokens.css` and
+//! `colors.xml` in a repo working copy, so the palette store stays the single
+//! source of truth. Targets are kept in settings and written after each save
+//! that changes their palette.
+
+use crate::pipeline::{self, Transform};
+use crate::{exporters, storage, Palette};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use tauri::Emitter;
+
+/// Wakes the export thread with ids of changed palettes; created with the
+/// first change.
+static WORKER: Mutex<Option<Sender<Vec<String>>>> = Mutex::new(None);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoExport {
+    /// Assigned by the backend when the target is added
+    #[serde(default)]
+    pub id: String,
+    pub palette_id: String,
+    /// Registry id of the exporter, as for `export_palette_as`
+    pub exporter: String,
+    pub path: String,
+    /// Transforms applied before exporting, as in export pipelines
+    #[serde(default)]
+    pub steps: Vec<Transform>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoExportFailed {
+    pub id: String,
+    pub path: String,
+    pub error: String,
+}
+
+/// Render `target` from `palette` and write it, leaving the file untouched
+/// when the content is already current so watchers and Git see no change.
+pub fn write(
+    registry: &[Box<dyn exporters::Exporter>],
+    target: &AutoExport,
+    palette: &Palette,
+) -> Result<(), String> {
+    let exporter = exporters::find(registry, &target.exporter)?;
+    let bytes = exporter.render(&pipeline::apply(palette, &target.steps)?)?;
+    if std::fs::read(&target.path).ok().as_deref() == Some(bytes.as_slice()) {
+        return Ok(());
+    }
+    std::fs::write(&target.path, bytes).map_err(|e| format!("Failed to write file: {}", e))
+}
+
+/// Queue a re-export of every target whose palette differs between the two
+/// snapshots. One thread writes them all from the latest palettes, so the
+/// exports of quick successive saves collapse and can't land out of order.
+pub fn schedule(app: &tauri::AppHandle, before: &[Palette], after: &[Palette]) {
+    if storage::load_settings(app).auto_exports.is_empty() {
+        return;
+    }
+    let old: HashMap<&str, &Palette> = before.iter().map(|p| (p.id.as_str(), p)).collect();
+    let mut changed: Vec<String> = after
+        .iter()
+        .filter(|p| old.get(p.id.as_str()) != Some(p))
+        .map(|p| p.id.clone())
+        .collect();
+    if changed.is_empty() {
+        return;
+    }
+
+    let mut worker = WORKER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(tx) = worker.as_ref() {
+        match tx.send(changed) {
+            Ok(()) => return,
+            Err(mpsc::SendError(unsent)) => changed = unsent,
+        }
+    }
+    let (tx, rx) = mpsc::channel::<Vec<String>>();
+    let _ = tx.send(changed);
+    let app = app.clone();
+    std::thread::spawn(move || {
+        while let Ok(ids) = rx.recv() {
+            let mut pending: HashSet<String> = ids.into_iter().collect();
+            while let Ok(more) = rx.try_recv() {
+                pending.extend(more);
+            }
+            run(&app, &pending);
+        }
+    });
+    *worker = Some(tx);
+}
+
+/// Re-export every target of the palettes in `changed`, as they are now.
+/// Failures are reported as `auto-export-failed` rather than failing the
+/// save that triggered them.
+fn run(app: &tauri::AppHandle, changed: &HashSet<String>) {
+    let settings = storage::load_settings(app);
+    let palettes = storage::load_palettes(app);
+    let targets: Vec<(&AutoExport, &Palette)> = settings
+        .auto_exports
+        .iter()
+        .filter(|target| changed.contains(&target.palette_id))
+        .filter_map(|target| {
+            let palette = palettes.iter().find(|p| p.id == target.palette_id)?;
+            Some((target, palette))
+        })
+        .collect();
+    if targets.is_empty() {
+        return;
+    }
+    // A broken exporters.json only fails the targets using user templates
+    let user = storage::load_user_exporters(app).unwrap_or_else(|e| {
+        eprintln!("Failed to load exporters: {}", e);
+        Vec::new()
+    });
+    let registry = exporters::registry(user, settings.png, settings.color_names);
+    for (target, palette) in targets {
+        if let Err(error) = write(&registry, target, palette) {
+            eprintln!("Failed to auto-export {}: {}", target.path, error);
+            let _ = app.emit(
+                "auto-export-failed",
+                AutoExportFailed {
+                    id: target.id.clone(),
+                    path: target.path.clone(),
+                    error,
+                },
+            );
+        }
+    }
+}
@@ -1,12 +1,28 @@
-use crate::{ColorInfo, LoupeData};
+//! Screen picking: sampling, the loupe, area averages and frozen snapshots.
+//! Platform code lives behind [`PickerBackend`], one implementation per
+//! platform or session type, chosen at runtime by [`backend`]; nothing here
+//! or in the commands using it depends on which one is in use.
+
+use crate::sampling::Sampling;
+use crate::{perf, ColorInfo, LoupeData};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 
-static CURSOR_CHANGED: AtomicBool = AtomicBool::new(false);
+#[cfg(windows)]
+mod gdi;
+#[cfg(target_os = "macos")]
+mod quartz;
+#[cfg(target_os = "linux")]
+mod wayland;
+#[cfg(target_os = "linux")]
+mod x11;
+
+/// Game mode: single-pixel picks go through Desktop Duplication, which can
+/// see exclusive-fullscreen and protected surfaces that GDI reads as black.
+static GAME_MODE: AtomicBool = AtomicBool::new(false);
 
 /// Full-virtual-screen snapshot taken when area mode starts, so the selection
 /// overlay tint is never part of the averaged region. Pixels are BGRA.
-#[cfg(windows)]
 struct AreaSnapshot {
     pixels: Vec<u8>,
     width: i32,
@@ -15,348 +31,507 @@ struct AreaSnapshot {
     origin_y: i32,
 }
 
-#[cfg(windows)]
 static AREA_SNAPSHOT: Mutex<Option<AreaSnapshot>> = Mutex::new(None);
 
+/// Frozen pick: picks and the loupe read the snapshot in `AREA_SNAPSHOT`
+/// instead of the live screen, so transient UI (menus, tooltips, video) can
+/// be sampled after the fact.
+static FROZEN: AtomicBool = AtomicBool::new(false);
+
+/// Foreground window (as a raw HWND) when picking started, for pick-and-paste.
 #[cfg(windows)]
-use windows::Win32::{
-    Foundation::{COLORREF, POINT},
-    Graphics::Gdi::{
-        BitBlt, CreateBitmap, CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject, GetDC,
-        GetPixel, ReleaseDC, SelectObject, BITMAPINFO, BITMAPINFOHEADER, CLR_INVALID,
-        DIB_RGB_COLORS, SRCCOPY,
-    },
-    UI::WindowsAndMessaging::{
-        CreateIconIndirect, GetCursorPos, GetSystemMetrics, SetSystemCursor, SystemParametersInfoW,
-        HCURSOR, ICONINFO, OCR_NORMAL, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
-        SM_YVIRTUALSCREEN, SPI_SETCURSORS, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
-    },
-};
-
-/// Get the current cursor position
-#[cfg(windows)]
-fn get_cursor_position() -> Result<(i32, i32), String> {
-    unsafe {
-        let mut point = POINT::default();
-        GetCursorPos(&mut point).map_err(|e| format!("Failed to get cursor position: {}", e))?;
-        Ok((point.x, point.y))
+static PASTE_TARGET: std::sync::atomic::AtomicIsize = std::sync::atomic::AtomicIsize::new(0);
+
+/// The frontmost window of another app, captured on its own.
+#[cfg_attr(not(any(windows, target_os = "linux")), allow(dead_code))]
+pub struct WindowCapture {
+    /// Executable name on Windows, window class on X11
+    pub app: Option<String>,
+    pub title: String,
+    pub image: image::RgbImage,
+}
+
+/// Platform capture code. Rectangles are `(x, y, width, height)` in the
+/// backend's screen coordinates, which the cursor position shares, and
+/// captured pixels are top-down BGRA.
+trait PickerBackend {
+    fn cursor_position(&self) -> Result<(i32, i32), String>;
+
+    /// Bounds of every monitor.
+    fn monitors(&self) -> Result<Vec<(i32, i32, i32, i32)>, String>;
+
+    fn capture_rect(
+        &self,
+        origin_x: i32,
+        origin_y: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<Vec<u8>, String>;
+
+    /// Color of one live pixel.
+    fn pixel(&self, x: i32, y: i32) -> Result<(u8, u8, u8), String> {
+        match self.capture_rect(x, y, 1, 1)?[..] {
+            [b, g, r, _] => Ok((r, g, b)),
+            _ => Err("Failed to get pixel color".to_string()),
+        }
+    }
+
+    /// Bounds of the virtual screen spanning every monitor.
+    fn virtual_screen(&self) -> Result<(i32, i32, i32, i32), String> {
+        let monitors = self.monitors()?;
+        let left = monitors
+            .iter()
+            .map(|m| m.0)
+            .min()
+            .ok_or("No active display")?;
+        let top = monitors.iter().map(|m| m.1).min().unwrap_or(0);
+        let right = monitors.iter().map(|m| m.0 + m.2).max().unwrap_or(0);
+        let bottom = monitors.iter().map(|m| m.1 + m.3).max().unwrap_or(0);
+        Ok((left, top, (right - left).max(1), (bottom - top).max(1)))
+    }
+
+    /// Bounds of the monitor containing a point, or of the first monitor.
+    fn monitor_at(&self, x: i32, y: i32) -> Result<(i32, i32, i32, i32), String> {
+        let monitors = self.monitors()?;
+        monitors
+            .iter()
+            .find(|m| x >= m.0 && y >= m.1 && x < m.0 + m.2 && y < m.1 + m.3)
+            .or(monitors.first())
+            .copied()
+            .ok_or_else(|| "No active display".to_string())
+    }
+
+    /// The whole virtual screen, for area mode and frozen picks.
+    fn capture_desktop(&self) -> Result<AreaSnapshot, String> {
+        let (origin_x, origin_y, width, height) = self.virtual_screen()?;
+        let pixels = self.capture_rect(origin_x, origin_y, width, height)?;
+        Ok(AreaSnapshot {
+            pixels,
+            width,
+            height,
+            origin_x,
+            origin_y,
+        })
+    }
+
+    /// The monitor under the cursor as an RGB image.
+    fn capture_active_monitor(&self) -> Result<image::RgbImage, String> {
+        let (x, y) = self.cursor_position()?;
+        let (left, top, width, height) = self.monitor_at(x, y)?;
+        let pixels = self.capture_rect(left, top, width, height)?;
+        let rgb = pixels
+            .chunks_exact(4)
+            .flat_map(|bgra| [bgra[2], bgra[1], bgra[0]])
+            .collect();
+        image::RgbImage::from_raw(width.max(1) as u32, height.max(1) as u32, rgb)
+            .ok_or_else(|| "Failed to build monitor image".to_string())
+    }
+
+    /// The topmost visible window that isn't ours.
+    fn capture_foreground_window(&self) -> Result<WindowCapture, String> {
+        Err("Window capture is not supported on this platform".to_string())
+    }
+
+    /// Swap the system cursor while picking, if the platform allows it.
+    fn set_pick_cursor(&self, _high_contrast: bool) {}
+
+    /// Recolor the high-contrast pick cursor for the average color around it.
+    fn adapt_pick_cursor(&self, _background: [u8; 3]) {}
+
+    /// Put back the system cursor; `force` even if we never changed it.
+    fn restore_cursor(&self, _force: bool) {}
+
+    /// Whether picks go through a picker the system shows itself instead of
+    /// the loupe (see [`pick_with_portal`]).
+    fn system_picker(&self) -> bool {
+        false
     }
 }
 
-/// Get the color of a pixel at the given screen coordinates
-#[cfg(windows)]
-fn get_pixel_color(x: i32, y: i32) -> Result<(u8, u8, u8), String> {
-    unsafe {
-        let hdc = GetDC(None);
-        if hdc.is_invalid() {
-            return Err("Failed to get device context".to_string());
+fn backend() -> &'static dyn PickerBackend {
+    #[cfg(windows)]
+    {
+        &gdi::Gdi
+    }
+    #[cfg(target_os = "macos")]
+    {
+        &quartz::Quartz
+    }
+    #[cfg(target_os = "linux")]
+    {
+        if crate::portal::active() {
+            &wayland::Portal
+        } else {
+            &x11::X11
         }
+    }
+    #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+    {
+        &Unsupported
+    }
+}
+
+/// Platforms without a capture backend: everything fails.
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+struct Unsupported;
+
+#[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
+impl PickerBackend for Unsupported {
+    fn cursor_position(&self) -> Result<(i32, i32), String> {
+        Err("Color picking is only supported on Windows, macOS and Linux".to_string())
+    }
 
-        let color = GetPixel(hdc, x, y);
-        let _ = ReleaseDC(None, hdc);
+    fn monitors(&self) -> Result<Vec<(i32, i32, i32, i32)>, String> {
+        Err("Color picking is only supported on Windows, macOS and Linux".to_string())
+    }
 
-        if color == COLORREF(CLR_INVALID) {
-            return Err("Failed to get pixel color".to_string());
+    fn capture_rect(
+        &self,
+        _origin_x: i32,
+        _origin_y: i32,
+        _width: i32,
+        _height: i32,
+    ) -> Result<Vec<u8>, String> {
+        Err("Screen capture is only supported on Windows, macOS and Linux".to_string())
+    }
+}
+
+/// Enable or disable game-mode sampling for subsequent picks.
+pub fn set_game_mode(enabled: bool) {
+    GAME_MODE.store(enabled, Ordering::SeqCst);
+}
+
+/// Whether picks open the system's own color picker instead of the loupe.
+pub fn uses_system_picker() -> bool {
+    backend().system_picker()
+}
+
+/// Color of the pixel at `(x, y)`, from the frozen snapshot while frozen.
+fn get_pixel_color(x: i32, y: i32) -> Result<(u8, u8, u8), String> {
+    if FROZEN.load(Ordering::SeqCst) {
+        if let Some(rgb) = frozen_pixel(x, y) {
+            return Ok(rgb);
         }
+    }
+    backend().pixel(x, y)
+}
 
-        // COLORREF is in BGR format
-        let color_val = color.0;
-        let r = (color_val & 0xFF) as u8;
-        let g = ((color_val >> 8) & 0xFF) as u8;
-        let b = ((color_val >> 16) & 0xFF) as u8;
+/// Color for a pick at `(x, y)` under `sampling`. Neighborhoods come from
+/// the frozen snapshot or a screen capture; in game mode, or when the capture
+/// fails, only the pixel under the cursor is read.
+fn sample_color(x: i32, y: i32, sampling: Sampling) -> Result<(u8, u8, u8), String> {
+    if sampling.mode == crate::sampling::SampleMode::Pixel || GAME_MODE.load(Ordering::SeqCst) {
+        return get_pixel_color(x, y);
+    }
+    match neighborhood(x, y, sampling.radius as i32)
+        .and_then(|pixels| crate::sampling::reduce(sampling.mode, &pixels))
+    {
+        Some([r, g, b]) => Ok((r, g, b)),
+        None => get_pixel_color(x, y),
+    }
+}
 
-        Ok((r, g, b))
+/// Pixels within `radius` of `(x, y)`, row-major.
+fn neighborhood(x: i32, y: i32, radius: i32) -> Option<Vec<[u8; 3]>> {
+    let side = radius * 2 + 1;
+    if FROZEN.load(Ordering::SeqCst) {
+        return (-radius..=radius)
+            .flat_map(|dy| (-radius..=radius).map(move |dx| (dx, dy)))
+            .map(|(dx, dy)| frozen_pixel(x + dx, y + dy).map(|(r, g, b)| [r, g, b]))
+            .collect();
     }
+    let bgra = backend()
+        .capture_rect(x - radius, y - radius, side, side)
+        .ok()?;
+    Some(bgra.chunks_exact(4).map(|p| [p[2], p[1], p[0]]).collect())
 }
 
-/// Get the color at the current cursor position
-#[cfg(windows)]
-pub fn get_color_at_cursor() -> Result<ColorInfo, String> {
-    let (x, y) = get_cursor_position()?;
-    let (r, g, b) = get_pixel_color(x, y)?;
+/// Get the color at the current cursor position, read under `sampling`.
+/// `delta_e` is left for the caller to fill in.
+pub fn get_color_at_cursor(sampling: Sampling) -> Result<ColorInfo, String> {
+    let (x, y) = cursor_pos()?;
+    let (r, g, b) = perf::time(perf::GET_PIXEL, || sample_color(x, y, sampling))?;
 
     Ok(ColorInfo {
         hex: format!("#{:02X}{:02X}{:02X}", r, g, b),
         rgb: [r, g, b],
         x,
         y,
+        label: None,
+        delta_e: None,
+        white_point: None,
+        color_shift: crate::gamma_ramp::shift_at(x, y),
+        representations: crate::conversion::representations([r, g, b]),
     })
 }
 
-/// Set a custom cursor from the app icon during pick mode
-#[cfg(windows)]
-pub fn set_pick_cursor() {
-    use std::ffi::c_void;
-    use windows::Win32::Foundation::BOOL;
-
-    // Load the 64x64 icon PNG embedded at compile time (bigger for visibility)
-    let icon_bytes = include_bytes!("../icons/64x64.png");
-    let img = match image::load_from_memory(icon_bytes) {
-        Ok(img) => img.to_rgba8(),
-        Err(e) => {
-            eprintln!("Failed to load cursor icon: {}", e);
-            return;
-        }
+/// Pick through the compositor's own picker on Wayland, where the cursor
+/// position and other windows' pixels are off limits. `None` when the user
+/// dismissed the picker or denied access. `delta_e` is left for the caller.
+pub async fn pick_with_portal() -> Result<Option<ColorInfo>, String> {
+    let Some([r, g, b]) = crate::portal::pick_color().await? else {
+        return Ok(None);
     };
+    Ok(Some(ColorInfo {
+        hex: format!("#{:02X}{:02X}{:02X}", r, g, b),
+        rgb: [r, g, b],
+        x: 0,
+        y: 0,
+        label: None,
+        delta_e: None,
+        white_point: None,
+        color_shift: None,
+        representations: crate::conversion::representations([r, g, b]),
+    }))
+}
 
-    let (w, h) = img.dimensions();
-    let pixels = img.as_raw();
-
-    unsafe {
-        let hdc_screen = GetDC(None);
-
-        // Create BITMAPINFO for 32-bit top-down DIB
-        let mut bmi: BITMAPINFO = std::mem::zeroed();
-        bmi.bmiHeader.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
-        bmi.bmiHeader.biWidth = w as i32;
-        bmi.bmiHeader.biHeight = -(h as i32); // negative = top-down
-        bmi.bmiHeader.biPlanes = 1;
-        bmi.bmiHeader.biBitCount = 32;
-        bmi.bmiHeader.biCompression = 0; // BI_RGB
-
-        let mut bits_ptr: *mut c_void = std::ptr::null_mut();
-        let color_bmp =
-            match CreateDIBSection(hdc_screen, &bmi, DIB_RGB_COLORS, &mut bits_ptr, None, 0) {
-                Ok(bmp) => bmp,
-                Err(e) => {
-                    eprintln!("Failed to create DIB section: {}", e);
-                    ReleaseDC(None, hdc_screen);
-                    return;
-                }
-            };
-
-        if bits_ptr.is_null() {
-            eprintln!("DIB section bits pointer is null");
-            let _ = DeleteObject(color_bmp);
-            ReleaseDC(None, hdc_screen);
-            return;
-        }
-
-        // Copy RGBA pixels -> premultiplied BGRA (Windows format)
-        let dst = std::slice::from_raw_parts_mut(bits_ptr as *mut u8, (w * h * 4) as usize);
-        for i in 0..(w * h) as usize {
-            let r = pixels[i * 4] as u32;
-            let g = pixels[i * 4 + 1] as u32;
-            let b = pixels[i * 4 + 2] as u32;
-            let a = pixels[i * 4 + 3] as u32;
-            // Premultiply alpha for Windows
-            dst[i * 4] = (b * a / 255) as u8;
-            dst[i * 4 + 1] = (g * a / 255) as u8;
-            dst[i * 4 + 2] = (r * a / 255) as u8;
-            dst[i * 4 + 3] = a as u8;
-        }
-
-        // Create monochrome AND mask (all zeros = fully opaque, alpha handles transparency)
-        let mask_bmp = CreateBitmap(w as i32, h as i32, 1, 1, None);
-
-        let icon_info = ICONINFO {
-            fIcon: BOOL(0), // FALSE = this is a cursor, not an icon
-            xHotspot: w / 2,
-            yHotspot: h / 2,
-            hbmMask: mask_bmp,
-            hbmColor: color_bmp,
-        };
-
-        match CreateIconIndirect(&icon_info) {
-            Ok(icon) => {
-                let cursor = HCURSOR(icon.0);
-                if SetSystemCursor(cursor, OCR_NORMAL).is_ok() {
-                    CURSOR_CHANGED.store(true, Ordering::SeqCst);
-                }
-            }
-            Err(e) => {
-                eprintln!("Failed to create cursor: {}", e);
-            }
-        }
-
-        // Cleanup bitmap handles (cursor owns copies)
-        let _ = DeleteObject(mask_bmp);
-        let _ = DeleteObject(color_bmp);
-        ReleaseDC(None, hdc_screen);
-    }
+/// Set the pick-mode cursor: the app icon, or with `high_contrast` a ring
+/// that adapts to the background under it. No-op where the system cursor
+/// can't be replaced.
+pub fn set_pick_cursor(high_contrast: bool) {
+    backend().set_pick_cursor(high_contrast);
 }
 
 /// Restore the default system cursor
-#[cfg(windows)]
 pub fn restore_default_cursor() {
-    if CURSOR_CHANGED.load(Ordering::SeqCst) {
-        unsafe {
-            let _ = SystemParametersInfoW(
-                SPI_SETCURSORS,
-                0,
-                None,
-                SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
-            );
-        }
-        CURSOR_CHANGED.store(false, Ordering::SeqCst);
-    }
+    backend().restore_cursor(false);
 }
 
 /// Force-restore the default system cursor unconditionally.
 /// Used on startup to recover from a previous crash/kill that left a custom cursor.
-#[cfg(windows)]
 pub fn restore_default_cursor_force() {
-    unsafe {
-        let _ = SystemParametersInfoW(
-            SPI_SETCURSORS,
-            0,
-            None,
-            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
-        );
-    }
-    CURSOR_CHANGED.store(false, Ordering::SeqCst);
+    backend().restore_cursor(true);
 }
 
-/// Capture a small pixel grid centered on the cursor for the loupe.
-/// Uses a single BitBlt of grid×grid pixels, so it is fast enough to poll.
-#[cfg(windows)]
+/// Capture a small pixel grid centered on the cursor for the loupe. A single
+/// grid×grid capture, so it is fast enough to poll.
 pub fn capture_loupe_grid(grid: u32) -> Result<LoupeData, String> {
-    use std::ffi::c_void;
+    let backend = backend();
+    let (cursor_x, cursor_y) = backend.cursor_position()?;
+    let half = (grid / 2) as i32;
+    if FROZEN.load(Ordering::SeqCst) {
+        if let Some(data) = frozen_loupe_grid(cursor_x, cursor_y, grid) {
+            return Ok(data);
+        }
+    }
+    let pixels = perf::time(perf::LOUPE_CAPTURE, || {
+        backend.capture_rect(cursor_x - half, cursor_y - half, grid as i32, grid as i32)
+    })?;
+
+    let encode_start = std::time::Instant::now();
+    let mut sum = [0u64; 3];
+    let colors: Vec<String> = pixels
+        .chunks_exact(4)
+        .map(|p| {
+            let (b, g, r) = (p[0], p[1], p[2]);
+            sum[0] += r as u64;
+            sum[1] += g as u64;
+            sum[2] += b as u64;
+            format!("#{:02X}{:02X}{:02X}", r, g, b)
+        })
+        .collect();
+    perf::record(perf::LOUPE_ENCODE, encode_start.elapsed());
+    if !colors.is_empty() {
+        backend.adapt_pick_cursor(sum.map(|c| (c / colors.len() as u64) as u8));
+    }
+    let center = colors
+        .get((half as u32 * grid + half as u32) as usize)
+        .cloned()
+        .ok_or("Failed to capture loupe region")?;
+    Ok(LoupeData {
+        colors,
+        hex: center,
+        x: cursor_x,
+        y: cursor_y,
+        flip_left: false,
+        flip_up: false,
+        delta_e: None,
+        name: String::new(),
+        oklch: None,
+    })
+}
 
-    let (cursor_x, cursor_y) = get_cursor_position()?;
+/// Loupe grid read from the frozen snapshot; pixels off the snapshot are black.
+fn frozen_loupe_grid(cursor_x: i32, cursor_y: i32, grid: u32) -> Option<LoupeData> {
+    if AREA_SNAPSHOT.lock().ok()?.is_none() {
+        return None;
+    }
     let half = (grid / 2) as i32;
+    let mut colors = Vec::with_capacity((grid * grid) as usize);
+    let mut sum = [0u64; 3];
+    for dy in 0..grid as i32 {
+        for dx in 0..grid as i32 {
+            let (r, g, b) =
+                frozen_pixel(cursor_x - half + dx, cursor_y - half + dy).unwrap_or((0, 0, 0));
+            colors.push(format!("#{:02X}{:02X}{:02X}", r, g, b));
+            sum[0] += r as u64;
+            sum[1] += g as u64;
+            sum[2] += b as u64;
+        }
+    }
+    if colors.is_empty() {
+        return None;
+    }
+    backend().adapt_pick_cursor(sum.map(|c| (c / colors.len() as u64) as u8));
+    let center = colors[(half as u32 * grid + half as u32) as usize].clone();
+    Some(LoupeData {
+        colors,
+        hex: center,
+        x: cursor_x,
+        y: cursor_y,
+        flip_left: false,
+        flip_up: false,
+        delta_e: None,
+        name: String::new(),
+        oklch: None,
+    })
+}
+
+/// Executable name (e.g. `chrome.exe`) of the top-level window under a
+/// screen point. Our own windows (loupe, region overlay) yield `None`.
+#[cfg(windows)]
+pub fn app_name_at(x: i32, y: i32) -> Option<String> {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::{CloseHandle, POINT};
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetAncestor, GetWindowThreadProcessId, WindowFromPoint, GA_ROOTOWNER,
+    };
 
     unsafe {
-        let screen_dc = GetDC(None);
-        if screen_dc.is_invalid() {
-            return Err("Failed to get screen device context".to_string());
+        let hwnd = WindowFromPoint(POINT { x, y });
+        if hwnd.is_invalid() {
+            return None;
+        }
+        let root = GetAncestor(hwnd, GA_ROOTOWNER);
+        let mut pid = 0u32;
+        let target = if root.is_invalid() { hwnd } else { root };
+        GetWindowThreadProcessId(target, Some(&mut pid as *mut u32));
+        if pid == 0 || pid == std::process::id() {
+            return None;
         }
-        let mem_dc = CreateCompatibleDC(screen_dc);
-
-        let mut bmi: BITMAPINFO = std::mem::zeroed();
-        bmi.bmiHeader.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
-        bmi.bmiHeader.biWidth = grid as i32;
-        bmi.bmiHeader.biHeight = -(grid as i32); // negative = top-down
-        bmi.bmiHeader.biPlanes = 1;
-        bmi.bmiHeader.biBitCount = 32;
-        bmi.bmiHeader.biCompression = 0; // BI_RGB
-
-        let mut bits_ptr: *mut c_void = std::ptr::null_mut();
-        let bmp = CreateDIBSection(screen_dc, &bmi, DIB_RGB_COLORS, &mut bits_ptr, None, 0)
-            .map_err(|e| {
-                let _ = DeleteDC(mem_dc);
-                ReleaseDC(None, screen_dc);
-                format!("Failed to create DIB section: {}", e)
-            })?;
-
-        let old_bmp = SelectObject(mem_dc, bmp);
-        let blit = BitBlt(
-            mem_dc,
-            0,
-            0,
-            grid as i32,
-            grid as i32,
-            screen_dc,
-            cursor_x - half,
-            cursor_y - half,
-            SRCCOPY,
+
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut buf = [0u16; 260];
+        let mut len = buf.len() as u32;
+        let queried = QueryFullProcessImageNameW(
+            process,
+            PROCESS_NAME_WIN32,
+            PWSTR(buf.as_mut_ptr()),
+            &mut len,
         );
+        let _ = CloseHandle(process);
+        queried.ok()?;
+
+        let path = String::from_utf16_lossy(&buf[..len as usize]);
+        path.rsplit(['\\', '/']).next().map(str::to_string)
+    }
+}
 
-        let mut colors = Vec::with_capacity((grid * grid) as usize);
-        if blit.is_ok() && !bits_ptr.is_null() {
-            let px = std::slice::from_raw_parts(bits_ptr as *const u8, (grid * grid * 4) as usize);
-            for i in 0..(grid * grid) as usize {
-                // DIB sections are BGRA
-                let (b, g, r) = (px[i * 4], px[i * 4 + 1], px[i * 4 + 2]);
-                colors.push(format!("#{:02X}{:02X}{:02X}", r, g, b));
-            }
+/// Remember the current foreground window as the pick-and-paste target.
+/// Our own windows are never a target (picking started from the main window).
+#[cfg(windows)]
+pub fn remember_paste_target() {
+    use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowThreadProcessId};
+
+    let target = unsafe {
+        let hwnd = GetForegroundWindow();
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut pid as *mut u32));
+        if hwnd.is_invalid() || pid == std::process::id() {
+            0
+        } else {
+            hwnd.0 as isize
         }
+    };
+    PASTE_TARGET.store(target, Ordering::SeqCst);
+}
 
-        SelectObject(mem_dc, old_bmp);
-        let _ = DeleteObject(bmp);
-        let _ = DeleteDC(mem_dc);
-        ReleaseDC(None, screen_dc);
+/// Refocus the remembered target window and type `text` into it as
+/// Unicode key events, so no clipboard round-trip or keyboard layout is involved.
+#[cfg(windows)]
+pub fn type_into_paste_target(text: &str) -> Result<(), String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        SendInput, INPUT, INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_KEYUP, KEYEVENTF_UNICODE,
+        VIRTUAL_KEY,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{IsWindow, SetForegroundWindow};
 
-        if colors.is_empty() {
-            return Err("Failed to capture loupe region".to_string());
+    let raw = PASTE_TARGET.load(Ordering::SeqCst);
+    if raw == 0 {
+        return Err("No window to paste into".to_string());
+    }
+    let hwnd = HWND(raw as *mut std::ffi::c_void);
+    unsafe {
+        if !IsWindow(hwnd).as_bool() {
+            return Err("The target window has closed".to_string());
         }
-
-        let center = colors[(half as u32 * grid + half as u32) as usize].clone();
-        Ok(LoupeData {
-            colors,
-            hex: center,
-            x: cursor_x,
-            y: cursor_y,
+        if crate::elevation::window_is_elevated(raw) {
+            return Err(
+                "The target window runs as administrator, so Windows blocks typing into it"
+                    .to_string(),
+            );
+        }
+        let _ = SetForegroundWindow(hwnd);
+    }
+    // Give the target a moment to take focus before typing
+    std::thread::sleep(std::time::Duration::from_millis(60));
+
+    let inputs: Vec<INPUT> = text
+        .encode_utf16()
+        .flat_map(|unit| {
+            [KEYEVENTF_UNICODE, KEYEVENTF_UNICODE | KEYEVENTF_KEYUP].map(|flags| INPUT {
+                r#type: INPUT_KEYBOARD,
+                Anonymous: INPUT_0 {
+                    ki: KEYBDINPUT {
+                        wVk: VIRTUAL_KEY(0),
+                        wScan: unit,
+                        dwFlags: flags,
+                        time: 0,
+                        dwExtraInfo: 0,
+                    },
+                },
+            })
         })
+        .collect();
+    let sent = unsafe { SendInput(&inputs, std::mem::size_of::<INPUT>() as i32) };
+    if sent as usize != inputs.len() {
+        return Err("Typing was blocked by the target window".to_string());
     }
+    Ok(())
 }
 
 /// Current physical cursor position in screen coordinates.
-#[cfg(windows)]
 pub fn cursor_pos() -> Result<(i32, i32), String> {
-    get_cursor_position()
+    backend().cursor_position()
 }
 
 /// Capture the entire virtual screen into a snapshot. Called the instant before
 /// the selection overlay is shown, so the overlay's dark tint is never captured.
-#[cfg(windows)]
 pub fn capture_area_snapshot() -> Result<(), String> {
-    use std::ffi::c_void;
-
-    unsafe {
-        let origin_x = GetSystemMetrics(SM_XVIRTUALSCREEN);
-        let origin_y = GetSystemMetrics(SM_YVIRTUALSCREEN);
-        let width = GetSystemMetrics(SM_CXVIRTUALSCREEN).max(1);
-        let height = GetSystemMetrics(SM_CYVIRTUALSCREEN).max(1);
-
-        let screen_dc = GetDC(None);
-        if screen_dc.is_invalid() {
-            return Err("Failed to get screen device context".to_string());
-        }
-        let mem_dc = CreateCompatibleDC(screen_dc);
-
-        let mut bmi: BITMAPINFO = std::mem::zeroed();
-        bmi.bmiHeader.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
-        bmi.bmiHeader.biWidth = width;
-        bmi.bmiHeader.biHeight = -height; // top-down
-        bmi.bmiHeader.biPlanes = 1;
-        bmi.bmiHeader.biBitCount = 32;
-        bmi.bmiHeader.biCompression = 0; // BI_RGB
-
-        let mut bits_ptr: *mut c_void = std::ptr::null_mut();
-        let bmp = CreateDIBSection(screen_dc, &bmi, DIB_RGB_COLORS, &mut bits_ptr, None, 0)
-            .map_err(|e| {
-                let _ = DeleteDC(mem_dc);
-                ReleaseDC(None, screen_dc);
-                format!("Failed to create DIB section: {}", e)
-            })?;
-
-        let old_bmp = SelectObject(mem_dc, bmp);
-        let blit = BitBlt(
-            mem_dc, 0, 0, width, height, screen_dc, origin_x, origin_y, SRCCOPY,
-        );
-
-        let mut pixels = Vec::new();
-        if blit.is_ok() && !bits_ptr.is_null() {
-            let px =
-                std::slice::from_raw_parts(bits_ptr as *const u8, (width * height * 4) as usize);
-            pixels = px.to_vec();
-        }
-
-        SelectObject(mem_dc, old_bmp);
-        let _ = DeleteObject(bmp);
-        let _ = DeleteDC(mem_dc);
-        ReleaseDC(None, screen_dc);
+    let snapshot = backend().capture_desktop()?;
+    *AREA_SNAPSHOT.lock().unwrap() = Some(snapshot);
+    Ok(())
+}
 
-        if pixels.is_empty() {
-            return Err("Failed to capture screen snapshot".to_string());
-        }
+/// Capture the monitor under the cursor as an RGB image.
+pub fn capture_active_monitor() -> Result<image::RgbImage, String> {
+    backend().capture_active_monitor()
+}
 
-        *AREA_SNAPSHOT.lock().unwrap() = Some(AreaSnapshot {
-            pixels,
-            width,
-            height,
-            origin_x,
-            origin_y,
-        });
-        Ok(())
-    }
+/// Capture the frontmost window of another app.
+pub fn capture_foreground_window() -> Result<WindowCapture, String> {
+    backend().capture_foreground_window()
 }
 
 /// Average the pixels of the rectangle (two screen points) from the snapshot
 /// captured at area-mode start. Falls back to an error if no snapshot exists.
-#[cfg(windows)]
 pub fn average_area_color(x1: i32, y1: i32, x2: i32, y2: i32) -> Result<(u8, u8, u8), String> {
+    let start = std::time::Instant::now();
     let guard = AREA_SNAPSHOT.lock().unwrap();
     let snap = guard.as_ref().ok_or("No screen snapshot available")?;
 
@@ -378,6 +553,7 @@ pub fn average_area_color(x1: i32, y1: i32, x2: i32, y2: i32) -> Result<(u8, u8,
         }
     }
 
+    perf::record(perf::AREA_CROP, start.elapsed());
     if count == 0 {
         return Err("Empty selection".to_string());
     }
@@ -389,45 +565,45 @@ pub fn average_area_color(x1: i32, y1: i32, x2: i32, y2: i32) -> Result<(u8, u8,
 }
 
 /// Drop the snapshot when area mode ends, freeing the buffer.
-#[cfg(windows)]
 pub fn clear_area_snapshot() {
     *AREA_SNAPSHOT.lock().unwrap() = None;
 }
 
-// Non-Windows fallback implementations
-#[cfg(not(windows))]
-pub fn get_color_at_cursor() -> Result<ColorInfo, String> {
-    Err("Color picking is only supported on Windows".to_string())
+/// Snapshot the screen and make picks read from it until `unfreeze_screen`.
+pub fn freeze_screen() -> Result<(), String> {
+    capture_area_snapshot()?;
+    FROZEN.store(true, Ordering::SeqCst);
+    Ok(())
 }
 
-#[cfg(not(windows))]
-pub fn capture_loupe_grid(_grid: u32) -> Result<LoupeData, String> {
-    Err("Loupe capture is only supported on Windows".to_string())
-}
-
-#[cfg(not(windows))]
-pub fn cursor_pos() -> Result<(i32, i32), String> {
-    Err("Area picking is only supported on Windows".to_string())
+pub fn unfreeze_screen() {
+    if FROZEN.swap(false, Ordering::SeqCst) {
+        clear_area_snapshot();
+    }
 }
 
-#[cfg(not(windows))]
-pub fn capture_area_snapshot() -> Result<(), String> {
-    Err("Area picking is only supported on Windows".to_string())
+/// Pixel from the frozen snapshot, `None` outside it or when there is none.
+fn frozen_pixel(x: i32, y: i32) -> Option<(u8, u8, u8)> {
+    let guard = AREA_SNAPSHOT.lock().ok()?;
+    let snap = guard.as_ref()?;
+    let (sx, sy) = (x - snap.origin_x, y - snap.origin_y);
+    if sx < 0 || sy < 0 || sx >= snap.width || sy >= snap.height {
+        return None;
+    }
+    let i = ((sy * snap.width + sx) * 4) as usize;
+    // BGRA
+    Some((snap.pixels[i + 2], snap.pixels[i + 1], snap.pixels[i]))
 }
 
 #[cfg(not(windows))]
-pub fn average_area_color(_x1: i32, _y1: i32, _x2: i32, _y2: i32) -> Result<(u8, u8, u8), String> {
-    Err("Area picking is only supported on Windows".to_string())
+pub fn app_name_at(_x: i32, _y: i32) -> Option<String> {
+    None
 }
 
 #[cfg(not(windows))]
-pub fn clear_area_snapshot() {}
+pub fn remember_paste_target() {}
 
 #[cfg(not(windows))]
-pub fn set_pick_cursor() {}
-
-#[cfg(not(windows))]
-pub fn restore_default_cursor() {}
-
-#[cfg(not(windows))]
-pub fn restore_default_cursor_force() {}
+pub fn type_into_paste_target(_text: &str) -> Result<(), String> {
+    Err("Pick and paste is only supported on Windows".to_string())
+}
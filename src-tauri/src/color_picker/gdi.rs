@@ -0,0 +1,397 @@
+//! Windows backend: GDI reads and captures, Desktop Duplication in game mode,
+//! and a custom system cursor while picking. Coordinates are physical
+//! virtual-screen pixels.
+
+use super::{PickerBackend, WindowCapture, GAME_MODE};
+use crate::perf;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use windows::Win32::{
+    Foundation::{COLORREF, POINT},
+    Graphics::Gdi::{
+        BitBlt, CreateBitmap, CreateCompatibleDC, CreateDIBSection, DeleteDC, DeleteObject, GetDC,
+        GetMonitorInfoW, GetPixel, MonitorFromPoint, ReleaseDC, SelectObject, BITMAPINFO,
+        BITMAPINFOHEADER, CLR_INVALID, DIB_RGB_COLORS, MONITORINFO, MONITOR_DEFAULTTONEAREST,
+        SRCCOPY,
+    },
+    UI::WindowsAndMessaging::{
+        CreateIconIndirect, GetCursorPos, GetSystemMetrics, SetSystemCursor, SystemParametersInfoW,
+        HCURSOR, ICONINFO, OCR_NORMAL, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+        SM_YVIRTUALSCREEN, SPI_SETCURSORS, SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+    },
+};
+
+static CURSOR_CHANGED: AtomicBool = AtomicBool::new(false);
+
+/// High-contrast cursor mode: the ring is regenerated whenever the
+/// background under it flips between light and dark.
+static ADAPTIVE_CURSOR: AtomicBool = AtomicBool::new(false);
+
+/// Ink color of the installed high-contrast cursor, packed as 0xRRGGBB, or
+/// `u32::MAX` when none is installed.
+static CURSOR_INK: AtomicU32 = AtomicU32::new(u32::MAX);
+
+/// Side length of the generated high-contrast cursor; the hotspot is its center.
+const RING_CURSOR_SIZE: u32 = 32;
+
+pub struct Gdi;
+
+impl PickerBackend for Gdi {
+    fn cursor_position(&self) -> Result<(i32, i32), String> {
+        unsafe {
+            let mut point = POINT::default();
+            GetCursorPos(&mut point)
+                .map_err(|e| format!("Failed to get cursor position: {}", e))?;
+            Ok((point.x, point.y))
+        }
+    }
+
+    fn monitors(&self) -> Result<Vec<(i32, i32, i32, i32)>, String> {
+        let monitors =
+            xcap::Monitor::all().map_err(|e| format!("Failed to list monitors: {}", e))?;
+        Ok(monitors
+            .iter()
+            .map(|m| (m.x(), m.y(), m.width() as i32, m.height() as i32))
+            .collect())
+    }
+
+    /// In game mode Desktop Duplication is tried first, falling back to GDI
+    /// when it is unavailable (e.g. remote sessions or another app holding
+    /// the output).
+    fn pixel(&self, x: i32, y: i32) -> Result<(u8, u8, u8), String> {
+        if GAME_MODE.load(Ordering::SeqCst) {
+            if let Ok(rgb) = crate::duplication::pixel_at(x, y) {
+                return Ok(rgb);
+            }
+        }
+        unsafe {
+            let hdc = GetDC(None);
+            if hdc.is_invalid() {
+                return Err("Failed to get device context".to_string());
+            }
+
+            let color = GetPixel(hdc, x, y);
+            let _ = ReleaseDC(None, hdc);
+
+            if color == COLORREF(CLR_INVALID) {
+                return self.captured_pixel(x, y);
+            }
+
+            // COLORREF is in BGR format
+            let color_val = color.0;
+            let r = (color_val & 0xFF) as u8;
+            let g = ((color_val >> 8) & 0xFF) as u8;
+            let b = ((color_val >> 16) & 0xFF) as u8;
+
+            Ok((r, g, b))
+        }
+    }
+
+    /// BitBlt a screen rectangle into top-down BGRA pixels.
+    fn capture_rect(
+        &self,
+        origin_x: i32,
+        origin_y: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<Vec<u8>, String> {
+        use std::ffi::c_void;
+
+        unsafe {
+            let screen_dc = GetDC(None);
+            if screen_dc.is_invalid() {
+                return Err("Failed to get screen device context".to_string());
+            }
+            let mem_dc = CreateCompatibleDC(screen_dc);
+
+            let mut bmi: BITMAPINFO = std::mem::zeroed();
+            bmi.bmiHeader.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
+            bmi.bmiHeader.biWidth = width;
+            bmi.bmiHeader.biHeight = -height; // top-down
+            bmi.bmiHeader.biPlanes = 1;
+            bmi.bmiHeader.biBitCount = 32;
+            bmi.bmiHeader.biCompression = 0; // BI_RGB
+
+            let mut bits_ptr: *mut c_void = std::ptr::null_mut();
+            let bmp = CreateDIBSection(screen_dc, &bmi, DIB_RGB_COLORS, &mut bits_ptr, None, 0)
+                .map_err(|e| {
+                    let _ = DeleteDC(mem_dc);
+                    ReleaseDC(None, screen_dc);
+                    format!("Failed to create DIB section: {}", e)
+                })?;
+
+            let old_bmp = SelectObject(mem_dc, bmp);
+            let blit = perf::time(perf::SCREEN_CAPTURE, || {
+                BitBlt(
+                    mem_dc, 0, 0, width, height, screen_dc, origin_x, origin_y, SRCCOPY,
+                )
+            });
+
+            let mut pixels = Vec::new();
+            if blit.is_ok() && !bits_ptr.is_null() {
+                let px = std::slice::from_raw_parts(
+                    bits_ptr as *const u8,
+                    (width * height * 4) as usize,
+                );
+                pixels = px.to_vec();
+            }
+
+            SelectObject(mem_dc, old_bmp);
+            let _ = DeleteObject(bmp);
+            let _ = DeleteDC(mem_dc);
+            ReleaseDC(None, screen_dc);
+
+            if pixels.is_empty() {
+                return Err("Failed to capture screen snapshot".to_string());
+            }
+            Ok(pixels)
+        }
+    }
+
+    fn virtual_screen(&self) -> Result<(i32, i32, i32, i32), String> {
+        unsafe {
+            Ok((
+                GetSystemMetrics(SM_XVIRTUALSCREEN),
+                GetSystemMetrics(SM_YVIRTUALSCREEN),
+                GetSystemMetrics(SM_CXVIRTUALSCREEN).max(1),
+                GetSystemMetrics(SM_CYVIRTUALSCREEN).max(1),
+            ))
+        }
+    }
+
+    /// The monitor nearest to the point, so points just off-screen still
+    /// resolve to one.
+    fn monitor_at(&self, x: i32, y: i32) -> Result<(i32, i32, i32, i32), String> {
+        let rect = unsafe {
+            let monitor = MonitorFromPoint(POINT { x, y }, MONITOR_DEFAULTTONEAREST);
+            let mut info = MONITORINFO {
+                cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+                ..Default::default()
+            };
+            if !GetMonitorInfoW(monitor, &mut info).as_bool() {
+                return Err("Failed to get monitor info".to_string());
+            }
+            info.rcMonitor
+        };
+        Ok((
+            rect.left,
+            rect.top,
+            (rect.right - rect.left).max(1),
+            (rect.bottom - rect.top).max(1),
+        ))
+    }
+
+    /// Capture the topmost visible window that isn't ours. Windows are
+    /// rendered through `PrintWindow`, so anything covering it stays out of
+    /// the capture.
+    fn capture_foreground_window(&self) -> Result<WindowCapture, String> {
+        let own = std::process::id();
+        // Listed front to back, without tool windows
+        let window = xcap::Window::all()
+            .map_err(|e| format!("Failed to list windows: {}", e))?
+            .into_iter()
+            .find(|w| w.process_id() != own && !w.is_minimized() && w.width() > 0 && w.height() > 0)
+            .ok_or("No window to capture")?;
+        let image = window
+            .capture_image()
+            .map_err(|e| format!("Failed to capture window: {}", e))?;
+        Ok(WindowCapture {
+            app: Some(window.app_name().to_string()).filter(|name| !name.is_empty()),
+            title: window.title().to_string(),
+            image: image::DynamicImage::from(image).to_rgb8(),
+        })
+    }
+
+    /// The app icon, or with `high_contrast` a ring that adapts to the
+    /// background (see `adapt_pick_cursor`).
+    fn set_pick_cursor(&self, high_contrast: bool) {
+        ADAPTIVE_CURSOR.store(high_contrast, Ordering::SeqCst);
+        if high_contrast {
+            let background = super::cursor_pos()
+                .and_then(|(x, y)| super::get_pixel_color(x, y))
+                .map(|(r, g, b)| [r, g, b])
+                .unwrap_or([0, 0, 0]);
+            self.adapt_pick_cursor(background);
+            return;
+        }
+
+        // Load the 64x64 icon PNG embedded at compile time (bigger for visibility)
+        let icon_bytes = include_bytes!("../../icons/64x64.png");
+        let img = match image::load_from_memory(icon_bytes) {
+            Ok(img) => img.to_rgba8(),
+            Err(e) => {
+                eprintln!("Failed to load cursor icon: {}", e);
+                return;
+            }
+        };
+        let (w, h) = img.dimensions();
+        install_cursor(img.as_raw(), w, h);
+    }
+
+    /// Swap the high-contrast cursor's colors for the average `background`
+    /// around the pointer: white ink with a black halo over dark content,
+    /// black on white over light content. No-op unless that cursor is active
+    /// or when the ink would not change.
+    fn adapt_pick_cursor(&self, background: [u8; 3]) {
+        if !ADAPTIVE_CURSOR.load(Ordering::SeqCst) {
+            return;
+        }
+        let ink = crate::color_math::readable_text_color(background, false);
+        let packed = u32::from_be_bytes([0, ink[0], ink[1], ink[2]]);
+        if CURSOR_INK.swap(packed, Ordering::SeqCst) == packed {
+            return;
+        }
+        install_cursor(&ring_cursor(ink), RING_CURSOR_SIZE, RING_CURSOR_SIZE);
+    }
+
+    /// Reload the system cursors. `force` does so even when we don't think
+    /// we changed them, to recover from a crash or kill that left ours.
+    fn restore_cursor(&self, force: bool) {
+        ADAPTIVE_CURSOR.store(false, Ordering::SeqCst);
+        CURSOR_INK.store(u32::MAX, Ordering::SeqCst);
+        if force || CURSOR_CHANGED.load(Ordering::SeqCst) {
+            unsafe {
+                let _ = SystemParametersInfoW(
+                    SPI_SETCURSORS,
+                    0,
+                    None,
+                    SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+                );
+            }
+            CURSOR_CHANGED.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+impl Gdi {
+    /// Fallback for when GetPixel is refused, as it can be over elevated
+    /// windows: copy the pixel out of a screen capture, then try Desktop
+    /// Duplication.
+    fn captured_pixel(&self, x: i32, y: i32) -> Result<(u8, u8, u8), String> {
+        if let Ok(bgra) = self.capture_rect(x, y, 1, 1) {
+            if let [b, g, r, _] = bgra[..] {
+                return Ok((r, g, b));
+            }
+        }
+        crate::duplication::pixel_at(x, y).map_err(|_| "Failed to get pixel color".to_string())
+    }
+}
+
+/// RGBA pixels of a hollow ring with four crosshair ticks, drawn in `ink`
+/// with a one-pixel halo of the inverse color so it reads on any content.
+/// The center is left clear so the sampled pixel stays visible.
+fn ring_cursor(ink: [u8; 3]) -> Vec<u8> {
+    const RADIUS: f32 = 9.0;
+    // Ticks span this distance from the center along each axis
+    const TICK_START: i32 = 12;
+    const TICK_END: i32 = 15;
+    let halo = ink.map(|c| 255 - c);
+    let center = (RING_CURSOR_SIZE / 2) as i32;
+    // Distance from the ring or nearest tick, in pixels
+    let distance = |x: i32, y: i32| -> f32 {
+        let (dx, dy) = (x - center, y - center);
+        let ring = (((dx * dx + dy * dy) as f32).sqrt() - RADIUS).abs();
+        let tick = |along: i32, across: i32| {
+            let gap = (along.abs() - TICK_END)
+                .max(TICK_START - along.abs())
+                .max(0);
+            gap.max(across.abs()) as f32
+        };
+        ring.min(tick(dx, dy)).min(tick(dy, dx))
+    };
+
+    let mut pixels = Vec::with_capacity((RING_CURSOR_SIZE * RING_CURSOR_SIZE * 4) as usize);
+    for y in 0..RING_CURSOR_SIZE as i32 {
+        for x in 0..RING_CURSOR_SIZE as i32 {
+            let d = distance(x, y);
+            let px = if d <= 0.75 {
+                [ink[0], ink[1], ink[2], 255]
+            } else if d <= 1.75 {
+                [halo[0], halo[1], halo[2], 255]
+            } else {
+                [0, 0, 0, 0]
+            };
+            pixels.extend_from_slice(&px);
+        }
+    }
+    pixels
+}
+
+/// Install straight-alpha RGBA pixels as the system arrow cursor, with the
+/// hotspot in the middle.
+fn install_cursor(pixels: &[u8], w: u32, h: u32) {
+    use std::ffi::c_void;
+    use windows::Win32::Foundation::BOOL;
+
+    unsafe {
+        let hdc_screen = GetDC(None);
+
+        // Create BITMAPINFO for 32-bit top-down DIB
+        let mut bmi: BITMAPINFO = std::mem::zeroed();
+        bmi.bmiHeader.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
+        bmi.bmiHeader.biWidth = w as i32;
+        bmi.bmiHeader.biHeight = -(h as i32); // negative = top-down
+        bmi.bmiHeader.biPlanes = 1;
+        bmi.bmiHeader.biBitCount = 32;
+        bmi.bmiHeader.biCompression = 0; // BI_RGB
+
+        let mut bits_ptr: *mut c_void = std::ptr::null_mut();
+        let color_bmp =
+            match CreateDIBSection(hdc_screen, &bmi, DIB_RGB_COLORS, &mut bits_ptr, None, 0) {
+                Ok(bmp) => bmp,
+                Err(e) => {
+                    eprintln!("Failed to create DIB section: {}", e);
+                    ReleaseDC(None, hdc_screen);
+                    return;
+                }
+            };
+
+        if bits_ptr.is_null() {
+            eprintln!("DIB section bits pointer is null");
+            let _ = DeleteObject(color_bmp);
+            ReleaseDC(None, hdc_screen);
+            return;
+        }
+
+        // Copy RGBA pixels -> premultiplied BGRA (Windows format)
+        let dst = std::slice::from_raw_parts_mut(bits_ptr as *mut u8, (w * h * 4) as usize);
+        for i in 0..(w * h) as usize {
+            let r = pixels[i * 4] as u32;
+            let g = pixels[i * 4 + 1] as u32;
+            let b = pixels[i * 4 + 2] as u32;
+            let a = pixels[i * 4 + 3] as u32;
+            // Premultiply alpha for Windows
+            dst[i * 4] = (b * a / 255) as u8;
+            dst[i * 4 + 1] = (g * a / 255) as u8;
+            dst[i * 4 + 2] = (r * a / 255) as u8;
+            dst[i * 4 + 3] = a as u8;
+        }
+
+        // Create monochrome AND mask (all zeros = fully opaque, alpha handles transparency)
+        let mask_bmp = CreateBitmap(w as i32, h as i32, 1, 1, None);
+
+        let icon_info = ICONINFO {
+            fIcon: BOOL(0), // FALSE = this is a cursor, not an icon
+            xHotspot: w / 2,
+            yHotspot: h / 2,
+            hbmMask: mask_bmp,
+            hbmColor: color_bmp,
+        };
+
+        match CreateIconIndirect(&icon_info) {
+            Ok(icon) => {
+                let cursor = HCURSOR(icon.0);
+                if SetSystemCursor(cursor, OCR_NORMAL).is_ok() {
+                    CURSOR_CHANGED.store(true, Ordering::SeqCst);
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to create cursor: {}", e);
+            }
+        }
+
+        // Cleanup bitmap handles (cursor owns copies)
+        let _ = DeleteObject(mask_bmp);
+        let _ = DeleteObject(color_bmp);
+        ReleaseDC(None, hdc_screen);
+    }
+}
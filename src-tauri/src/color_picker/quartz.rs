@@ -0,0 +1,87 @@
+//! macOS backend: Quartz window-list captures. Coordinates are global display
+//! points (top-left origin, like the cursor location), and captures are taken
+//! at nominal resolution so one pixel is one point on Retina displays too.
+//! Without the Screen Recording permission the captures only contain the
+//! desktop and our own windows.
+
+use super::PickerBackend;
+use crate::perf;
+use core_graphics::{
+    display::CGDisplay,
+    event::CGEvent,
+    event_source::{CGEventSource, CGEventSourceStateID},
+    geometry::{CGPoint, CGRect, CGSize},
+    window::{kCGNullWindowID, kCGWindowImageNominalResolution, kCGWindowListOptionOnScreenOnly},
+};
+
+pub struct Quartz;
+
+impl PickerBackend for Quartz {
+    fn cursor_position(&self) -> Result<(i32, i32), String> {
+        let source = CGEventSource::new(CGEventSourceStateID::CombinedSessionState)
+            .map_err(|_| "Failed to get cursor position".to_string())?;
+        let event =
+            CGEvent::new(source).map_err(|_| "Failed to get cursor position".to_string())?;
+        let point = event.location();
+        Ok((point.x.floor() as i32, point.y.floor() as i32))
+    }
+
+    fn monitors(&self) -> Result<Vec<(i32, i32, i32, i32)>, String> {
+        let displays =
+            CGDisplay::active_displays().map_err(|e| format!("Failed to list displays: {}", e))?;
+        Ok(displays
+            .into_iter()
+            .map(|id| {
+                let b = CGDisplay::new(id).bounds();
+                (
+                    b.origin.x as i32,
+                    b.origin.y as i32,
+                    b.size.width as i32,
+                    b.size.height as i32,
+                )
+            })
+            .collect())
+    }
+
+    /// Capture a screen rectangle into top-down BGRA pixels, one per point.
+    fn capture_rect(
+        &self,
+        origin_x: i32,
+        origin_y: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<Vec<u8>, String> {
+        let rect = CGRect::new(
+            &CGPoint::new(origin_x as f64, origin_y as f64),
+            &CGSize::new(width as f64, height as f64),
+        );
+        let image = perf::time(perf::SCREEN_CAPTURE, || {
+            CGDisplay::screenshot(
+                rect,
+                kCGWindowListOptionOnScreenOnly,
+                kCGNullWindowID,
+                kCGWindowImageNominalResolution,
+            )
+        })
+        .ok_or("Failed to capture screen (is Screen Recording allowed?)")?;
+        if image.bits_per_pixel() != 32 || image.width() == 0 || image.height() == 0 {
+            return Err("Unsupported screen capture format".to_string());
+        }
+        let data = image.data();
+        let bytes = data.bytes();
+        let (image_w, image_h, stride) = (image.width(), image.height(), image.bytes_per_row());
+        let (width, height) = (width.max(1) as usize, height.max(1) as usize);
+
+        // Nominal-resolution captures match the rectangle; anything else (a
+        // display with a fractional scale) is resampled to nearest
+        let mut pixels = Vec::with_capacity(width * height * 4);
+        for y in 0..height {
+            let row = (y * image_h / height) * stride;
+            for x in 0..width {
+                let i = row + (x * image_w / width) * 4;
+                pixels.extend_from_slice(bytes.get(i..i + 4).ok_or("Truncated screen capture")?);
+            }
+        }
+        Ok(pixels)
+    }
+}
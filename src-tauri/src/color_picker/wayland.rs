@@ -0,0 +1,60 @@
+//! Wayland backend (`XDG_SESSION_TYPE=wayland`): apps can neither read the
+//! cursor position nor other windows' pixels, so picks go through the
+//! compositor's own picker and captures through the screenshot portal.
+
+use super::{AreaSnapshot, PickerBackend};
+
+const NO_CURSOR: &str = "The cursor position is not available on Wayland";
+const NO_DIRECT_CAPTURE: &str = "Reading the screen directly is not available on Wayland";
+
+pub struct Portal;
+
+impl PickerBackend for Portal {
+    fn cursor_position(&self) -> Result<(i32, i32), String> {
+        Err(NO_CURSOR.to_string())
+    }
+
+    fn monitors(&self) -> Result<Vec<(i32, i32, i32, i32)>, String> {
+        Err(NO_DIRECT_CAPTURE.to_string())
+    }
+
+    fn capture_rect(
+        &self,
+        _origin_x: i32,
+        _origin_y: i32,
+        _width: i32,
+        _height: i32,
+    ) -> Result<Vec<u8>, String> {
+        Err(NO_DIRECT_CAPTURE.to_string())
+    }
+
+    /// Physical pixels, with the origin at the top-left of the desktop.
+    fn capture_desktop(&self) -> Result<AreaSnapshot, String> {
+        let image = crate::portal::screenshot()?;
+        let (width, height) = (image.width() as i32, image.height() as i32);
+        let pixels = image
+            .pixels()
+            .flat_map(|p| [p[2], p[1], p[0], p[3]])
+            .collect();
+        Ok(AreaSnapshot {
+            pixels,
+            width,
+            height,
+            origin_x: 0,
+            origin_y: 0,
+        })
+    }
+
+    /// The portal only hands out the whole desktop.
+    fn capture_active_monitor(&self) -> Result<image::RgbImage, String> {
+        Ok(image::DynamicImage::from(crate::portal::screenshot()?).to_rgb8())
+    }
+
+    fn capture_foreground_window(&self) -> Result<super::WindowCapture, String> {
+        Err("Window capture is not available on Wayland".to_string())
+    }
+
+    fn system_picker(&self) -> bool {
+        true
+    }
+}
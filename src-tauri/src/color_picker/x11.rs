@@ -0,0 +1,224 @@
+//! Linux X11 backend through x11rb. Coordinates are root-window pixels, which
+//! span every monitor. Under Wayland X11 would only see XWayland windows, so
+//! those sessions use the portal backend instead.
+
+use super::{PickerBackend, WindowCapture};
+use crate::perf;
+use x11rb::{
+    connection::Connection,
+    errors::ReplyError,
+    protocol::{
+        randr::ConnectionExt as _,
+        xproto::{
+            AtomEnum, ConnectionExt as _, GetPropertyReply, ImageFormat, ImageOrder, MapState,
+            Screen,
+        },
+    },
+    rust_connection::RustConnection,
+};
+
+/// Connection to the X server, opened on first use and kept for the loupe's
+/// polling.
+static CONNECTION: std::sync::OnceLock<Result<(RustConnection, usize), String>> =
+    std::sync::OnceLock::new();
+
+fn x11() -> Result<(&'static RustConnection, &'static Screen), String> {
+    let (conn, screen) = CONNECTION
+        .get_or_init(|| {
+            x11rb::connect(None).map_err(|e| format!("Failed to connect to the X server: {}", e))
+        })
+        .as_ref()
+        .map_err(Clone::clone)?;
+    Ok((conn, &conn.setup().roots[*screen]))
+}
+
+pub struct X11;
+
+impl PickerBackend for X11 {
+    fn cursor_position(&self) -> Result<(i32, i32), String> {
+        let (conn, screen) = x11()?;
+        let pointer = conn
+            .query_pointer(screen.root)
+            .map_err(ReplyError::from)
+            .and_then(|cookie| cookie.reply())
+            .map_err(|e| format!("Failed to get cursor position: {}", e))?;
+        Ok((pointer.root_x as i32, pointer.root_y as i32))
+    }
+
+    /// From RandR; the whole root window when RandR is unavailable.
+    fn monitors(&self) -> Result<Vec<(i32, i32, i32, i32)>, String> {
+        let (conn, screen) = x11()?;
+        let monitors: Vec<_> = conn
+            .randr_get_monitors(screen.root, true)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .map(|reply| reply.monitors)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|m| (m.x as i32, m.y as i32, m.width as i32, m.height as i32))
+            .collect();
+        if monitors.is_empty() {
+            let (w, h) = (
+                screen.width_in_pixels as i32,
+                screen.height_in_pixels as i32,
+            );
+            return Ok(vec![(0, 0, w, h)]);
+        }
+        Ok(monitors)
+    }
+
+    /// Read a screen rectangle into top-down BGRA pixels. Parts outside the
+    /// root window, which X refuses to read, are black.
+    fn capture_rect(
+        &self,
+        origin_x: i32,
+        origin_y: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<Vec<u8>, String> {
+        let (conn, screen) = x11()?;
+        let (width, height) = (width.max(1), height.max(1));
+        let mut pixels = vec![0u8; (width * height * 4) as usize];
+
+        let (root_w, root_h) = (
+            screen.width_in_pixels as i32,
+            screen.height_in_pixels as i32,
+        );
+        let (left, top) = (origin_x.max(0), origin_y.max(0));
+        let right = (origin_x + width).min(root_w);
+        let bottom = (origin_y + height).min(root_h);
+        if left >= right || top >= bottom {
+            return Ok(pixels);
+        }
+        let image = perf::time(perf::SCREEN_CAPTURE, || {
+            conn.get_image(
+                ImageFormat::Z_PIXMAP,
+                screen.root,
+                left as i16,
+                top as i16,
+                (right - left) as u16,
+                (bottom - top) as u16,
+                !0,
+            )
+            .map_err(ReplyError::from)
+            .and_then(|cookie| cookie.reply())
+        })
+        .map_err(|e| format!("Failed to capture screen: {}", e))?;
+
+        // 24- and 32-bit TrueColor roots store pixels as 32-bit BGRX
+        let bits_per_pixel = conn
+            .setup()
+            .pixmap_formats
+            .iter()
+            .find(|f| f.depth == image.depth)
+            .map(|f| f.bits_per_pixel);
+        if bits_per_pixel != Some(32) || conn.setup().image_byte_order != ImageOrder::LSB_FIRST {
+            return Err(format!(
+                "Unsupported X11 pixel format (depth {})",
+                image.depth
+            ));
+        }
+        let src_w = (right - left) as usize;
+        let src_stride = image.data.len() / (bottom - top) as usize;
+        for y in 0..(bottom - top) as usize {
+            let src = &image.data[y * src_stride..][..src_w * 4];
+            let dst_y = y + (top - origin_y) as usize;
+            let dst = (dst_y * width as usize + (left - origin_x) as usize) * 4;
+            pixels[dst..dst + src_w * 4].copy_from_slice(src);
+        }
+        Ok(pixels)
+    }
+
+    /// Capture the topmost mapped client window that isn't ours, as stacked
+    /// by the window manager. The capture is read from the screen, so it
+    /// only holds the window itself when nothing covers it.
+    fn capture_foreground_window(&self) -> Result<WindowCapture, String> {
+        let (conn, screen) = x11()?;
+        let stacking = property(
+            conn,
+            screen.root,
+            atom(conn, "_NET_CLIENT_LIST_STACKING")?,
+            AtomEnum::WINDOW,
+        )
+        .ok_or("The window manager does not list its windows")?;
+        let pid = atom(conn, "_NET_WM_PID")?;
+        let own = std::process::id();
+        // Bottom to top
+        let window = stacking
+            .value32()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .find(|&window| {
+                let mapped = conn
+                    .get_window_attributes(window)
+                    .map_err(ReplyError::from)
+                    .and_then(|cookie| cookie.reply())
+                    .is_ok_and(|attributes| attributes.map_state == MapState::VIEWABLE);
+                let owner = property(conn, window, pid, AtomEnum::CARDINAL)
+                    .and_then(|reply| reply.value32()?.next());
+                mapped && owner != Some(own)
+            })
+            .ok_or("No window to capture")?;
+
+        let geometry = conn
+            .get_geometry(window)
+            .map_err(ReplyError::from)
+            .and_then(|cookie| cookie.reply())
+            .map_err(|e| format!("Failed to get window bounds: {}", e))?;
+        let origin = conn
+            .translate_coordinates(window, screen.root, 0, 0)
+            .map_err(ReplyError::from)
+            .and_then(|cookie| cookie.reply())
+            .map_err(|e| format!("Failed to get window bounds: {}", e))?;
+        let (width, height) = (geometry.width.max(1) as i32, geometry.height.max(1) as i32);
+        let pixels = self.capture_rect(origin.dst_x as i32, origin.dst_y as i32, width, height)?;
+        let rgb = pixels
+            .chunks_exact(4)
+            .flat_map(|bgra| [bgra[2], bgra[1], bgra[0]])
+            .collect();
+        let image = image::RgbImage::from_raw(width as u32, height as u32, rgb)
+            .ok_or("Failed to build window image")?;
+
+        // WM_CLASS is "instance\0class\0"
+        let app = property(conn, window, AtomEnum::WM_CLASS, AtomEnum::STRING).and_then(|reply| {
+            let class = reply.value.split(|&b| b == 0).nth(1)?;
+            Some(String::from_utf8_lossy(class).into_owned()).filter(|c| !c.is_empty())
+        });
+        let title = property(
+            conn,
+            window,
+            atom(conn, "_NET_WM_NAME")?,
+            atom(conn, "UTF8_STRING")?,
+        )
+        .or_else(|| property(conn, window, AtomEnum::WM_NAME, AtomEnum::STRING))
+        .map(|reply| String::from_utf8_lossy(&reply.value).into_owned())
+        .unwrap_or_default();
+        Ok(WindowCapture { app, title, image })
+    }
+}
+
+/// Interned atom `name`.
+fn atom(conn: &RustConnection, name: &str) -> Result<u32, String> {
+    conn.intern_atom(false, name.as_bytes())
+        .map_err(ReplyError::from)
+        .and_then(|cookie| cookie.reply())
+        .map(|reply| reply.atom)
+        .map_err(|e| format!("Failed to look up {}: {}", name, e))
+}
+
+/// Property `property` of `window`, `None` when it is unset or unreadable.
+fn property(
+    conn: &RustConnection,
+    window: u32,
+    property: impl Into<u32>,
+    kind: impl Into<u32>,
+) -> Option<GetPropertyReply> {
+    conn.get_property(false, window, property, kind, 0, u32::MAX / 4)
+        .ok()?
+        .reply()
+        .ok()
+        .filter(|reply| reply.type_ != u32::from(AtomEnum::NONE))
+}
@@ -0,0 +1,105 @@
+//! Conversions the loupe repeats on every frame. Hover sampling polls at
+//! 30–60 Hz and mostly sees the same few colors while the cursor crosses a
+//! flat area, so Lab, OKLCH and the nearest name are kept per color in a
+//! small least-recently-used cache instead of being recomputed each time.
+//! The cache lives in [`AppState`].
+
+use crate::color_math;
+use crate::color_names::{self, NameLocale};
+use crate::state::AppState;
+use std::collections::{BTreeMap, HashMap};
+use tauri::Manager;
+
+/// Distinct colors remembered; a screenful of UI rarely has more.
+const CAPACITY: usize = 4096;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Converted {
+    pub lab: color_math::Lab,
+    pub oklch: color_math::Oklch,
+    /// Nearest color name in the chosen language
+    pub name: &'static str,
+}
+
+/// Entries plus their recency: every lookup stamps its entry with a fresh
+/// tick, and `order` maps ticks back to colors so the oldest is first.
+struct Lru {
+    entries: HashMap<[u8; 3], (Converted, u64)>,
+    order: BTreeMap<u64, [u8; 3]>,
+    tick: u64,
+    names: NameLocale,
+}
+
+impl Lru {
+    fn new(names: NameLocale) -> Self {
+        Lru {
+            entries: HashMap::with_capacity(CAPACITY),
+            order: BTreeMap::new(),
+            tick: 0,
+            names,
+        }
+    }
+
+    fn get(&mut self, rgb: [u8; 3]) -> Converted {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some((converted, stamp)) = self.entries.get_mut(&rgb) {
+            self.order.remove(stamp);
+            *stamp = tick;
+            self.order.insert(tick, rgb);
+            return *converted;
+        }
+        if self.entries.len() >= CAPACITY {
+            if let Some((_, oldest)) = self.order.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        let converted = Converted {
+            lab: color_math::rgb_to_lab(rgb),
+            oklch: color_math::rgb_to_oklch(rgb),
+            name: color_names::nearest_name_in(rgb, self.names),
+        };
+        self.entries.insert(rgb, (converted, tick));
+        self.order.insert(tick, rgb);
+        converted
+    }
+}
+
+/// The cache as held in [`AppState`], created on first use.
+#[derive(Default)]
+pub struct ConversionCache(Option<Lru>);
+
+impl ConversionCache {
+    fn lru(&mut self) -> &mut Lru {
+        self.0
+            .get_or_insert_with(|| Lru::new(NameLocale::default()))
+    }
+}
+
+/// Conversions of an opaque screen color, computed on first sight.
+pub fn get(app: &tauri::AppHandle, rgb: [u8; 3]) -> Converted {
+    app.state::<AppState>().conversions().lru().get(rgb)
+}
+
+/// Give names in `names` from now on, forgetting names cached in another
+/// language.
+pub fn set_names(app: &tauri::AppHandle, names: NameLocale) {
+    let state = app.state::<AppState>();
+    let mut cache = state.conversions();
+    if !matches!(cache.0.as_ref(), Some(lru) if lru.names == names) {
+        cache.0 = Some(Lru::new(names));
+    }
+}
+
+/// Nearest color name, through the cache.
+pub fn name(app: &tauri::AppHandle, rgb: [u8; 3]) -> &'static str {
+    get(app, rgb).name
+}
+
+/// CIEDE2000 distance between two colors, through the cache.
+pub fn delta_e(app: &tauri::AppHandle, a: [u8; 3], b: [u8; 3]) -> f64 {
+    let state = app.state::<AppState>();
+    let mut cache = state.conversions();
+    let lru = cache.lru();
+    color_math::ciede2000(lru.get(a).lab, lru.get(b).lab)
+}
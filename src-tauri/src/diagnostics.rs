@@ -0,0 +1,66 @@
+use crate::storage;
+use serde::Serialize;
+
+/// Environment summary for bug reports.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppInfo {
+    pub version: String,
+    pub build_hash: String,
+    pub build_profile: String,
+    pub tauri_version: String,
+    pub webview_version: String,
+    pub os: String,
+    pub arch: String,
+    /// Optional behaviours currently switched on
+    pub features: Vec<String>,
+}
+
+pub fn app_info(app: &tauri::AppHandle) -> AppInfo {
+    let settings = storage::load_settings(app);
+    let mut features = Vec::new();
+    if settings.pick_hook.is_some_and(|h| h.enabled) {
+        features.push("pick-hook".to_string());
+    }
+    if settings.active_workspace.is_some() {
+        features.push("workspaces".to_string());
+    }
+    if storage::load_user_exporters(app).is_ok_and(|e| !e.is_empty()) {
+        features.push("user-exporters".to_string());
+    }
+
+    AppInfo {
+        version: app.package_info().version.to_string(),
+        build_hash: env!("PIXNIB_BUILD_HASH").to_string(),
+        build_profile: if cfg!(debug_assertions) {
+            "debug"
+        } else {
+            "release"
+        }
+        .to_string(),
+        tauri_version: tauri::VERSION.to_string(),
+        webview_version: tauri::webview_version().unwrap_or_else(|_| "unknown".to_string()),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        features,
+    }
+}
+
+/// Plain-text block suitable for pasting into an issue.
+pub fn debug_text(info: &AppInfo) -> String {
+    format!(
+        "Pixnib {} ({}, {})\nTauri {}\nWebView {}\nOS {} {}\nFeatures: {}\n",
+        info.version,
+        info.build_hash,
+        info.build_profile,
+        info.tauri_version,
+        info.webview_version,
+        info.os,
+        info.arch,
+        if info.features.is_empty() {
+            "none".to_string()
+        } else {
+            info.features.join(", ")
+        }
+    )
+}
@@ -0,0 +1,119 @@
+use serde::Serialize;
+use std::sync::OnceLock;
+
+/// System changes that invalidate screen coordinates, cached screen pixels
+/// or the installed cursor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(not(windows), allow(dead_code))]
+pub enum DisplayEvent {
+    /// Resolution, scaling or monitor layout changed (`WM_DISPLAYCHANGE`)
+    TopologyChanged,
+    /// The user unlocked the session
+    SessionUnlocked,
+}
+
+type Handler = Box<dyn Fn(DisplayEvent) + Send + Sync>;
+
+static HANDLER: OnceLock<Handler> = OnceLock::new();
+
+/// Start listening for display and session changes on a background thread.
+/// Only the first call installs a handler.
+#[cfg(windows)]
+pub fn watch(handler: impl Fn(DisplayEvent) + Send + Sync + 'static) {
+    if HANDLER.set(Box::new(handler)).is_err() {
+        return;
+    }
+    std::thread::spawn(|| {
+        if let Err(e) = run_message_loop() {
+            eprintln!("Failed to watch display changes: {}", e);
+        }
+    });
+}
+
+#[cfg(not(windows))]
+pub fn watch(handler: impl Fn(DisplayEvent) + Send + Sync + 'static) {
+    // No display notifications on this platform; keep the handler for parity.
+    let _ = HANDLER.set(Box::new(handler));
+}
+
+#[cfg(windows)]
+unsafe extern "system" fn window_proc(
+    hwnd: windows::Win32::Foundation::HWND,
+    msg: u32,
+    wparam: windows::Win32::Foundation::WPARAM,
+    lparam: windows::Win32::Foundation::LPARAM,
+) -> windows::Win32::Foundation::LRESULT {
+    use windows::Win32::UI::WindowsAndMessaging::{
+        DefWindowProcW, WM_DISPLAYCHANGE, WM_WTSSESSION_CHANGE, WTS_SESSION_UNLOCK,
+    };
+
+    let event = match msg {
+        WM_DISPLAYCHANGE => Some(DisplayEvent::TopologyChanged),
+        WM_WTSSESSION_CHANGE if wparam.0 as u32 == WTS_SESSION_UNLOCK => {
+            Some(DisplayEvent::SessionUnlocked)
+        }
+        _ => None,
+    };
+    if let (Some(event), Some(handler)) = (event, HANDLER.get()) {
+        handler(event);
+    }
+    DefWindowProcW(hwnd, msg, wparam, lparam)
+}
+
+/// Create a hidden top-level window (message-only windows miss the
+/// `WM_DISPLAYCHANGE` broadcast), subscribe it to session notifications and
+/// pump its messages for the lifetime of the app.
+#[cfg(windows)]
+fn run_message_loop() -> Result<(), String> {
+    use windows::core::w;
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::System::RemoteDesktop::{
+        WTSRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DispatchMessageW, GetMessageW, RegisterClassW, TranslateMessage, MSG,
+        WINDOW_EX_STYLE, WNDCLASSW, WS_OVERLAPPED,
+    };
+
+    unsafe {
+        let instance =
+            GetModuleHandleW(None).map_err(|e| format!("Failed to get module handle: {}", e))?;
+        let class_name = w!("PixnibDisplayWatch");
+        let class = WNDCLASSW {
+            lpfnWndProc: Some(window_proc),
+            hInstance: instance.into(),
+            lpszClassName: class_name,
+            ..Default::default()
+        };
+        if RegisterClassW(&class) == 0 {
+            return Err("Failed to register window class".to_string());
+        }
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE(0),
+            class_name,
+            w!(""),
+            WS_OVERLAPPED,
+            0,
+            0,
+            0,
+            0,
+            None,
+            None,
+            instance,
+            None,
+        )
+        .map_err(|e| format!("Failed to create window: {}", e))?;
+        if let Err(e) = WTSRegisterSessionNotification(hwnd, NOTIFY_FOR_THIS_SESSION) {
+            // Display changes still work without session notifications
+            eprintln!("Failed to register session notifications: {}", e);
+        }
+
+        let mut msg = MSG::default();
+        while GetMessageW(&mut msg, None, 0, 0).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+    Ok(())
+}
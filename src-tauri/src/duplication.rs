@@ -0,0 +1,261 @@
+//! Screen sampling through the DXGI Desktop Duplication API ("game mode").
+//! GDI reads composed desktop pixels, which come back black over
+//! exclusive-fullscreen and some hardware-composed D3D surfaces; duplication
+//! reads the frame the display is actually scanning out.
+//!
+//! The device and duplication of the last sampled display are kept between
+//! picks, with a copy of its newest frame: duplication only hands out frames
+//! when the screen changes, so a still screen is read from the copy.
+
+#[cfg(windows)]
+use std::sync::Mutex;
+
+/// How long to wait for the first duplicated frame before giving up.
+#[cfg(windows)]
+const FRAME_TIMEOUT_MS: u32 = 200;
+
+#[cfg(windows)]
+static CURRENT: Mutex<Option<win::Duplicator>> = Mutex::new(None);
+
+/// Color of the screen pixel at `(x, y)` in virtual-screen coordinates.
+#[cfg(windows)]
+pub fn pixel_at(x: i32, y: i32) -> Result<(u8, u8, u8), String> {
+    let mut current = CURRENT.lock().unwrap_or_else(|e| e.into_inner());
+    if !current.as_ref().is_some_and(|d| d.contains(x, y)) {
+        *current = None;
+        *current = Some(win::Duplicator::new(x, y)?);
+    }
+    let result = current.as_mut().map_or_else(
+        || Err("Display duplication unavailable".to_string()),
+        |d| d.pixel(x, y),
+    );
+    // Access is lost on mode switches, secure desktops and the like; start
+    // over on the next pick
+    if result.is_err() {
+        *current = None;
+    }
+    result
+}
+
+/// Release the duplication, e.g. after the display layout changed.
+#[cfg(windows)]
+pub fn reset() {
+    *CURRENT.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+#[cfg(windows)]
+mod win {
+    use super::FRAME_TIMEOUT_MS;
+    use windows::core::Interface;
+    use windows::Win32::Foundation::{HMODULE, RECT};
+    use windows::Win32::Graphics::Direct3D::D3D_DRIVER_TYPE_HARDWARE;
+    use windows::Win32::Graphics::Direct3D11::{
+        D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext, ID3D11Texture2D, D3D11_BOX,
+        D3D11_CPU_ACCESS_READ, D3D11_CREATE_DEVICE_BGRA_SUPPORT, D3D11_MAPPED_SUBRESOURCE,
+        D3D11_MAP_READ, D3D11_SDK_VERSION, D3D11_TEXTURE2D_DESC, D3D11_USAGE_DEFAULT,
+        D3D11_USAGE_STAGING,
+    };
+    use windows::Win32::Graphics::Dxgi::Common::{
+        DXGI_FORMAT_B8G8R8A8_UNORM, DXGI_MODE_ROTATION_IDENTITY, DXGI_MODE_ROTATION_UNSPECIFIED,
+        DXGI_SAMPLE_DESC,
+    };
+    use windows::Win32::Graphics::Dxgi::{
+        IDXGIAdapter, IDXGIDevice, IDXGIOutput1, IDXGIOutputDuplication, DXGI_ERROR_WAIT_TIMEOUT,
+        DXGI_OUTDUPL_FRAME_INFO,
+    };
+
+    /// Duplication of one display, with the newest frame it delivered.
+    pub struct Duplicator {
+        device: ID3D11Device,
+        context: ID3D11DeviceContext,
+        duplication: IDXGIOutputDuplication,
+        bounds: RECT,
+        /// GPU copy of the newest frame, `None` until the first arrives
+        frame: Option<ID3D11Texture2D>,
+        /// 1×1 CPU-readable texture the sampled pixel is copied into
+        staging: ID3D11Texture2D,
+    }
+
+    // Only ever used behind `super::CURRENT`, one thread at a time
+    unsafe impl Send for Duplicator {}
+
+    impl Duplicator {
+        /// Duplicate the display containing `(x, y)`.
+        pub fn new(x: i32, y: i32) -> Result<Self, String> {
+            unsafe {
+                let mut device: Option<ID3D11Device> = None;
+                let mut context: Option<ID3D11DeviceContext> = None;
+                D3D11CreateDevice(
+                    None::<&IDXGIAdapter>,
+                    D3D_DRIVER_TYPE_HARDWARE,
+                    HMODULE::default(),
+                    D3D11_CREATE_DEVICE_BGRA_SUPPORT,
+                    None,
+                    D3D11_SDK_VERSION,
+                    Some(&mut device),
+                    None,
+                    Some(&mut context),
+                )
+                .map_err(|e| format!("Failed to create Direct3D device: {}", e))?;
+                let device = device.ok_or("Direct3D device unavailable")?;
+                let context = context.ok_or("Direct3D context unavailable")?;
+
+                // Find the output (monitor) containing the point
+                let adapter = device
+                    .cast::<IDXGIDevice>()
+                    .and_then(|d| d.GetAdapter())
+                    .map_err(|e| format!("Failed to get display adapter: {}", e))?;
+                let mut found = None;
+                let mut index = 0;
+                while let Ok(output) = adapter.EnumOutputs(index) {
+                    index += 1;
+                    let Ok(desc) = output.GetDesc() else {
+                        continue;
+                    };
+                    let r = desc.DesktopCoordinates;
+                    if x >= r.left && x < r.right && y >= r.top && y < r.bottom {
+                        found = Some((output, desc));
+                        break;
+                    }
+                }
+                let (output, desc) = found.ok_or("No display found at the cursor")?;
+                if desc.Rotation != DXGI_MODE_ROTATION_IDENTITY
+                    && desc.Rotation != DXGI_MODE_ROTATION_UNSPECIFIED
+                {
+                    return Err("Game mode does not support rotated displays".to_string());
+                }
+
+                let duplication = output
+                    .cast::<IDXGIOutput1>()
+                    .and_then(|o| o.DuplicateOutput(&device))
+                    .map_err(|e| format!("Failed to duplicate display: {}", e))?;
+
+                let staging_desc = D3D11_TEXTURE2D_DESC {
+                    Width: 1,
+                    Height: 1,
+                    MipLevels: 1,
+                    ArraySize: 1,
+                    Format: DXGI_FORMAT_B8G8R8A8_UNORM,
+                    SampleDesc: DXGI_SAMPLE_DESC {
+                        Count: 1,
+                        Quality: 0,
+                    },
+                    Usage: D3D11_USAGE_STAGING,
+                    CPUAccessFlags: D3D11_CPU_ACCESS_READ.0 as u32,
+                    ..Default::default()
+                };
+                let mut staging: Option<ID3D11Texture2D> = None;
+                device
+                    .CreateTexture2D(&staging_desc, None, Some(&mut staging))
+                    .map_err(|e| format!("Failed to create staging texture: {}", e))?;
+
+                Ok(Duplicator {
+                    device,
+                    context,
+                    duplication,
+                    bounds: desc.DesktopCoordinates,
+                    frame: None,
+                    staging: staging.ok_or("Staging texture unavailable")?,
+                })
+            }
+        }
+
+        pub fn contains(&self, x: i32, y: i32) -> bool {
+            let r = self.bounds;
+            x >= r.left && x < r.right && y >= r.top && y < r.bottom
+        }
+
+        /// Copy a newly presented frame, if there is one. Waits for the first
+        /// frame; after that a still screen just keeps the copy.
+        fn update_frame(&mut self) -> Result<(), String> {
+            let timeout = if self.frame.is_some() {
+                0
+            } else {
+                FRAME_TIMEOUT_MS
+            };
+            let mut info = DXGI_OUTDUPL_FRAME_INFO::default();
+            let mut resource = None;
+            unsafe {
+                match self
+                    .duplication
+                    .AcquireNextFrame(timeout, &mut info, &mut resource)
+                {
+                    Ok(()) => {}
+                    Err(e) if e.code() == DXGI_ERROR_WAIT_TIMEOUT && self.frame.is_some() => {
+                        return Ok(());
+                    }
+                    Err(e) => return Err(format!("Failed to capture display frame: {}", e)),
+                }
+                let copied = (|| -> Result<(), String> {
+                    let acquired: ID3D11Texture2D = resource
+                        .ok_or("Display frame unavailable")?
+                        .cast()
+                        .map_err(|e| format!("Failed to read display frame: {}", e))?;
+                    if self.frame.is_none() {
+                        let mut desc = D3D11_TEXTURE2D_DESC::default();
+                        acquired.GetDesc(&mut desc);
+                        desc.Usage = D3D11_USAGE_DEFAULT;
+                        desc.BindFlags = 0;
+                        desc.CPUAccessFlags = 0;
+                        desc.MiscFlags = 0;
+                        let mut frame: Option<ID3D11Texture2D> = None;
+                        self.device
+                            .CreateTexture2D(&desc, None, Some(&mut frame))
+                            .map_err(|e| format!("Failed to create frame texture: {}", e))?;
+                        self.frame = frame;
+                    }
+                    let frame = self.frame.as_ref().ok_or("Frame texture unavailable")?;
+                    self.context.CopyResource(frame, &acquired);
+                    Ok(())
+                })();
+                let _ = self.duplication.ReleaseFrame();
+                copied
+            }
+        }
+
+        /// Color of the pixel at `(x, y)` in the newest frame.
+        pub fn pixel(&mut self, x: i32, y: i32) -> Result<(u8, u8, u8), String> {
+            self.update_frame()?;
+            let frame = self.frame.as_ref().ok_or("Display frame unavailable")?;
+            let (px, py) = ((x - self.bounds.left) as u32, (y - self.bounds.top) as u32);
+            let region = D3D11_BOX {
+                left: px,
+                top: py,
+                front: 0,
+                right: px + 1,
+                bottom: py + 1,
+                back: 1,
+            };
+            unsafe {
+                self.context.CopySubresourceRegion(
+                    &self.staging,
+                    0,
+                    0,
+                    0,
+                    0,
+                    frame,
+                    0,
+                    Some(&region),
+                );
+                let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+                self.context
+                    .Map(&self.staging, 0, D3D11_MAP_READ, 0, Some(&mut mapped))
+                    .map_err(|e| format!("Failed to map staging texture: {}", e))?;
+                // BGRA
+                let bytes = std::slice::from_raw_parts(mapped.pData as *const u8, 4);
+                let color = (bytes[2], bytes[1], bytes[0]);
+                self.context.Unmap(&self.staging, 0);
+                Ok(color)
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+#[allow(dead_code)]
+pub fn pixel_at(_x: i32, _y: i32) -> Result<(u8, u8, u8), String> {
+    Err("Game mode is only supported on Windows".to_string())
+}
+
+#[cfg(not(windows))]
+pub fn reset() {}
@@ -0,0 +1,206 @@
+//! JSON-RPC 2.0 surface for editor plugins (VS Code, JetBrains, ...), served
+//! on the named pipe `\\.\pipe\pixnib-rpc` while the `editor_rpc` setting is
+//! on.
+//!
+//! Messages are single lines of JSON in both directions. A client first sends
+//! `initialize` with the protocol versions it speaks and gets back the one in
+//! use plus the methods available in it; every other method is refused until
+//! then:
+//!
+//! ```text
+//! → {"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersions":[1],"client":"pixnib-vscode 0.1"}}
+//! ← {"jsonrpc":"2.0","id":1,"result":{"protocolVersion":1,"server":{"name":"Pixnib","version":"0.1.17"},"methods":["pick","getHistory","formatColor","search","query","importCssVariables"]}}
+//! ```
+//!
+//! Methods of protocol version 1:
+//!
+//! - `pick` — start loupe pick mode and wait for the user. Returns the picked
+//!   color as emitted in `color-picked`, or `null` when the pick is cancelled.
+//! - `getHistory` `{limit?}` — history entries, newest first.
+//! - `formatColor` `{color, format?, template?}` — `color` (hex) as `hex`,
+//!   `rgb`, `hsl`, `oklch` or `custom` text; defaults to the user's copy
+//!   format and template.
+//! - `search` `{query}` — workspaces, palettes, palette colors and history, as
+//!   in the quick switcher.
+//! - `query` `{query, limit?}` — fuzzy matches over history and palettes with
+//!   copy-ready text, for launcher extensions (see [`crate::launcher`]).
+//! - `importCssVariables` `{name, variables: [{name, value}]}` — save a web
+//!   page's color custom properties as a palette, for the browser extension
+//!   (see [`crate::native_messaging`]).
+//!
+//! Errors use the JSON-RPC codes, plus [`json_rpc::NOT_INITIALIZED`] and
+//! [`json_rpc::REQUEST_FAILED`] (with the app's error message).
+
+use crate::json_rpc::{self, params, to_value, RpcError};
+use crate::pipe_server::{PipeServer, Protocol};
+use crate::{copy_format, import, launcher, pick_controller, storage};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::Manager;
+
+/// Protocol versions this build speaks, oldest first.
+const PROTOCOL_VERSIONS: [u32; 1] = [1];
+const METHODS: [&str; 6] = [
+    "pick",
+    "getHistory",
+    "formatColor",
+    "search",
+    "query",
+    "importCssVariables",
+];
+/// A pick left open longer than this is cancelled.
+const PICK_TIMEOUT: Duration = Duration::from_secs(120);
+
+pub const PIPE_NAME: &str = r"\\.\pipe\pixnib-rpc";
+
+static SERVER: PipeServer = PipeServer::new(PIPE_NAME);
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitializeParams {
+    protocol_versions: Vec<u32>,
+}
+
+#[derive(Deserialize)]
+struct HistoryParams {
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct FormatParams {
+    color: String,
+    format: Option<copy_format::CopyFormat>,
+    template: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CssParams {
+    name: String,
+    variables: Vec<import::CssVariable>,
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    query: String,
+    limit: Option<usize>,
+}
+
+/// Per-connection state.
+#[derive(Default)]
+struct Session {
+    /// Negotiated by `initialize`
+    version: Option<u32>,
+}
+
+fn initialize(
+    app: &tauri::AppHandle,
+    session: &mut Session,
+    p: InitializeParams,
+) -> Result<Value, RpcError> {
+    let version = PROTOCOL_VERSIONS
+        .iter()
+        .rev()
+        .find(|v| p.protocol_versions.contains(v))
+        .copied()
+        .ok_or_else(|| {
+            RpcError::new(
+                json_rpc::INVALID_PARAMS,
+                format!(
+                    "No common protocol version; supported: {:?}",
+                    PROTOCOL_VERSIONS
+                ),
+            )
+        })?;
+    session.version = Some(version);
+    Ok(json!({
+        "protocolVersion": version,
+        "server": {
+            "name": "Pixnib",
+            "version": app.package_info().version.to_string(),
+        },
+        "methods": METHODS,
+    }))
+}
+
+fn dispatch(
+    app: &tauri::AppHandle,
+    session: &mut Session,
+    method: &str,
+    p: Value,
+) -> Result<Value, RpcError> {
+    if method == "initialize" {
+        return initialize(app, session, params(p)?);
+    }
+    if session.version.is_none() {
+        return Err(RpcError::new(
+            json_rpc::NOT_INITIALIZED,
+            "Call initialize first",
+        ));
+    }
+    match method {
+        "pick" => to_value(pick_controller::wait_for_pick(app, PICK_TIMEOUT)?),
+        "getHistory" => {
+            let p: HistoryParams = params(p)?;
+            let mut history = tauri::async_runtime::block_on(storage::load_color_history(app))?;
+            if let Some(limit) = p.limit {
+                history.truncate(limit);
+            }
+            to_value(history)
+        }
+        "formatColor" => {
+            let p: FormatParams = params(p)?;
+            let rgb = crate::color_math::parse_hex(&p.color)
+                .map_err(|e| RpcError::new(json_rpc::INVALID_PARAMS, e))?;
+            let settings = storage::load_settings(app);
+            let template = p.template.or(settings.copy_template);
+            Ok(Value::String(copy_format::format(
+                rgb,
+                p.format.unwrap_or(settings.copy_format),
+                template.as_deref(),
+            )))
+        }
+        "search" => {
+            let p: SearchParams = params(p)?;
+            let hits =
+                tauri::async_runtime::block_on(crate::search_everywhere(app.clone(), p.query))?;
+            to_value(hits)
+        }
+        "query" => {
+            let p: SearchParams = params(p)?;
+            tauri::async_runtime::block_on(crate::refresh_search_index(app))?;
+            to_value(launcher::query(
+                &p.query,
+                p.limit.unwrap_or(launcher::DEFAULT_LIMIT),
+                &storage::load_palettes(app),
+                &storage::load_settings(app),
+            ))
+        }
+        "importCssVariables" => {
+            let p: CssParams = params(p)?;
+            let imported = import::from_css_variables(&p.name, &p.variables)?;
+            to_value(crate::save_imported_palette(app, imported, "Browser")?)
+        }
+        _ => Err(RpcError::new(
+            json_rpc::METHOD_NOT_FOUND,
+            format!("Unknown method: {method}"),
+        )),
+    }
+}
+
+impl Protocol for Session {
+    fn handle(&mut self, app: &tauri::AppHandle, line: &str) -> Option<String> {
+        json_rpc::handle(line, |method, p| dispatch(app, self, method, p))
+    }
+}
+
+/// Start serving the pipe, unless already running.
+pub fn start(app: &tauri::AppHandle) {
+    SERVER.start::<Session>(app);
+}
+
+/// Stop accepting connections; clients already connected are served until
+/// they disconnect.
+pub fn stop() {
+    SERVER.stop();
+}
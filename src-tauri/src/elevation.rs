@@ -0,0 +1,126 @@
+//! Windows UIPI (User Interface Privilege Isolation) keeps a non-elevated
+//! process from sending input to, and sometimes reading from, windows of
+//! elevated ("Run as administrator") processes. These helpers detect that
+//! case so picks can fall back or fail with an explanation.
+
+use serde::Serialize;
+
+/// Error returned by pick commands. Serialized with a `kind` tag so the
+/// frontend can explain the elevated-window limitation instead of a raw error.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum PickError {
+    /// The window under the cursor runs as administrator and every sampling
+    /// path was blocked; restarting Pixnib as administrator works around it
+    Elevated {
+        app: Option<String>,
+    },
+    Other {
+        message: String,
+    },
+}
+
+impl From<String> for PickError {
+    fn from(message: String) -> Self {
+        PickError::Other { message }
+    }
+}
+
+impl std::fmt::Display for PickError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PickError::Elevated { app: Some(app) } => write!(
+                f,
+                "{app} runs as administrator; restart Pixnib as administrator to pick from it"
+            ),
+            PickError::Elevated { app: None } => f.write_str(
+                "This window runs as administrator; restart Pixnib as administrator to pick from it",
+            ),
+            PickError::Other { message } => f.write_str(message),
+        }
+    }
+}
+
+/// Token elevation of a process handle; `None` when it can't be queried.
+#[cfg(windows)]
+unsafe fn process_elevated(process: windows::Win32::Foundation::HANDLE) -> Option<bool> {
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::Security::{
+        GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY,
+    };
+    use windows::Win32::System::Threading::OpenProcessToken;
+
+    let mut token = HANDLE::default();
+    OpenProcessToken(process, TOKEN_QUERY, &mut token).ok()?;
+    let mut elevation = TOKEN_ELEVATION::default();
+    let mut len = 0u32;
+    let queried = GetTokenInformation(
+        token,
+        TokenElevation,
+        Some(&mut elevation as *mut TOKEN_ELEVATION as *mut std::ffi::c_void),
+        std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+        &mut len,
+    );
+    let _ = CloseHandle(token);
+    queried.ok()?;
+    Some(elevation.TokenIsElevated != 0)
+}
+
+/// Whether `hwnd` (a raw window handle) belongs to an elevated process while
+/// we are not elevated. A target whose token can't be read is treated as
+/// elevated, since being denied is exactly what UIPI does.
+#[cfg(windows)]
+pub fn window_is_elevated(hwnd: isize) -> bool {
+    use windows::Win32::Foundation::{CloseHandle, HWND};
+    use windows::Win32::System::Threading::{
+        GetCurrentProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
+
+    unsafe {
+        if process_elevated(GetCurrentProcess()) == Some(true) {
+            return false;
+        }
+        let mut pid = 0u32;
+        GetWindowThreadProcessId(
+            HWND(hwnd as *mut std::ffi::c_void),
+            Some(&mut pid as *mut u32),
+        );
+        if pid == 0 || pid == std::process::id() {
+            return false;
+        }
+        let Ok(process) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+            return true;
+        };
+        let elevated = process_elevated(process);
+        let _ = CloseHandle(process);
+        elevated.unwrap_or(true)
+    }
+}
+
+/// [`window_is_elevated`] for the top-level window under a screen point.
+#[cfg(windows)]
+pub fn window_at_is_elevated(x: i32, y: i32) -> bool {
+    use windows::Win32::Foundation::POINT;
+    use windows::Win32::UI::WindowsAndMessaging::{GetAncestor, WindowFromPoint, GA_ROOT};
+
+    unsafe {
+        let hwnd = WindowFromPoint(POINT { x, y });
+        if hwnd.is_invalid() {
+            return false;
+        }
+        let root = GetAncestor(hwnd, GA_ROOT);
+        let target = if root.is_invalid() { hwnd } else { root };
+        window_is_elevated(target.0 as isize)
+    }
+}
+
+#[cfg(not(windows))]
+pub fn window_is_elevated(_hwnd: isize) -> bool {
+    false
+}
+
+#[cfg(not(windows))]
+pub fn window_at_is_elevated(_x: i32, _y: i32) -> bool {
+    false
+}
@@ -0,0 +1,201 @@
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Prefix of encrypted store files, followed by a one-byte scheme tag.
+/// Files without it are plain JSON, so existing stores keep loading.
+const MAGIC: &[u8] = b"PIXNIB-ENC1";
+const TAG_DPAPI: u8 = b'D';
+const TAG_PASSPHRASE: u8 = b'P';
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// How the history and palette stores are protected at rest.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StoreEncryption {
+    #[default]
+    None,
+    /// Windows DPAPI, bound to the current user account
+    Dpapi,
+    /// ChaCha20-Poly1305 with an Argon2 key derived from a passphrase
+    Passphrase,
+}
+
+/// Key derived from the passphrase this session. Never persisted.
+static KEY: Mutex<Option<[u8; 32]>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreStatus {
+    pub mode: StoreEncryption,
+    /// Passphrase mode without the passphrase entered yet
+    pub locked: bool,
+}
+
+pub fn status(mode: StoreEncryption) -> StoreStatus {
+    StoreStatus {
+        mode,
+        locked: mode == StoreEncryption::Passphrase && !is_unlocked(),
+    }
+}
+
+fn is_unlocked() -> bool {
+    KEY.lock().map(|k| k.is_some()).unwrap_or(false)
+}
+
+pub fn new_salt() -> String {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    base64::engine::general_purpose::STANDARD.encode(salt)
+}
+
+/// Derive the store key from `passphrase` and the base64 `salt` kept in
+/// settings, and hold it in memory for this session.
+pub fn unlock(passphrase: &str, salt: &str) -> Result<(), String> {
+    let salt = base64::engine::general_purpose::STANDARD
+        .decode(salt)
+        .map_err(|e| format!("Invalid store salt: {}", e))?;
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| format!("Failed to derive key: {}", e))?;
+    *KEY.lock().map_err(|e| e.to_string())? = Some(key);
+    Ok(())
+}
+
+/// Forget the session key.
+pub fn lock() {
+    if let Ok(mut key) = KEY.lock() {
+        *key = None;
+    }
+}
+
+/// The session key, to put back with [`restore_key`] if a switch fails.
+pub fn session_key() -> Option<[u8; 32]> {
+    KEY.lock().ok().and_then(|k| *k)
+}
+
+pub fn restore_key(key: Option<[u8; 32]>) {
+    if let Ok(mut current) = KEY.lock() {
+        *current = key;
+    }
+}
+
+/// Encode store contents for writing under `mode`.
+pub fn seal(mode: StoreEncryption, plaintext: &[u8]) -> Result<Vec<u8>, String> {
+    let (tag, body) = match mode {
+        StoreEncryption::None => return Ok(plaintext.to_vec()),
+        StoreEncryption::Dpapi => (TAG_DPAPI, dpapi::protect(plaintext)?),
+        StoreEncryption::Passphrase => {
+            let key = KEY
+                .lock()
+                .map_err(|e| e.to_string())?
+                .ok_or("Storage is locked; enter the passphrase first")?;
+            let cipher = ChaCha20Poly1305::new(Key::from_slice(&key));
+            let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+            let ciphertext = cipher
+                .encrypt(&nonce, plaintext)
+                .map_err(|e| format!("Failed to encrypt store: {}", e))?;
+            let mut body = nonce.to_vec();
+            body.extend_from_slice(&ciphertext);
+            (TAG_PASSPHRASE, body)
+        }
+    };
+    let mut out = MAGIC.to_vec();
+    out.push(tag);
+    out.extend_from_slice(&body);
+    Ok(out)
+}
+
+/// Decode a store file, whichever scheme (or none) it was written with.
+pub fn open(data: &[u8]) -> Result<Vec<u8>, String> {
+    let Some(rest) = data.strip_prefix(MAGIC) else {
+        return Ok(data.to_vec());
+    };
+    match rest.split_first() {
+        Some((&TAG_DPAPI, body)) => dpapi::unprotect(body),
+        Some((&TAG_PASSPHRASE, body)) if body.len() > NONCE_LEN => {
+            let key = KEY
+                .lock()
+                .map_err(|e| e.to_string())?
+                .ok_or("Storage is locked; enter the passphrase first")?;
+            let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+            ChaCha20Poly1305::new(Key::from_slice(&key))
+                .decrypt(Nonce::from_slice(nonce), ciphertext)
+                .map_err(|_| "Wrong passphrase or damaged store".to_string())
+        }
+        _ => Err("Unrecognized encrypted store".to_string()),
+    }
+}
+
+#[cfg(windows)]
+mod dpapi {
+    use windows::core::w;
+    use windows::Win32::Foundation::{LocalFree, HLOCAL};
+    use windows::Win32::Security::Cryptography::{
+        CryptProtectData, CryptUnprotectData, CRYPTPROTECT_UI_FORBIDDEN, CRYPT_INTEGER_BLOB,
+    };
+
+    fn input(data: &[u8]) -> CRYPT_INTEGER_BLOB {
+        CRYPT_INTEGER_BLOB {
+            cbData: data.len() as u32,
+            pbData: data.as_ptr() as *mut u8,
+        }
+    }
+
+    /// Copy the system-allocated output blob and free it.
+    unsafe fn take(blob: CRYPT_INTEGER_BLOB) -> Vec<u8> {
+        let data = std::slice::from_raw_parts(blob.pbData, blob.cbData as usize).to_vec();
+        let _ = LocalFree(HLOCAL(blob.pbData as *mut core::ffi::c_void));
+        data
+    }
+
+    pub fn protect(data: &[u8]) -> Result<Vec<u8>, String> {
+        let mut out = CRYPT_INTEGER_BLOB::default();
+        unsafe {
+            CryptProtectData(
+                &input(data),
+                w!("Pixnib store"),
+                None,
+                None,
+                None,
+                CRYPTPROTECT_UI_FORBIDDEN,
+                &mut out,
+            )
+            .map_err(|e| format!("Failed to encrypt store: {}", e))?;
+            Ok(take(out))
+        }
+    }
+
+    pub fn unprotect(data: &[u8]) -> Result<Vec<u8>, String> {
+        let mut out = CRYPT_INTEGER_BLOB::default();
+        unsafe {
+            CryptUnprotectData(
+                &input(data),
+                None,
+                None,
+                None,
+                None,
+                CRYPTPROTECT_UI_FORBIDDEN,
+                &mut out,
+            )
+            .map_err(|e| format!("Failed to decrypt store: {}", e))?;
+            Ok(take(out))
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod dpapi {
+    pub fn protect(_data: &[u8]) -> Result<Vec<u8>, String> {
+        Err("DPAPI encryption is only available on Windows".to_string())
+    }
+
+    pub fn unprotect(_data: &[u8]) -> Result<Vec<u8>, String> {
+        Err("DPAPI encryption is only available on Windows".to_string())
+    }
+}
@@ -0,0 +1,91 @@
+use crate::{ColorEntry, Palette};
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::Emitter;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorRemoved {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Updated,
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteChanged {
+    pub kind: ChangeKind,
+    pub id: String,
+    /// The palette after the change; absent for removals
+    pub palette: Option<Palette>,
+}
+
+/// Compare two snapshots of history and emit `color-added`, `color-updated`
+/// (payload: the entry) and `color-removed` (payload: `{ id }`) for each
+/// entry that differs, so every window can patch its copy in place.
+pub fn emit_history_changes(app: &tauri::AppHandle, before: &[ColorEntry], after: &[ColorEntry]) {
+    let old: HashMap<&str, &ColorEntry> = before.iter().map(|e| (e.id.as_str(), e)).collect();
+    let new: HashMap<&str, &ColorEntry> = after.iter().map(|e| (e.id.as_str(), e)).collect();
+
+    for entry in after {
+        match old.get(entry.id.as_str()) {
+            None => {
+                let _ = app.emit("color-added", entry);
+            }
+            Some(prev) if *prev != entry => {
+                let _ = app.emit("color-updated", entry);
+            }
+            _ => {}
+        }
+    }
+    for entry in before {
+        if !new.contains_key(entry.id.as_str()) {
+            let _ = app.emit(
+                "color-removed",
+                ColorRemoved {
+                    id: entry.id.clone(),
+                },
+            );
+        }
+    }
+}
+
+/// Emit one `palette-changed` event per added, updated or removed palette.
+pub fn emit_palette_changes(app: &tauri::AppHandle, before: &[Palette], after: &[Palette]) {
+    let old: HashMap<&str, &Palette> = before.iter().map(|p| (p.id.as_str(), p)).collect();
+    let new: HashMap<&str, &Palette> = after.iter().map(|p| (p.id.as_str(), p)).collect();
+
+    for palette in after {
+        let kind = match old.get(palette.id.as_str()) {
+            None => ChangeKind::Added,
+            Some(prev) if *prev != palette => ChangeKind::Updated,
+            _ => continue,
+        };
+        let _ = app.emit(
+            "palette-changed",
+            PaletteChanged {
+                kind,
+                id: palette.id.clone(),
+                palette: Some(palette.clone()),
+            },
+        );
+    }
+    for palette in before {
+        if !new.contains_key(palette.id.as_str()) {
+            let _ = app.emit(
+                "palette-changed",
+                PaletteChanged {
+                    kind: ChangeKind::Removed,
+                    id: palette.id.clone(),
+                    palette: None,
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,347 @@
+//! Local HTTP bridge for the Pixnib Figma plugin, on
+//! `http://127.0.0.1:47321` while the `figma_bridge` setting is on. Figma
+//! plugins can only reach the app over HTTP, so this is separate from the
+//! pipe protocols; the plugin's manifest must list `http://localhost:47321`
+//! under `networkAccess.allowedDomains`.
+//!
+//! The plugin pairs once: the user starts pairing in Pixnib, types the
+//! six-digit code into the plugin, which trades it for a token sent as
+//! `Authorization: Bearer <token>` from then on.
+//!
+//! - `POST /pair` `{code}` → `{token}`
+//! - `GET /status` → `{name, version}`
+//! - `GET /picks?after=<seq>` — long-polls for colors picked (or sent with
+//!   `send_color_to_figma`) after `seq`, as Figma `SolidPaint`s:
+//!   `{seq, paints: [{seq, hex, paint: {type: "SOLID", color: {r, g, b}}}]}`.
+//!   The plugin applies them to the current selection.
+//! - `POST /styles` `{name, styles: [{name, paints}]}` — the document's paint
+//!   styles as returned by `figma.getLocalPaintStyles()`; the first solid
+//!   paint of each becomes a color of a new palette `name`.
+
+use crate::state::AppState;
+use crate::{color_math, import, storage};
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::OsRng;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::io::Read;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{EventId, Listener, Manager};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const ADDRESS: &str = "127.0.0.1:47321";
+/// How long a pairing code stays valid, and how many wrong codes end it
+const PAIRING_TTL: Duration = Duration::from_secs(120);
+const PAIRING_ATTEMPTS: u32 = 5;
+/// `/picks` answers after this long even when nothing was picked
+const LONG_POLL: Duration = Duration::from_secs(25);
+/// Colors kept for plugins that poll late
+const MAX_QUEUED: usize = 20;
+const MAX_BODY: u64 = 1024 * 1024;
+
+struct Pairing {
+    code: String,
+    started: Instant,
+    attempts: u32,
+}
+
+#[derive(Clone)]
+struct Push {
+    seq: u64,
+    hex: String,
+}
+
+#[derive(Default)]
+struct Queue {
+    next_seq: u64,
+    pushes: VecDeque<Push>,
+}
+
+struct Running {
+    server: Arc<Server>,
+    listener: EventId,
+}
+
+/// Bridge state, held in [`AppState`].
+#[derive(Default)]
+pub struct FigmaBridge {
+    running: Mutex<Option<Running>>,
+    pairing: Mutex<Option<Pairing>>,
+    queue: Mutex<Queue>,
+    /// Signalled whenever a color is queued
+    ready: Condvar,
+}
+
+fn bridge(app: &tauri::AppHandle) -> &FigmaBridge {
+    app.state::<AppState>().inner().figma()
+}
+
+#[derive(Deserialize)]
+struct PairRequest {
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct FigmaColor {
+    r: f64,
+    g: f64,
+    b: f64,
+}
+
+#[derive(Deserialize)]
+struct FigmaPaint {
+    #[serde(rename = "type")]
+    kind: String,
+    color: Option<FigmaColor>,
+}
+
+#[derive(Deserialize)]
+struct FigmaStyle {
+    #[serde(default)]
+    paints: Vec<FigmaPaint>,
+}
+
+#[derive(Deserialize)]
+struct StylesRequest {
+    name: String,
+    styles: Vec<FigmaStyle>,
+}
+
+/// Start serving, unless already running. Picks are queued for the plugin
+/// from here on.
+pub fn start(app: &tauri::AppHandle) -> Result<(), String> {
+    let mut running = bridge(app).running.lock().map_err(|e| e.to_string())?;
+    if running.is_some() {
+        return Ok(());
+    }
+    let server = Arc::new(
+        Server::http(ADDRESS).map_err(|e| format!("Failed to listen on {}: {}", ADDRESS, e))?,
+    );
+    let listener_app = app.clone();
+    let listener = app.listen("color-picked", move |event| {
+        let color: Value = serde_json::from_str(event.payload()).unwrap_or_default();
+        if let Some(hex) = color.get("hex").and_then(Value::as_str) {
+            push(&listener_app, hex);
+        }
+    });
+    let (thread_server, app_handle) = (server.clone(), app.clone());
+    std::thread::spawn(move || {
+        for request in thread_server.incoming_requests() {
+            let app = app_handle.clone();
+            std::thread::spawn(move || handle(&app, request));
+        }
+    });
+    *running = Some(Running { server, listener });
+    Ok(())
+}
+
+pub fn stop(app: &tauri::AppHandle) {
+    let running = bridge(app).running.lock().ok().and_then(|mut r| r.take());
+    if let Some(running) = running {
+        app.unlisten(running.listener);
+        running.server.unblock();
+    }
+}
+
+/// Queue `hex` for the plugin to apply to the selection.
+pub fn push(app: &tauri::AppHandle, hex: &str) {
+    let bridge = bridge(app);
+    let Ok(mut queue) = bridge.queue.lock() else {
+        return;
+    };
+    queue.next_seq += 1;
+    let seq = queue.next_seq;
+    queue.pushes.push_back(Push {
+        seq,
+        hex: hex.to_string(),
+    });
+    if queue.pushes.len() > MAX_QUEUED {
+        queue.pushes.pop_front();
+    }
+    bridge.ready.notify_all();
+}
+
+/// Start pairing and return the code the user types into the plugin.
+pub fn start_pairing(app: &tauri::AppHandle) -> Result<String, String> {
+    let code = format!("{:06}", OsRng.next_u32() % 1_000_000);
+    *bridge(app).pairing.lock().map_err(|e| e.to_string())? = Some(Pairing {
+        code: code.clone(),
+        started: Instant::now(),
+        attempts: 0,
+    });
+    Ok(code)
+}
+
+/// Trade a pairing code for a new token, replacing any earlier pairing.
+fn pair(app: &tauri::AppHandle, code: &str) -> Result<String, String> {
+    let mut pairing = bridge(app).pairing.lock().map_err(|e| e.to_string())?;
+    let current = pairing
+        .as_mut()
+        .filter(|p| p.started.elapsed() < PAIRING_TTL)
+        .ok_or("No pairing in progress; start pairing in Pixnib")?;
+    if current.code != code {
+        current.attempts += 1;
+        if current.attempts >= PAIRING_ATTEMPTS {
+            *pairing = None;
+        }
+        return Err("Wrong pairing code".to_string());
+    }
+    *pairing = None;
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    let token = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes);
+    let mut settings = storage::load_settings(app);
+    settings.figma_token = Some(token.clone());
+    storage::save_settings(app, &settings)?;
+    Ok(token)
+}
+
+/// Pushes after `after`, waiting up to [`LONG_POLL`] for one to arrive.
+fn pushes_after(app: &tauri::AppHandle, after: u64) -> (u64, Vec<Push>) {
+    let bridge = bridge(app);
+    let Ok(guard) = bridge.queue.lock() else {
+        return (after, Vec::new());
+    };
+    let queue = match bridge
+        .ready
+        .wait_timeout_while(guard, LONG_POLL, |q| q.next_seq <= after)
+    {
+        Ok((queue, _)) => queue,
+        Err(_) => return (after, Vec::new()),
+    };
+    (
+        queue.next_seq,
+        queue
+            .pushes
+            .iter()
+            .filter(|p| p.seq > after)
+            .cloned()
+            .collect(),
+    )
+}
+
+fn paint(push: &Push) -> Value {
+    let [r, g, b] = color_math::parse_hex(&push.hex).unwrap_or([0, 0, 0]);
+    json!({
+        "seq": push.seq,
+        "hex": push.hex,
+        "paint": {
+            "type": "SOLID",
+            "color": { "r": r as f64 / 255.0, "g": g as f64 / 255.0, "b": b as f64 / 255.0 },
+        },
+    })
+}
+
+/// Palette from the first solid paint of each style, skipping styles with
+/// none (gradients, images).
+fn import_styles(app: &tauri::AppHandle, request: StylesRequest) -> Result<Value, String> {
+    let colors: Vec<String> = request
+        .styles
+        .iter()
+        .filter_map(|style| {
+            let paint = style.paints.iter().find(|p| p.kind == "SOLID")?;
+            let c = paint.color.as_ref()?;
+            let channel = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+            Some(color_math::to_hex([
+                channel(c.r),
+                channel(c.g),
+                channel(c.b),
+            ]))
+        })
+        .collect();
+    if colors.is_empty() {
+        return Err("No solid paint styles to import".to_string());
+    }
+    let imported = import::ImportedPalette {
+        name: request.name,
+        colors,
+    };
+    let palette = crate::save_imported_palette(app, imported, "Figma")?;
+    serde_json::to_value(palette).map_err(|e| format!("Failed to serialize palette: {}", e))
+}
+
+fn header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("valid header")
+}
+
+/// JSON response with the CORS headers Figma's sandboxed iframe (origin
+/// `null`) needs.
+fn respond(request: Request, status: u16, body: Value) {
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(header("Content-Type", "application/json"))
+        .with_header(header("Access-Control-Allow-Origin", "*"))
+        .with_header(header("Access-Control-Allow-Methods", "GET, POST"))
+        .with_header(header(
+            "Access-Control-Allow-Headers",
+            "Authorization, Content-Type",
+        ));
+    let _ = request.respond(response);
+}
+
+fn body<T: serde::de::DeserializeOwned>(request: &mut Request) -> Result<T, String> {
+    let mut text = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY)
+        .read_to_string(&mut text)
+        .map_err(|e| format!("Failed to read request: {}", e))?;
+    serde_json::from_str(&text).map_err(|e| format!("Invalid request: {}", e))
+}
+
+fn authorized(app: &tauri::AppHandle, request: &Request) -> bool {
+    let Some(token) = storage::load_settings(app).figma_token else {
+        return false;
+    };
+    request.headers().iter().any(|h| {
+        h.field.equiv("Authorization")
+            && h.value.as_str().strip_prefix("Bearer ") == Some(token.as_str())
+    })
+}
+
+fn handle(app: &tauri::AppHandle, mut request: Request) {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let method = request.method().clone();
+    if method == Method::Options {
+        return respond(request, 200, Value::Null);
+    }
+    let error = |message: String| json!({ "error": message });
+    if method == Method::Post && path == "/pair" {
+        let result = body::<PairRequest>(&mut request).and_then(|p| pair(app, &p.code));
+        return match result {
+            Ok(token) => respond(request, 200, json!({ "token": token })),
+            Err(e) => respond(request, 403, error(e)),
+        };
+    }
+    if !authorized(app, &request) {
+        return respond(request, 401, error("Not paired".to_string()));
+    }
+    match (method, path) {
+        (Method::Get, "/status") => respond(
+            request,
+            200,
+            json!({ "name": "Pixnib", "version": app.package_info().version.to_string() }),
+        ),
+        (Method::Get, "/picks") => {
+            let after = query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("after="))
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0);
+            let (seq, pushes) = pushes_after(app, after);
+            let paints: Vec<Value> = pushes.iter().map(paint).collect();
+            respond(request, 200, json!({ "seq": seq, "paints": paints }))
+        }
+        (Method::Post, "/styles") => {
+            match body::<StylesRequest>(&mut request).and_then(|r| import_styles(app, r)) {
+                Ok(palette) => respond(request, 200, palette),
+                Err(e) => respond(request, 400, error(e)),
+            }
+        }
+        _ => respond(request, 404, error(format!("Not found: {path}"))),
+    }
+}
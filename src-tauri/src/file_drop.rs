@@ -0,0 +1,281 @@
+use crate::{color_math, extract, import, palette_file, quantize, Palette};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::Emitter;
+
+/// Colors pulled out of a dropped image.
+const IMAGE_PALETTE_SIZE: usize = 6;
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "gif", "webp", "tif", "tiff"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DropProgress {
+    pub path: String,
+    pub current: usize,
+    pub total: usize,
+}
+
+/// Position, in original image pixels, of a pixel representing one
+/// extracted color.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorMarker {
+    pub hex: String,
+    pub x: u32,
+    pub y: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DropResult {
+    pub path: String,
+    pub palette: Option<Palette>,
+    /// For dropped images, one marker per palette color in palette order
+    pub markers: Option<Vec<ColorMarker>>,
+    pub error: Option<String>,
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("Dropped palette")
+        .to_string()
+}
+
+fn is_image(path: &Path) -> bool {
+    IMAGE_EXTENSIONS.contains(&extension(path).as_str())
+}
+
+/// Dominant colors of an image file, with where each color was found.
+fn image_palette(path: &Path) -> Result<(import::ImportedPalette, Vec<ColorMarker>), String> {
+    let img = image::open(path)
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .to_rgb8();
+    let colors = extract::dominant_colors(&img, IMAGE_PALETTE_SIZE);
+    if colors.is_empty() {
+        return Err("Image contains no pixels".to_string());
+    }
+    let markers: Vec<ColorMarker> = colors
+        .into_iter()
+        .map(|c| ColorMarker {
+            hex: color_math::to_hex(c.rgb),
+            x: c.x,
+            y: c.y,
+        })
+        .collect();
+    let imported = import::ImportedPalette {
+        name: file_stem(path),
+        colors: markers.iter().map(|m| m.hex.clone()).collect(),
+    };
+    Ok((imported, markers))
+}
+
+/// Turn one dropped file into an imported palette: images go through
+/// dominant-color extraction (with where each color was found), swatch files
+/// through the matching parser.
+fn import_file(path: &Path) -> Result<(import::ImportedPalette, Option<Vec<ColorMarker>>), String> {
+    if is_image(path) {
+        let (imported, markers) = image_palette(path)?;
+        return Ok((imported, Some(markers)));
+    }
+    let ext = extension(path);
+    let name = file_stem(path);
+    let imported = match ext.as_str() {
+        "gpl" => {
+            let text =
+                std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+            import::parse_gpl(&text, &name)
+        }
+        "ase" => {
+            let data = std::fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+            import::parse_ase(&data, &name)
+        }
+        palette_file::EXTENSION => {
+            let text =
+                std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+            palette_file::parse(&text)
+        }
+        _ => Err(format!("Unsupported file type: .{ext}")),
+    }?;
+    Ok((imported, None))
+}
+
+/// Process files dropped on a window off the UI thread, emitting
+/// `file-drop-progress` before each file and `file-drop-result` after it.
+pub fn handle_drop(app: &tauri::AppHandle, paths: Vec<PathBuf>) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let total = paths.len();
+        for (i, path) in paths.iter().enumerate() {
+            let display = path.to_string_lossy().into_owned();
+            let _ = app.emit(
+                "file-drop-progress",
+                DropProgress {
+                    path: display.clone(),
+                    current: i + 1,
+                    total,
+                },
+            );
+            let result = import_file(path).and_then(|(imported, markers)| {
+                crate::save_imported_palette(&app, imported, &display).map(|p| (p, markers))
+            });
+            let (palette, markers, error) = match result {
+                Ok((p, markers)) => (Some(p), markers, None),
+                Err(e) => (None, None, Some(e)),
+            };
+            let _ = app.emit(
+                "file-drop-result",
+                DropResult {
+                    path: display,
+                    palette,
+                    markers,
+                    error,
+                },
+            );
+        }
+    });
+}
+
+/// Colors kept when a whole folder is merged into one palette.
+const MERGED_PALETTE_SIZE: usize = 12;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSummary {
+    pub folder: String,
+    pub imported: usize,
+    pub failed: usize,
+    /// The single quantized palette, in merged mode
+    pub merged: Option<Palette>,
+    /// Subfolders that couldn't be read and were left out
+    pub skipped: Vec<String>,
+}
+
+/// Image files in `dir`, and in its subfolders when `recursive`. Symlinked
+/// folders aren't followed, so a link loop can't recurse forever; subfolders
+/// that can't be read are added to `skipped`.
+fn collect_images(
+    dir: &Path,
+    recursive: bool,
+    out: &mut Vec<PathBuf>,
+    skipped: &mut Vec<String>,
+) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read folder: {}", e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            if recursive && collect_images(&path, recursive, out, skipped).is_err() {
+                skipped.push(path.to_string_lossy().into_owned());
+            }
+        } else if is_image(&path) && path.is_file() {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Extract palettes from every image in a folder off the UI thread: one
+/// palette per file, or a single palette quantized from all of them when
+/// `merged`. Emits `folder-extract-progress` before each file,
+/// `folder-extract-result` after it and `folder-extract-done` at the end.
+/// Returns the number of images found.
+pub async fn extract_folder(
+    app: &tauri::AppHandle,
+    folder: PathBuf,
+    merged: bool,
+    recursive: bool,
+) -> Result<usize, String> {
+    let root = folder.clone();
+    let (mut paths, skipped) = tauri::async_runtime::spawn_blocking(move || {
+        let mut paths = Vec::new();
+        let mut skipped = Vec::new();
+        collect_images(&root, recursive, &mut paths, &mut skipped).map(|()| (paths, skipped))
+    })
+    .await
+    .map_err(|e| format!("Failed to scan folder: {}", e))??;
+    paths.sort();
+    let found = paths.len();
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let total = paths.len();
+        let mut pool = Vec::new();
+        let mut imported = 0;
+        let mut failed = 0;
+        for (i, path) in paths.iter().enumerate() {
+            let display = path.to_string_lossy().into_owned();
+            let _ = app.emit(
+                "folder-extract-progress",
+                DropProgress {
+                    path: display.clone(),
+                    current: i + 1,
+                    total,
+                },
+            );
+            let result = image_palette(path).and_then(|(palette, markers)| {
+                if merged {
+                    for hex in &palette.colors {
+                        pool.extend(color_math::parse_hex(hex));
+                    }
+                    Ok((None, markers))
+                } else {
+                    crate::save_imported_palette(&app, palette, &display)
+                        .map(|p| (Some(p), markers))
+                }
+            });
+            let (palette, markers, error) = match result {
+                Ok((p, markers)) => {
+                    imported += 1;
+                    (p, Some(markers), None)
+                }
+                Err(e) => {
+                    failed += 1;
+                    (None, None, Some(e))
+                }
+            };
+            let _ = app.emit(
+                "folder-extract-result",
+                DropResult {
+                    path: display,
+                    palette,
+                    markers,
+                    error,
+                },
+            );
+        }
+
+        let folder_name = file_stem(&folder);
+        let merged = (merged && !pool.is_empty())
+            .then(|| {
+                let colors = quantize::kmeans_oklab(&pool, MERGED_PALETTE_SIZE);
+                let palette = import::ImportedPalette {
+                    name: folder_name,
+                    colors: colors.into_iter().map(color_math::to_hex).collect(),
+                };
+                crate::save_imported_palette(&app, palette, &folder.to_string_lossy())
+            })
+            .and_then(Result::ok);
+        let _ = app.emit(
+            "folder-extract-done",
+            FolderSummary {
+                folder: folder.to_string_lossy().into_owned(),
+                imported,
+                failed,
+                merged,
+                skipped,
+            },
+        );
+    });
+    Ok(found)
+}
@@ -0,0 +1,74 @@
+//! Windows Focus Assist: while it is holding notifications back, picks stay
+//! silent too — no toasts in the window and no spoken colors.
+//!
+//! Windows has no public API for the "Do not disturb" toggle itself. The
+//! WinRT focus-session manager (Windows 11) covers focus sessions, which turn
+//! it on, and the shell's notification state covers quiet time and
+//! presentation mode. A fullscreen app alone isn't treated as Focus Assist;
+//! muting for those is the separate `quiet_in_fullscreen` setting.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FocusAssist {
+    Off,
+    /// A Windows 11 focus session is running
+    FocusSession,
+    /// Quiet hours, e.g. right after a feature update
+    QuietTime,
+    /// Presentation mode
+    Presentation,
+}
+
+/// What Focus Assist is doing and whether picks are muted because of it.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusAssistState {
+    pub state: FocusAssist,
+    pub muted: bool,
+}
+
+pub fn current(ignore: bool) -> FocusAssistState {
+    let state = win::state();
+    FocusAssistState {
+        state,
+        muted: !ignore && state != FocusAssist::Off,
+    }
+}
+
+#[cfg(windows)]
+mod win {
+    use super::FocusAssist;
+    use windows::Win32::UI::Shell::{
+        SHQueryUserNotificationState, QUNS_PRESENTATION_MODE, QUNS_QUIET_TIME,
+    };
+    use windows::UI::Shell::FocusSessionManager;
+
+    fn focus_session_active() -> bool {
+        FocusSessionManager::IsSupported().unwrap_or(false)
+            && FocusSessionManager::GetDefault()
+                .and_then(|manager| manager.IsFocusActive())
+                .unwrap_or(false)
+    }
+
+    pub fn state() -> FocusAssist {
+        if focus_session_active() {
+            return FocusAssist::FocusSession;
+        }
+        match unsafe { SHQueryUserNotificationState() } {
+            Ok(QUNS_QUIET_TIME) => FocusAssist::QuietTime,
+            Ok(QUNS_PRESENTATION_MODE) => FocusAssist::Presentation,
+            _ => FocusAssist::Off,
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod win {
+    use super::FocusAssist;
+
+    pub fn state() -> FocusAssist {
+        FocusAssist::Off
+    }
+}
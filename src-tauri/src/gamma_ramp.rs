@@ -0,0 +1,195 @@
+//! Night-light tools such as f.lux tint the screen through the monitor's
+//! gamma ramp, which sits after the framebuffer: a capture returns the color
+//! the app drew ("as encoded"), while the panel shows it through the ramp
+//! ("as seen"). Both are reported for picks when the ramp isn't neutral.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Largest deviation from the identity ramp, in 16-bit ramp units, still
+/// treated as neutral; drivers round the default ramp differently.
+const NEUTRAL_TOLERANCE: u16 = 256;
+
+/// How long a tint reading is reused for picks on the same monitor. Picks
+/// come in bursts, and the registry read and ramp query cost more than the
+/// capture itself.
+const SHIFT_TTL: Duration = Duration::from_secs(2);
+
+struct ShiftReading {
+    at: Instant,
+    monitor: isize,
+    shift: Option<ColorShift>,
+}
+
+static LAST_SHIFT: Mutex<Option<ShiftReading>> = Mutex::new(None);
+
+/// Red, green and blue lookup tables of 256 16-bit entries each.
+#[cfg_attr(not(windows), allow(dead_code))]
+pub struct Ramp([[u16; 256]; 3]);
+
+impl Ramp {
+    pub fn is_neutral(&self) -> bool {
+        self.0.iter().all(|channel| {
+            channel
+                .iter()
+                .enumerate()
+                .all(|(i, &v)| v.abs_diff(i as u16 * 257) <= NEUTRAL_TOLERANCE)
+        })
+    }
+
+    /// Framebuffer value as the panel shows it.
+    pub fn apply(&self, rgb: [u8; 3]) -> [u8; 3] {
+        std::array::from_fn(|c| (self.0[c][rgb[c] as usize] >> 8) as u8)
+    }
+}
+
+/// Why picks at a point may not match what the user sees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ColorShift {
+    /// Windows Night Light is on
+    NightLight,
+    /// Another tool (f.lux, calibration loaders) set a non-neutral ramp
+    GammaRamp,
+}
+
+/// What is tinting the screen at a point, if anything. Night Light is
+/// checked separately since recent Windows builds apply it in the display
+/// pipeline rather than through the ramp. Readings are reused for
+/// [`SHIFT_TTL`] while picks stay on one monitor.
+pub fn shift_at(x: i32, y: i32) -> Option<ColorShift> {
+    let monitor = win::monitor_at(x, y);
+    let mut last = LAST_SHIFT.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(reading) = last
+        .as_ref()
+        .filter(|r| r.monitor == monitor && r.at.elapsed() < SHIFT_TTL)
+    {
+        return reading.shift;
+    }
+    let shift = if win::night_light_active() {
+        Some(ColorShift::NightLight)
+    } else {
+        win::read(x, y)
+            .filter(|ramp| !ramp.is_neutral())
+            .map(|_| ColorShift::GammaRamp)
+    };
+    *last = Some(ShiftReading {
+        at: Instant::now(),
+        monitor,
+        shift,
+    });
+    shift
+}
+
+/// Pick reported both ways, as `#RRGGBB`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WhitePoint {
+    /// The picked color through the ramp, as shown on the panel
+    pub seen: String,
+    /// The picked color as captured from the framebuffer, before the ramp
+    pub encoded: String,
+}
+
+/// Both readings of a color picked at a screen point, or `None` when the
+/// monitor's ramp is neutral or can't be read.
+pub fn white_point(x: i32, y: i32, rgb: [u8; 3]) -> Option<WhitePoint> {
+    let ramp = win::read(x, y)?;
+    if ramp.is_neutral() {
+        return None;
+    }
+    Some(WhitePoint {
+        seen: crate::color_math::to_hex(ramp.apply(rgb)),
+        encoded: crate::color_math::to_hex(rgb),
+    })
+}
+
+#[cfg(windows)]
+mod win {
+    use super::Ramp;
+    use windows::core::{w, PCWSTR};
+    use windows::Win32::Foundation::POINT;
+    use windows::Win32::Graphics::Gdi::{
+        CreateDCW, DeleteDC, GetMonitorInfoW, MonitorFromPoint, MONITORINFO, MONITORINFOEXW,
+        MONITOR_DEFAULTTONEAREST,
+    };
+    use windows::Win32::System::Registry::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_BINARY};
+    use windows::Win32::UI::ColorSystem::GetDeviceGammaRamp;
+
+    /// Byte of the undocumented Night Light state blob that reads 0x15 while
+    /// it is on and 0x13 while off.
+    const NIGHT_LIGHT_FLAG: usize = 18;
+    const NIGHT_LIGHT_KEY: PCWSTR = w!("Software\\Microsoft\\Windows\\CurrentVersion\\CloudStore\\Store\\DefaultAccount\\Current\\default$windows.data.bluelightreduction.bluelightreductionstate\\windows.data.bluelightreduction.bluelightreductionstate");
+
+    /// Handle of the monitor containing the point, to tell monitors apart.
+    pub fn monitor_at(x: i32, y: i32) -> isize {
+        unsafe { MonitorFromPoint(POINT { x, y }, MONITOR_DEFAULTTONEAREST).0 as isize }
+    }
+
+    /// Current gamma ramp of the monitor containing the point.
+    pub fn read(x: i32, y: i32) -> Option<Ramp> {
+        unsafe {
+            let monitor = MonitorFromPoint(POINT { x, y }, MONITOR_DEFAULTTONEAREST);
+            let mut info = MONITORINFOEXW {
+                monitorInfo: MONITORINFO {
+                    cbSize: std::mem::size_of::<MONITORINFOEXW>() as u32,
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            if !GetMonitorInfoW(monitor, &mut info.monitorInfo).as_bool() {
+                return None;
+            }
+            let hdc = CreateDCW(
+                PCWSTR(info.szDevice.as_ptr()),
+                PCWSTR(info.szDevice.as_ptr()),
+                None,
+                None,
+            );
+            if hdc.is_invalid() {
+                return None;
+            }
+            let mut ramp = [[0u16; 256]; 3];
+            let ok = GetDeviceGammaRamp(hdc, ramp.as_mut_ptr().cast()).as_bool();
+            let _ = DeleteDC(hdc);
+            ok.then_some(Ramp(ramp))
+        }
+    }
+
+    /// Night Light state from the per-user cloud store; `false` when the key
+    /// is missing (never used, or Windows before 10 1703).
+    pub fn night_light_active() -> bool {
+        let mut data = [0u8; 256];
+        let mut len = data.len() as u32;
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                NIGHT_LIGHT_KEY,
+                w!("Data"),
+                RRF_RT_REG_BINARY,
+                None,
+                Some(data.as_mut_ptr().cast()),
+                Some(&mut len as *mut u32),
+            )
+        };
+        status.is_ok() && (len as usize) > NIGHT_LIGHT_FLAG && data[NIGHT_LIGHT_FLAG] == 0x15
+    }
+}
+
+#[cfg(not(windows))]
+mod win {
+    use super::Ramp;
+
+    pub fn monitor_at(_x: i32, _y: i32) -> isize {
+        0
+    }
+
+    pub fn read(_x: i32, _y: i32) -> Option<Ramp> {
+        None
+    }
+
+    pub fn night_light_active() -> bool {
+        false
+    }
+}
@@ -0,0 +1,348 @@
+use crate::activity::{self, ActivityEntry};
+use crate::color_math;
+use crate::conversion::Representations;
+use crate::ColorEntry;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Largest page a single query may return.
+const MAX_PAGE: usize = 500;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPage {
+    pub entries: Vec<ColorEntry>,
+    /// Entries in the whole history, for sizing a virtualized list
+    pub total: usize,
+    pub offset: usize,
+    /// Offset of the following page, absent on the last one
+    pub next_offset: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HistorySort {
+    /// Stored order, newest pick first
+    #[default]
+    Recent,
+    MostUsed,
+    LastUsed,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageKind {
+    Copy,
+    Paste,
+    Export,
+}
+
+/// Slice `limit` entries (clamped to 1..=[`MAX_PAGE`], so paging always
+/// advances) starting at `offset`, after ordering them by `sort`.
+pub fn page(
+    mut entries: Vec<ColorEntry>,
+    sort: HistorySort,
+    offset: usize,
+    limit: usize,
+) -> HistoryPage {
+    // Stable sorts, so ties keep newest-first order
+    match sort {
+        HistorySort::Recent => {}
+        HistorySort::MostUsed => entries.sort_by(|a, b| b.usage_count.cmp(&a.usage_count)),
+        HistorySort::LastUsed => entries.sort_by(|a, b| b.last_used.cmp(&a.last_used)),
+    }
+    let total = entries.len();
+    let limit = limit.clamp(1, MAX_PAGE);
+    let entries: Vec<ColorEntry> = entries.into_iter().skip(offset).take(limit).collect();
+    let end = offset.saturating_add(entries.len());
+    HistoryPage {
+        entries,
+        total,
+        offset,
+        next_offset: (end < total).then_some(end),
+    }
+}
+
+/// Count one use of every entry matching `matches`.
+pub fn record_usage(
+    entries: &mut [ColorEntry],
+    matches: impl Fn(&ColorEntry) -> bool,
+    kind: UsageKind,
+    now: u64,
+) {
+    for entry in entries.iter_mut().filter(|e| matches(e)) {
+        entry.usage_count += 1;
+        match kind {
+            UsageKind::Copy => entry.usage.copy += 1,
+            UsageKind::Paste => entry.usage.paste += 1,
+            UsageKind::Export => entry.usage.export += 1,
+        }
+        entry.last_used = Some(now);
+    }
+}
+
+/// Group history entries whose colors are within `threshold` (CIEDE2000) of
+/// the group's first (newest) entry. Only groups with duplicates are returned.
+pub fn find_similar(entries: &[ColorEntry], threshold: f64) -> Vec<Vec<ColorEntry>> {
+    let mut groups: Vec<Vec<ColorEntry>> = Vec::new();
+    for entry in entries {
+        match groups
+            .iter_mut()
+            .find(|g| color_math::delta_e(g[0].rgb, entry.rgb) <= threshold)
+        {
+            Some(group) => group.push(entry.clone()),
+            None => groups.push(vec![entry.clone()]),
+        }
+    }
+    groups.retain(|g| g.len() > 1);
+    groups
+}
+
+/// Fold the entries in `ids` into `keep_id`: usage counts are summed, the
+/// latest use kept, tags unioned, and a missing label is taken from the first
+/// merged entry that has one. Merged entries are removed. Returns the consolidated entry.
+pub fn merge(
+    entries: &mut Vec<ColorEntry>,
+    ids: &[String],
+    keep_id: &str,
+) -> Result<ColorEntry, String> {
+    let keep_index = entries
+        .iter()
+        .position(|e| e.id == keep_id)
+        .ok_or_else(|| format!("Entry not found: {keep_id}"))?;
+    let merged: Vec<ColorEntry> = entries
+        .iter()
+        .filter(|e| e.id != keep_id && ids.contains(&e.id))
+        .cloned()
+        .collect();
+
+    let keep = &mut entries[keep_index];
+    for other in &merged {
+        keep.created_at = keep.created_at.min(other.created_at);
+        keep.usage_count += other.usage_count;
+        keep.usage.copy += other.usage.copy;
+        keep.usage.paste += other.usage.paste;
+        keep.usage.export += other.usage.export;
+        keep.last_used = keep.last_used.max(other.last_used);
+        for tag in &other.tags {
+            if !keep.tags.contains(tag) {
+                keep.tags.push(tag.clone());
+            }
+        }
+        if keep.label.is_none() {
+            keep.label = other.label.clone();
+        }
+        keep.pinned |= other.pinned;
+    }
+    let kept = keep.clone();
+    entries.retain(|e| e.id == keep_id || !ids.contains(&e.id));
+    Ok(kept)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneReport {
+    /// Entries picked before this are removed
+    pub cutoff: DateTime<Utc>,
+    pub removed: Vec<ColorEntry>,
+    pub kept: usize,
+}
+
+/// Remove unpinned entries picked more than `days` days before `now`. A
+/// cutoff before the earliest representable date keeps everything.
+pub fn prune_older_than(
+    entries: &mut Vec<ColorEntry>,
+    days: u32,
+    now: DateTime<Utc>,
+) -> PruneReport {
+    let cutoff = now
+        .checked_sub_signed(chrono::Duration::days(days as i64))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC);
+    let (removed, kept): (Vec<ColorEntry>, Vec<ColorEntry>) = std::mem::take(entries)
+        .into_iter()
+        .partition(|e| !e.pinned && e.created_at < cutoff);
+    *entries = kept;
+    PruneReport {
+        cutoff,
+        removed,
+        kept: entries.len(),
+    }
+}
+
+/// Drop the color representations from a serialized [`ColorEntry`], or from
+/// each one of an array. They are derived from `rgb` and recomputed on
+/// load, so stores and the journal leave them out.
+pub fn strip_representations(value: &mut serde_json::Value) {
+    let Ok(serde_json::Value::Object(derived)) = serde_json::to_value(Representations::default())
+    else {
+        return;
+    };
+    let entries: Vec<&mut serde_json::Value> = match value {
+        serde_json::Value::Array(entries) => entries.iter_mut().collect(),
+        entry => vec![entry],
+    };
+    for entry in entries.into_iter().filter_map(|e| e.as_object_mut()) {
+        for key in derived.keys() {
+            entry.remove(key);
+        }
+    }
+}
+
+/// Set `modified_at` on entries whose content (color, label, tags) differs
+/// from `previous`. New entries start out unmodified since their creation.
+pub fn stamp_modified(previous: &[ColorEntry], entries: &mut [ColorEntry], now: DateTime<Utc>) {
+    for entry in entries.iter_mut() {
+        match previous.iter().find(|p| p.id == entry.id) {
+            Some(before) => {
+                let changed = before.hex != entry.hex
+                    || before.rgb != entry.rgb
+                    || before.label != entry.label
+                    || before.tags != entry.tags;
+                entry.modified_at = if changed {
+                    Some(now)
+                } else {
+                    before.modified_at
+                };
+            }
+            None => {
+                entry.modified_at.get_or_insert(entry.created_at);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TableFormat {
+    #[default]
+    Csv,
+    Tsv,
+}
+
+const TABLE_COLUMNS: [&str; 35] = [
+    "id",
+    "hex",
+    "r",
+    "g",
+    "b",
+    "hsl_h",
+    "hsl_s",
+    "hsl_l",
+    "hsv_h",
+    "hsv_s",
+    "hsv_v",
+    "cmyk_c",
+    "cmyk_m",
+    "cmyk_y",
+    "cmyk_k",
+    "lab_l",
+    "lab_a",
+    "lab_b",
+    "oklab_l",
+    "oklab_a",
+    "oklab_b",
+    "oklch_l",
+    "oklch_c",
+    "oklch_h",
+    "label",
+    "tags",
+    "source_app",
+    "created_at",
+    "modified_at",
+    "last_used",
+    "usage_count",
+    "copies",
+    "pastes",
+    "exports",
+    "pinned",
+];
+
+fn table_field(value: &str, format: TableFormat) -> String {
+    match format {
+        TableFormat::Csv => activity::csv_field(value),
+        // TSV has no quoting, so separators inside a value become spaces
+        TableFormat::Tsv => value.replace(['\t', '\n', '\r'], " "),
+    }
+}
+
+/// Free text as a cell spreadsheets won't evaluate: values starting like a
+/// formula get a leading `'`. Only for text columns, so negative numbers
+/// stay numbers.
+fn text_cell(value: &str) -> String {
+    if value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    }
+}
+
+fn decimal(value: f64, places: usize) -> String {
+    format!("{value:.places$}")
+}
+
+/// Every entry with its color in each supported space, label, tags, the app
+/// it was picked from (looked up in `activity`), timestamps and usage, as one
+/// row per entry for spreadsheets. Timestamps are RFC 3339 in UTC; free text
+/// is guarded against formula injection.
+pub fn to_table(entries: &[ColorEntry], activity: &[ActivityEntry], format: TableFormat) -> String {
+    let separator = match format {
+        TableFormat::Csv => ",",
+        TableFormat::Tsv => "\t",
+    };
+    let mut out = TABLE_COLUMNS.join(separator);
+    out.push('\n');
+    for e in entries {
+        let hsl = color_math::rgb_to_hsl(e.rgb);
+        let hsv = color_math::rgb_to_hsv(e.rgb);
+        let cmyk = color_math::rgb_to_cmyk(e.rgb);
+        let lab = color_math::rgb_to_lab(e.rgb);
+        let oklab = color_math::rgb_to_oklab(e.rgb);
+        let oklch = color_math::oklab_to_oklch(oklab);
+        let rfc3339 = |t: DateTime<Utc>| t.to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        let created_ms = e.created_at.timestamp_millis().max(0) as u64;
+        let row = [
+            text_cell(&e.id),
+            e.hex.clone(),
+            e.rgb[0].to_string(),
+            e.rgb[1].to_string(),
+            e.rgb[2].to_string(),
+            decimal(hsl.h, 2),
+            decimal(hsl.s, 2),
+            decimal(hsl.l, 2),
+            decimal(hsv.h, 2),
+            decimal(hsv.s, 2),
+            decimal(hsv.v, 2),
+            decimal(cmyk.c, 2),
+            decimal(cmyk.m, 2),
+            decimal(cmyk.y, 2),
+            decimal(cmyk.k, 2),
+            decimal(lab.l, 3),
+            decimal(lab.a, 3),
+            decimal(lab.b, 3),
+            decimal(oklab.l, 4),
+            decimal(oklab.a, 4),
+            decimal(oklab.b, 4),
+            decimal(oklch.l, 4),
+            decimal(oklch.c, 4),
+            decimal(oklch.h, 2),
+            text_cell(e.label.as_deref().unwrap_or_default()),
+            text_cell(&e.tags.join("; ")),
+            text_cell(activity::pick_source(activity, &e.hex, created_ms).unwrap_or_default()),
+            rfc3339(e.created_at),
+            e.modified_at.map(rfc3339).unwrap_or_default(),
+            e.last_used
+                .and_then(|ms| DateTime::<Utc>::from_timestamp_millis(ms as i64))
+                .map(rfc3339)
+                .unwrap_or_default(),
+            e.usage_count.to_string(),
+            e.usage.copy.to_string(),
+            e.usage.paste.to_string(),
+            e.usage.export.to_string(),
+            e.pinned.to_string(),
+        ];
+        let row: Vec<String> = row.iter().map(|f| table_field(f, format)).collect();
+        out.push_str(&row.join(separator));
+        out.push('\n');
+    }
+    out
+}
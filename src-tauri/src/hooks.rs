@@ -0,0 +1,141 @@
+use crate::{color_math, storage, ColorInfo};
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+const DEFAULT_TIMEOUT_MS: u64 = 5000;
+/// Keep captured output bounded so a chatty script can't bloat the event.
+const MAX_OUTPUT: usize = 16 * 1024;
+
+/// A user command run after every pick. `args` may contain `{hex}`, `{r}`,
+/// `{g}`, `{b}`, `{x}` and `{y}` placeholders; the same values are also
+/// passed as `COLORSNAP_*` environment variables.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PickHook {
+    pub enabled: bool,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_timeout")]
+    pub timeout_ms: u64,
+}
+
+fn default_timeout() -> u64 {
+    DEFAULT_TIMEOUT_MS
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookResult {
+    pub hex: String,
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+    pub stdout: String,
+    pub stderr: String,
+    pub error: Option<String>,
+}
+
+fn substitute(arg: &str, color: &ColorInfo, hex: &str) -> String {
+    let [r, g, b] = color.rgb;
+    arg.replace("{hex}", hex)
+        .replace("{r}", &r.to_string())
+        .replace("{g}", &g.to_string())
+        .replace("{b}", &b.to_string())
+        .replace("{x}", &color.x.to_string())
+        .replace("{y}", &color.y.to_string())
+}
+
+fn read_capped(mut pipe: impl Read) -> String {
+    let mut buf = Vec::new();
+    let _ = pipe.by_ref().take(MAX_OUTPUT as u64).read_to_end(&mut buf);
+    // Drain the rest so the child never blocks on a full pipe
+    let _ = std::io::copy(&mut pipe, &mut std::io::sink());
+    String::from_utf8_lossy(&buf).into_owned()
+}
+
+/// Run the hook to completion or until its timeout, killing it if needed.
+fn run(hook: &PickHook, color: &ColorInfo) -> HookResult {
+    let hex = color_math::to_hex(color.rgb);
+    let mut result = HookResult {
+        hex: hex.clone(),
+        exit_code: None,
+        timed_out: false,
+        stdout: String::new(),
+        stderr: String::new(),
+        error: None,
+    };
+
+    let spawned = Command::new(&hook.command)
+        .args(hook.args.iter().map(|a| substitute(a, color, &hex)))
+        .env("COLORSNAP_HEX", &hex)
+        .env("COLORSNAP_R", color.rgb[0].to_string())
+        .env("COLORSNAP_G", color.rgb[1].to_string())
+        .env("COLORSNAP_B", color.rgb[2].to_string())
+        .env("COLORSNAP_X", color.x.to_string())
+        .env("COLORSNAP_Y", color.y.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            result.error = Some(format!("Failed to start hook: {}", e));
+            return result;
+        }
+    };
+
+    let stdout = child
+        .stdout
+        .take()
+        .map(|p| std::thread::spawn(|| read_capped(p)));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|p| std::thread::spawn(|| read_capped(p)));
+
+    let deadline = Instant::now() + Duration::from_millis(hook.timeout_ms);
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                result.exit_code = status.code();
+                break;
+            }
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                result.timed_out = true;
+                break;
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(20)),
+            Err(e) => {
+                result.error = Some(format!("Failed to wait for hook: {}", e));
+                break;
+            }
+        }
+    }
+
+    result.stdout = stdout.and_then(|t| t.join().ok()).unwrap_or_default();
+    result.stderr = stderr.and_then(|t| t.join().ok()).unwrap_or_default();
+    result
+}
+
+/// Fire the configured hook (if enabled) on a background thread and emit
+/// `pick-hook-finished` with its exit status and captured output.
+pub fn run_after_pick(app: &tauri::AppHandle, color: &ColorInfo) {
+    let Some(hook) = storage::load_settings(app).pick_hook else {
+        return;
+    };
+    if !hook.enabled || hook.command.trim().is_empty() {
+        return;
+    }
+    let app = app.clone();
+    let color = color.clone();
+    std::thread::spawn(move || {
+        let result = run(&hook, &color);
+        let _ = app.emit("pick-hook-finished", result);
+    });
+}
@@ -0,0 +1,32 @@
+//! Palette import: the parsers live in the core crate, fetching from online
+//! sources happens here.
+
+pub use pixnib_core::import::*;
+
+/// Fetch a palette from the Lospec palette-list JSON API.
+pub async fn fetch_lospec(source: &str) -> Result<ImportedPalette, String> {
+    let slug = lospec_slug(source)?;
+    let response = reqwest::get(format!("{LOSPEC_API}{slug}.json"))
+        .await
+        .map_err(|e| format!("Failed to reach Lospec: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Lospec palette \"{slug}\" not found ({})",
+            response.status()
+        ));
+    }
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read Lospec response: {}", e))?;
+    parse_lospec(&body)
+}
+
+/// Import from a coolors.co URL, a Lospec URL, or a bare Lospec slug.
+pub async fn import_from_source(source: &str) -> Result<ImportedPalette, String> {
+    if source.contains("coolors.co") {
+        parse_coolors_url(source)
+    } else {
+        fetch_lospec(source).await
+    }
+}
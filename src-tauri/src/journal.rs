@@ -0,0 +1,98 @@
+//! Append-only JSONL journal of history changes, written next to the store
+//! when enabled in settings, so scripts can tail one file instead of polling
+//! the REST API. The journal is plain text, so it is never written while the
+//! store is encrypted. Past [`MAX_BYTES`] its oldest lines are dropped.
+
+use crate::history;
+use crate::ColorEntry;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Size the journal may reach before it is cut to its newest half.
+pub const MAX_BYTES: u64 = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalOp {
+    Add,
+    Update,
+    Delete,
+}
+
+/// One line of the journal.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalLine<'a> {
+    /// Unix milliseconds of the save that made the change
+    pub at: u64,
+    pub op: JournalOp,
+    pub id: &'a str,
+    /// The entry after the change; absent for deletes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entry: Option<&'a ColorEntry>,
+}
+
+/// Journal lines turning `before` into `after`: adds and updates in the
+/// order of `after`, then deletes in the order of `before`.
+pub fn changes<'a>(
+    before: &'a [ColorEntry],
+    after: &'a [ColorEntry],
+    at: u64,
+) -> Vec<JournalLine<'a>> {
+    let old: HashMap<&str, &ColorEntry> = before.iter().map(|e| (e.id.as_str(), e)).collect();
+    let new: HashMap<&str, &ColorEntry> = after.iter().map(|e| (e.id.as_str(), e)).collect();
+
+    let mut lines = Vec::new();
+    for entry in after {
+        let op = match old.get(entry.id.as_str()) {
+            None => JournalOp::Add,
+            Some(prev) if *prev != entry => JournalOp::Update,
+            _ => continue,
+        };
+        lines.push(JournalLine {
+            at,
+            op,
+            id: &entry.id,
+            entry: Some(entry),
+        });
+    }
+    for entry in before {
+        if !new.contains_key(entry.id.as_str()) {
+            lines.push(JournalLine {
+                at,
+                op: JournalOp::Delete,
+                id: &entry.id,
+                entry: None,
+            });
+        }
+    }
+    lines
+}
+
+/// Serialize `lines` as newline-terminated JSON, ready to append.
+pub fn to_jsonl(lines: &[JournalLine]) -> Result<String, String> {
+    let mut text = String::new();
+    for line in lines {
+        let mut value = serde_json::to_value(line)
+            .map_err(|e| format!("Failed to serialize journal line: {}", e))?;
+        if let Some(entry) = value.get_mut("entry") {
+            history::strip_representations(entry);
+        }
+        text.push_str(&value.to_string());
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// The newest whole lines of `text` that fit in half of [`MAX_BYTES`].
+pub fn newest_lines(text: &str) -> &str {
+    let keep = (MAX_BYTES / 2) as usize;
+    if text.len() <= keep {
+        return text;
+    }
+    let start = text.len() - keep;
+    match text.as_bytes()[start..].iter().position(|&b| b == b'\n') {
+        Some(i) => &text[start + i + 1..],
+        None => "",
+    }
+}
@@ -0,0 +1,97 @@
+//! JSON-RPC 2.0 framing shared by the protocols served on local pipes: one
+//! request or response per line.
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// A method other than `initialize` was called first
+pub const NOT_INITIALIZED: i64 = -32002;
+/// The method ran and failed
+pub const REQUEST_FAILED: i64 = -32000;
+
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<String> for RpcError {
+    fn from(message: String) -> Self {
+        RpcError::new(REQUEST_FAILED, message)
+    }
+}
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: String,
+    /// Absent for notifications, which get no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// Deserialize a method's params; methods without required params may be
+/// called with none at all.
+pub fn params<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| RpcError::new(INVALID_PARAMS, e.to_string()))
+}
+
+pub fn to_value(value: impl serde::Serialize) -> Result<Value, RpcError> {
+    serde_json::to_value(value)
+        .map_err(|e| RpcError::from(format!("Failed to serialize result: {}", e)))
+}
+
+fn response(id: Value, result: Result<Value, RpcError>) -> String {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": e.code, "message": e.message },
+        }),
+    }
+    .to_string()
+}
+
+/// Parse one line, run `dispatch(method, params)` on it and return the
+/// response line, or `None` for notifications.
+pub fn handle(
+    line: &str,
+    dispatch: impl FnOnce(&str, Value) -> Result<Value, RpcError>,
+) -> Option<String> {
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(e) => {
+            let e = RpcError::new(PARSE_ERROR, e.to_string());
+            return Some(response(Value::Null, Err(e)));
+        }
+    };
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let request = match serde_json::from_value::<Request>(value) {
+        Ok(request) if request.jsonrpc == "2.0" => request,
+        Ok(_) => {
+            let e = RpcError::new(INVALID_REQUEST, "Only JSON-RPC 2.0 is supported");
+            return Some(response(id, Err(e)));
+        }
+        Err(e) => {
+            let e = RpcError::new(INVALID_REQUEST, e.to_string());
+            return Some(response(id, Err(e)));
+        }
+    };
+    let result = dispatch(&request.method, request.params);
+    request.id.map(|id| response(id, result))
+}
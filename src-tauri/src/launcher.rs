@@ -0,0 +1,130 @@
+//! Search shaped for launcher extensions (Raycast, Flow Launcher, ...): fuzzy
+//! matches over history and palettes, each with the text to copy. Served as
+//! the `query` method of the editor pipe and on the command line as
+//! `pixnib --query <text> [--limit <n>]`, which prints the matches as JSON.
+
+use crate::search::{self, HitKind, SearchHit};
+use crate::storage::AppSettings;
+use crate::{copy_format, editor_rpc, pipe_server, Palette};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+pub const DEFAULT_LIMIT: usize = 10;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherItem {
+    pub kind: HitKind,
+    pub id: String,
+    pub title: String,
+    /// Palette the hit belongs to, or "History"
+    pub subtitle: String,
+    pub hex: Option<String>,
+    /// Text to copy: the color in the user's copy format, or a palette's
+    /// colors comma-separated
+    pub copy: String,
+    /// The color as hex, rgb, hsl and oklch, for alternate copy actions
+    pub formats: BTreeMap<&'static str, String>,
+}
+
+fn formats(rgb: [u8; 3]) -> BTreeMap<&'static str, String> {
+    [
+        ("hex", copy_format::CopyFormat::Hex),
+        ("rgb", copy_format::CopyFormat::Rgb),
+        ("hsl", copy_format::CopyFormat::Hsl),
+        ("oklch", copy_format::CopyFormat::Oklch),
+    ]
+    .into_iter()
+    .map(|(name, format)| (name, copy_format::format(rgb, format, None)))
+    .collect()
+}
+
+fn item(hit: SearchHit, palettes: &[Palette], settings: &AppSettings) -> LauncherItem {
+    let copy_as =
+        |rgb| copy_format::format(rgb, settings.copy_format, settings.copy_template.as_deref());
+    let rgb = hit
+        .hex
+        .as_deref()
+        .and_then(|hex| crate::color_math::parse_hex(hex).ok());
+    let copy = match (rgb, hit.kind) {
+        (Some(rgb), _) => copy_as(rgb),
+        (None, HitKind::Palette) => palettes
+            .iter()
+            .find(|p| p.id == hit.id)
+            .map(|p| {
+                p.colors
+                    .iter()
+                    .filter_map(|hex| crate::color_math::parse_hex(hex).ok())
+                    .map(copy_as)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .unwrap_or_default(),
+        (None, _) => hit.hex.clone().unwrap_or_default(),
+    };
+    let subtitle = match hit.kind {
+        HitKind::History => "History".to_string(),
+        _ => hit.location.palette_name.clone().unwrap_or_default(),
+    };
+    LauncherItem {
+        kind: hit.kind,
+        id: hit.id,
+        title: hit.title,
+        subtitle,
+        formats: rgb.map(formats).unwrap_or_default(),
+        hex: hit.hex,
+        copy,
+    }
+}
+
+/// Top `limit` fuzzy matches for `query`. The search index must be current.
+pub fn query(
+    query: &str,
+    limit: usize,
+    palettes: &[Palette],
+    settings: &AppSettings,
+) -> Vec<LauncherItem> {
+    search::fuzzy_query(query, limit)
+        .into_iter()
+        .map(|hit| item(hit, palettes, settings))
+        .collect()
+}
+
+/// `pixnib --query <text> [--limit <n>]`: ask the running app over the
+/// editor pipe and return the matches as JSON.
+pub fn run_cli(args: &[String]) -> Result<String, String> {
+    let mut text = None;
+    let mut limit = DEFAULT_LIMIT;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--limit" => {
+                limit = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .ok_or("--limit needs a number")?;
+            }
+            _ if text.is_none() => text = Some(arg.clone()),
+            _ => return Err(format!("Unexpected argument: {arg}")),
+        }
+    }
+    let text = text.ok_or("Usage: pixnib --query <text> [--limit <n>]")?;
+    let requests = [
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize",
+            "params": { "protocolVersions": [1] } }),
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "query",
+            "params": { "query": text, "limit": limit } }),
+    ];
+    let responses = pipe_server::request(editor_rpc::PIPE_NAME, &requests)?;
+    for response in &responses {
+        if let Some(message) = response.pointer("/error/message").and_then(Value::as_str) {
+            return Err(message.to_string());
+        }
+    }
+    let items = responses
+        .last()
+        .and_then(|r| r.get("result"))
+        .ok_or("No response from Pixnib")?;
+    serde_json::to_string(items).map_err(|e| format!("Failed to serialize results: {}", e))
+}
@@ -1,230 +1,2379 @@
+mod activity;
+mod ambient;
+mod auto_export;
 mod color_picker;
+mod conversion_cache;
+mod diagnostics;
+mod display_watch;
+mod duplication;
+mod editor_rpc;
+mod elevation;
+mod encryption;
+mod events;
+mod figma_bridge;
+mod file_drop;
+mod focus_assist;
+mod fullscreen;
+mod gamma_ramp;
+mod history;
+mod hooks;
+mod import;
+mod journal;
+mod json_rpc;
+mod launcher;
+mod loupe;
+mod mcp;
+mod native_messaging;
+mod palette_dir;
+mod palette_versions;
+mod perf;
+mod pick_controller;
+mod pipe_server;
+mod portal;
+mod profile;
+mod quick_palette;
+mod reference;
+mod sampling;
+mod search;
+mod selftest;
+mod shortcuts;
+mod speech;
+mod state;
 mod storage;
+mod tag_rules;
+mod timestamps;
+mod window_state;
 
+pub use pixnib_core::{
+    average, color_math, color_names, contrast, contrast_grid, conversion, copy_format, cvd,
+    dark_mode, dedupe, export, exporters, extract, histogram, palette_diff, palette_file,
+    palette_lock, pdf, pipeline, png_encoder, print_gamut, quantize, share, workspace, Palette,
+};
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Mutex;
 use tauri::{
-    menu::{Menu, MenuItem},
+    menu::{Menu, MenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
     Emitter, Manager,
 };
-use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
-
-// Global state for pick mode
-static PICK_MODE_ACTIVE: AtomicBool = AtomicBool::new(false);
-
-// Stores the registered pick shortcut and its display label
-static ACTIVE_SHORTCUT: Mutex<Option<(Shortcut, String)>> = Mutex::new(None);
-
-/// Candidate shortcuts to try in order of preference (all work on Win 10 & 11)
-fn pick_shortcut_candidates() -> Vec<(Shortcut, &'static str)> {
-    vec![
-        (
-            Shortcut::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyC),
-            "Win+Shift+C",
-        ),
-        (
-            Shortcut::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyC),
-            "Ctrl+Shift+C",
-        ),
-        (
-            Shortcut::new(Some(Modifiers::SUPER | Modifiers::SHIFT), Code::KeyP),
-            "Win+Shift+P",
-        ),
-        (
-            Shortcut::new(Some(Modifiers::CONTROL | Modifiers::ALT), Code::KeyC),
-            "Ctrl+Alt+C",
-        ),
-        (
-            Shortcut::new(Some(Modifiers::SUPER | Modifiers::ALT), Code::KeyC),
-            "Win+Alt+C",
-        ),
-    ]
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Shortcut, ShortcutState};
+
+static SHUT_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Candidate pick shortcuts to try in order of preference (all work on Win 10 & 11)
+const PICK_SHORTCUT_CANDIDATES: [&str; 5] = [
+    "Win+Shift+C",
+    "Ctrl+Shift+C",
+    "Win+Shift+P",
+    "Ctrl+Alt+C",
+    "Win+Alt+C",
+];
+
+/// Time for the tray menu to close before a pick samples the screen.
+const TRAY_MENU_CLOSE_DELAY: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// How long tray-initiated countdown picks wait before sampling.
+const TRAY_PICK_COUNTDOWN: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// Longest delay `pick_after` accepts.
+const MAX_PICK_DELAY_MS: u64 = 60_000;
+
+/// How often the history retention setting is enforced after startup.
+const HISTORY_PRUNE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorInfo {
+    pub hex: String,
+    pub rgb: [u8; 3],
+    pub x: i32,
+    pub y: i32,
+    /// Suggested history label, set when auto-labelling is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// CIEDE2000 distance from the reference color, when one is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta_e: Option<f64>,
+    /// The pick as shown through a non-neutral gamma ramp (night light),
+    /// when white-point reporting is on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub white_point: Option<gamma_ramp::WhitePoint>,
+    /// Set when Night Light or a gamma ramp tints the screen, so the pick
+    /// may not match what the user sees
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_shift: Option<gamma_ramp::ColorShift>,
+    /// `hsl`, `hsv`, `cmyk` and `oklch`
+    #[serde(flatten)]
+    pub representations: conversion::Representations,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ColorEntry {
+    pub id: String,
+    pub hex: String,
+    pub rgb: [u8; 3],
+    /// When the color was picked. Stores from before RFC 3339 timestamps
+    /// have epoch milliseconds under `timestamp`; those still load.
+    #[serde(alias = "timestamp", with = "timestamps")]
+    pub created_at: DateTime<Utc>,
+    /// Last change to the color, label or tags, stamped by the backend on
+    /// save; usage tracking doesn't count as a change
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "timestamps::option"
+    )]
+    pub modified_at: Option<DateTime<Utc>>,
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub usage_count: u32,
+    /// Breakdown of `usage_count` by how the color was used
+    #[serde(default)]
+    pub usage: UsageCounts,
+    /// Milliseconds since the epoch of the most recent copy/paste/export
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<u64>,
+    /// Pinned entries are never removed by age-based pruning
+    #[serde(default)]
+    pub pinned: bool,
+    /// `hsl`, `hsv`, `cmyk` and `oklch` for the frontend, recomputed from
+    /// `rgb` whenever history is loaded or saved and never stored
+    #[serde(flatten, skip_deserializing)]
+    pub representations: conversion::Representations,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageCounts {
+    pub copy: u32,
+    pub paste: u32,
+    pub export: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BrandColor {
+    pub role: String,
+    pub hex: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BrandKit {
+    pub name: String,
+    pub colors: Vec<BrandColor>,
+    pub heading_font: String,
+    pub body_font: String,
+    pub notes: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextColor {
+    pub hex: String,
+    pub contrast: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoupeData {
+    pub colors: Vec<String>, // grid×grid hex values, row-major
+    pub hex: String,         // center pixel
+    pub x: i32,
+    pub y: i32,
+    /// Loupe sits left of / above the cursor because the default
+    /// bottom-right placement would leave the monitor
+    #[serde(default)]
+    pub flip_left: bool,
+    #[serde(default)]
+    pub flip_up: bool,
+    /// CIEDE2000 distance of the center pixel from the reference color
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta_e: Option<f64>,
+    /// Nearest CSS name of the center pixel
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oklch: Option<color_math::Oklch>,
+}
+
+/// Where to put a `size` loupe next to `cursor` on the monitor spanning
+/// `origin`..`origin + extent`: bottom-right by default, flipped per axis
+/// when that would overflow, and finally clamped onto the monitor.
+fn loupe_placement(
+    cursor: (i32, i32),
+    size: (i32, i32),
+    origin: (i32, i32),
+    extent: (i32, i32),
+) -> ((i32, i32), bool, bool) {
+    const GAP: i32 = 24;
+    let place = |c: i32, len: i32, lo: i32, span: i32| {
+        let hi = lo + span;
+        let flip = c + GAP + len > hi;
+        let pos = if flip { c - GAP - len } else { c + GAP };
+        (pos.clamp(lo, (hi - len).max(lo)), flip)
+    };
+    let (x, flip_left) = place(cursor.0, size.0, origin.0, extent.0);
+    let (y, flip_up) = place(cursor.1, size.1, origin.1, extent.1);
+    ((x, y), flip_left, flip_up)
+}
+
+/// "darkorange — chrome.exe": nearest color name plus the app under the
+/// pick point, when auto-labelling is enabled in settings.
+fn auto_label(app: &tauri::AppHandle, color: &mut ColorInfo) {
+    if !storage::load_settings(app).auto_label {
+        return;
+    }
+    let name = conversion_cache::name(app, color.rgb);
+    color.label = Some(match color_picker::app_name_at(color.x, color.y) {
+        Some(source) => format!("{name} — {source}"),
+        None => name.to_string(),
+    });
+}
+
+#[tauri::command]
+fn get_auto_label(app: tauri::AppHandle) -> bool {
+    storage::load_settings(&app).auto_label
+}
+
+#[tauri::command]
+fn set_auto_label(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.auto_label = enabled;
+    storage::save_settings(&app, &settings)
+}
+
+/// Log the pick, label the color, run the post-pick hook and emit `color-picked`.
+/// While an averaging session runs the color is collected for it instead.
+fn deliver_pick(app: &tauri::AppHandle, mut color: ColorInfo) {
+    activity::record(
+        app,
+        activity::ActivityKind::Pick,
+        vec![color.hex.clone()],
+        None,
+        color_picker::app_name_at(color.x, color.y),
+    );
+    if add_averaging_sample(app, &color) {
+        return;
+    }
+    auto_label(app, &mut color);
+    if storage::load_settings(app).white_point_report {
+        color.white_point = gamma_ramp::white_point(color.x, color.y, color.rgb);
+    }
+    hooks::run_after_pick(app, &color);
+    let pasted = paste_pick(app, &color);
+    announce(app, color.rgb, pasted.then_some("pasted"));
+    let _ = app.emit("color-picked", color);
+}
+
+/// Collect `color` for the running averaging session, if any, and emit
+/// `averaging-sample-added` with the running average. Pick mode still ends,
+/// so `pick-mode-stopped` is emitted in place of `color-picked`.
+fn add_averaging_sample(app: &tauri::AppHandle, color: &ColorInfo) -> bool {
+    let state = app.state::<state::AppState>();
+    let stats = match state.averaging().as_mut() {
+        Some(samples) => {
+            samples.push(color.rgb);
+            average::average(samples)
+        }
+        None => return false,
+    };
+    let _ = app.emit("averaging-sample-added", stats);
+    let _ = app.emit("pick-mode-stopped", ());
+    true
+}
+
+/// Speak the color if announcements are on and Focus Assist isn't muting picks.
+fn announce(app: &tauri::AppHandle, rgb: [u8; 3], outcome: Option<&str>) {
+    let settings = storage::load_settings(app);
+    if settings.announce_colors && !focus_assist::current(settings.ignore_focus_assist).muted {
+        speech::announce(speech::describe(app, rgb, outcome));
+    }
+}
+
+#[tauri::command]
+fn set_high_contrast_cursor(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.high_contrast_cursor = enabled;
+    storage::save_settings(&app, &settings)
+}
+
+/// Report picks through the monitor's gamma ramp as well, for night-light
+/// tools that tint the screen.
+#[tauri::command]
+fn set_white_point_report(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.white_point_report = enabled;
+    storage::save_settings(&app, &settings)
+}
+
+/// Serve (or stop serving) the JSON-RPC pipe for editor plugins.
+#[tauri::command]
+fn set_editor_rpc(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.editor_rpc = enabled;
+    storage::save_settings(&app, &settings)?;
+    if enabled {
+        editor_rpc::start(&app);
+    } else {
+        editor_rpc::stop();
+    }
+    Ok(())
+}
+
+/// Serve (or stop serving) the MCP pipe for AI assistants.
+#[tauri::command]
+fn set_mcp_server(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.mcp_server = enabled;
+    storage::save_settings(&app, &settings)?;
+    if enabled {
+        mcp::start(&app);
+    } else {
+        mcp::stop();
+    }
+    Ok(())
+}
+
+/// Serve (or stop serving) the HTTP bridge for the Figma plugin.
+#[tauri::command]
+fn set_figma_bridge(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    if enabled {
+        figma_bridge::start(&app)?;
+    } else {
+        figma_bridge::stop(&app);
+    }
+    let mut settings = storage::load_settings(&app);
+    settings.figma_bridge = enabled;
+    storage::save_settings(&app, &settings)
+}
+
+/// Emit the average color of the monitor under the cursor every
+/// `interval_ms` as `ambient-color` events, or with `None` stop.
+#[tauri::command]
+fn set_ambient_stream(app: tauri::AppHandle, interval_ms: Option<u64>) -> Result<(), String> {
+    match interval_ms {
+        Some(ms) => ambient::start(&app, ms)?,
+        None => ambient::stop(),
+    }
+    let mut settings = storage::load_settings(&app);
+    settings.ambient_interval_ms = interval_ms;
+    storage::save_settings(&app, &settings)
+}
+
+/// Start pairing the Figma plugin; returns the code to type into it.
+#[tauri::command]
+fn start_figma_pairing(app: tauri::AppHandle) -> Result<String, String> {
+    figma_bridge::start_pairing(&app)
+}
+
+/// Forget the paired Figma plugin; it has to pair again.
+#[tauri::command]
+fn unpair_figma(app: tauri::AppHandle) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.figma_token = None;
+    storage::save_settings(&app, &settings)
+}
+
+/// Register Pixnib as the native-messaging host of the companion browser
+/// extension, for Chrome and Edge and/or Firefox. The host relays over the
+/// editor pipe, so that has to be turned on first.
+#[tauri::command]
+fn install_browser_host(
+    app: tauri::AppHandle,
+    chrome_extension_id: Option<String>,
+    firefox_extension_id: Option<String>,
+) -> Result<(), String> {
+    if !storage::load_settings(&app).editor_rpc {
+        return Err(
+            "The browser host relays through the editor pipe; enable it (set_editor_rpc) first"
+                .to_string(),
+        );
+    }
+    native_messaging::install(
+        &storage::native_host_dir(&app)?,
+        chrome_extension_id.as_deref(),
+        firefox_extension_id.as_deref(),
+    )
+}
+
+/// Queue a color for the Figma plugin to apply to the selection.
+#[tauri::command]
+fn send_color_to_figma(app: tauri::AppHandle, hex: String) -> Result<(), String> {
+    let rgb = color_math::parse_hex(&hex)?;
+    figma_bridge::push(&app, &color_math::to_hex(rgb));
+    Ok(())
+}
+
+#[tauri::command]
+fn set_game_mode(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.game_mode = enabled;
+    storage::save_settings(&app, &settings)?;
+    color_picker::set_game_mode(enabled);
+    if !enabled {
+        duplication::reset();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn set_announce_colors(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.announce_colors = enabled;
+    storage::save_settings(&app, &settings)
+}
+
+/// Pick-and-paste: type the color, in the current copy format, into the
+/// window that was focused before picking. Failures surface as `paste-failed`.
+/// Returns whether a paste was attempted.
+fn paste_pick(app: &tauri::AppHandle, color: &ColorInfo) -> bool {
+    let settings = storage::load_settings(app);
+    if !settings.paste_after_pick {
+        return false;
+    }
+    let text = copy_format::format(
+        color.rgb,
+        settings.copy_format,
+        settings.copy_template.as_deref(),
+    );
+    let app = app.clone();
+    std::thread::spawn(move || {
+        if let Err(e) = color_picker::type_into_paste_target(&text) {
+            let _ = app.emit("paste-failed", e);
+        }
+    });
+    true
+}
+
+/// Whether the main window should come back after a pick. With
+/// pick-and-paste on, focus belongs to the app being pasted into.
+fn show_main_after_pick(app: &tauri::AppHandle) -> bool {
+    !storage::load_settings(app).paste_after_pick && !fullscreen_quiet(app)
+}
+
+/// Another app is fullscreen and the user asked not to be interrupted then:
+/// shortcuts are ignored and the main window is not popped up.
+fn fullscreen_quiet(app: &tauri::AppHandle) -> bool {
+    storage::load_settings(app).quiet_in_fullscreen && fullscreen::foreground_is_fullscreen()
+}
+
+/// Lets the frontend hold back toasts while a fullscreen app is in front.
+#[tauri::command]
+fn is_fullscreen_quiet(app: tauri::AppHandle) -> bool {
+    fullscreen_quiet(&app)
+}
+
+#[tauri::command]
+fn set_quiet_in_fullscreen(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.quiet_in_fullscreen = enabled;
+    storage::save_settings(&app, &settings)
+}
+
+/// Focus Assist's state, so the UI can hold back toasts and say why.
+#[tauri::command]
+fn get_focus_assist_state(app: tauri::AppHandle) -> focus_assist::FocusAssistState {
+    focus_assist::current(storage::load_settings(&app).ignore_focus_assist)
+}
+
+#[tauri::command]
+fn set_ignore_focus_assist(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.ignore_focus_assist = enabled;
+    storage::save_settings(&app, &settings)
+}
+
+#[tauri::command]
+fn set_paste_after_pick(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.paste_after_pick = enabled;
+    storage::save_settings(&app, &settings)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CopyFormatChanged {
+    format: copy_format::CopyFormat,
+    /// The last color in the new format, when there was one to copy
+    text: Option<String>,
+}
+
+/// Re-copy the newest history color in the current copy format and emit
+/// `color-copied` with the copied text.
+async fn copy_last_color(app: tauri::AppHandle) -> Result<String, String> {
+    let history = storage::load_color_history(&app).await?;
+    let last = history.first().ok_or("History is empty")?;
+    copy_entry(app.clone(), last).await
+}
+
+/// Copy a history entry in the saved copy format and count the use.
+async fn copy_entry(app: tauri::AppHandle, entry: &ColorEntry) -> Result<String, String> {
+    let settings = storage::load_settings(&app);
+    let text = copy_format::format(
+        entry.rgb,
+        settings.copy_format,
+        settings.copy_template.as_deref(),
+    );
+    app.clipboard()
+        .write_text(text.clone())
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    let _ = app.emit("color-copied", text.clone());
+    announce(&app, entry.rgb, Some("copied"));
+    track_usage(
+        app.clone(),
+        vec![entry.id.clone()],
+        Vec::new(),
+        history::UsageKind::Copy,
+    )
+    .await?;
+    Ok(text)
+}
+
+/// Copy a color clicked in the quick palette and close the popup.
+#[tauri::command]
+async fn quick_palette_copy(app: tauri::AppHandle, id: String) -> Result<String, String> {
+    quick_palette::hide(&app);
+    let history = storage::load_color_history(&app).await?;
+    let entry = history
+        .iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| format!("Entry not found: {id}"))?;
+    copy_entry(app.clone(), entry).await
+}
+
+/// Advance to the next copy format, re-copy the last color in it and emit
+/// `copy-format-changed` so the UI can update and show a toast.
+async fn cycle_copy_format(app: tauri::AppHandle) -> Result<copy_format::CopyFormat, String> {
+    let mut settings = storage::load_settings(&app);
+    settings.copy_format = settings.copy_format.next(settings.copy_template.is_some());
+    storage::save_settings(&app, &settings)?;
+    let text = copy_last_color(app.clone()).await.ok();
+    let _ = app.emit(
+        "copy-format-changed",
+        CopyFormatChanged {
+            format: settings.copy_format,
+            text,
+        },
+    );
+    Ok(settings.copy_format)
+}
+
+#[tauri::command]
+async fn cycle_format(app: tauri::AppHandle) -> Result<copy_format::CopyFormat, String> {
+    cycle_copy_format(app).await
+}
+
+/// Set the backend copy format and, optionally, the custom template.
+#[tauri::command]
+fn set_copy_format(
+    app: tauri::AppHandle,
+    format: copy_format::CopyFormat,
+    template: Option<String>,
+) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.copy_format = format;
+    if template.is_some() {
+        settings.copy_template = template;
+    }
+    storage::save_settings(&app, &settings)
+}
+
+/// Dispatch a global shortcut. Every action is also announced with
+/// `shortcut-triggered` so the frontend can react or show a toast.
+fn run_shortcut_action(app: &tauri::AppHandle, action: shortcuts::Action) {
+    // Leaving pick mode must always work, even if a fullscreen app took over
+    let picking = pick_controller::is_active(app);
+    if !picking && fullscreen_quiet(app) {
+        return;
+    }
+    let _ = app.emit("shortcut-triggered", action);
+    match action {
+        shortcuts::Action::Pick => {
+            match pick_controller::current(app) {
+                // The portal's picker is open and takes the click itself
+                Some(pick_controller::PickMode::Loupe | pick_controller::PickMode::Frozen)
+                    if color_picker::uses_system_picker() => {}
+                // If already in pick mode, pick the color
+                Some(pick_controller::PickMode::Loupe | pick_controller::PickMode::Frozen) => {
+                    let picked = pick_at_cursor(app).and_then(|color| {
+                        pick_controller::transition(app, pick_controller::PickEvent::Picked(color))
+                            .map_err(Into::into)
+                    });
+                    if let Err(e) = picked {
+                        let _ = app.emit("pick-failed", e);
+                    }
+                }
+                // The region overlay picks by dragging
+                Some(pick_controller::PickMode::Area) => {}
+                None => {
+                    let _ = pick_controller::transition(
+                        app,
+                        pick_controller::PickEvent::Start(pick_controller::PickMode::Loupe),
+                    );
+                }
+            }
+        }
+        shortcuts::Action::InstantPick => instant_pick(app),
+        shortcuts::Action::CopyLast => {
+            tauri::async_runtime::spawn(copy_last_color(app.clone()));
+        }
+        shortcuts::Action::ToggleWindow => {
+            if let Some(window) = app.get_webview_window("main") {
+                if window.is_visible().unwrap_or(false) {
+                    let _ = window.hide();
+                } else {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+        }
+        shortcuts::Action::CycleFormat => {
+            tauri::async_runtime::spawn(cycle_copy_format(app.clone()));
+        }
+        shortcuts::Action::QuickPalette => {
+            tauri::async_runtime::spawn(quick_palette::toggle(app.clone()));
+        }
+    }
+}
+
+/// Sample the pixel under the cursor. When every sampling path fails over a
+/// window of an elevated process, the error says so instead of a generic one.
+fn pick_at_cursor(app: &tauri::AppHandle) -> Result<ColorInfo, elevation::PickError> {
+    let mut color = color_picker::get_color_at_cursor(sampling::current(app)).map_err(|e| {
+        match color_picker::cursor_pos() {
+            Ok((x, y)) if elevation::window_at_is_elevated(x, y) => {
+                elevation::PickError::Elevated {
+                    app: color_picker::app_name_at(x, y),
+                }
+            }
+            _ => e.into(),
+        }
+    })?;
+    color.delta_e = reference::delta_e(app, color.rgb);
+    Ok(color)
+}
+
+/// Pick the pixel under the cursor without entering pick mode. Failures are
+/// emitted as `pick-failed` since there is no caller to return them to.
+fn instant_pick(app: &tauri::AppHandle) {
+    // Nothing can be read under the cursor on Wayland; open the portal picker
+    if color_picker::uses_system_picker() {
+        if let Err(e) = pick_controller::transition(
+            app,
+            pick_controller::PickEvent::Start(pick_controller::PickMode::Loupe),
+        ) {
+            let _ = app.emit("pick-failed", elevation::PickError::from(e));
+        }
+        return;
+    }
+    color_picker::remember_paste_target();
+    match pick_at_cursor(app) {
+        Ok(color) => deliver_pick(app, color),
+        Err(e) => {
+            let _ = app.emit("pick-failed", e);
+        }
+    }
+}
+
+/// Instant pick once `delay` has passed, giving time to move the cursor or
+/// open a menu or tooltip. Emits `pick-countdown` with the whole seconds left
+/// at each second boundary, and `0` right before sampling.
+fn countdown_pick(app: &tauri::AppHandle, delay: std::time::Duration) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let deadline = std::time::Instant::now() + delay;
+        loop {
+            let left = deadline.saturating_duration_since(std::time::Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            let seconds = left.as_millis().div_ceil(1000) as u64;
+            let _ = app.emit("pick-countdown", seconds);
+            std::thread::sleep(left.saturating_sub(std::time::Duration::from_secs(seconds - 1)));
+        }
+        let _ = app.emit("pick-countdown", 0);
+        instant_pick(&app);
+    });
+}
+
+#[tauri::command]
+fn get_color_at_cursor(app: tauri::AppHandle) -> Result<ColorInfo, elevation::PickError> {
+    pick_at_cursor(&app)
+}
+
+#[tauri::command]
+fn get_loupe_settings(app: tauri::AppHandle) -> loupe::LoupeSettings {
+    storage::load_settings(&app).loupe.sanitized()
+}
+
+/// Save loupe preferences and resize the loupe window to match.
+#[tauri::command]
+fn set_loupe_settings(
+    app: tauri::AppHandle,
+    loupe: loupe::LoupeSettings,
+) -> Result<loupe::LoupeSettings, String> {
+    let loupe = loupe.sanitized();
+    let mut settings = storage::load_settings(&app);
+    settings.loupe = loupe;
+    storage::save_settings(&app, &settings)?;
+    loupe::apply(&app, loupe);
+    Ok(loupe)
+}
+
+#[tauri::command]
+fn get_sampling(app: tauri::AppHandle) -> sampling::Sampling {
+    storage::load_settings(&app).sampling
+}
+
+/// Name colors in the language of a BCP 47 `locale` (the UI's), falling back
+/// to the English CSS names. Returns the language used.
+#[tauri::command]
+fn set_color_name_locale(
+    app: tauri::AppHandle,
+    locale: String,
+) -> Result<color_names::NameLocale, String> {
+    let names = color_names::NameLocale::from_tag(&locale).unwrap_or_default();
+    let mut settings = storage::load_settings(&app);
+    if settings.color_names != names {
+        settings.color_names = names;
+        storage::save_settings(&app, &settings)?;
+    }
+    conversion_cache::set_names(&app, names);
+    Ok(names)
+}
+
+/// Choose how picks read the pixels around the cursor.
+#[tauri::command]
+fn set_sampling(
+    app: tauri::AppHandle,
+    sampling: sampling::Sampling,
+) -> Result<sampling::Sampling, String> {
+    let sampling = sampling.sanitized();
+    let mut settings = storage::load_settings(&app);
+    settings.sampling = sampling;
+    storage::save_settings(&app, &settings)?;
+    sampling::set(&app, sampling);
+    Ok(sampling)
+}
+
+#[tauri::command]
+fn get_reference_color(app: tauri::AppHandle) -> Option<String> {
+    storage::load_settings(&app).reference_color
+}
+
+/// Set the color picks and the loupe are compared against, or clear it with
+/// `None`. Emits `reference-color-changed` with the normalized hex.
+#[tauri::command]
+fn set_reference_color(
+    app: tauri::AppHandle,
+    hex: Option<String>,
+) -> Result<Option<String>, String> {
+    let rgb = hex.as_deref().map(color_math::parse_hex).transpose()?;
+    let hex = rgb.map(color_math::to_hex);
+    let mut settings = storage::load_settings(&app);
+    settings.reference_color = hex.clone();
+    storage::save_settings(&app, &settings)?;
+    reference::set(&app, rgb);
+    let _ = app.emit("reference-color-changed", hex.clone());
+    Ok(hex)
+}
+
+/// Start collecting picks for an average instead of delivering them.
+#[tauri::command]
+fn start_averaging_session(app: tauri::AppHandle) -> Result<(), String> {
+    let state = app.state::<state::AppState>();
+    let mut averaging = state.averaging();
+    if averaging.is_some() {
+        return Err("An averaging session is already running".to_string());
+    }
+    *averaging = Some(Vec::new());
+    Ok(())
+}
+
+/// Running average of the current session, `None` without a session or picks.
+#[tauri::command]
+fn get_averaging_session(app: tauri::AppHandle) -> Option<average::AverageStats> {
+    let state = app.state::<state::AppState>();
+    let averaging = state.averaging();
+    averaging.as_deref().and_then(average::average)
+}
+
+#[tauri::command]
+fn cancel_averaging_session(app: tauri::AppHandle) -> Result<(), String> {
+    let state = app.state::<state::AppState>();
+    if state.averaging().take().is_none() {
+        return Err("No averaging session is running".to_string());
+    }
+    Ok(())
+}
+
+/// End the session and add the average of its picks to history, tagged
+/// `averaged`. Needs at least two picks; with fewer, or when the entry can't
+/// be saved, the session keeps running. Read variance stats from
+/// `get_averaging_session` before finishing.
+#[tauri::command]
+async fn finish_averaging_session(
+    app: tauri::AppHandle,
+    label: Option<String>,
+) -> Result<ColorEntry, String> {
+    let stats = {
+        let state = app.state::<state::AppState>();
+        let averaging = state.averaging();
+        let samples = averaging
+            .as_ref()
+            .ok_or("No averaging session is running")?;
+        if samples.len() < 2 {
+            return Err(format!(
+                "Averaging needs at least 2 picks, got {}",
+                samples.len()
+            ));
+        }
+        average::average(samples).ok_or("No picks to average")?
+    };
+    let entry = ColorEntry {
+        id: storage::generate_id(),
+        hex: stats.hex.clone(),
+        rgb: stats.rgb,
+        created_at: Utc::now(),
+        modified_at: None,
+        label: label.filter(|l| !l.trim().is_empty()),
+        tags: vec!["averaged".to_string()],
+        usage_count: 0,
+        usage: UsageCounts::default(),
+        last_used: None,
+        pinned: false,
+        representations: conversion::representations(stats.rgb),
+    };
+    storage::update_color_history(&app, |entries| {
+        entries.insert(0, entry.clone());
+        Ok(())
+    })
+    .await?;
+    *app.state::<state::AppState>().averaging() = None;
+    Ok(entry)
+}
+
+/// Capture the pixels around the cursor and move the loupe next to it.
+/// Without `grid`, the size derived from the saved loupe settings is used.
+#[tauri::command]
+fn capture_loupe(app: tauri::AppHandle, grid: Option<u32>) -> Result<LoupeData, String> {
+    let grid = grid.unwrap_or_else(loupe::current_grid);
+    let mut data = color_picker::capture_loupe_grid(grid)?;
+    if let Ok(rgb) = color_math::parse_hex(&data.hex) {
+        let converted = conversion_cache::get(&app, rgb);
+        data.name = converted.name.to_string();
+        data.oklch = Some(converted.oklch);
+        data.delta_e = reference::delta_e(&app, rgb);
+    }
+
+    // Follow the cursor, flipping to the other side near screen edges
+    if let Some(loupe) = app.get_webview_window("loupe") {
+        let size = loupe
+            .outer_size()
+            .unwrap_or_else(|_| tauri::PhysicalSize::new(160, 190));
+        let size = (size.width as i32, size.height as i32);
+        let (origin, extent) = match app.monitor_from_point(data.x as f64, data.y as f64) {
+            Ok(Some(monitor)) => {
+                let (mpos, msize) = (monitor.position(), monitor.size());
+                ((mpos.x, mpos.y), (msize.width as i32, msize.height as i32))
+            }
+            // Unknown monitor: never flip or clamp
+            _ => ((i32::MIN / 2, i32::MIN / 2), (i32::MAX, i32::MAX)),
+        };
+        let ((nx, ny), flip_left, flip_up) =
+            loupe_placement((data.x, data.y), size, origin, extent);
+        data.flip_left = flip_left;
+        data.flip_up = flip_up;
+        let _ = loupe.set_position(tauri::PhysicalPosition::new(nx, ny));
+    }
+
+    Ok(data)
+}
+
+/// Legible label color for a swatch background, by WCAG relative luminance.
+#[tauri::command]
+fn text_color_for(bg: String, tuned: Option<bool>) -> Result<TextColor, String> {
+    let rgb = color_math::parse_hex(&bg)?;
+    let text = color_math::readable_text_color(rgb, tuned.unwrap_or(false));
+    Ok(TextColor {
+        hex: color_math::to_hex(text),
+        contrast: color_math::contrast_ratio(rgb, text),
+    })
+}
+
+/// Contrast of every color against white, black and optional extra
+/// backgrounds, as a compact pass/fail table.
+#[tauri::command]
+fn check_palette_contrast(
+    colors: Vec<String>,
+    backgrounds: Option<Vec<String>>,
+) -> Result<contrast::ContrastTable, String> {
+    let parse = |list: &[String]| {
+        list.iter()
+            .map(|hex| color_math::parse_hex(hex))
+            .collect::<Result<Vec<_>, _>>()
+    };
+    let colors = parse(&colors)?;
+    let backgrounds = parse(&backgrounds.unwrap_or_default())?;
+    Ok(contrast::contrast_table(&colors, &backgrounds))
+}
+
+/// A CSS color (hex, `rgb()`, `oklab()` or `oklch()`) in every supported
+/// space, flagging OKLab/OKLCH input that had to be clipped into sRGB.
+#[tauri::command]
+fn convert_color(color: String) -> Result<conversion::Conversion, String> {
+    conversion::convert(&color)
+}
+
+/// How a color will reproduce in CMYK print, with the closest printable
+/// alternative when it falls well outside the press gamut.
+#[tauri::command]
+fn check_print_safety(
+    hex: String,
+    profile: Option<print_gamut::PrintProfile>,
+) -> Result<print_gamut::PrintSafety, String> {
+    let rgb = color_math::parse_hex(&hex)?;
+    Ok(print_gamut::check(rgb, profile.unwrap_or_default()))
+}
+
+/// Hue and lightness distribution of the monitor under the cursor.
+#[tauri::command]
+async fn get_screen_histogram(
+    hue_buckets: Option<usize>,
+    lightness_buckets: Option<usize>,
+) -> Result<histogram::Histogram, String> {
+    let img = color_picker::capture_active_monitor()?;
+    Ok(histogram::histogram(
+        &img,
+        hue_buckets.unwrap_or(histogram::DEFAULT_HUE_BUCKETS),
+        lightness_buckets.unwrap_or(histogram::DEFAULT_LIGHTNESS_BUCKETS),
+    ))
+}
+
+/// Colors returned by `get_window_dominant_color` unless asked for more or fewer
+const WINDOW_COLORS: usize = 5;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WindowColors {
+    /// Executable name on Windows, window class on X11
+    app: Option<String>,
+    title: String,
+    /// The most common color, same as the first of `colors`
+    dominant: String,
+    /// Most common first
+    colors: Vec<String>,
+}
+
+/// Dominant colors of the frontmost window of another app, for per-app
+/// accent theming or overlays. Pixnib's own windows are skipped.
+#[tauri::command]
+async fn get_window_dominant_color(count: Option<usize>) -> Result<WindowColors, String> {
+    let window = color_picker::capture_foreground_window()?;
+    let colors: Vec<String> =
+        extract::dominant_colors(&window.image, count.unwrap_or(WINDOW_COLORS).max(1))
+            .iter()
+            .map(|c| color_math::to_hex(c.rgb))
+            .collect();
+    let dominant = colors.first().cloned().ok_or("The window is empty")?;
+    Ok(WindowColors {
+        app: window.app,
+        title: window.title,
+        dominant,
+        colors,
+    })
+}
+
+/// Mean and median color of an image file, plus brightness and dominant hue.
+#[tauri::command]
+async fn get_image_average(path: String) -> Result<extract::ImageAverage, String> {
+    let img = image::open(&path)
+        .map_err(|e| format!("Failed to open image: {}", e))?
+        .to_rgb8();
+    Ok(extract::image_average(&img))
+}
+
+/// Extract a palette from every image in a folder (or one merged palette),
+/// reporting progress through `folder-extract-*` events.
+#[tauri::command]
+async fn extract_folder_palettes(
+    app: tauri::AppHandle,
+    folder: String,
+    merged: Option<bool>,
+    recursive: Option<bool>,
+) -> Result<usize, String> {
+    file_drop::extract_folder(
+        &app,
+        std::path::PathBuf::from(folder),
+        merged.unwrap_or(false),
+        recursive.unwrap_or(false),
+    )
+    .await
+}
+
+#[tauri::command]
+async fn save_color_history(app: tauri::AppHandle, colors: Vec<ColorEntry>) -> Result<(), String> {
+    storage::save_color_history(&app, &colors).await
+}
+
+/// Entries kept in history; adding past this drops the oldest.
+const MAX_HISTORY: usize = 100;
+
+/// Add a picked color to the front of history. The backend assigns the id
+/// and timestamp, so usage counts and other entries are never overwritten.
+#[tauri::command]
+async fn add_history_entry(
+    app: tauri::AppHandle,
+    hex: String,
+    label: Option<String>,
+) -> Result<ColorEntry, String> {
+    let rgb = color_math::parse_hex(&hex)?;
+    let entry = ColorEntry {
+        id: storage::generate_id(),
+        hex: color_math::to_hex(rgb),
+        rgb,
+        created_at: Utc::now(),
+        modified_at: None,
+        label: label.filter(|l| !l.trim().is_empty()),
+        tags: Vec::new(),
+        usage_count: 0,
+        usage: UsageCounts::default(),
+        last_used: None,
+        pinned: false,
+        representations: conversion::representations(rgb),
+    };
+    storage::update_color_history(&app, |entries| {
+        entries.insert(0, entry.clone());
+        entries.truncate(MAX_HISTORY);
+        Ok(())
+    })
+    .await?;
+    Ok(entry)
+}
+
+#[tauri::command]
+async fn remove_history_entries(app: tauri::AppHandle, ids: Vec<String>) -> Result<(), String> {
+    storage::update_color_history(&app, |entries| {
+        entries.retain(|e| !ids.contains(&e.id));
+        Ok(())
+    })
+    .await
+}
+
+/// Set or, with an empty label, clear the label of one history entry.
+#[tauri::command]
+async fn set_entry_label(app: tauri::AppHandle, id: String, label: String) -> Result<(), String> {
+    storage::update_color_history(&app, |entries| {
+        let entry = entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .ok_or("History entry not found")?;
+        entry.label = Some(label).filter(|l| !l.trim().is_empty());
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+async fn clear_color_history(app: tauri::AppHandle) -> Result<(), String> {
+    storage::update_color_history(&app, |entries| {
+        entries.clear();
+        Ok(())
+    })
+    .await
+}
+
+#[tauri::command]
+async fn load_color_history(app: tauri::AppHandle) -> Result<Vec<ColorEntry>, String> {
+    storage::load_color_history(&app).await
+}
+
+/// One page of history (newest first) plus the total count, so the list can
+/// be virtualized instead of loading every entry up front.
+#[tauri::command]
+async fn query_color_history(
+    app: tauri::AppHandle,
+    offset: usize,
+    limit: usize,
+    sort: Option<history::HistorySort>,
+) -> Result<history::HistoryPage, String> {
+    let entries = storage::load_color_history(&app).await?;
+    Ok(history::page(
+        entries,
+        sort.unwrap_or_default(),
+        offset,
+        limit,
+    ))
+}
+
+/// Bump usage counters of history entries matching `ids` or `hexes`.
+async fn track_usage(
+    app: tauri::AppHandle,
+    ids: Vec<String>,
+    hexes: Vec<String>,
+    kind: history::UsageKind,
+) -> Result<(), String> {
+    let matches = |e: &ColorEntry| {
+        ids.contains(&e.id) || hexes.iter().any(|h| h.eq_ignore_ascii_case(&e.hex))
+    };
+    storage::update_color_history(&app, |entries| {
+        history::record_usage(entries, matches, kind, storage::now_millis());
+        Ok(())
+    })
+    .await
+}
+
+/// Record that the frontend used a color (e.g. pasted it somewhere) without
+/// going through a backend copy/export path.
+#[tauri::command]
+async fn record_color_usage(
+    app: tauri::AppHandle,
+    id: String,
+    kind: history::UsageKind,
+) -> Result<(), String> {
+    track_usage(app, vec![id], Vec::new(), kind).await
+}
+
+/// Copy `text` (a color in the user's chosen format) to the clipboard and
+/// count it as a copy of history entry `id`, when given.
+#[tauri::command]
+async fn copy_color(app: tauri::AppHandle, text: String, id: Option<String>) -> Result<(), String> {
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    if let Some(id) = id {
+        track_usage(app, vec![id], Vec::new(), history::UsageKind::Copy).await?;
+    }
+    Ok(())
+}
+
+/// Groups of history entries within `threshold` delta-E (CIEDE2000) of each
+/// other, for cleaning up near-duplicate picks.
+#[tauri::command]
+async fn find_similar_entries(
+    app: tauri::AppHandle,
+    threshold: f64,
+) -> Result<Vec<Vec<ColorEntry>>, String> {
+    let entries = storage::load_color_history(&app).await?;
+    Ok(history::find_similar(&entries, threshold))
+}
+
+/// Consolidate the entries in `ids` into `keep_id` and save the history.
+#[tauri::command]
+async fn merge_entries(
+    app: tauri::AppHandle,
+    ids: Vec<String>,
+    keep_id: String,
+) -> Result<ColorEntry, String> {
+    storage::update_color_history(&app, |entries| history::merge(entries, &ids, &keep_id)).await
+}
+
+/// Pin or unpin history entries; pinned entries survive age-based pruning.
+#[tauri::command]
+async fn set_entries_pinned(
+    app: tauri::AppHandle,
+    ids: Vec<String>,
+    pinned: bool,
+) -> Result<(), String> {
+    storage::update_color_history(&app, |entries| {
+        for entry in entries.iter_mut().filter(|e| ids.contains(&e.id)) {
+            entry.pinned = pinned;
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// Delete unpinned entries older than the retention setting, if one is set.
+async fn prune_history(app: &tauri::AppHandle) -> Result<Option<history::PruneReport>, String> {
+    let Some(days) = storage::load_settings(app).history_retention_days else {
+        return Ok(None);
+    };
+    storage::update_color_history(app, |entries| {
+        Ok(Some(history::prune_older_than(entries, days, Utc::now())))
+    })
+    .await
+}
+
+/// Enforce history retention now and then every [`HISTORY_PRUNE_INTERVAL`].
+fn schedule_history_pruning(app: &tauri::AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        if let Err(e) = tauri::async_runtime::block_on(prune_history(&app)) {
+            eprintln!("Failed to prune history: {}", e);
+        }
+        std::thread::sleep(HISTORY_PRUNE_INTERVAL);
+    });
+}
+
+/// Set (or with `None`, turn off) how many days unpinned history is kept.
+/// Takes effect at the next scheduled pruning.
+#[tauri::command]
+fn set_history_retention(app: tauri::AppHandle, days: Option<u32>) -> Result<(), String> {
+    if days == Some(0) {
+        return Err("Retention must be at least one day".to_string());
+    }
+    let mut settings = storage::load_settings(&app);
+    settings.history_retention_days = days;
+    storage::save_settings(&app, &settings)
+}
+
+/// Dry run of age-based pruning: what `days` (or the saved retention) would
+/// remove right now. Nothing is deleted.
+#[tauri::command]
+async fn preview_history_pruning(
+    app: tauri::AppHandle,
+    days: Option<u32>,
+) -> Result<history::PruneReport, String> {
+    let days = days
+        .or(storage::load_settings(&app).history_retention_days)
+        .ok_or("No history retention is set")?;
+    let mut entries = storage::load_color_history(&app).await?;
+    Ok(history::prune_older_than(&mut entries, days, Utc::now()))
+}
+
+/// Save the active workspace's palettes; other workspaces are left as they are.
+/// Refused if it would edit or remove a locked palette.
+#[tauri::command]
+async fn save_palettes(
+    app: tauri::AppHandle,
+    palettes: Vec<Palette>,
+) -> Result<(), palette_lock::PaletteError> {
+    let active = storage::load_settings(&app).active_workspace;
+    let stored = storage::load_palettes(&app);
+    palette_lock::check_save(&stored, &palettes, active.as_deref())?;
+    let all = workspace::replace_workspace_palettes(stored, active.as_deref(), palettes);
+    Ok(storage::save_palettes(&app, &all)?)
+}
+
+/// Lock or unlock a palette; the only way to change a locked palette.
+#[tauri::command]
+async fn set_palette_locked(
+    app: tauri::AppHandle,
+    palette_id: String,
+    locked: bool,
+) -> Result<Palette, String> {
+    let mut palettes = storage::load_palettes(&app);
+    let palette = palettes
+        .iter_mut()
+        .find(|p| p.id == palette_id)
+        .ok_or_else(|| format!("Palette not found: {palette_id}"))?;
+    palette.locked = locked;
+    let palette = palette.clone();
+    storage::save_palettes(&app, &palettes)?;
+    Ok(palette)
+}
+
+/// Earlier states of a palette, newest first, including palettes that have
+/// since been deleted.
+#[tauri::command]
+fn list_palette_versions(
+    app: tauri::AppHandle,
+    palette_id: String,
+) -> Result<Vec<palette_versions::PaletteVersion>, String> {
+    Ok(palette_versions::list(
+        &storage::load_palette_versions(&app)?,
+        &palette_id,
+    ))
+}
+
+/// Put a palette back the way it was at `version`, re-creating it if it was
+/// deleted. The state being replaced becomes a version of its own, so a
+/// restore can be undone the same way.
+#[tauri::command]
+async fn restore_palette_version(
+    app: tauri::AppHandle,
+    palette_id: String,
+    version: u32,
+) -> Result<Palette, palette_lock::PaletteError> {
+    let restored =
+        palette_versions::find(&storage::load_palette_versions(&app)?, &palette_id, version)
+            .ok_or_else(|| format!("Version {version} of palette {palette_id} not found"))?;
+    let mut palettes = storage::load_palettes(&app);
+    match palettes.iter_mut().find(|p| p.id == palette_id) {
+        Some(current) => {
+            palette_lock::ensure_unlocked(current)?;
+            *current = restored.clone();
+        }
+        None => palettes.push(restored.clone()),
+    }
+    storage::save_palettes(&app, &palettes)?;
+    Ok(restored)
+}
+
+/// Entry counts and on-disk sizes of the data directory, including the trash
+/// of deleted palettes that are still restorable.
+#[tauri::command]
+async fn get_storage_info(app: tauri::AppHandle) -> Result<storage::StorageInfo, String> {
+    storage::storage_info(&app).await
+}
+
+/// Empty the trash and write pending changes, then report the new sizes.
+#[tauri::command]
+async fn compact_storage(app: tauri::AppHandle) -> Result<storage::StorageInfo, String> {
+    storage::compact(&app)?;
+    storage::storage_info(&app).await
+}
+
+#[tauri::command]
+fn get_palette_dir(app: tauri::AppHandle) -> Option<String> {
+    storage::load_settings(&app).palette_dir
+}
+
+/// Keep every palette as a plain-text file in `dir`, or stop with `None`.
+/// Palette files already in `dir` are merged in and the rest written right
+/// away; like the history journal this can't be enabled while the store is
+/// encrypted.
+#[tauri::command]
+fn set_palette_dir(app: tauri::AppHandle, dir: Option<String>) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    let dir = dir.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+    if dir.is_some() && settings.store_encryption != encryption::StoreEncryption::None {
+        return Err("The palette directory can't be used while the store is encrypted".to_string());
+    }
+    settings.palette_dir = dir;
+    storage::save_settings(&app, &settings)?;
+    storage::sync_palette_dir(&app).map(|_| ())
+}
+
+/// Pick up palette files changed in the palette directory since the app
+/// started, e.g. after a `git pull`.
+#[tauri::command]
+fn sync_palette_dir(app: tauri::AppHandle) -> Result<(), String> {
+    storage::sync_palette_dir(&app).map(|_| ())
+}
+
+/// Palettes of the active workspace.
+#[tauri::command]
+async fn load_palettes(app: tauri::AppHandle) -> Result<Vec<Palette>, String> {
+    let active = storage::load_settings(&app).active_workspace;
+    Ok(storage::load_palettes(&app)
+        .into_iter()
+        .filter(|p| workspace::in_workspace(p, active.as_deref()))
+        .collect())
+}
+
+#[tauri::command]
+fn list_workspaces(app: tauri::AppHandle) -> Vec<workspace::Workspace> {
+    storage::load_workspaces(&app)
+}
+
+#[tauri::command]
+fn create_workspace(app: tauri::AppHandle, name: String) -> Result<workspace::Workspace, String> {
+    let mut workspaces = storage::load_workspaces(&app);
+    let created = workspace::Workspace {
+        id: storage::generate_id(),
+        name: name.trim().to_string(),
+        export_defaults: Default::default(),
+    };
+    workspaces.push(created.clone());
+    storage::save_workspaces(&app, &workspaces)?;
+    Ok(created)
+}
+
+#[tauri::command]
+fn rename_workspace(app: tauri::AppHandle, id: String, name: String) -> Result<(), String> {
+    let mut workspaces = storage::load_workspaces(&app);
+    let target = workspaces
+        .iter_mut()
+        .find(|w| w.id == id)
+        .ok_or_else(|| format!("Workspace not found: {id}"))?;
+    target.name = name.trim().to_string();
+    storage::save_workspaces(&app, &workspaces)
+}
+
+#[tauri::command]
+fn set_workspace_export_defaults(
+    app: tauri::AppHandle,
+    id: String,
+    defaults: workspace::ExportDefaults,
+) -> Result<(), String> {
+    let mut workspaces = storage::load_workspaces(&app);
+    let target = workspaces
+        .iter_mut()
+        .find(|w| w.id == id)
+        .ok_or_else(|| format!("Workspace not found: {id}"))?;
+    target.export_defaults = defaults;
+    storage::save_workspaces(&app, &workspaces)
+}
+
+/// Delete a workspace. Its palettes move to the default workspace rather than
+/// being lost; if it was active, the default workspace becomes active.
+#[tauri::command]
+fn delete_workspace(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let mut workspaces = storage::load_workspaces(&app);
+    workspaces.retain(|w| w.id != id);
+    storage::save_workspaces(&app, &workspaces)?;
+
+    let mut palettes = storage::load_palettes(&app);
+    for p in palettes.iter_mut() {
+        if p.workspace_id.as_deref() == Some(id.as_str()) {
+            p.workspace_id = None;
+        }
+    }
+    storage::save_palettes(&app, &palettes)?;
+
+    let mut settings = storage::load_settings(&app);
+    if settings.active_workspace.as_deref() == Some(id.as_str()) {
+        settings.active_workspace = None;
+        storage::save_settings(&app, &settings)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_active_workspace(app: tauri::AppHandle) -> Option<String> {
+    storage::load_settings(&app).active_workspace
+}
+
+/// Switch workspace (`None` = default) and emit `workspace-changed` so the
+/// frontend reloads its palettes.
+#[tauri::command]
+fn set_active_workspace(app: tauri::AppHandle, id: Option<String>) -> Result<(), String> {
+    if let Some(id) = &id {
+        if !storage::load_workspaces(&app).iter().any(|w| &w.id == id) {
+            return Err(format!("Workspace not found: {id}"));
+        }
+    }
+    let mut settings = storage::load_settings(&app);
+    settings.active_workspace = id.clone();
+    storage::save_settings(&app, &settings)?;
+    let _ = app.emit("workspace-changed", id);
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DerivedPalette {
+    palette: Palette,
+    /// Per color, in order: whether it had to be clipped into sRGB and the
+    /// values before clipping
+    gamut: Vec<color_math::GamutMapped>,
+}
+
+/// Derive a dark-theme version of a palette and save it linked to the source.
+/// Re-deriving updates the existing linked dark palette instead of adding
+/// another one.
+#[tauri::command]
+async fn derive_dark_palette(
+    app: tauri::AppHandle,
+    palette_id: String,
+) -> Result<DerivedPalette, palette_lock::PaletteError> {
+    let mut palettes = storage::load_palettes(&app);
+    let source = palettes
+        .iter()
+        .find(|p| p.id == palette_id)
+        .ok_or_else(|| format!("Palette not found: {palette_id}"))?;
+    let gamut = dark_mode::dark_palette_mapped(&export::palette_rgb(source)?);
+    let colors = gamut.iter().map(|g| color_math::to_hex(g.rgb)).collect();
+    let name = format!("{} (Dark)", source.name);
+    let workspace_id = source.workspace_id.clone();
+
+    let dark = match palettes
+        .iter_mut()
+        .find(|p| p.dark_variant_of.as_deref() == Some(palette_id.as_str()))
+    {
+        Some(existing) => {
+            palette_lock::ensure_unlocked(existing)?;
+            existing.colors = colors;
+            existing.clone()
+        }
+        None => {
+            let dark = Palette {
+                id: storage::generate_id(),
+                name,
+                colors,
+                dark_variant_of: Some(palette_id),
+                workspace_id,
+                locked: false,
+                export: None,
+            };
+            palettes.insert(0, dark.clone());
+            dark
+        }
+    };
+    storage::save_palettes(&app, &palettes)?;
+    Ok(DerivedPalette {
+        palette: dark,
+        gamut,
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CvdAdjustment {
+    /// The palette with the proposed colors; not saved
+    palette: Palette,
+    report: cvd::CvdReport,
+}
+
+/// Propose the smallest hue and lightness changes that keep a palette's
+/// colors distinguishable with the given color vision deficiency. Nothing is
+/// saved; the frontend applies the proposal through `save_palettes`.
+#[tauri::command]
+async fn adjust_palette_for_cvd(
+    app: tauri::AppHandle,
+    palette_id: String,
+    cvd: cvd::CvdType,
+    min_distance: Option<f64>,
+) -> Result<CvdAdjustment, String> {
+    let mut palette = storage::load_palettes(&app)
+        .into_iter()
+        .find(|p| p.id == palette_id)
+        .ok_or_else(|| format!("Palette not found: {palette_id}"))?;
+    let colors = export::palette_rgb(&palette)?;
+    let min_distance = min_distance.unwrap_or(cvd::DEFAULT_MIN_DISTANCE);
+    let report =
+        tauri::async_runtime::spawn_blocking(move || cvd::adjust(&colors, cvd, min_distance))
+            .await
+            .map_err(|e| format!("Palette adjustment failed: {}", e))??;
+    palette.colors = report.colors.clone();
+    Ok(CvdAdjustment { palette, report })
+}
+
+/// Write paired light/dark CSS variables for a palette. Uses the linked dark
+/// palette when one was derived, otherwise derives it on the fly and returns
+/// the gamut outcome of each derived color (empty for a linked palette).
+#[tauri::command]
+async fn export_dual_css(
+    app: tauri::AppHandle,
+    palette_id: String,
+    selector: export::DarkSelector,
+    path: String,
+) -> Result<Vec<color_math::GamutMapped>, String> {
+    let palettes = storage::load_palettes(&app);
+    let light = palettes
+        .iter()
+        .find(|p| p.id == palette_id)
+        .ok_or_else(|| format!("Palette not found: {palette_id}"))?;
+    let (dark, gamut) = match palettes
+        .iter()
+        .find(|p| p.dark_variant_of.as_deref() == Some(palette_id.as_str()))
+    {
+        Some(dark) => (dark.clone(), Vec::new()),
+        None => {
+            let gamut = dark_mode::dark_palette_mapped(&export::palette_rgb(light)?);
+            let dark = Palette {
+                colors: gamut.iter().map(|g| color_math::to_hex(g.rgb)).collect(),
+                dark_variant_of: Some(palette_id.clone()),
+                ..light.clone()
+            };
+            (dark, gamut)
+        }
+    };
+    let css = export::dual_css(light, &dark, selector)?;
+    std::fs::write(&path, css).map_err(|e| format!("Failed to write file: {}", e))?;
+    log_export(&app, light, &path);
+    Ok(gamut)
+}
+
+/// Reduce a large palette to `n` representative colors (k-means in OKLab) and
+/// save the result as a new palette.
+#[tauri::command]
+async fn quantize_palette(
+    app: tauri::AppHandle,
+    palette_id: String,
+    n: usize,
+) -> Result<Palette, String> {
+    let mut palettes = storage::load_palettes(&app);
+    let source = palettes
+        .iter()
+        .find(|p| p.id == palette_id)
+        .ok_or_else(|| format!("Palette not found: {palette_id}"))?;
+    let reduced = quantize::kmeans_oklab(&export::palette_rgb(source)?, n);
+    let palette = Palette {
+        id: storage::generate_id(),
+        name: format!("{} ({} colors)", source.name, reduced.len()),
+        colors: reduced.into_iter().map(color_math::to_hex).collect(),
+        dark_variant_of: None,
+        workspace_id: source.workspace_id.clone(),
+        locked: false,
+        export: None,
+    };
+    palettes.insert(0, palette.clone());
+    storage::save_palettes(&app, &palettes)?;
+    Ok(palette)
+}
+
+#[tauri::command]
+async fn save_brand_kit(app: tauri::AppHandle, kit: BrandKit) -> Result<(), String> {
+    storage::save_brand_kit(&app, &kit)
+}
+
+#[tauri::command]
+async fn load_brand_kit(app: tauri::AppHandle) -> Result<Option<BrandKit>, String> {
+    Ok(storage::load_brand_kit(&app))
+}
+
+/// Largest delta-E accepted for collapsing near-identical colors; beyond
+/// this, clearly different colors would be merged.
+const MAX_DEDUPE_THRESHOLD: f64 = 20.0;
+/// Roughly a just-noticeable difference in CIEDE2000.
+const DEFAULT_DEDUPE_THRESHOLD: f64 = 1.0;
+
+fn check_dedupe_threshold(threshold: f64) -> Result<(), String> {
+    if !(0.0..=MAX_DEDUPE_THRESHOLD).contains(&threshold) {
+        return Err(format!(
+            "Delta-E threshold must be between 0 and {}",
+            MAX_DEDUPE_THRESHOLD
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PaletteDeduplicated {
+    palette_id: String,
+    report: dedupe::DedupReport,
+}
+
+/// Save an imported palette as a new named palette, log where it came from
+/// and notify the frontend with `palette-imported`. With import dedup on,
+/// near-identical colors are collapsed first and what was merged is reported
+/// with `palette-deduplicated`.
+fn save_imported_palette(
+    app: &tauri::AppHandle,
+    imported: import::ImportedPalette,
+    source: &str,
+) -> Result<Palette, String> {
+    let settings = storage::load_settings(app);
+    let (colors, report) = match settings.import_dedupe {
+        Some(threshold) => {
+            let (colors, report) = dedupe::dedupe(&imported.colors, threshold);
+            (colors, Some(report).filter(|r| !r.merged.is_empty()))
+        }
+        None => (imported.colors, None),
+    };
+    let palette = Palette {
+        id: storage::generate_id(),
+        name: imported.name,
+        colors,
+        dark_variant_of: None,
+        workspace_id: settings.active_workspace,
+        locked: false,
+        export: None,
+    };
+    let mut palettes = storage::load_palettes(app);
+    palettes.insert(0, palette.clone());
+    storage::save_palettes(app, &palettes)?;
+    activity::record(
+        app,
+        activity::ActivityKind::Import,
+        palette.colors.clone(),
+        Some(palette.name.clone()),
+        Some(source.to_string()),
+    );
+    let _ = app.emit("palette-imported", palette.clone());
+    if let Some(report) = report {
+        let _ = app.emit(
+            "palette-deduplicated",
+            PaletteDeduplicated {
+                palette_id: palette.id.clone(),
+                report,
+            },
+        );
+    }
+    Ok(palette)
+}
+
+/// Turn on collapsing of imported colors within `threshold` delta-E
+/// (CIEDE2000) of an earlier color in the palette, or with `None` turn it off.
+#[tauri::command]
+fn set_import_dedupe(app: tauri::AppHandle, threshold: Option<f64>) -> Result<(), String> {
+    if let Some(threshold) = threshold {
+        check_dedupe_threshold(threshold)?;
+    }
+    let mut settings = storage::load_settings(&app);
+    settings.import_dedupe = threshold;
+    storage::save_settings(&app, &settings)
+}
+
+#[tauri::command]
+fn list_auto_exports(app: tauri::AppHandle) -> Vec<auto_export::AutoExport> {
+    storage::load_settings(&app).auto_exports
+}
+
+/// Keep `target.path` exported from its palette from now on. The file is
+/// written right away, so a bad exporter id or path is reported here rather
+/// than on the next change.
+#[tauri::command]
+fn add_auto_export(
+    app: tauri::AppHandle,
+    mut target: auto_export::AutoExport,
+) -> Result<auto_export::AutoExport, String> {
+    let palette = storage::load_palettes(&app)
+        .into_iter()
+        .find(|p| p.id == target.palette_id)
+        .ok_or_else(|| format!("Palette not found: {}", target.palette_id))?;
+    auto_export::write(&load_exporters(&app)?, &target, &palette)?;
+    target.id = storage::generate_id();
+    let mut settings = storage::load_settings(&app);
+    settings.auto_exports.push(target.clone());
+    storage::save_settings(&app, &settings)?;
+    Ok(target)
+}
+
+/// Stop updating a target; the file already written is left in place.
+#[tauri::command]
+fn delete_auto_export(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    let before = settings.auto_exports.len();
+    settings.auto_exports.retain(|t| t.id != id);
+    if settings.auto_exports.len() == before {
+        return Err(format!("Auto-export not found: {}", id));
+    }
+    storage::save_settings(&app, &settings)
+}
+
+#[tauri::command]
+fn list_tag_rules(app: tauri::AppHandle) -> Vec<tag_rules::TagRule> {
+    storage::load_settings(&app).tag_rules
+}
+
+/// Add a rule that tags matching new history entries; returns it with its id.
+#[tauri::command]
+fn add_tag_rule(
+    app: tauri::AppHandle,
+    mut rule: tag_rules::TagRule,
+) -> Result<tag_rules::TagRule, String> {
+    rule.validate()?;
+    rule.id = storage::generate_id();
+    let mut settings = storage::load_settings(&app);
+    settings.tag_rules.push(rule.clone());
+    storage::save_settings(&app, &settings)?;
+    Ok(rule)
+}
+
+#[tauri::command]
+fn update_tag_rule(app: tauri::AppHandle, rule: tag_rules::TagRule) -> Result<(), String> {
+    rule.validate()?;
+    let mut settings = storage::load_settings(&app);
+    let existing = settings
+        .tag_rules
+        .iter_mut()
+        .find(|r| r.id == rule.id)
+        .ok_or_else(|| format!("Tag rule not found: {}", rule.id))?;
+    *existing = rule;
+    storage::save_settings(&app, &settings)
+}
+
+#[tauri::command]
+fn delete_tag_rule(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    let before = settings.tag_rules.len();
+    settings.tag_rules.retain(|r| r.id != id);
+    if settings.tag_rules.len() == before {
+        return Err(format!("Tag rule not found: {}", id));
+    }
+    storage::save_settings(&app, &settings)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DedupedColors {
+    colors: Vec<String>,
+    report: dedupe::DedupReport,
+}
+
+/// Collapse near-identical colors in a bulk-parsed list before it is
+/// inserted, reporting what was merged. `threshold` defaults to the import
+/// dedup setting, or a just-noticeable difference when that is off.
+#[tauri::command]
+fn dedupe_colors(
+    app: tauri::AppHandle,
+    colors: Vec<String>,
+    threshold: Option<f64>,
+) -> Result<DedupedColors, String> {
+    let threshold = threshold
+        .or(storage::load_settings(&app).import_dedupe)
+        .unwrap_or(DEFAULT_DEDUPE_THRESHOLD);
+    check_dedupe_threshold(threshold)?;
+    let (colors, report) = dedupe::dedupe(&colors, threshold);
+    Ok(DedupedColors { colors, report })
+}
+
+/// Colors further apart than this are reported as removed and added rather
+/// than changed.
+const DEFAULT_DIFF_THRESHOLD: f64 = 10.0;
+/// Largest delta-E accepted for pairing colors in a diff; beyond this,
+/// unrelated colors would be reported as one color that changed.
+const MAX_DIFF_THRESHOLD: f64 = 50.0;
+
+fn check_diff_threshold(threshold: f64) -> Result<(), String> {
+    if !(0.0..=MAX_DIFF_THRESHOLD).contains(&threshold) {
+        return Err(format!(
+            "Diff threshold must be a delta-E between 0 and {}",
+            MAX_DIFF_THRESHOLD
+        ));
+    }
+    Ok(())
+}
+
+/// What changed from palette `a` to palette `b`, e.g. two captures of a
+/// design system taken months apart. `threshold` is the largest delta-E
+/// still counted as the same color having changed.
+#[tauri::command]
+fn diff_palettes(
+    a: Palette,
+    b: Palette,
+    threshold: Option<f64>,
+) -> Result<palette_diff::PaletteDiff, String> {
+    let threshold = threshold.unwrap_or(DEFAULT_DIFF_THRESHOLD);
+    check_diff_threshold(threshold)?;
+    Ok(palette_diff::diff(&a.colors, &b.colors, threshold))
+}
+
+/// Import every `.colorsnap` file among launch arguments (double-clicked files
+/// arrive here, both on first launch and from a second instance).
+fn open_palette_files(app: &tauri::AppHandle, args: &[String]) {
+    for path in args.iter().filter(|a| palette_file::is_palette_file(a)) {
+        let result = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read palette file: {}", e))
+            .and_then(|json| palette_file::parse(&json))
+            .and_then(|imported| save_imported_palette(app, imported, path));
+        if let Err(e) = result {
+            eprintln!("Failed to open {path}: {e}");
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ColorInfo {
-    pub hex: String,
-    pub rgb: [u8; 3],
-    pub x: i32,
-    pub y: i32,
+/// Import palettes from `colorsnap://palette?…` links.
+fn open_palette_links(app: &tauri::AppHandle, urls: &[String]) {
+    for url in urls {
+        if let Err(e) = share::parse_share_url(url)
+            .and_then(|imported| save_imported_palette(app, imported, url))
+        {
+            eprintln!("Failed to open {url}: {e}");
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ColorEntry {
-    pub id: String,
-    pub hex: String,
-    pub rgb: [u8; 3],
-    pub timestamp: u64,
-    pub label: Option<String>,
+/// Import a palette from a coolors.co URL or Lospec slug/URL and save it as a
+/// new named palette.
+#[tauri::command]
+async fn import_palette_url(app: tauri::AppHandle, source: String) -> Result<Palette, String> {
+    let imported = import::import_from_source(&source).await?;
+    save_imported_palette(&app, imported, &source)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Palette {
-    pub id: String,
-    pub name: String,
-    pub colors: Vec<String>, // hex values
+/// Write a palette as a versioned `.colorsnap` file.
+#[tauri::command]
+fn export_colorsnap_file(
+    app: tauri::AppHandle,
+    palette: Palette,
+    path: String,
+) -> Result<(), String> {
+    let version = app.package_info().version.to_string();
+    let png = storage::load_settings(&app).png;
+    let json = palette_file::to_json(&palette, &version, storage::now_millis(), &png)?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write file: {}", e))?;
+    log_export(&app, &palette, &path);
+    Ok(())
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BrandColor {
-    pub role: String,
-    pub hex: String,
+#[tauri::command]
+fn import_colorsnap_file(app: tauri::AppHandle, path: String) -> Result<Palette, String> {
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read palette file: {}", e))?;
+    save_imported_palette(&app, palette_file::parse(&json)?, &path)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct BrandKit {
-    pub name: String,
-    pub colors: Vec<BrandColor>,
-    pub heading_font: String,
-    pub body_font: String,
-    pub notes: String,
+#[tauri::command]
+fn get_store_status(app: tauri::AppHandle) -> encryption::StoreStatus {
+    encryption::status(storage::load_settings(&app).store_encryption)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct LoupeData {
-    pub colors: Vec<String>, // grid×grid hex values, row-major
-    pub hex: String,         // center pixel
-    pub x: i32,
-    pub y: i32,
+/// Turn encryption at rest on or off for history and palettes, or switch
+/// scheme. Passphrase mode needs `passphrase`; DPAPI ties the stores to the
+/// Windows account. Turning it on deletes the history journal and stops the
+/// palette directory mirror, both being plain text.
+#[tauri::command]
+fn set_store_encryption(
+    app: tauri::AppHandle,
+    mode: encryption::StoreEncryption,
+    passphrase: Option<String>,
+) -> Result<(), String> {
+    storage::set_store_encryption(&app, mode, passphrase.as_deref())
 }
 
-/// Put the app into pick mode: hide the main window, show the loupe,
-/// swap the cursor and notify the frontend.
-fn enter_pick_mode(app: &tauri::AppHandle) {
-    PICK_MODE_ACTIVE.store(true, Ordering::SeqCst);
-    let _ = app.emit("pick-mode-started", ());
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.hide();
-    }
-    if let Some(loupe) = app.get_webview_window("loupe") {
-        let _ = loupe.show();
-    }
-    color_picker::set_pick_cursor();
+/// Unlock passphrase-encrypted stores for this session.
+#[tauri::command]
+fn unlock_store(app: tauri::AppHandle, passphrase: String) -> Result<(), String> {
+    storage::unlock_store(&app, &passphrase)?;
+    let _ = app.emit("store-unlocked", ());
+    Ok(())
 }
 
-/// Leave pick mode, restoring the cursor and windows. Emits `color-picked`
-/// when a color was captured, `pick-mode-stopped` otherwise.
-fn exit_pick_mode(app: &tauri::AppHandle, picked: Option<ColorInfo>) {
-    PICK_MODE_ACTIVE.store(false, Ordering::SeqCst);
-    color_picker::restore_default_cursor();
-    if let Some(loupe) = app.get_webview_window("loupe") {
-        let _ = loupe.hide();
-    }
-    match picked {
-        Some(color) => {
-            let _ = app.emit("color-picked", color);
-        }
-        None => {
-            let _ = app.emit("pick-mode-stopped", ());
+/// Write pending changes, then forget the passphrase key and everything
+/// decrypted with it.
+#[tauri::command]
+fn lock_store(app: tauri::AppHandle) -> Result<(), String> {
+    storage::lock_store(&app)
+}
+
+/// Write the shareable parts of the configuration (shortcuts, copy formats,
+/// templates, custom exporters) as a single JSON profile.
+#[tauri::command]
+fn export_settings(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let settings = storage::load_settings(&app);
+    let exporters = storage::load_user_exporters(&app)?;
+    let version = app.package_info().version.to_string();
+    let json = profile::to_json(&settings, &exporters, &version, storage::now_millis())?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write file: {}", e))
+}
+
+/// Apply a profile written by `export_settings`. Shortcuts that can't be
+/// registered here are reported in the result instead of aborting.
+#[tauri::command]
+fn import_settings(app: tauri::AppHandle, path: String) -> Result<profile::ProfileImport, String> {
+    let json =
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read profile: {}", e))?;
+    let imported = profile::parse(&json)?;
+
+    let mut exporters = storage::load_user_exporters(&app)?;
+    profile::merge_exporters(&mut exporters, &imported.exporters);
+    storage::save_user_exporters(&app, &exporters)?;
+
+    let mut settings = storage::load_settings(&app);
+    profile::apply(&imported, &mut settings);
+    let mut report = profile::ProfileImport {
+        exporters_imported: imported.exporters.len(),
+        ..Default::default()
+    };
+    for (&action, accelerator) in &imported.shortcuts {
+        match shortcuts::bind(&app, action, accelerator) {
+            Ok(label) => {
+                settings.shortcuts.insert(action, label);
+                report.shortcuts_applied += 1;
+            }
+            Err(e) => report.shortcut_errors.push(e),
         }
     }
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.show();
-        let _ = window.set_focus();
+    storage::save_settings(&app, &settings)?;
+    loupe::apply(&app, settings.loupe);
+    sampling::set(&app, settings.sampling);
+    conversion_cache::set_names(&app, settings.color_names);
+    Ok(report)
+}
+
+/// Write text to a path chosen via the save dialog (used to export the brand sheet).
+#[tauri::command]
+fn write_file(path: String, contents: String) -> Result<(), String> {
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write file: {}", e))
+}
+
+fn write_export(
+    app: &tauri::AppHandle,
+    exporter: &dyn exporters::Exporter,
+    palette: &Palette,
+    path: &str,
+) -> Result<(), String> {
+    let bytes = exporter.render(palette)?;
+    std::fs::write(path, bytes).map_err(|e| format!("Failed to write file: {}", e))?;
+    log_export(app, palette, path);
+    // Exported colors that are also in history count as used
+    tauri::async_runtime::spawn(track_usage(
+        app.clone(),
+        Vec::new(),
+        palette.colors.clone(),
+        history::UsageKind::Export,
+    ));
+    Ok(())
+}
+
+fn log_export(app: &tauri::AppHandle, palette: &Palette, path: &str) {
+    activity::record(
+        app,
+        activity::ActivityKind::Export,
+        palette.colors.clone(),
+        Some(palette.name.clone()),
+        Some(path.to_string()),
+    );
+}
+
+/// Logged picks, imports and exports matching `query`, newest first.
+#[tauri::command]
+fn query_activity(
+    app: tauri::AppHandle,
+    query: activity::ActivityQuery,
+) -> Result<Vec<activity::ActivityEntry>, String> {
+    Ok(activity::query(storage::load_activity(&app)?, &query))
+}
+
+/// Write the activity matching `query` as CSV for auditing.
+#[tauri::command]
+fn export_activity(
+    app: tauri::AppHandle,
+    query: activity::ActivityQuery,
+    path: String,
+) -> Result<usize, String> {
+    let entries = activity::query(storage::load_activity(&app)?, &query);
+    std::fs::write(&path, activity::to_csv(&entries))
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+    Ok(entries.len())
+}
+
+/// Write the whole pick history with full metadata as CSV or TSV.
+#[tauri::command]
+async fn export_history_table(
+    app: tauri::AppHandle,
+    path: String,
+    format: history::TableFormat,
+) -> Result<usize, String> {
+    let entries = storage::load_color_history(&app).await?;
+    // Without a readable log the source column is just left empty
+    let activity = storage::load_activity(&app).unwrap_or_default();
+    std::fs::write(&path, history::to_table(&entries, &activity, format))
+        .map_err(|e| format!("Failed to write file: {}", e))?;
+    Ok(entries.len())
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PngSettings {
+    options: png_encoder::PngOptions,
+    /// Whether this build includes the multithreaded encoder
+    mtpng_available: bool,
+}
+
+#[tauri::command]
+fn get_png_options(app: tauri::AppHandle) -> PngSettings {
+    PngSettings {
+        options: storage::load_settings(&app).png,
+        mtpng_available: png_encoder::mtpng_available(),
     }
 }
 
+/// Choose how image exports, QR codes and palette thumbnails are encoded.
 #[tauri::command]
-fn get_color_at_cursor() -> Result<ColorInfo, String> {
-    color_picker::get_color_at_cursor()
+fn set_png_options(app: tauri::AppHandle, options: png_encoder::PngOptions) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.png = options;
+    storage::save_settings(&app, &settings)
+}
+
+fn load_exporters(app: &tauri::AppHandle) -> Result<Vec<Box<dyn exporters::Exporter>>, String> {
+    let settings = storage::load_settings(app);
+    Ok(exporters::registry(
+        storage::load_user_exporters(app)?,
+        settings.png,
+        settings.color_names,
+    ))
 }
 
+/// Built-in formats plus the user templates declared in `exporters.json`.
 #[tauri::command]
-fn capture_loupe(app: tauri::AppHandle, grid: u32) -> Result<LoupeData, String> {
-    let data = color_picker::capture_loupe_grid(grid)?;
+fn list_exporters(app: tauri::AppHandle) -> Result<Vec<exporters::ExporterInfo>, String> {
+    Ok(exporters::info(&load_exporters(&app)?))
+}
 
-    // Follow the cursor, flipping to the other side near screen edges
-    if let Some(loupe) = app.get_webview_window("loupe") {
-        const GAP: i32 = 24;
-        let size = loupe
-            .outer_size()
-            .unwrap_or_else(|_| tauri::PhysicalSize::new(160, 190));
-        let (mut nx, mut ny) = (data.x + GAP, data.y + GAP);
-        if let Ok(Some(monitor)) = app.monitor_from_point(data.x as f64, data.y as f64) {
-            let (mpos, msize) = (monitor.position(), monitor.size());
-            if nx + size.width as i32 > mpos.x + msize.width as i32 {
-                nx = data.x - GAP - size.width as i32;
-            }
-            if ny + size.height as i32 > mpos.y + msize.height as i32 {
-                ny = data.y - GAP - size.height as i32;
-            }
-        }
-        let _ = loupe.set_position(tauri::PhysicalPosition::new(nx, ny));
+/// Turn the history journal on or off. It is plain text, so it can't be
+/// enabled while the store is encrypted.
+#[tauri::command]
+fn set_history_journal(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    if enabled && settings.store_encryption != encryption::StoreEncryption::None {
+        return Err(
+            "The history journal can't be enabled while the store is encrypted".to_string(),
+        );
     }
+    settings.history_journal = enabled;
+    storage::save_settings(&app, &settings)
+}
 
-    Ok(data)
+#[tauri::command]
+fn get_history_journal_path(app: tauri::AppHandle) -> Result<String, String> {
+    Ok(storage::journal_path(&app)?.to_string_lossy().into_owned())
 }
 
 #[tauri::command]
-async fn save_color_history(app: tauri::AppHandle, colors: Vec<ColorEntry>) -> Result<(), String> {
-    storage::save_color_history(&app, &colors).await
+fn get_exporters_config_path(app: tauri::AppHandle) -> Result<String, String> {
+    Ok(storage::exporters_config_path(&app)?
+        .to_string_lossy()
+        .into_owned())
 }
 
+/// Export a palette with any registered exporter, looked up by id.
 #[tauri::command]
-async fn load_color_history(app: tauri::AppHandle) -> Result<Vec<ColorEntry>, String> {
-    storage::load_color_history(&app).await
+fn export_palette_as(
+    app: tauri::AppHandle,
+    palette: Palette,
+    exporter_id: String,
+    path: String,
+) -> Result<(), String> {
+    let registry = load_exporters(&app)?;
+    write_export(
+        &app,
+        exporters::find(&registry, &exporter_id)?,
+        &palette,
+        &path,
+    )
 }
 
+/// Save (or with `None`, clear) a palette's own export settings. Allowed on
+/// locked palettes, which stay exportable.
 #[tauri::command]
-async fn save_palettes(app: tauri::AppHandle, palettes: Vec<Palette>) -> Result<(), String> {
+fn set_palette_export(
+    app: tauri::AppHandle,
+    palette_id: String,
+    export: Option<export::PaletteExport>,
+) -> Result<(), String> {
+    let mut palettes = storage::load_palettes(&app);
+    let target = palettes
+        .iter_mut()
+        .find(|p| p.id == palette_id)
+        .ok_or_else(|| format!("Palette not found: {palette_id}"))?;
+    target.export = export;
     storage::save_palettes(&app, &palettes)
 }
 
+/// Export a palette with its saved exporter, directory and file naming,
+/// falling back to its workspace's export defaults. Returns the path written.
 #[tauri::command]
-async fn load_palettes(app: tauri::AppHandle) -> Result<Vec<Palette>, String> {
-    Ok(storage::load_palettes(&app))
+fn export_palette_default(app: tauri::AppHandle, palette_id: String) -> Result<String, String> {
+    let palette = storage::load_palettes(&app)
+        .into_iter()
+        .find(|p| p.id == palette_id)
+        .ok_or_else(|| format!("Palette not found: {palette_id}"))?;
+    let preset = palette.export.clone().unwrap_or_default();
+    let defaults = palette
+        .workspace_id
+        .as_ref()
+        .and_then(|id| {
+            storage::load_workspaces(&app)
+                .into_iter()
+                .find(|w| &w.id == id)
+        })
+        .map(|w| w.export_defaults)
+        .unwrap_or_default();
+    let exporter_id = preset
+        .exporter
+        .or(defaults.format)
+        .ok_or("No default exporter set for this palette")?;
+    let directory = preset
+        .directory
+        .or(defaults.directory)
+        .ok_or("No default export directory set for this palette")?;
+    let registry = load_exporters(&app)?;
+    let exporter = exporters::find(&registry, &exporter_id)?;
+    let path = std::path::Path::new(&directory)
+        .join(format!(
+            "{}.{}",
+            export::file_stem(&palette.name, preset.naming),
+            exporter.extension()
+        ))
+        .to_string_lossy()
+        .into_owned();
+    write_export(&app, exporter, &palette, &path)?;
+    Ok(path)
 }
 
+/// Transform a palette through the pipeline's steps and write the result
+/// with its exporter, in one action. Returns the transformed palette.
 #[tauri::command]
-async fn save_brand_kit(app: tauri::AppHandle, kit: BrandKit) -> Result<(), String> {
-    storage::save_brand_kit(&app, &kit)
+fn run_export_pipeline(
+    app: tauri::AppHandle,
+    palette: Palette,
+    pipeline: pipeline::Pipeline,
+    path: String,
+) -> Result<Palette, String> {
+    let registry = load_exporters(&app)?;
+    let exporter = exporters::find(&registry, &pipeline.exporter)?;
+    let transformed = pipeline::apply(&palette, &pipeline.steps)?;
+    write_export(&app, exporter, &transformed, &path)?;
+    Ok(transformed)
 }
 
+/// Render a standalone HTML report of a palette and write it to `path`.
 #[tauri::command]
-async fn load_brand_kit(app: tauri::AppHandle) -> Result<Option<BrandKit>, String> {
-    Ok(storage::load_brand_kit(&app))
+fn export_palette_html(
+    app: tauri::AppHandle,
+    palette: Palette,
+    path: String,
+) -> Result<(), String> {
+    let names = storage::load_settings(&app).color_names;
+    write_export(&app, &exporters::HtmlReport(names), &palette, &path)
 }
 
-/// Write text to a path chosen via the save dialog (used to export the brand sheet).
+/// Render a printable PDF swatch sheet (A4 or Letter) and write it to `path`.
 #[tauri::command]
-fn write_file(path: String, contents: String) -> Result<(), String> {
-    std::fs::write(&path, contents).map_err(|e| format!("Failed to write file: {}", e))
+fn export_palette_pdf(
+    app: tauri::AppHandle,
+    palette: Palette,
+    path: String,
+    page_size: pdf::PageSize,
+) -> Result<(), String> {
+    write_export(&app, &exporters::PdfSheet(page_size), &palette, &path)
+}
+
+/// Encode a palette as a colorsnap:// link plus a coolors.co-compatible URL.
+/// When `qr_path` is given, a QR code of the coolors URL (which opens on any
+/// phone) is written there as a PNG.
+#[tauri::command]
+fn share_palette(
+    app: tauri::AppHandle,
+    palette: Palette,
+    qr_path: Option<String>,
+) -> Result<share::SharedPalette, String> {
+    let url = share::share_url(&palette)?;
+    let coolors_url = share::coolors_url(&palette)?;
+    if let Some(path) = &qr_path {
+        share::write_qr_png(&coolors_url, path, &storage::load_settings(&app).png)?;
+    }
+    Ok(share::SharedPalette {
+        url,
+        coolors_url,
+        qr_path,
+    })
+}
+
+/// Write a 16+ color palette as a Windows Terminal, Alacritty or kitty theme.
+#[tauri::command]
+fn export_terminal_theme(
+    app: tauri::AppHandle,
+    palette: Palette,
+    format: export::TerminalFormat,
+    path: String,
+) -> Result<(), String> {
+    write_export(&app, &exporters::Terminal(format), &palette, &path)
+}
+
+/// Write a minimal VS Code color theme (workbench + token colors) generated
+/// from the palette.
+#[tauri::command]
+fn export_vscode_theme(
+    app: tauri::AppHandle,
+    palette: Palette,
+    path: String,
+) -> Result<(), String> {
+    write_export(&app, &exporters::VsCode, &palette, &path)
+}
+
+/// Search workspaces, palettes, palette colors and history in one go.
+#[tauri::command]
+async fn search_everywhere(
+    app: tauri::AppHandle,
+    query: String,
+) -> Result<Vec<search::SearchHit>, String> {
+    refresh_search_index(&app).await?;
+    Ok(search::query(&query))
+}
+
+/// Rebuild the search index if a storage write invalidated it, again if
+/// another write lands while rebuilding.
+async fn refresh_search_index(app: &tauri::AppHandle) -> Result<(), String> {
+    while let Some(generation) = search::stale_generation() {
+        let history = storage::load_color_history(app).await?;
+        search::rebuild(
+            generation,
+            &storage::load_workspaces(app),
+            &storage::load_palettes(app),
+            &history,
+        );
+    }
+    Ok(())
+}
+
+/// Rolling capture timings (GetPixel, loupe blit/encode, screen snapshot,
+/// area crop) in milliseconds.
+#[tauri::command]
+fn get_perf_metrics() -> Vec<perf::StageMetrics> {
+    perf::snapshot()
+}
+
+#[tauri::command]
+fn reset_perf_metrics() {
+    perf::reset();
+}
+
+/// Paint known colors on every monitor and report what each capture backend
+/// reads back, for diagnosing wrong picks on a particular display.
+#[tauri::command]
+async fn run_capture_selftest(app: tauri::AppHandle) -> Result<selftest::SelftestReport, String> {
+    let monitors = app
+        .available_monitors()
+        .map_err(|e| format!("Failed to list monitors: {}", e))?
+        .iter()
+        .map(|m| selftest::MonitorArea {
+            name: m.name().cloned(),
+            x: m.position().x,
+            y: m.position().y,
+            width: m.size().width,
+            height: m.size().height,
+            scale_factor: m.scale_factor(),
+        })
+        .collect();
+    tauri::async_runtime::spawn_blocking(move || selftest::run(monitors))
+        .await
+        .map_err(|e| format!("Capture self-test failed: {}", e))?
+}
+
+#[tauri::command]
+fn get_app_info(app: tauri::AppHandle) -> diagnostics::AppInfo {
+    diagnostics::app_info(&app)
 }
 
 #[tauri::command]
 fn start_pick_mode(app: tauri::AppHandle) -> Result<(), String> {
-    enter_pick_mode(&app);
+    pick_controller::transition(
+        &app,
+        pick_controller::PickEvent::Start(pick_controller::PickMode::Loupe),
+    )
+}
+
+/// Sample the pixel under the cursor after `ms`, emitting `pick-countdown`
+/// events meanwhile; the pick arrives as a normal `color-picked`.
+#[tauri::command]
+fn pick_after(app: tauri::AppHandle, ms: u64) -> Result<(), String> {
+    if ms > MAX_PICK_DELAY_MS {
+        return Err(format!("Delay must be at most {} ms", MAX_PICK_DELAY_MS));
+    }
+    countdown_pick(&app, std::time::Duration::from_millis(ms));
     Ok(())
 }
 
+#[tauri::command]
+fn start_frozen_pick_mode(app: tauri::AppHandle) -> Result<(), String> {
+    pick_controller::transition(
+        &app,
+        pick_controller::PickEvent::Start(pick_controller::PickMode::Frozen),
+    )
+}
+
 #[tauri::command]
 fn stop_pick_mode(app: tauri::AppHandle) -> Result<(), String> {
-    exit_pick_mode(&app, None);
+    // Stopping when already stopped is not an error
+    let _ = pick_controller::transition(&app, pick_controller::PickEvent::Cancel);
     Ok(())
 }
 
 #[tauri::command]
-fn is_pick_mode_active() -> bool {
-    PICK_MODE_ACTIVE.load(Ordering::SeqCst)
+fn is_pick_mode_active(state: tauri::State<'_, state::AppState>) -> bool {
+    state.pick().is_some()
+}
+
+/// Current pick mode, `None` when not picking.
+#[tauri::command]
+fn get_pick_state(state: tauri::State<'_, state::AppState>) -> Option<pick_controller::PickMode> {
+    *state.pick()
 }
 
 #[tauri::command]
-fn get_active_shortcut() -> String {
-    ACTIVE_SHORTCUT
-        .lock()
-        .ok()
-        .and_then(|guard| guard.as_ref().map(|(_, label)| label.clone()))
-        .unwrap_or_default()
+fn get_active_shortcut(app: tauri::AppHandle) -> String {
+    shortcuts::label_for(&app, shortcuts::Action::Pick).unwrap_or_default()
 }
 
 #[tauri::command]
 fn list_shortcut_options() -> Vec<String> {
-    pick_shortcut_candidates()
-        .into_iter()
-        .map(|(_, label)| label.to_string())
+    PICK_SHORTCUT_CANDIDATES
+        .iter()
+        .map(|label| label.to_string())
         .collect()
 }
 
@@ -232,42 +2381,56 @@ fn list_shortcut_options() -> Vec<String> {
 /// previous shortcut if the requested one cannot be registered.
 #[tauri::command]
 fn set_pick_shortcut(app: tauri::AppHandle, label: String) -> Result<String, String> {
-    let (shortcut, name) = pick_shortcut_candidates()
-        .into_iter()
-        .find(|(_, l)| *l == label)
-        .ok_or_else(|| format!("Unknown shortcut: {label}"))?;
-
-    let previous = ACTIVE_SHORTCUT.lock().unwrap().clone();
-    if let Some((old, _)) = &previous {
-        let _ = app.global_shortcut().unregister(*old);
-    }
-
-    match app.global_shortcut().register(shortcut) {
-        Ok(_) => {
-            *ACTIVE_SHORTCUT.lock().unwrap() = Some((shortcut, name.to_string()));
-            let _ = storage::save_settings(
-                &app,
-                &storage::AppSettings {
-                    preferred_shortcut: Some(name.to_string()),
-                },
-            );
-            Ok(name.to_string())
-        }
-        Err(e) => {
-            if let Some((old, old_label)) = previous {
-                if app.global_shortcut().register(old).is_ok() {
-                    *ACTIVE_SHORTCUT.lock().unwrap() = Some((old, old_label));
-                }
-            }
-            Err(format!("{name} is unavailable: {e}"))
+    set_action_shortcut(app, shortcuts::Action::Pick, Some(label)).map(Option::unwrap_or_default)
+}
+
+/// Every shortcut action with its current accelerator.
+#[tauri::command]
+fn list_action_shortcuts(app: tauri::AppHandle) -> Vec<shortcuts::Binding> {
+    shortcuts::bindings(&app)
+}
+
+/// Bind `action` to `accelerator` (e.g. `Ctrl+Shift+X`), or unbind it with
+/// `None`, and persist the change. Returns the active accelerator.
+#[tauri::command]
+fn set_action_shortcut(
+    app: tauri::AppHandle,
+    action: shortcuts::Action,
+    accelerator: Option<String>,
+) -> Result<Option<String>, String> {
+    let bound = match &accelerator {
+        Some(accelerator) => Some(shortcuts::bind(&app, action, accelerator)?),
+        None => {
+            shortcuts::unbind(&app, action);
+            None
         }
-    }
+    };
+    let mut settings = storage::load_settings(&app);
+    match &bound {
+        Some(label) => settings.shortcuts.insert(action, label.clone()),
+        None => settings.shortcuts.remove(&action),
+    };
+    storage::save_settings(&app, &settings)?;
+    Ok(bound)
+}
+
+#[tauri::command]
+fn get_pick_hook(app: tauri::AppHandle) -> Option<hooks::PickHook> {
+    storage::load_settings(&app).pick_hook
+}
+
+/// Configure (or with `None`, remove) the post-pick hook.
+#[tauri::command]
+fn set_pick_hook(app: tauri::AppHandle, hook: Option<hooks::PickHook>) -> Result<(), String> {
+    let mut settings = storage::load_settings(&app);
+    settings.pick_hook = hook;
+    storage::save_settings(&app, &settings)
 }
 
 #[tauri::command]
-fn pick_color_now(app: tauri::AppHandle) -> Result<ColorInfo, String> {
-    let color = color_picker::get_color_at_cursor()?;
-    exit_pick_mode(&app, Some(color.clone()));
+fn pick_color_now(app: tauri::AppHandle) -> Result<ColorInfo, elevation::PickError> {
+    let color = pick_at_cursor(&app)?;
+    pick_controller::transition(&app, pick_controller::PickEvent::Picked(color.clone()))?;
     Ok(color)
 }
 
@@ -280,37 +2443,31 @@ fn cursor_screen_pos() -> Result<(i32, i32), String> {
 /// Snapshots the screen first so the overlay tint never taints the average.
 #[tauri::command]
 fn start_area_mode(app: tauri::AppHandle) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.hide();
-    }
-    // Capture the clean screen before the overlay appears
-    color_picker::capture_area_snapshot()?;
-
-    let region = app
-        .get_webview_window("region")
-        .ok_or("region window missing")?;
+    pick_controller::transition(
+        &app,
+        pick_controller::PickEvent::Start(pick_controller::PickMode::Area),
+    )
+}
 
-    let (cx, cy) = color_picker::cursor_pos().unwrap_or((0, 0));
-    if let Ok(Some(monitor)) = app.monitor_from_point(cx as f64, cy as f64) {
-        let _ = region.set_position(*monitor.position());
-        let _ = region.set_size(*monitor.size());
+/// Screen coordinates and snapshots taken before a display or session change
+/// no longer line up with the screen: drop the area snapshot and display
+/// duplication, cancel any pick in progress and make sure no custom cursor
+/// outlives it.
+fn handle_display_event(app: &tauri::AppHandle, event: display_watch::DisplayEvent) {
+    color_picker::clear_area_snapshot();
+    duplication::reset();
+    if pick_controller::transition(app, pick_controller::PickEvent::Cancel).is_err() {
+        color_picker::restore_default_cursor();
     }
-    let _ = region.show();
-    let _ = region.set_focus();
-    Ok(())
+    let _ = app.emit("display-changed", event);
 }
 
 #[tauri::command]
 fn cancel_area_mode(app: tauri::AppHandle) -> Result<(), String> {
-    color_picker::clear_area_snapshot();
-    if let Some(region) = app.get_webview_window("region") {
-        let _ = region.hide();
-    }
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.show();
-        let _ = window.set_focus();
+    if pick_controller::current(&app) != Some(pick_controller::PickMode::Area) {
+        return Err("Area mode is not active".to_string());
     }
-    Ok(())
+    pick_controller::transition(&app, pick_controller::PickEvent::Cancel)
 }
 
 /// Average the pixels in the dragged rectangle, close the overlay, and emit
@@ -323,29 +2480,74 @@ fn pick_area(
     x2: i32,
     y2: i32,
 ) -> Result<ColorInfo, String> {
+    if pick_controller::current(&app) != Some(pick_controller::PickMode::Area) {
+        return Err("Area mode is not active".to_string());
+    }
     let (r, g, b) = color_picker::average_area_color(x1, y1, x2, y2)?;
-    color_picker::clear_area_snapshot();
     let color = ColorInfo {
         hex: format!("#{:02X}{:02X}{:02X}", r, g, b),
         rgb: [r, g, b],
         x: (x1 + x2) / 2,
         y: (y1 + y2) / 2,
+        label: None,
+        delta_e: reference::delta_e(&app, [r, g, b]),
+        white_point: None,
+        color_shift: gamma_ramp::shift_at((x1 + x2) / 2, (y1 + y2) / 2),
+        representations: conversion::representations([r, g, b]),
     };
 
-    if let Some(region) = app.get_webview_window("region") {
-        let _ = region.hide();
+    pick_controller::transition(&app, pick_controller::PickEvent::Picked(color.clone()))?;
+    Ok(color)
+}
+
+/// Leave the system as we found it and lose no pending writes. Runs once,
+/// however the app exits (tray quit, updater restart, last window closed).
+fn shutdown(app: &tauri::AppHandle) {
+    if SHUT_DOWN.swap(true, Ordering::SeqCst) {
+        return;
     }
-    let _ = app.emit("color-picked", color.clone());
-    if let Some(window) = app.get_webview_window("main") {
-        let _ = window.show();
-        let _ = window.set_focus();
+    let _ = pick_controller::transition(app, pick_controller::PickEvent::Abort);
+    color_picker::restore_default_cursor();
+    let _ = app.global_shortcut().unregister_all();
+    if let Err(e) = window_state::save(app) {
+        eprintln!("Failed to save window position: {}", e);
+    }
+    if let Err(e) = storage::flush(app) {
+        eprintln!("Failed to save before exiting: {}", e);
     }
-    Ok(color)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Modes for external clients: each talks to the running app over a pipe
+    // and exits without starting a second instance
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let client = match args.first().map(String::as_str) {
+        Some("--mcp") => Some(mcp::run_stdio()),
+        Some("--query") => Some(launcher::run_cli(&args[1..]).map(|json| println!("{json}"))),
+        _ if native_messaging::is_host_launch(&args) => Some(native_messaging::run_host()),
+        _ => None,
+    };
+    if let Some(result) = client {
+        if let Err(e) = result {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
     tauri::Builder::default()
+        .manage(state::AppState::default())
+        // Must be registered first so a second launch (double-clicked
+        // .colorsnap file, deep link) is forwarded here and exits early
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            open_palette_files(app, argv.get(1..).unwrap_or(&[]));
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_autostart::init(
@@ -362,56 +2564,92 @@ pub fn run() {
                         return;
                     }
 
-                    // Check if this is the active pick shortcut
-                    let is_pick = ACTIVE_SHORTCUT
-                        .lock()
-                        .ok()
-                        .and_then(|guard| guard.as_ref().map(|(s, _)| shortcut == s))
-                        .unwrap_or(false);
-
-                    if is_pick {
-                        if PICK_MODE_ACTIVE.load(Ordering::SeqCst) {
-                            // If already in pick mode, pick the color
-                            if let Ok(color) = color_picker::get_color_at_cursor() {
-                                exit_pick_mode(app, Some(color));
-                            }
-                        } else {
-                            enter_pick_mode(app);
-                        }
+                    if let Some(action) = shortcuts::action_for(app, shortcut) {
+                        run_shortcut_action(app, action);
                     }
 
-                    // Escape to cancel pick mode
+                    // Escape to cancel pick mode or dismiss the quick palette
                     let escape_shortcut = Shortcut::new(None, Code::Escape);
-                    if shortcut == &escape_shortcut && PICK_MODE_ACTIVE.load(Ordering::SeqCst) {
-                        exit_pick_mode(app, None);
+                    if shortcut == &escape_shortcut && pick_controller::is_active(app) {
+                        let _ =
+                            pick_controller::transition(app, pick_controller::PickEvent::Cancel);
+                    } else if shortcut == &escape_shortcut && quick_palette::is_visible(app) {
+                        quick_palette::hide(app);
                     }
                 })
                 .build(),
         )
+        .on_window_event(|window, event| match event {
+            tauri::WindowEvent::DragDrop(tauri::DragDropEvent::Drop { paths, .. }) => {
+                file_drop::handle_drop(window.app_handle(), paths.clone());
+            }
+            tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_)
+                if window.label() == "main" =>
+            {
+                window_state::track(window.app_handle());
+            }
+            tauri::WindowEvent::Focused(false) if window.label() == quick_palette::LABEL => {
+                quick_palette::hide(window.app_handle());
+            }
+            // The app may live on in the tray, so save what closing would lose
+            tauri::WindowEvent::CloseRequested { .. } if window.label() == "main" => {
+                let app = window.app_handle();
+                if let Err(e) = window_state::save(app).and_then(|_| storage::flush(app)) {
+                    eprintln!("Failed to save on close: {}", e);
+                }
+            }
+            _ => {}
+        })
         .setup(|app| {
             // Restore cursor in case a previous instance was killed without cleanup
             color_picker::restore_default_cursor_force();
+            window_state::restore(app.handle());
 
-            // Try registering pick shortcut from candidates, preferred one first
-            let mut candidates = pick_shortcut_candidates();
-            if let Some(pref) = storage::load_settings(app.handle()).preferred_shortcut {
-                if let Some(pos) = candidates.iter().position(|(_, l)| *l == pref) {
-                    let preferred = candidates.remove(pos);
-                    candidates.insert(0, preferred);
-                }
+            let handle = app.handle().clone();
+            display_watch::watch(move |event| handle_display_event(&handle, event));
+            schedule_history_pruning(app.handle());
+            if let Err(e) = storage::sync_palette_dir(app.handle()) {
+                eprintln!("Failed to sync palette directory: {}", e);
+            }
+
+            // Shortcut picks that skip the loupe read game mode too
+            let settings = storage::load_settings(app.handle());
+            color_picker::set_game_mode(settings.game_mode);
+
+            // Try registering the pick shortcut: the saved one first, then the
+            // built-in candidates
+            let saved_pick = settings
+                .shortcuts
+                .get(&shortcuts::Action::Pick)
+                .cloned()
+                .or(settings.preferred_shortcut.clone());
+            let mut candidates: Vec<String> = PICK_SHORTCUT_CANDIDATES
+                .iter()
+                .map(|label| label.to_string())
+                .collect();
+            if let Some(pref) = saved_pick {
+                candidates.retain(|l| *l != pref);
+                candidates.insert(0, pref);
             }
             let mut shortcut_label = String::new();
-            for (shortcut, label) in candidates {
-                let _ = app.global_shortcut().unregister(shortcut);
-                match app.global_shortcut().register(shortcut) {
+            for label in candidates {
+                match shortcuts::bind(app.handle(), shortcuts::Action::Pick, &label) {
                     Ok(_) => {
                         println!("Pick shortcut registered: {label}");
-                        shortcut_label = label.to_string();
-                        *ACTIVE_SHORTCUT.lock().unwrap() = Some((shortcut, label.to_string()));
+                        shortcut_label = label;
                         break;
                     }
                     Err(e) => {
-                        eprintln!("Shortcut {label} unavailable: {e}, trying next...");
+                        eprintln!("{e}, trying next...");
+                    }
+                }
+            }
+
+            // Shortcuts for the other actions are opt-in
+            for (action, accelerator) in &settings.shortcuts {
+                if *action != shortcuts::Action::Pick {
+                    if let Err(e) = shortcuts::bind(app.handle(), *action, accelerator) {
+                        eprintln!("Shortcut for {action:?} not registered: {e}");
                     }
                 }
             }
@@ -430,6 +2668,21 @@ pub fn run() {
                 Err(e) => eprintln!("Failed to register Escape shortcut: {e}"),
             }
 
+            // Palette files and links the app was launched with
+            let args: Vec<String> = std::env::args().skip(1).collect();
+            open_palette_files(app.handle(), &args);
+            #[cfg(any(windows, target_os = "linux"))]
+            let _ = app.deep_link().register_all();
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                let urls: Vec<String> = urls.iter().map(|u| u.to_string()).collect();
+                open_palette_links(app.handle(), &urls);
+            }
+            let handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                let urls: Vec<String> = event.urls().iter().map(|u| u.to_string()).collect();
+                open_palette_links(&handle, &urls);
+            });
+
             // Frameless always-on-top loupe window; hidden until pick mode
             let loupe = tauri::WebviewWindowBuilder::new(
                 app,
@@ -448,6 +2701,32 @@ pub fn run() {
             .transparent(true)
             .build()?;
             let _ = loupe.set_ignore_cursor_events(true);
+            let settings = storage::load_settings(app.handle());
+            loupe::apply(app.handle(), settings.loupe);
+            sampling::set(app.handle(), settings.sampling);
+            conversion_cache::set_names(app.handle(), settings.color_names);
+            reference::set(
+                app.handle(),
+                settings
+                    .reference_color
+                    .and_then(|hex| color_math::parse_hex(&hex).ok()),
+            );
+            if settings.editor_rpc {
+                editor_rpc::start(app.handle());
+            }
+            if settings.mcp_server {
+                mcp::start(app.handle());
+            }
+            if settings.figma_bridge {
+                if let Err(e) = figma_bridge::start(app.handle()) {
+                    eprintln!("Figma bridge not started: {e}");
+                }
+            }
+            if let Some(ms) = settings.ambient_interval_ms {
+                if let Err(e) = ambient::start(app.handle(), ms) {
+                    eprintln!("Ambient color stream not started: {e}");
+                }
+            }
 
             // Fullscreen transparent overlay for area (drag-to-average) picking
             let _region = tauri::WebviewWindowBuilder::new(
@@ -466,6 +2745,8 @@ pub fn run() {
             .shadow(false)
             .build()?;
 
+            quick_palette::create(app)?;
+
             // Setup system tray
             let tray_shortcut_text = if shortcut_label.is_empty() {
                 String::new()
@@ -480,9 +2761,39 @@ pub fn run() {
                 true,
                 None::<&str>,
             )?;
+            let pick_menu = Submenu::with_id_and_items(
+                app,
+                "pick_menu",
+                "Pick",
+                true,
+                &[
+                    &MenuItem::with_id(app, "pick_loupe", "Pick with Loupe", true, None::<&str>)?,
+                    &MenuItem::with_id(
+                        app,
+                        "pick_instant",
+                        "Instant Pick and Copy",
+                        true,
+                        None::<&str>,
+                    )?,
+                    &MenuItem::with_id(app, "pick_area", "Region Average", true, None::<&str>)?,
+                    &MenuItem::with_id(app, "pick_frozen", "Frozen Pick", true, None::<&str>)?,
+                    &MenuItem::with_id(
+                        app,
+                        "pick_delayed",
+                        "Pick in 3 Seconds",
+                        true,
+                        None::<&str>,
+                    )?,
+                ],
+            )?;
             let show_item = MenuItem::with_id(app, "show", "Show Window", true, None::<&str>)?;
+            let debug_item =
+                MenuItem::with_id(app, "debug_info", "Copy Debug Info", true, None::<&str>)?;
 
-            let menu = Menu::with_items(app, &[&pick_item, &show_item, &quit_item])?;
+            let menu = Menu::with_items(
+                app,
+                &[&pick_item, &pick_menu, &show_item, &debug_item, &quit_item],
+            )?;
 
             let _tray = TrayIconBuilder::new()
                 .icon(
@@ -502,12 +2813,43 @@ pub fn run() {
                 ))
                 .on_menu_event(|app, event| match event.id.as_ref() {
                     "quit" => {
-                        // Restore cursor before quitting
-                        color_picker::restore_default_cursor();
+                        // Cleanup runs from RunEvent::Exit
                         app.exit(0);
                     }
-                    "pick" => {
-                        enter_pick_mode(app);
+                    "pick" | "pick_loupe" => {
+                        let _ = pick_controller::transition(
+                            app,
+                            pick_controller::PickEvent::Start(pick_controller::PickMode::Loupe),
+                        );
+                    }
+                    "pick_instant" => {
+                        // Let the menu close so it isn't what gets sampled
+                        let app = app.clone();
+                        std::thread::spawn(move || {
+                            std::thread::sleep(TRAY_MENU_CLOSE_DELAY);
+                            instant_pick(&app);
+                        });
+                    }
+                    "pick_delayed" => countdown_pick(app, TRAY_PICK_COUNTDOWN),
+                    "pick_area" => {
+                        // The region snapshot must not include the menu either
+                        let app = app.clone();
+                        std::thread::spawn(move || {
+                            std::thread::sleep(TRAY_MENU_CLOSE_DELAY);
+                            let _ = start_area_mode(app);
+                        });
+                    }
+                    "pick_frozen" => {
+                        let app = app.clone();
+                        std::thread::spawn(move || {
+                            std::thread::sleep(TRAY_MENU_CLOSE_DELAY);
+                            let _ = pick_controller::transition(
+                                &app,
+                                pick_controller::PickEvent::Start(
+                                    pick_controller::PickMode::Frozen,
+                                ),
+                            );
+                        });
                     }
                     "show" => {
                         if let Some(window) = app.get_webview_window("main") {
@@ -515,21 +2857,30 @@ pub fn run() {
                             let _ = window.set_focus();
                         }
                     }
+                    "debug_info" => {
+                        let info = diagnostics::app_info(app);
+                        let _ = app.clipboard().write_text(diagnostics::debug_text(&info));
+                    }
                     _ => {}
                 })
-                .on_tray_icon_event(|tray, event| {
-                    if let TrayIconEvent::Click {
+                .on_tray_icon_event(|tray, event| match event {
+                    TrayIconEvent::Click {
                         button: MouseButton::Left,
                         button_state: MouseButtonState::Up,
                         ..
-                    } = event
-                    {
+                    } => {
                         let app = tray.app_handle();
                         if let Some(window) = app.get_webview_window("main") {
                             let _ = window.show();
                             let _ = window.set_focus();
                         }
                     }
+                    TrayIconEvent::Click {
+                        button: MouseButton::Middle,
+                        button_state: MouseButtonState::Up,
+                        ..
+                    } => countdown_pick(tray.app_handle(), TRAY_PICK_COUNTDOWN),
+                    _ => {}
                 })
                 .build(app)?;
 
@@ -538,25 +2889,151 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             get_color_at_cursor,
             capture_loupe,
+            get_sampling,
+            set_sampling,
+            set_color_name_locale,
+            get_reference_color,
+            set_reference_color,
+            start_averaging_session,
+            get_averaging_session,
+            cancel_averaging_session,
+            finish_averaging_session,
+            get_loupe_settings,
+            set_loupe_settings,
+            text_color_for,
+            check_palette_contrast,
+            convert_color,
+            check_print_safety,
+            get_screen_histogram,
+            get_window_dominant_color,
+            get_image_average,
+            extract_folder_palettes,
             save_color_history,
+            add_history_entry,
+            remove_history_entries,
+            set_entry_label,
+            clear_color_history,
             load_color_history,
+            query_color_history,
+            record_color_usage,
+            copy_color,
+            quick_palette_copy,
+            find_similar_entries,
+            merge_entries,
+            set_import_dedupe,
+            list_auto_exports,
+            add_auto_export,
+            delete_auto_export,
+            list_tag_rules,
+            add_tag_rule,
+            update_tag_rule,
+            delete_tag_rule,
+            dedupe_colors,
+            diff_palettes,
+            set_entries_pinned,
+            set_history_retention,
+            preview_history_pruning,
             save_palettes,
+            set_palette_locked,
+            list_palette_versions,
+            restore_palette_version,
+            get_storage_info,
+            compact_storage,
+            get_palette_dir,
+            set_palette_dir,
+            sync_palette_dir,
             load_palettes,
+            list_workspaces,
+            create_workspace,
+            rename_workspace,
+            set_workspace_export_defaults,
+            delete_workspace,
+            get_active_workspace,
+            set_active_workspace,
+            derive_dark_palette,
+            adjust_palette_for_cvd,
+            export_dual_css,
+            quantize_palette,
             save_brand_kit,
             load_brand_kit,
+            import_palette_url,
+            export_colorsnap_file,
+            import_colorsnap_file,
+            export_settings,
+            query_activity,
+            export_activity,
+            export_history_table,
+            get_store_status,
+            set_store_encryption,
+            unlock_store,
+            lock_store,
+            import_settings,
             write_file,
+            export_palette_html,
+            export_palette_pdf,
+            export_terminal_theme,
+            export_vscode_theme,
+            list_exporters,
+            get_png_options,
+            set_png_options,
+            set_history_journal,
+            get_history_journal_path,
+            get_exporters_config_path,
+            export_palette_as,
+            set_palette_export,
+            export_palette_default,
+            run_export_pipeline,
+            share_palette,
+            search_everywhere,
+            get_perf_metrics,
+            reset_perf_metrics,
+            get_app_info,
+            run_capture_selftest,
             start_pick_mode,
+            start_frozen_pick_mode,
+            pick_after,
             stop_pick_mode,
             is_pick_mode_active,
+            get_pick_state,
             pick_color_now,
             get_active_shortcut,
             list_shortcut_options,
             set_pick_shortcut,
+            list_action_shortcuts,
+            set_action_shortcut,
+            cycle_format,
+            set_copy_format,
+            get_pick_hook,
+            get_auto_label,
+            set_auto_label,
+            set_paste_after_pick,
+            set_announce_colors,
+            set_high_contrast_cursor,
+            set_white_point_report,
+            set_editor_rpc,
+            set_mcp_server,
+            set_figma_bridge,
+            set_ambient_stream,
+            start_figma_pairing,
+            unpair_figma,
+            send_color_to_figma,
+            install_browser_host,
+            set_game_mode,
+            set_quiet_in_fullscreen,
+            get_focus_assist_state,
+            set_ignore_focus_assist,
+            is_fullscreen_quiet,
+            set_pick_hook,
             cursor_screen_pos,
             start_area_mode,
             cancel_area_mode,
             pick_area,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                shutdown(app);
+            }
+        });
 }
@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+use tauri::Manager;
+
+/// Height of the hex readout under the pixel grid, in logical pixels.
+const FOOTER_HEIGHT: u32 = 30;
+/// Border plus padding around the pixel grid, in logical pixels.
+const FRAME: u32 = 8;
+
+/// Grid captured when the frontend doesn't ask for a specific one; kept in
+/// sync with the saved settings by [`apply`].
+static GRID: AtomicU32 = AtomicU32::new(11);
+
+/// Loupe preferences. Missing fields fall back to the defaults, so older
+/// settings files and profiles keep working.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LoupeSettings {
+    /// Width of the loupe window, in logical pixels
+    pub size: u32,
+    /// Logical pixels drawn per captured screen pixel
+    pub zoom: u32,
+    /// Draw separators between magnified pixels
+    pub grid_lines: bool,
+    /// Capture interval while picking, in milliseconds
+    pub refresh_ms: u32,
+}
+
+impl Default for LoupeSettings {
+    fn default() -> Self {
+        LoupeSettings {
+            size: 148,
+            zoom: 13,
+            grid_lines: true,
+            refresh_ms: 50,
+        }
+    }
+}
+
+impl LoupeSettings {
+    /// Clamp every field into a range the loupe can render and poll at.
+    pub fn sanitized(self) -> Self {
+        LoupeSettings {
+            size: self.size.clamp(96, 400),
+            zoom: self.zoom.clamp(4, 40),
+            grid_lines: self.grid_lines,
+            refresh_ms: self.refresh_ms.clamp(16, 1000),
+        }
+    }
+
+    /// Captured pixels per side: as many as fit at this zoom, always odd so
+    /// the picked pixel sits in the middle.
+    pub fn grid(&self) -> u32 {
+        let settings = self.sanitized();
+        ((settings.size - FRAME) / settings.zoom).max(3) | 1
+    }
+}
+
+/// Grid to capture when none is requested.
+pub fn current_grid() -> u32 {
+    GRID.load(Ordering::SeqCst)
+}
+
+/// Make `settings` take effect: resize the loupe window and change the
+/// default capture grid.
+pub fn apply(app: &tauri::AppHandle, settings: LoupeSettings) {
+    let settings = settings.sanitized();
+    GRID.store(settings.grid(), Ordering::SeqCst);
+    if let Some(loupe) = app.get_webview_window("loupe") {
+        let _ = loupe.set_size(tauri::LogicalSize::new(
+            settings.size,
+            settings.size + FOOTER_HEIGHT,
+        ));
+    }
+}
@@ -0,0 +1,211 @@
+//! Model Context Protocol server so AI assistants can ask the user for a live
+//! color pick, read the history and generate palettes mid-session. Opt-in
+//! via the `mcp_server` setting; served on `\\.\pipe\pixnib-mcp`, and over
+//! stdio by launching `pixnib --mcp`, which relays to the running app.
+
+use crate::json_rpc::{self, params, RpcError};
+use crate::pipe_server::{self, PipeServer, Protocol};
+use crate::{import, pick_controller, pipeline, storage, Palette};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::Manager;
+
+/// MCP revisions this build speaks, newest first; a client asking for
+/// another gets the newest.
+const PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+/// The assistant waits on the user, so allow a generous pick.
+const PICK_TIMEOUT: Duration = Duration::from_secs(300);
+const DEFAULT_HISTORY_LIMIT: usize = 20;
+const DEFAULT_SCALE_STEPS: usize = 9;
+const PIPE_NAME: &str = r"\\.\pipe\pixnib-mcp";
+
+static SERVER: PipeServer = PipeServer::new(PIPE_NAME);
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitializeParams {
+    protocol_version: String,
+}
+
+#[derive(Deserialize)]
+struct CallParams {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
+}
+
+#[derive(Deserialize)]
+struct HistoryArgs {
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct GenerateArgs {
+    base: String,
+    steps: Option<usize>,
+    /// Save the result as a palette of this name
+    name: Option<String>,
+}
+
+#[derive(Default)]
+struct Session {
+    initialized: bool,
+}
+
+fn tools() -> Value {
+    json!([
+        {
+            "name": "pick_color",
+            "description": "Ask the user to pick a color from anywhere on their screen with \
+                Pixnib's loupe. Blocks until they pick or cancel.",
+            "inputSchema": { "type": "object", "properties": {} },
+        },
+        {
+            "name": "get_history",
+            "description": "Colors the user picked recently, newest first, with labels and tags.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "limit": {
+                        "type": "integer",
+                        "minimum": 1,
+                        "description": "Number of entries (default 20)",
+                    },
+                },
+            },
+        },
+        {
+            "name": "generate_palette",
+            "description": "Generate a light-to-dark scale from a base color, optionally \
+                saving it as a palette in Pixnib.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "base": { "type": "string", "description": "Base color as hex, e.g. #3b82f6" },
+                    "steps": {
+                        "type": "integer",
+                        "minimum": 3,
+                        "maximum": 11,
+                        "description": "Colors in the scale (default 9)",
+                    },
+                    "name": { "type": "string", "description": "Save as a palette of this name" },
+                },
+                "required": ["base"],
+            },
+        },
+    ])
+}
+
+fn call_tool(app: &tauri::AppHandle, name: &str, args: Value) -> Result<Value, String> {
+    let args = if args.is_null() { json!({}) } else { args };
+    let invalid = |e: serde_json::Error| format!("Invalid arguments: {}", e);
+    match name {
+        "pick_color" => Ok(pick_controller::wait_for_pick(app, PICK_TIMEOUT)?
+            .unwrap_or_else(|| json!({ "cancelled": true }))),
+        "get_history" => {
+            let args: HistoryArgs = serde_json::from_value(args).map_err(invalid)?;
+            let mut history = tauri::async_runtime::block_on(storage::load_color_history(app))?;
+            history.truncate(args.limit.unwrap_or(DEFAULT_HISTORY_LIMIT));
+            serde_json::to_value(history).map_err(|e| format!("Failed to serialize: {}", e))
+        }
+        "generate_palette" => {
+            let args: GenerateArgs = serde_json::from_value(args).map_err(invalid)?;
+            let steps = [pipeline::Transform::Scales {
+                steps: args.steps.unwrap_or(DEFAULT_SCALE_STEPS),
+            }];
+            let base = Palette {
+                id: String::new(),
+                name: args.name.clone().unwrap_or_default(),
+                colors: vec![args.base],
+                dark_variant_of: None,
+                workspace_id: None,
+                locked: false,
+                export: None,
+            };
+            let scale = pipeline::apply(&base, &steps)?;
+            match args.name {
+                Some(name) => {
+                    let imported = import::ImportedPalette {
+                        name,
+                        colors: scale.colors,
+                    };
+                    let saved = crate::save_imported_palette(app, imported, "MCP")?;
+                    serde_json::to_value(saved).map_err(|e| format!("Failed to serialize: {}", e))
+                }
+                None => Ok(json!({ "colors": scale.colors })),
+            }
+        }
+        _ => Err(format!("Unknown tool: {name}")),
+    }
+}
+
+fn dispatch(
+    app: &tauri::AppHandle,
+    session: &mut Session,
+    method: &str,
+    p: Value,
+) -> Result<Value, RpcError> {
+    match method {
+        "initialize" => {
+            let p: InitializeParams = params(p)?;
+            let version = PROTOCOL_VERSIONS
+                .into_iter()
+                .find(|v| *v == p.protocol_version)
+                .unwrap_or(PROTOCOL_VERSIONS[0]);
+            session.initialized = true;
+            Ok(json!({
+                "protocolVersion": version,
+                "capabilities": { "tools": {} },
+                "serverInfo": {
+                    "name": "pixnib",
+                    "version": app.package_info().version.to_string(),
+                },
+            }))
+        }
+        "ping" => Ok(json!({})),
+        _ if !session.initialized => Err(RpcError::new(
+            json_rpc::NOT_INITIALIZED,
+            "Call initialize first",
+        )),
+        "notifications/initialized" => Ok(Value::Null),
+        "tools/list" => Ok(json!({ "tools": tools() })),
+        "tools/call" => {
+            let p: CallParams = params(p)?;
+            // Tool failures are results the assistant can read, not protocol
+            // errors
+            let (text, is_error) = match call_tool(app, &p.name, p.arguments) {
+                Ok(value) => (value.to_string(), false),
+                Err(e) => (e, true),
+            };
+            Ok(json!({
+                "content": [{ "type": "text", "text": text }],
+                "isError": is_error,
+            }))
+        }
+        _ => Err(RpcError::new(
+            json_rpc::METHOD_NOT_FOUND,
+            format!("Unknown method: {method}"),
+        )),
+    }
+}
+
+impl Protocol for Session {
+    fn handle(&mut self, app: &tauri::AppHandle, line: &str) -> Option<String> {
+        json_rpc::handle(line, |method, p| dispatch(app, self, method, p))
+    }
+}
+
+/// Start serving the pipe, unless already running.
+pub fn start(app: &tauri::AppHandle) {
+    SERVER.start::<Session>(app);
+}
+
+pub fn stop() {
+    SERVER.stop();
+}
+
+/// `pixnib --mcp`: speak MCP on stdio by relaying to the running app.
+pub fn run_stdio() -> Result<(), String> {
+    pipe_server::relay(PIPE_NAME)
+}
@@ -0,0 +1,225 @@
+//! Native-messaging host for the companion browser extension, which pushes
+//! the CSS color variables of the inspected page into a palette. Chrome, Edge
+//! and Firefox launch `pixnib.exe` as the host `com.pixnib.host`; it relays
+//! each message to the running app over the editor pipe.
+//!
+//! Messages are JSON framed by a 4-byte native-endian length, both ways:
+//!
+//! - `{"type": "ping"}` → `{"ok": true}`
+//! - `{"type": "pushColors", "page": {"title", "url"}, "variables": [{"name", "value"}]}`
+//!   → `{"ok": true, "palette": {...}}`. Values are computed colors as the
+//!   browser serializes them (hex or `rgb()`).
+//!
+//! Failures answer `{"ok": false, "error": "..."}`.
+
+use crate::import::CssVariable;
+use crate::{editor_rpc, pipe_server};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+
+pub const HOST_NAME: &str = "com.pixnib.host";
+/// Browsers refuse host messages larger than this
+const MAX_OUTGOING: usize = 1024 * 1024;
+/// Largest message read from the extension (the browser's own limit)
+const MAX_INCOMING: u32 = 64 * 1024 * 1024;
+
+#[derive(Deserialize)]
+struct Page {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    url: String,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum Message {
+    Ping,
+    PushColors {
+        page: Page,
+        variables: Vec<CssVariable>,
+    },
+}
+
+/// Whether the browser launched us as the host: Chrome and Edge pass the
+/// calling extension's origin, Firefox the path of the manifest [`install`]
+/// wrote and the add-on id. Anything else, such as a file opened with
+/// Pixnib, starts the app normally.
+pub fn is_host_launch(args: &[String]) -> bool {
+    match args {
+        [origin, ..]
+            if origin.starts_with("chrome-extension://")
+                || origin.starts_with("moz-extension://") =>
+        {
+            true
+        }
+        [manifest, _id, ..] => std::path::Path::new(manifest)
+            .file_name()
+            .is_some_and(|name| name == firefox_manifest_name().as_str()),
+        _ => false,
+    }
+}
+
+fn firefox_manifest_name() -> String {
+    format!("{HOST_NAME}.firefox.json")
+}
+
+fn read_message(input: &mut impl Read) -> Result<Option<Vec<u8>>, String> {
+    let mut len = [0u8; 4];
+    match input.read_exact(&mut len) {
+        Ok(()) => {}
+        // The browser closed the port
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(format!("Failed to read message: {}", e)),
+    }
+    let len = u32::from_ne_bytes(len);
+    if len > MAX_INCOMING {
+        return Err(format!("Message too large: {len} bytes"));
+    }
+    let mut message = vec![0u8; len as usize];
+    input
+        .read_exact(&mut message)
+        .map_err(|e| format!("Failed to read message: {}", e))?;
+    Ok(Some(message))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> Result<(), String> {
+    let mut bytes = message.to_string().into_bytes();
+    if bytes.len() > MAX_OUTGOING {
+        bytes = json!({ "ok": false, "error": "Response too large" })
+            .to_string()
+            .into_bytes();
+    }
+    output
+        .write_all(&(bytes.len() as u32).to_ne_bytes())
+        .and_then(|()| output.write_all(&bytes))
+        .and_then(|()| output.flush())
+        .map_err(|e| format!("Failed to write message: {}", e))
+}
+
+/// Forward one extension message to the running app.
+fn relay(message: &[u8]) -> Result<Value, String> {
+    let message: Message =
+        serde_json::from_slice(message).map_err(|e| format!("Invalid message: {}", e))?;
+    let (page, variables) = match message {
+        Message::Ping => return Ok(json!({ "ok": true })),
+        Message::PushColors { page, variables } => (page, variables),
+    };
+    let name = if page.title.trim().is_empty() {
+        page.url
+    } else {
+        page.title
+    };
+    let requests = [
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize",
+            "params": { "protocolVersions": [1] } }),
+        json!({ "jsonrpc": "2.0", "id": 2, "method": "importCssVariables",
+            "params": { "name": name, "variables": variables } }),
+    ];
+    let responses = pipe_server::request(editor_rpc::PIPE_NAME, &requests)?;
+    for response in &responses {
+        if let Some(message) = response.pointer("/error/message").and_then(Value::as_str) {
+            return Err(message.to_string());
+        }
+    }
+    let palette = responses
+        .last()
+        .and_then(|r| r.get("result"))
+        .cloned()
+        .ok_or("No response from Pixnib")?;
+    Ok(json!({ "ok": true, "palette": palette }))
+}
+
+/// Serve the browser on stdin/stdout until it closes the port.
+pub fn run_host() -> Result<(), String> {
+    let mut input = std::io::stdin().lock();
+    let mut output = std::io::stdout().lock();
+    while let Some(message) = read_message(&mut input)? {
+        let reply = relay(&message).unwrap_or_else(|e| json!({ "ok": false, "error": e }));
+        write_message(&mut output, &reply)?;
+    }
+    Ok(())
+}
+
+/// Write the host manifests to `dir` and register them with Chrome, Edge and
+/// Firefox for the current user. `chrome_extension_id` covers Chrome and
+/// Edge; `firefox_extension_id` is the add-on id from its manifest.
+pub fn install(
+    dir: &std::path::Path,
+    chrome_extension_id: Option<&str>,
+    firefox_extension_id: Option<&str>,
+) -> Result<(), String> {
+    let exe = std::env::current_exe()
+        .map_err(|e| format!("Failed to locate executable: {}", e))?
+        .to_string_lossy()
+        .into_owned();
+    let manifest = |key: &str, allowed: Value| {
+        let mut manifest = json!({
+            "name": HOST_NAME,
+            "description": "Pixnib color picker",
+            "path": exe,
+            "type": "stdio",
+        });
+        manifest[key] = allowed;
+        manifest
+    };
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let mut registrations = Vec::new();
+    if let Some(id) = chrome_extension_id {
+        let path = dir.join(format!("{HOST_NAME}.chrome.json"));
+        let origin = format!("chrome-extension://{id}/");
+        write_manifest(&path, &manifest("allowed_origins", json!([origin])))?;
+        registrations.push((r"Software\Google\Chrome\NativeMessagingHosts", path.clone()));
+        registrations.push((r"Software\Microsoft\Edge\NativeMessagingHosts", path));
+    }
+    if let Some(id) = firefox_extension_id {
+        let path = dir.join(firefox_manifest_name());
+        write_manifest(&path, &manifest("allowed_extensions", json!([id])))?;
+        registrations.push((r"Software\Mozilla\NativeMessagingHosts", path));
+    }
+    if registrations.is_empty() {
+        return Err("Give a Chrome or Firefox extension id".to_string());
+    }
+    for (key, path) in registrations {
+        win::register(&format!(r"{key}\{HOST_NAME}"), &path.to_string_lossy())?;
+    }
+    Ok(())
+}
+
+fn write_manifest(path: &std::path::Path, manifest: &Value) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(manifest)
+        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    std::fs::write(path, text).map_err(|e| format!("Failed to write manifest: {}", e))
+}
+
+#[cfg(windows)]
+mod win {
+    use windows::core::{HSTRING, PCWSTR};
+    use windows::Win32::System::Registry::{RegSetKeyValueW, HKEY_CURRENT_USER, REG_SZ};
+
+    /// Point the default value of `HKCU\<key>` at the manifest.
+    pub fn register(key: &str, manifest: &str) -> Result<(), String> {
+        let value: Vec<u16> = manifest.encode_utf16().chain(Some(0)).collect();
+        unsafe {
+            RegSetKeyValueW(
+                HKEY_CURRENT_USER,
+                &HSTRING::from(key),
+                PCWSTR::null(),
+                REG_SZ.0,
+                Some(value.as_ptr().cast()),
+                (value.len() * 2) as u32,
+            )
+        }
+        .ok()
+        .map_err(|e| format!("Failed to register {}: {}", key, e))
+    }
+}
+
+#[cfg(not(windows))]
+mod win {
+    pub fn register(_key: &str, _manifest: &str) -> Result<(), String> {
+        Err("Registering the browser host is only supported on Windows".to_string())
+    }
+}
@@ -0,0 +1,144 @@
+//! Optional store of palettes as one plain-text file per palette in a
+//! user-chosen directory, so a team can keep palettes in Git. Files are
+//! pretty-printed with sorted keys and only rewritten when their content
+//! changes, so diffs show just what was edited. Palette files added,
+//! changed or removed in the directory (e.g. by `git pull`) are read back
+//! with [`sync`].
+
+use crate::{export, Palette};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Suffix of palette files; other files in the directory are never touched.
+const EXTENSION: &str = ".palette.json";
+/// File names this app last wrote, with their palette ids. Only these are
+/// ever deleted, so palette files added by others survive.
+const MANIFEST: &str = ".pixnib-palettes.json";
+
+type Manifest = BTreeMap<String, String>;
+
+fn read_manifest(dir: &Path) -> Manifest {
+    std::fs::read_to_string(dir.join(MANIFEST))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Palette as written to its file: keys sorted, two-space indent, trailing
+/// newline.
+fn to_text(palette: &Palette) -> Result<String, String> {
+    // serde_json's map is ordered by key, so the round trip sorts them
+    let value =
+        serde_json::to_value(palette).map_err(|e| format!("Failed to serialize palette: {}", e))?;
+    let mut text = serde_json::to_string_pretty(&value)
+        .map_err(|e| format!("Failed to serialize palette: {}", e))?;
+    text.push('\n');
+    Ok(text)
+}
+
+/// File names for `palettes`, by slug of the name. Clashing names get the
+/// palette id appended; palettes are visited by id so the same palette wins
+/// the plain name on every write.
+fn file_names(palettes: &[Palette]) -> Vec<(String, &Palette)> {
+    let mut sorted: Vec<&Palette> = palettes.iter().collect();
+    sorted.sort_by(|a, b| a.id.cmp(&b.id));
+    let mut taken = HashSet::new();
+    sorted
+        .into_iter()
+        .map(|palette| {
+            let slug = export::slug(&palette.name);
+            let name = if taken.insert(slug.clone()) {
+                format!("{slug}{EXTENSION}")
+            } else {
+                format!("{slug}-{}{EXTENSION}", export::slug(&palette.id))
+            };
+            (name, palette)
+        })
+        .collect()
+}
+
+/// Write every palette to `dir`, skipping files whose content is already
+/// current, and delete files this app wrote for palettes since removed or
+/// renamed.
+pub fn write_all(dir: &Path, palettes: &[Palette]) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let mut written = Manifest::new();
+    for (name, palette) in file_names(palettes) {
+        let path = dir.join(&name);
+        let text = to_text(palette)?;
+        if std::fs::read_to_string(&path).ok().as_deref() != Some(text.as_str()) {
+            std::fs::write(&path, text)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        }
+        written.insert(name, palette.id.clone());
+    }
+
+    for name in read_manifest(dir).into_keys() {
+        let path = dir.join(&name);
+        if !written.contains_key(&name) && path.exists() {
+            std::fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", name, e))?;
+        }
+    }
+    let json = serde_json::to_string_pretty(&written)
+        .map_err(|e| format!("Failed to serialize palette manifest: {}", e))?;
+    std::fs::write(dir.join(MANIFEST), json + "\n")
+        .map_err(|e| format!("Failed to write palette manifest: {}", e))
+}
+
+/// `palettes` updated from the files in `dir`: a palette file's content
+/// replaces the stored palette with its id, files of unknown palettes are
+/// added, and palettes whose file this app wrote has since disappeared are
+/// dropped. Files that don't parse are skipped.
+pub fn sync(dir: &Path, palettes: &[Palette]) -> Result<Vec<Palette>, String> {
+    if !dir.exists() {
+        return Ok(palettes.to_vec());
+    }
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    let mut found = HashSet::new();
+    let mut from_files: Vec<Palette> = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !name.ends_with(EXTENSION) {
+            continue;
+        }
+        found.insert(name);
+        let parsed = std::fs::read_to_string(entry.path())
+            .ok()
+            .and_then(|json| serde_json::from_str::<Palette>(&json).ok());
+        match parsed {
+            Some(palette) if !from_files.iter().any(|p| p.id == palette.id) => {
+                from_files.push(palette)
+            }
+            Some(_) => {}
+            None => eprintln!(
+                "Skipping unreadable palette file {}",
+                entry.path().display()
+            ),
+        }
+    }
+
+    let removed: HashSet<String> = read_manifest(dir)
+        .into_iter()
+        .filter(|(name, id)| !found.contains(name) && !from_files.iter().any(|p| &p.id == id))
+        .map(|(_, id)| id)
+        .collect();
+    let mut merged: Vec<Palette> = palettes
+        .iter()
+        .filter(|p| !removed.contains(&p.id))
+        .map(|p| {
+            from_files
+                .iter()
+                .find(|f| f.id == p.id)
+                .unwrap_or(p)
+                .clone()
+        })
+        .collect();
+    for palette in from_files {
+        if !merged.iter().any(|p| p.id == palette.id) {
+            merged.push(palette);
+        }
+    }
+    Ok(merged)
+}
@@ -0,0 +1,78 @@
+//! Snapshots of each palette as it was before every change, so an accidental
+//! bulk edit or deletion can be rolled back. Only the newest
+//! [`MAX_VERSIONS`] per palette are kept.
+
+use crate::Palette;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+const MAX_VERSIONS: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteVersion {
+    /// Increases with every snapshot of the palette, never reused
+    pub version: u32,
+    /// Unix milliseconds when the palette was changed away from this state
+    pub saved_at: u64,
+    pub palette: Palette,
+}
+
+/// Snapshots by palette id, oldest first.
+pub type Versions = BTreeMap<String, Vec<PaletteVersion>>;
+
+/// Snapshot every palette of `before` that `after` edits or removes.
+/// Locking or unlocking alone isn't an edit. Returns whether anything was
+/// recorded.
+pub fn record(versions: &mut Versions, before: &[Palette], after: &[Palette], at: u64) -> bool {
+    let new: HashMap<&str, &Palette> = after.iter().map(|p| (p.id.as_str(), p)).collect();
+    let mut recorded = false;
+    for palette in before {
+        let unchanged = new.get(palette.id.as_str()).is_some_and(|p| {
+            **p == Palette {
+                locked: p.locked,
+                ..palette.clone()
+            }
+        });
+        if unchanged {
+            continue;
+        }
+        let snapshots = versions.entry(palette.id.clone()).or_default();
+        let version = snapshots.last().map_or(1, |v| v.version + 1);
+        snapshots.push(PaletteVersion {
+            version,
+            saved_at: at,
+            palette: palette.clone(),
+        });
+        if snapshots.len() > MAX_VERSIONS {
+            snapshots.drain(..snapshots.len() - MAX_VERSIONS);
+        }
+        recorded = true;
+    }
+    recorded
+}
+
+/// Snapshots of one palette, newest first.
+pub fn list(versions: &Versions, palette_id: &str) -> Vec<PaletteVersion> {
+    versions
+        .get(palette_id)
+        .map(|v| v.iter().rev().cloned().collect())
+        .unwrap_or_default()
+}
+
+pub fn find(versions: &Versions, palette_id: &str, version: u32) -> Option<Palette> {
+    versions
+        .get(palette_id)?
+        .iter()
+        .find(|v| v.version == version)
+        .map(|v| v.palette.clone())
+}
+
+/// Ids of deleted palettes that still have snapshots, so they can be restored.
+pub fn deleted<'a>(versions: &'a Versions, palettes: &[Palette]) -> Vec<&'a str> {
+    versions
+        .keys()
+        .filter(|id| !palettes.iter().any(|p| &p.id == *id))
+        .map(String::as_str)
+        .collect()
+}
@@ -0,0 +1,82 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Samples kept per stage; at loupe polling rates this is a few seconds.
+const WINDOW: usize = 120;
+
+pub const GET_PIXEL: &str = "getPixel";
+pub const LOUPE_CAPTURE: &str = "loupeCapture";
+pub const LOUPE_ENCODE: &str = "loupeEncode";
+pub const SCREEN_CAPTURE: &str = "screenCapture";
+pub const AREA_CROP: &str = "areaCrop";
+
+static SAMPLES: Mutex<Vec<(&'static str, VecDeque<Duration>)>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StageMetrics {
+    pub stage: String,
+    pub samples: usize,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+    pub last_ms: f64,
+}
+
+pub fn record(stage: &'static str, elapsed: Duration) {
+    let Ok(mut samples) = SAMPLES.lock() else {
+        return;
+    };
+    let idx = match samples.iter().position(|(s, _)| *s == stage) {
+        Some(i) => i,
+        None => {
+            samples.push((stage, VecDeque::with_capacity(WINDOW)));
+            samples.len() - 1
+        }
+    };
+    let window = &mut samples[idx].1;
+    if window.len() == WINDOW {
+        window.pop_front();
+    }
+    window.push_back(elapsed);
+}
+
+/// Run `f`, recording how long it took under `stage`.
+pub fn time<T>(stage: &'static str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    record(stage, start.elapsed());
+    result
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Rolling averages over the last [`WINDOW`] samples of every stage seen.
+pub fn snapshot() -> Vec<StageMetrics> {
+    let Ok(samples) = SAMPLES.lock() else {
+        return Vec::new();
+    };
+    samples
+        .iter()
+        .filter(|(_, w)| !w.is_empty())
+        .map(|(stage, w)| {
+            let total: Duration = w.iter().sum();
+            StageMetrics {
+                stage: stage.to_string(),
+                samples: w.len(),
+                avg_ms: ms(total) / w.len() as f64,
+                max_ms: w.iter().copied().max().map(ms).unwrap_or(0.0),
+                last_ms: w.back().copied().map(ms).unwrap_or(0.0),
+            }
+        })
+        .collect()
+}
+
+pub fn reset() {
+    if let Ok(mut samples) = SAMPLES.lock() {
+        samples.clear();
+    }
+}
@@ -0,0 +1,233 @@
+//! Single owner of pick-mode state. Every way into or out of picking (global
+//! shortcuts, the tray, commands, display changes, shutdown) goes through
+//! [`transition`], so the cursor, helper windows and frontend events always
+//! agree with the state.
+
+use crate::state::AppState;
+use crate::{color_picker, storage, ColorInfo};
+use serde::Serialize;
+use std::sync::mpsc;
+use std::time::Duration;
+use tauri::{Emitter, Listener, Manager};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PickMode {
+    /// Live screen with the loupe following the cursor
+    Loupe,
+    /// As `Loupe`, but reading a snapshot taken when picking started
+    Frozen,
+    /// Drag-to-average overlay over the monitor under the cursor
+    Area,
+}
+
+pub enum PickEvent {
+    Start(PickMode),
+    /// A color was captured; leaves pick mode and delivers it
+    Picked(ColorInfo),
+    /// Leave pick mode without a color
+    Cancel,
+    /// Leave pick mode without bringing back the main window or reporting
+    /// an outcome, for shutdown
+    Abort,
+}
+
+/// Emitted as `pick-state-changed` after every transition; `None` is idle.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PickStateChanged {
+    pub from: Option<PickMode>,
+    pub to: Option<PickMode>,
+}
+
+pub fn current(app: &tauri::AppHandle) -> Option<PickMode> {
+    *app.state::<AppState>().pick()
+}
+
+pub fn is_active(app: &tauri::AppHandle) -> bool {
+    current(app).is_some()
+}
+
+/// Apply `event`. Starting while already picking, or picking or cancelling
+/// while idle, is refused and changes nothing.
+pub fn transition(app: &tauri::AppHandle, event: PickEvent) -> Result<(), String> {
+    // Only the state change happens under the lock: entering and leaving
+    // deliver picks, run hooks and show windows, any of which may come back
+    // here
+    let (from, to) = {
+        let app_state = app.state::<AppState>();
+        let mut state = app_state.pick();
+        let from = *state;
+        let to = match (from, &event) {
+            (None, PickEvent::Start(mode)) => Some(*mode),
+            (Some(_), PickEvent::Start(_)) => return Err("Already picking".to_string()),
+            (Some(_), _) => None,
+            (None, _) => return Err("Not picking".to_string()),
+        };
+        *state = to;
+        (from, to)
+    };
+    match (from, to) {
+        (Some(mode), _) => match event {
+            PickEvent::Picked(color) => leave(app, mode, Some(color)),
+            PickEvent::Abort => release(app, mode),
+            _ => leave(app, mode, None),
+        },
+        (None, Some(mode)) => {
+            if let Err(e) = enter(app, mode) {
+                let app_state = app.state::<AppState>();
+                let mut state = app_state.pick();
+                if *state == Some(mode) {
+                    *state = None;
+                }
+                return Err(e);
+            }
+            // A cancel that arrived while entering released too early
+            if current(app) != Some(mode) {
+                release(app, mode);
+            }
+        }
+        (None, None) => {}
+    }
+    let _ = app.emit("pick-state-changed", PickStateChanged { from, to });
+    Ok(())
+}
+
+/// Start a loupe pick and block until it ends, for callers outside the UI.
+/// Returns the color as emitted in `color-picked`, or `None` when the pick
+/// was cancelled. A pick still open after `timeout` is cancelled. Refused
+/// while an averaging session runs, since that would take the pick as a
+/// sample.
+pub fn wait_for_pick(
+    app: &tauri::AppHandle,
+    timeout: Duration,
+) -> Result<Option<serde_json::Value>, String> {
+    if app.state::<AppState>().averaging().is_some() {
+        return Err("An averaging session is running; finish or cancel it first".to_string());
+    }
+    let (tx, rx) = mpsc::channel();
+    let picked_tx = tx.clone();
+    let picked = app.listen("color-picked", move |event| {
+        let _ = picked_tx.send(serde_json::from_str(event.payload()).ok());
+    });
+    let stopped = app.listen("pick-mode-stopped", move |_| {
+        let _ = tx.send(None);
+    });
+    let result = transition(app, PickEvent::Start(PickMode::Loupe)).and_then(|()| {
+        rx.recv_timeout(timeout).map_err(|_| {
+            let _ = transition(app, PickEvent::Cancel);
+            "Pick timed out".to_string()
+        })
+    });
+    app.unlisten(picked);
+    app.unlisten(stopped);
+    result
+}
+
+fn show_main(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn enter(app: &tauri::AppHandle, mode: PickMode) -> Result<(), String> {
+    color_picker::remember_paste_target();
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+    match mode {
+        // Wayland: the compositor's picker replaces the loupe and cursor.
+        // It reports back here once the user picks or dismisses it
+        PickMode::Loupe | PickMode::Frozen if color_picker::uses_system_picker() => {
+            let _ = app.emit("pick-mode-started", ());
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let event = match color_picker::pick_with_portal().await {
+                    Ok(Some(mut color)) => {
+                        color.delta_e = crate::reference::delta_e(&app, color.rgb);
+                        PickEvent::Picked(color)
+                    }
+                    Ok(None) => PickEvent::Cancel,
+                    Err(e) => {
+                        let _ = app.emit("pick-failed", crate::elevation::PickError::from(e));
+                        PickEvent::Cancel
+                    }
+                };
+                let _ = transition(&app, event);
+            });
+        }
+        PickMode::Loupe | PickMode::Frozen => {
+            if mode == PickMode::Frozen {
+                if let Err(e) = color_picker::freeze_screen() {
+                    show_main(app);
+                    return Err(e);
+                }
+            }
+            let _ = app.emit("pick-mode-started", ());
+            if let Some(loupe) = app.get_webview_window("loupe") {
+                let _ = loupe.show();
+            }
+            let settings = storage::load_settings(app);
+            color_picker::set_game_mode(settings.game_mode);
+            color_picker::set_pick_cursor(settings.high_contrast_cursor);
+        }
+        PickMode::Area => {
+            // Capture the clean screen before the overlay appears
+            if let Err(e) = color_picker::capture_area_snapshot() {
+                show_main(app);
+                return Err(e);
+            }
+            let region = app
+                .get_webview_window("region")
+                .ok_or("region window missing")?;
+            let (cx, cy) = color_picker::cursor_pos().unwrap_or((0, 0));
+            if let Ok(Some(monitor)) = app.monitor_from_point(cx as f64, cy as f64) {
+                let _ = region.set_position(*monitor.position());
+                let _ = region.set_size(*monitor.size());
+            }
+            let _ = region.show();
+            let _ = region.set_focus();
+        }
+    }
+    Ok(())
+}
+
+/// Undo what entering `mode` changed: cursor, snapshots and helper windows.
+fn release(app: &tauri::AppHandle, mode: PickMode) {
+    match mode {
+        PickMode::Loupe | PickMode::Frozen => {
+            color_picker::restore_default_cursor();
+            color_picker::unfreeze_screen();
+            if let Some(loupe) = app.get_webview_window("loupe") {
+                let _ = loupe.hide();
+            }
+        }
+        PickMode::Area => {
+            color_picker::clear_area_snapshot();
+            if let Some(region) = app.get_webview_window("region") {
+                let _ = region.hide();
+            }
+        }
+    }
+}
+
+/// Release `mode` and report the outcome: `color-picked` when a color was
+/// captured, `pick-mode-stopped` otherwise. The main window comes back unless
+/// the pick is being pasted or a fullscreen app asked for quiet.
+fn leave(app: &tauri::AppHandle, mode: PickMode, picked: Option<ColorInfo>) {
+    release(app, mode);
+    let restore_main = match picked {
+        Some(_) => crate::show_main_after_pick(app),
+        None => !crate::fullscreen_quiet(app),
+    };
+    match picked {
+        Some(color) => crate::deliver_pick(app, color),
+        None => {
+            let _ = app.emit("pick-mode-stopped", ());
+        }
+    }
+    if restore_main {
+        show_main(app);
+    }
+}
@@ -0,0 +1,242 @@
+//! Local named-pipe transport for the line-based protocols external tools
+//! speak to the running app. Each connection gets its own thread and its own
+//! protocol session.
+#![cfg_attr(not(windows), allow(dead_code))]
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// A protocol served over a pipe: one instance per connection, fed one
+/// line at a time.
+pub trait Protocol: Default + Send + 'static {
+    /// The response line for `line`, if any.
+    fn handle(&mut self, app: &tauri::AppHandle, line: &str) -> Option<String>;
+}
+
+pub struct PipeServer {
+    name: &'static str,
+    running: AtomicBool,
+    /// Bumped on every start so a listener left over from a previous start
+    /// exits instead of serving alongside the new one
+    generation: AtomicU64,
+}
+
+impl PipeServer {
+    pub const fn new(name: &'static str) -> Self {
+        PipeServer {
+            name,
+            running: AtomicBool::new(false),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Start listening on a background thread, unless already running.
+    pub fn start<P: Protocol>(&'static self, app: &tauri::AppHandle) {
+        if self.running.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let app = app.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = imp::listen::<P>(self, generation, &app) {
+                eprintln!("Pipe {} stopped: {}", self.name, e);
+            }
+            if self.generation.load(Ordering::SeqCst) == generation {
+                self.running.store(false, Ordering::SeqCst);
+            }
+        });
+    }
+
+    /// Stop accepting connections; clients already connected are served
+    /// until they disconnect.
+    pub fn stop(&self) {
+        if self.running.swap(false, Ordering::SeqCst) {
+            // Wake the listener blocked waiting for a client so it sees the flag
+            let _ = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(self.name);
+        }
+    }
+
+    fn is_current(&self, generation: u64) -> bool {
+        self.running.load(Ordering::SeqCst) && self.generation.load(Ordering::SeqCst) == generation
+    }
+}
+
+/// Connect to the pipe `name` of the running app and shuttle lines between
+/// it and stdin/stdout until either side closes, for clients that can only
+/// launch a process.
+pub fn relay(name: &str) -> Result<(), String> {
+    use std::io::{BufRead, Write};
+
+    let pipe = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(name)
+        .map_err(|e| format!("Failed to connect to {} (is Pixnib running?): {}", name, e))?;
+    let reader = pipe
+        .try_clone()
+        .map_err(|e| format!("Failed to clone pipe handle: {}", e))?;
+    std::thread::spawn(move || {
+        let mut stdout = std::io::stdout().lock();
+        for line in std::io::BufReader::new(reader)
+            .lines()
+            .map_while(Result::ok)
+        {
+            if writeln!(stdout, "{line}")
+                .and_then(|()| stdout.flush())
+                .is_err()
+            {
+                break;
+            }
+        }
+        // The app went away; nothing more will arrive
+        std::process::exit(0);
+    });
+    let mut pipe = pipe;
+    for line in std::io::stdin().lock().lines() {
+        let line = line.map_err(|e| format!("Failed to read stdin: {}", e))?;
+        writeln!(pipe, "{line}")
+            .and_then(|()| pipe.flush())
+            .map_err(|e| format!("Failed to write to pipe: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Send `requests` to the pipe `name`, one line each, and read one response
+/// line per request, for one-shot command-line clients.
+pub fn request(
+    name: &str,
+    requests: &[serde_json::Value],
+) -> Result<Vec<serde_json::Value>, String> {
+    use std::io::{BufRead, Write};
+
+    let mut pipe = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(name)
+        .map_err(|e| format!("Failed to connect to {} (is Pixnib running?): {}", name, e))?;
+    let mut lines = std::io::BufReader::new(
+        pipe.try_clone()
+            .map_err(|e| format!("Failed to clone pipe handle: {}", e))?,
+    )
+    .lines();
+    requests
+        .iter()
+        .map(|request| {
+            writeln!(pipe, "{request}")
+                .and_then(|()| pipe.flush())
+                .map_err(|e| format!("Failed to write to pipe: {}", e))?;
+            let line = lines
+                .next()
+                .ok_or("Pipe closed before responding")?
+                .map_err(|e| format!("Failed to read response: {}", e))?;
+            serde_json::from_str(&line).map_err(|e| format!("Invalid response: {}", e))
+        })
+        .collect()
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::{PipeServer, Protocol};
+    use std::fs::File;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::windows::io::FromRawHandle;
+    use windows::core::HSTRING;
+    use windows::Win32::Foundation::{CloseHandle, ERROR_PIPE_CONNECTED};
+    use windows::Win32::Storage::FileSystem::{FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_DUPLEX};
+    use windows::Win32::System::Pipes::{
+        ConnectNamedPipe, CreateNamedPipeW, PIPE_READMODE_BYTE, PIPE_REJECT_REMOTE_CLIENTS,
+        PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+    };
+
+    const BUFFER_SIZE: u32 = 64 * 1024;
+
+    /// Create one pipe instance per client, serving each on its own thread.
+    /// The first instance claims the name so no other process can squat it.
+    pub fn listen<P: Protocol>(
+        server: &PipeServer,
+        generation: u64,
+        app: &tauri::AppHandle,
+    ) -> Result<(), String> {
+        let name = HSTRING::from(server.name);
+        let mut first = true;
+        while server.is_current(generation) {
+            let mut open_mode = PIPE_ACCESS_DUPLEX;
+            if first {
+                open_mode |= FILE_FLAG_FIRST_PIPE_INSTANCE;
+            }
+            let pipe = unsafe {
+                CreateNamedPipeW(
+                    &name,
+                    open_mode,
+                    PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+                    PIPE_UNLIMITED_INSTANCES,
+                    BUFFER_SIZE,
+                    BUFFER_SIZE,
+                    0,
+                    None,
+                )
+            };
+            if pipe.is_invalid() {
+                return Err(format!(
+                    "Failed to create pipe: {}",
+                    windows::core::Error::from_win32()
+                ));
+            }
+            first = false;
+            if let Err(e) = unsafe { ConnectNamedPipe(pipe, None) } {
+                if e.code() != ERROR_PIPE_CONNECTED.to_hresult() {
+                    unsafe {
+                        let _ = CloseHandle(pipe);
+                    }
+                    return Err(format!("Failed to accept connection: {}", e));
+                }
+            }
+            // The handle is owned by the File from here on
+            let file = unsafe { File::from_raw_handle(pipe.0) };
+            if !server.is_current(generation) {
+                break;
+            }
+            let app = app.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = serve::<P>(&app, file) {
+                    eprintln!("Pipe client dropped: {}", e);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn serve<P: Protocol>(app: &tauri::AppHandle, file: File) -> Result<(), String> {
+        let mut writer = file
+            .try_clone()
+            .map_err(|e| format!("Failed to clone pipe handle: {}", e))?;
+        let mut session = P::default();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| format!("Failed to read request: {}", e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(reply) = session.handle(app, &line) {
+                writeln!(writer, "{reply}")
+                    .and_then(|()| writer.flush())
+                    .map_err(|e| format!("Failed to write response: {}", e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(windows))]
+mod imp {
+    use super::{PipeServer, Protocol};
+
+    pub fn listen<P: Protocol>(
+        _server: &PipeServer,
+        _generation: u64,
+        _app: &tauri::AppHandle,
+    ) -> Result<(), String> {
+        Err("Named pipes are only available on Windows".to_string())
+    }
+}
@@ -0,0 +1,110 @@
+//! xdg-desktop-portal access for Wayland sessions, where apps can neither
+//! read the cursor position nor other windows' pixels. The compositor picks
+//! colors and takes screenshots on our behalf (`org.freedesktop.portal.
+//! Screenshot`), asking the user for permission the first time; a denied or
+//! dismissed request comes back as cancelled.
+
+/// Whether picks and captures must go through the portal instead of X11.
+pub fn active() -> bool {
+    imp::active()
+}
+
+/// Let the user pick a color with the compositor's picker. `None` when they
+/// dismissed it or denied access.
+pub async fn pick_color() -> Result<Option<[u8; 3]>, String> {
+    imp::pick_color().await
+}
+
+/// Full-desktop screenshot, waiting for the user to allow it if asked.
+/// Blocks; run it off the UI thread.
+pub fn screenshot() -> Result<image::RgbaImage, String> {
+    imp::screenshot()
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use ashpd::desktop::{screenshot::Screenshot, Color, ResponseError};
+    use std::sync::OnceLock;
+
+    pub fn active() -> bool {
+        static WAYLAND: OnceLock<bool> = OnceLock::new();
+        *WAYLAND.get_or_init(|| {
+            std::env::var("XDG_SESSION_TYPE").is_ok_and(|t| t.eq_ignore_ascii_case("wayland"))
+        })
+    }
+
+    fn message(e: ashpd::Error) -> String {
+        match e {
+            ashpd::Error::PortalNotFound(_) => {
+                "No screenshot portal is available (is xdg-desktop-portal installed?)".to_string()
+            }
+            ashpd::Error::Response(ResponseError::Other) => {
+                "The screenshot portal refused the request".to_string()
+            }
+            e => format!("Screenshot portal failed: {}", e),
+        }
+    }
+
+    pub async fn pick_color() -> Result<Option<[u8; 3]>, String> {
+        let color = match Color::pick().send().await.and_then(|r| r.response()) {
+            Ok(color) => color,
+            Err(ashpd::Error::Response(ResponseError::Cancelled)) => return Ok(None),
+            Err(e) => return Err(message(e)),
+        };
+        let channel = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Ok(Some([
+            channel(color.red()),
+            channel(color.green()),
+            channel(color.blue()),
+        ]))
+    }
+
+    pub fn screenshot() -> Result<image::RgbaImage, String> {
+        // The request is async and may wait on a permission dialog; callers
+        // can be on a runtime thread already, so drive it from a fresh one
+        let response = std::thread::spawn(|| {
+            tauri::async_runtime::block_on(async {
+                Screenshot::request()
+                    .interactive(false)
+                    .modal(false)
+                    .send()
+                    .await
+                    .and_then(|r| r.response())
+            })
+        })
+        .join()
+        .map_err(|_| "Screenshot portal request panicked".to_string())?;
+        let screenshot = match response {
+            Ok(screenshot) => screenshot,
+            Err(ashpd::Error::Response(ResponseError::Cancelled)) => {
+                return Err("Screen capture was not allowed".to_string())
+            }
+            Err(e) => return Err(message(e)),
+        };
+        let path = screenshot
+            .uri()
+            .to_file_path()
+            .map_err(|_| format!("Unexpected screenshot location: {}", screenshot.uri()))?;
+        let image = image::open(&path)
+            .map_err(|e| format!("Failed to read screenshot: {}", e))?
+            .to_rgba8();
+        // The portal saves into the user's pictures; this one was only for us
+        let _ = std::fs::remove_file(&path);
+        Ok(image)
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod imp {
+    pub fn active() -> bool {
+        false
+    }
+
+    pub async fn pick_color() -> Result<Option<[u8; 3]>, String> {
+        Err("The screenshot portal is only used on Linux".to_string())
+    }
+
+    pub fn screenshot() -> Result<image::RgbaImage, String> {
+        Err("The screenshot portal is only used on Linux".to_string())
+    }
+}
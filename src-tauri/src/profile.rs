@@ -0,0 +1,172 @@
+use crate::color_names::NameLocale;
+use crate::copy_format::CopyFormat;
+use crate::exporters::TemplateExporter;
+use crate::loupe::LoupeSettings;
+use crate::png_encoder::PngOptions;
+use crate::sampling::Sampling;
+use crate::shortcuts::Action;
+use crate::storage::AppSettings;
+use crate::tag_rules::TagRule;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const FORMAT_TAG: &str = "colorsnap-profile";
+/// Bump when the layout changes; profiles newer than this are rejected.
+const CURRENT_VERSION: u32 = 1;
+
+/// Shareable settings profile: the team-wide parts of the configuration.
+/// Machine-specific settings (active workspace, post-pick hook) are left out
+/// so importing a profile never runs someone else's command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    format: String,
+    version: u32,
+    #[serde(default)]
+    exported_at: u64,
+    #[serde(default)]
+    app_version: String,
+    #[serde(default)]
+    pub shortcuts: BTreeMap<Action, String>,
+    #[serde(default)]
+    pub copy_format: CopyFormat,
+    #[serde(default)]
+    pub copy_template: Option<String>,
+    #[serde(default)]
+    pub auto_label: bool,
+    #[serde(default)]
+    pub paste_after_pick: bool,
+    #[serde(default)]
+    pub announce_colors: bool,
+    #[serde(default)]
+    pub high_contrast_cursor: bool,
+    #[serde(default)]
+    pub game_mode: bool,
+    #[serde(default)]
+    pub white_point_report: bool,
+    #[serde(default)]
+    pub quiet_in_fullscreen: bool,
+    #[serde(default)]
+    pub ignore_focus_assist: bool,
+    #[serde(default)]
+    pub color_names: NameLocale,
+    #[serde(default)]
+    pub loupe: LoupeSettings,
+    #[serde(default)]
+    pub sampling: Sampling,
+    #[serde(default)]
+    pub history_retention_days: Option<u32>,
+    #[serde(default)]
+    pub import_dedupe: Option<f64>,
+    #[serde(default)]
+    pub tag_rules: Vec<TagRule>,
+    #[serde(default)]
+    pub history_journal: bool,
+    #[serde(default)]
+    pub png: PngOptions,
+    #[serde(default)]
+    pub exporters: Vec<TemplateExporter>,
+}
+
+/// Outcome of an import; shortcuts that could not be registered on this
+/// machine are reported rather than failing the whole profile.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileImport {
+    pub shortcuts_applied: usize,
+    pub shortcut_errors: Vec<String>,
+    pub exporters_imported: usize,
+}
+
+pub fn to_json(
+    settings: &AppSettings,
+    exporters: &[TemplateExporter],
+    app_version: &str,
+    exported_at: u64,
+) -> Result<String, String> {
+    let profile = Profile {
+        format: FORMAT_TAG.to_string(),
+        version: CURRENT_VERSION,
+        exported_at,
+        app_version: app_version.to_string(),
+        shortcuts: settings.shortcuts.clone(),
+        copy_format: settings.copy_format,
+        copy_template: settings.copy_template.clone(),
+        auto_label: settings.auto_label,
+        paste_after_pick: settings.paste_after_pick,
+        announce_colors: settings.announce_colors,
+        high_contrast_cursor: settings.high_contrast_cursor,
+        game_mode: settings.game_mode,
+        white_point_report: settings.white_point_report,
+        quiet_in_fullscreen: settings.quiet_in_fullscreen,
+        ignore_focus_assist: settings.ignore_focus_assist,
+        color_names: settings.color_names,
+        loupe: settings.loupe,
+        sampling: settings.sampling,
+        history_retention_days: settings.history_retention_days,
+        import_dedupe: settings.import_dedupe,
+        tag_rules: settings.tag_rules.clone(),
+        history_journal: settings.history_journal,
+        png: settings.png,
+        exporters: exporters.to_vec(),
+    };
+    serde_json::to_string_pretty(&profile)
+        .map_err(|e| format!("Failed to serialize profile: {}", e))
+}
+
+pub fn parse(json: &str) -> Result<Profile, String> {
+    let profile: Profile =
+        serde_json::from_str(json).map_err(|e| format!("Not a valid settings profile: {}", e))?;
+    if profile.format != FORMAT_TAG {
+        return Err("Not a ColorSnap settings profile".to_string());
+    }
+    if profile.version > CURRENT_VERSION {
+        return Err(format!(
+            "This profile was saved by a newer version (format v{}); please update",
+            profile.version
+        ));
+    }
+    if profile.copy_format == CopyFormat::Custom && profile.copy_template.is_none() {
+        return Err("Profile uses the custom copy format without a template".to_string());
+    }
+    Ok(profile)
+}
+
+/// Copy the profile's formats and toggles into `settings`. Shortcuts are
+/// applied separately since they have to be registered with the OS.
+pub fn apply(profile: &Profile, settings: &mut AppSettings) {
+    settings.copy_format = profile.copy_format;
+    settings.copy_template = profile.copy_template.clone();
+    settings.auto_label = profile.auto_label;
+    settings.paste_after_pick = profile.paste_after_pick;
+    settings.announce_colors = profile.announce_colors;
+    settings.high_contrast_cursor = profile.high_contrast_cursor;
+    settings.game_mode = profile.game_mode;
+    settings.white_point_report = profile.white_point_report;
+    settings.quiet_in_fullscreen = profile.quiet_in_fullscreen;
+    settings.ignore_focus_assist = profile.ignore_focus_assist;
+    settings.color_names = profile.color_names;
+    settings.loupe = profile.loupe.sanitized();
+    settings.sampling = profile.sampling.sanitized();
+    settings.history_retention_days = profile.history_retention_days;
+    settings.import_dedupe = profile.import_dedupe;
+    settings.tag_rules = profile
+        .tag_rules
+        .iter()
+        .filter(|r| r.validate().is_ok())
+        .cloned()
+        .collect();
+    settings.history_journal = profile.history_journal;
+    settings.png = profile.png;
+}
+
+/// Merge imported exporters into the existing ones: same id replaces,
+/// everything else is kept in order.
+pub fn merge_exporters(existing: &mut Vec<TemplateExporter>, imported: &[TemplateExporter]) {
+    for exporter in imported {
+        match existing.iter_mut().find(|e| e.id == exporter.id) {
+            Some(slot) => *slot = exporter.clone(),
+            None => existing.push(exporter.clone()),
+        }
+    }
+}
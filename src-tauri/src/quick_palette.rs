@@ -0,0 +1,82 @@
+//! Popup of recent colors summoned by its own shortcut, like Win+V for the
+//! clipboard: it opens next to the cursor, a click copies a color, and it
+//! hides again on copy, Escape or losing focus. The window is created once at
+//! startup and only shown, moved and hidden from here.
+
+use crate::{color_picker, storage, ColorEntry};
+use serde::Serialize;
+use tauri::{Emitter, Manager};
+
+pub const LABEL: &str = "quick";
+/// Colors offered, newest first.
+const RECENT: usize = 24;
+const WIDTH: f64 = 264.0;
+const HEIGHT: f64 = 176.0;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickPaletteOpened {
+    pub colors: Vec<ColorEntry>,
+}
+
+pub fn create(app: &tauri::App) -> tauri::Result<()> {
+    tauri::WebviewWindowBuilder::new(app, LABEL, tauri::WebviewUrl::App("index.html".into()))
+        .title("Pixnib Quick Palette")
+        .inner_size(WIDTH, HEIGHT)
+        .decorations(false)
+        .resizable(false)
+        .always_on_top(true)
+        .skip_taskbar(true)
+        .visible(false)
+        .transparent(true)
+        .shadow(false)
+        .build()?;
+    Ok(())
+}
+
+pub fn is_visible(app: &tauri::AppHandle) -> bool {
+    app.get_webview_window(LABEL)
+        .and_then(|w| w.is_visible().ok())
+        .unwrap_or(false)
+}
+
+pub fn hide(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window(LABEL) {
+        let _ = window.hide();
+    }
+}
+
+/// Hide the popup if it is open, otherwise open it beside the cursor with
+/// the newest history colors, sent along as `quick-palette-opened` so the
+/// window can render without asking for them.
+pub async fn toggle(app: tauri::AppHandle) -> Result<(), String> {
+    if is_visible(&app) {
+        hide(&app);
+        return Ok(());
+    }
+    let window = app
+        .get_webview_window(LABEL)
+        .ok_or("quick palette window missing")?;
+    let mut colors = storage::load_color_history(&app).await?;
+    colors.truncate(RECENT);
+
+    let (cx, cy) = color_picker::cursor_pos()?;
+    let size = window
+        .outer_size()
+        .map(|s| (s.width as i32, s.height as i32))
+        .unwrap_or((WIDTH as i32, HEIGHT as i32));
+    let (origin, extent) = match app.monitor_from_point(cx as f64, cy as f64) {
+        Ok(Some(monitor)) => {
+            let (pos, size) = (monitor.position(), monitor.size());
+            ((pos.x, pos.y), (size.width as i32, size.height as i32))
+        }
+        _ => ((i32::MIN / 2, i32::MIN / 2), (i32::MAX, i32::MAX)),
+    };
+    let ((x, y), _, _) = crate::loupe_placement((cx, cy), size, origin, extent);
+    let _ = window.set_position(tauri::PhysicalPosition::new(x, y));
+
+    let _ = app.emit_to(LABEL, "quick-palette-opened", QuickPaletteOpened { colors });
+    let _ = window.show();
+    let _ = window.set_focus();
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use crate::conversion_cache;
+use crate::state::AppState;
+use tauri::Manager;
+
+/// Set the color the user is matching against, kept in [`AppState`].
+pub fn set(app: &tauri::AppHandle, rgb: Option<[u8; 3]>) {
+    *app.state::<AppState>().reference() = rgb;
+}
+
+pub fn get(app: &tauri::AppHandle) -> Option<[u8; 3]> {
+    *app.state::<AppState>().reference()
+}
+
+/// CIEDE2000 distance from the reference color, when one is set.
+pub fn delta_e(app: &tauri::AppHandle, rgb: [u8; 3]) -> Option<f64> {
+    get(app).map(|reference| conversion_cache::delta_e(app, reference, rgb))
+}
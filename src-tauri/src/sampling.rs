@@ -0,0 +1,89 @@
+use crate::state::AppState;
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+/// Largest neighborhood radius; 5 reads an 11×11 square.
+const MAX_RADIUS: u32 = 5;
+
+/// How a pick turns the pixels around the cursor into one color.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SampleMode {
+    /// Just the pixel under the cursor
+    #[default]
+    Pixel,
+    /// Mean of the neighborhood
+    Average,
+    /// Most frequent color in the neighborhood, so anti-aliased edges don't
+    /// blend into an in-between color
+    Dominant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Sampling {
+    pub mode: SampleMode,
+    /// Pixels the neighborhood reaches from the cursor in each direction
+    pub radius: u32,
+}
+
+impl Default for Sampling {
+    fn default() -> Self {
+        Sampling {
+            mode: SampleMode::Pixel,
+            radius: 1,
+        }
+    }
+}
+
+impl Sampling {
+    pub fn sanitized(self) -> Self {
+        Sampling {
+            radius: self.radius.clamp(1, MAX_RADIUS),
+            ..self
+        }
+    }
+}
+
+/// Set the sampling used by picks, kept in [`AppState`] in sync with the
+/// saved settings.
+pub fn set(app: &tauri::AppHandle, sampling: Sampling) {
+    *app.state::<AppState>().sampling() = sampling.sanitized();
+}
+
+pub fn current(app: &tauri::AppHandle) -> Sampling {
+    *app.state::<AppState>().sampling()
+}
+
+/// Reduce a square neighborhood, row-major with the cursor pixel in the
+/// middle, to one color. Ties for the dominant color go to the one closest
+/// to the cursor.
+pub fn reduce(mode: SampleMode, pixels: &[[u8; 3]]) -> Option<[u8; 3]> {
+    let center = *pixels.get(pixels.len() / 2)?;
+    match mode {
+        SampleMode::Pixel => Some(center),
+        SampleMode::Average => {
+            let n = pixels.len() as u64;
+            Some(std::array::from_fn(|i| {
+                (pixels.iter().map(|p| p[i] as u64).sum::<u64>() / n) as u8
+            }))
+        }
+        SampleMode::Dominant => {
+            let mut counts: std::collections::HashMap<[u8; 3], usize> = Default::default();
+            for p in pixels {
+                *counts.entry(*p).or_default() += 1;
+            }
+            let side = (pixels.len() as f64).sqrt() as usize;
+            let half = side / 2;
+            let distance = |i: usize| (i % side).abs_diff(half).max((i / side).abs_diff(half));
+            let mut nearest_first: Vec<usize> = (0..pixels.len()).collect();
+            nearest_first.sort_by_key(|&i| distance(i));
+            // max_by_key keeps the last maximum, so scan from the farthest
+            nearest_first
+                .into_iter()
+                .rev()
+                .map(|i| pixels[i])
+                .max_by_key(|p| counts[p])
+        }
+    }
+}
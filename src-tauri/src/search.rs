@@ -0,0 +1,243 @@
+use crate::workspace::Workspace;
+use crate::{ColorEntry, Palette};
+use serde::Serialize;
+use std::sync::Mutex;
+
+/// Results returned per query; the quick-switcher only shows a screenful.
+const MAX_RESULTS: usize = 50;
+
+/// Flattened view of everything searchable, rebuilt lazily after any storage
+/// write invalidates it.
+static INDEX: Mutex<Index> = Mutex::new(Index {
+    generation: 0,
+    entries: None,
+});
+
+struct Index {
+    /// Bumped by every invalidation, so a rebuild from data loaded before a
+    /// write isn't installed as current
+    generation: u64,
+    entries: Option<Vec<IndexEntry>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HitKind {
+    Workspace,
+    Palette,
+    Color,
+    History,
+}
+
+/// Where a hit lives, so the UI can jump straight to it.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub workspace_id: Option<String>,
+    pub workspace_name: Option<String>,
+    pub palette_id: Option<String>,
+    pub palette_name: Option<String>,
+    /// Position of the color within its palette
+    pub index: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub kind: HitKind,
+    pub id: String,
+    pub title: String,
+    pub hex: Option<String>,
+    pub location: Location,
+}
+
+struct IndexEntry {
+    hit: SearchHit,
+    /// Lowercased text the query is matched against
+    haystack: Vec<String>,
+}
+
+pub fn invalidate() {
+    if let Ok(mut index) = INDEX.lock() {
+        index.generation += 1;
+        index.entries = None;
+    }
+}
+
+fn build(
+    workspaces: &[Workspace],
+    palettes: &[Palette],
+    history: &[ColorEntry],
+) -> Vec<IndexEntry> {
+    let mut entries = Vec::new();
+    for w in workspaces {
+        entries.push(IndexEntry {
+            hit: SearchHit {
+                kind: HitKind::Workspace,
+                id: w.id.clone(),
+                title: w.name.clone(),
+                hex: None,
+                location: Location {
+                    workspace_id: Some(w.id.clone()),
+                    workspace_name: Some(w.name.clone()),
+                    ..Default::default()
+                },
+            },
+            haystack: vec![w.name.to_lowercase()],
+        });
+    }
+    for p in palettes {
+        let workspace_name = p
+            .workspace_id
+            .as_ref()
+            .and_then(|id| workspaces.iter().find(|w| &w.id == id))
+            .map(|w| w.name.clone());
+        let location = Location {
+            workspace_id: p.workspace_id.clone(),
+            workspace_name,
+            palette_id: Some(p.id.clone()),
+            palette_name: Some(p.name.clone()),
+            index: None,
+        };
+        entries.push(IndexEntry {
+            hit: SearchHit {
+                kind: HitKind::Palette,
+                id: p.id.clone(),
+                title: p.name.clone(),
+                hex: None,
+                location: location.clone(),
+            },
+            haystack: vec![p.name.to_lowercase()],
+        });
+        for (i, hex) in p.colors.iter().enumerate() {
+            entries.push(IndexEntry {
+                hit: SearchHit {
+                    kind: HitKind::Color,
+                    id: format!("{}:{i}", p.id),
+                    title: format!("{} — {}", hex, p.name),
+                    hex: Some(hex.clone()),
+                    location: Location {
+                        index: Some(i),
+                        ..location.clone()
+                    },
+                },
+                haystack: vec![hex.to_lowercase()],
+            });
+        }
+    }
+    for e in history {
+        let mut haystack = vec![e.hex.to_lowercase()];
+        haystack.extend(e.label.iter().map(|l| l.to_lowercase()));
+        haystack.extend(e.tags.iter().map(|t| t.to_lowercase()));
+        entries.push(IndexEntry {
+            hit: SearchHit {
+                kind: HitKind::History,
+                id: e.id.clone(),
+                title: e.label.clone().unwrap_or_else(|| e.hex.clone()),
+                hex: Some(e.hex.clone()),
+                location: Location::default(),
+            },
+            haystack,
+        });
+    }
+    entries
+}
+
+/// The generation to pass to [`rebuild`] if the index needs rebuilding
+/// before the next query.
+pub fn stale_generation() -> Option<u64> {
+    let index = INDEX.lock().ok()?;
+    index.entries.is_none().then_some(index.generation)
+}
+
+/// Install an index built from data loaded at `generation`; dropped if a
+/// write invalidated the index since.
+pub fn rebuild(
+    generation: u64,
+    workspaces: &[Workspace],
+    palettes: &[Palette],
+    history: &[ColorEntry],
+) {
+    let entries = build(workspaces, palettes, history);
+    if let Ok(mut index) = INDEX.lock() {
+        if index.generation == generation {
+            index.entries = Some(entries);
+        }
+    }
+}
+
+/// Whether `needle`'s characters appear in `text` in order, e.g. "bblu" in
+/// "brand blue". Spaces in the needle are ignored.
+fn is_subsequence(text: &str, needle: &str) -> bool {
+    let mut chars = text.chars();
+    needle
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|c| chars.any(|t| t == c))
+}
+
+/// Rank: exact match, then prefix, then substring, then (when `fuzzy`)
+/// subsequence. A leading `#` is optional for hex queries.
+fn score(haystack: &[String], needle: &str, fuzzy: bool) -> Option<u8> {
+    let bare = needle.trim_start_matches('#');
+    haystack
+        .iter()
+        .filter_map(|text| {
+            let text_bare = text.trim_start_matches('#');
+            if text == needle || (!bare.is_empty() && text_bare == bare) {
+                Some(0)
+            } else if text.starts_with(needle) || (!bare.is_empty() && text_bare.starts_with(bare))
+            {
+                Some(1)
+            } else if text.contains(needle) {
+                Some(2)
+            } else if fuzzy && is_subsequence(text, needle) {
+                Some(3)
+            } else {
+                None
+            }
+        })
+        .min()
+}
+
+fn ranked(
+    query: &str,
+    fuzzy: bool,
+    limit: usize,
+    keep: impl Fn(&SearchHit) -> bool,
+) -> Vec<SearchHit> {
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let Ok(index) = INDEX.lock() else {
+        return Vec::new();
+    };
+    let Some(entries) = index.entries.as_ref() else {
+        return Vec::new();
+    };
+    let mut hits: Vec<(u8, &SearchHit)> = entries
+        .iter()
+        .filter(|e| keep(&e.hit))
+        .filter_map(|e| score(&e.haystack, &needle, fuzzy).map(|s| (s, &e.hit)))
+        .collect();
+    // Stable sort keeps workspace/palette/color/history order within a rank
+    hits.sort_by_key(|(s, _)| *s);
+    hits.into_iter()
+        .take(limit)
+        .map(|(_, hit)| hit.clone())
+        .collect()
+}
+
+pub fn query(query: &str) -> Vec<SearchHit> {
+    ranked(query, false, MAX_RESULTS, |_| true)
+}
+
+/// Palettes, palette colors and history matching `query`, also by
+/// characters in order with gaps, for launchers where users type
+/// abbreviations.
+pub fn fuzzy_query(query: &str, limit: usize) -> Vec<SearchHit> {
+    ranked(query, true, limit.min(MAX_RESULTS), |hit| {
+        !matches!(hit.kind, HitKind::Workspace)
+    })
+}
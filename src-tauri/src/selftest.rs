@@ -0,0 +1,284 @@
+//! Capture self-test for "the picked color is wrong on my other monitor"
+//! reports: known colors are painted into a temporary topmost window on each
+//! monitor and read back through every capture backend.
+
+use crate::color_math;
+use serde::Serialize;
+
+/// Patches painted on every monitor: grays catch gamma and scaling, the
+/// primaries catch channel swaps and color management.
+const PATCHES: [[u8; 3]; 5] = [
+    [0x80, 0x80, 0x80],
+    [0xF0, 0xF0, 0xF0],
+    [0xE0, 0x30, 0x20],
+    [0x20, 0xB0, 0x40],
+    [0x30, 0x50, 0xD0],
+];
+/// Side of the painted patch in physical pixels.
+const PATCH_SIZE: i32 = 64;
+/// Time for the compositor to put a freshly shown window on screen.
+const SETTLE_MS: u64 = 150;
+/// Largest CIEDE2000 distance still counted as agreement; absorbs dithering
+/// and 8-bit rounding.
+const TOLERANCE: f64 = 1.0;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendReading {
+    pub backend: &'static str,
+    pub hex: Option<String>,
+    pub error: Option<String>,
+    /// CIEDE2000 distance from the painted color
+    pub delta_e: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchResult {
+    pub expected: String,
+    pub x: i32,
+    pub y: i32,
+    pub readings: Vec<BackendReading>,
+    /// Every backend that could read the patch saw the painted color
+    pub agree: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorResult {
+    pub name: Option<String>,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub patches: Vec<PatchResult>,
+    pub agree: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelftestReport {
+    pub tolerance: f64,
+    pub monitors: Vec<MonitorResult>,
+    pub agree: bool,
+}
+
+/// Monitor bounds in physical pixels, as reported by the windowing layer.
+pub struct MonitorArea {
+    pub name: Option<String>,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+}
+
+fn reading(
+    backend: &'static str,
+    expected: [u8; 3],
+    read: Result<[u8; 3], String>,
+) -> BackendReading {
+    match read {
+        Ok(rgb) => BackendReading {
+            backend,
+            hex: Some(color_math::to_hex(rgb)),
+            error: None,
+            delta_e: Some(color_math::delta_e(expected, rgb)),
+        },
+        Err(e) => BackendReading {
+            backend,
+            hex: None,
+            error: Some(e),
+            delta_e: None,
+        },
+    }
+}
+
+fn patch_result(expected: [u8; 3], x: i32, y: i32, readings: Vec<BackendReading>) -> PatchResult {
+    let agree = readings
+        .iter()
+        .filter_map(|r| r.delta_e)
+        .all(|d| d <= TOLERANCE);
+    PatchResult {
+        expected: color_math::to_hex(expected),
+        x,
+        y,
+        readings,
+        agree,
+    }
+}
+
+/// Paint each patch in the middle of each monitor and read it back through
+/// GDI `GetPixel`, xcap and Desktop Duplication. Blocks for a few hundred
+/// milliseconds per monitor; the patches briefly flash on screen.
+pub fn run(monitors: Vec<MonitorArea>) -> Result<SelftestReport, String> {
+    let mut results = Vec::new();
+    for monitor in monitors {
+        let cx = monitor.x + monitor.width as i32 / 2;
+        let cy = monitor.y + monitor.height as i32 / 2;
+        let mut patches = Vec::new();
+        for expected in PATCHES {
+            let readings = {
+                let _patch = win::Patch::show(cx - PATCH_SIZE / 2, cy - PATCH_SIZE / 2, expected)?;
+                std::thread::sleep(std::time::Duration::from_millis(SETTLE_MS));
+                vec![
+                    reading("getPixel", expected, win::get_pixel(cx, cy)),
+                    reading("xcap", expected, xcap_pixel(cx, cy)),
+                    reading(
+                        "duplication",
+                        expected,
+                        crate::duplication::pixel_at(cx, cy).map(|(r, g, b)| [r, g, b]),
+                    ),
+                ]
+            };
+            patches.push(patch_result(expected, cx, cy, readings));
+        }
+        results.push(MonitorResult {
+            name: monitor.name,
+            x: monitor.x,
+            y: monitor.y,
+            width: monitor.width,
+            height: monitor.height,
+            scale_factor: monitor.scale_factor,
+            agree: patches.iter().all(|p| p.agree),
+            patches,
+        });
+    }
+    Ok(SelftestReport {
+        tolerance: TOLERANCE,
+        agree: results.iter().all(|m| m.agree),
+        monitors: results,
+    })
+}
+
+fn xcap_pixel(x: i32, y: i32) -> Result<[u8; 3], String> {
+    let monitor =
+        xcap::Monitor::from_point(x, y).map_err(|e| format!("Failed to find monitor: {}", e))?;
+    let image = monitor
+        .capture_image()
+        .map_err(|e| format!("Failed to capture monitor: {}", e))?;
+    let (px, py) = ((x - monitor.x()) as u32, (y - monitor.y()) as u32);
+    if px >= image.width() || py >= image.height() {
+        return Err(format!(
+            "Capture is {}x{}, smaller than the monitor",
+            image.width(),
+            image.height()
+        ));
+    }
+    let [r, g, b, _] = image.get_pixel(px, py).0;
+    Ok([r, g, b])
+}
+
+#[cfg(windows)]
+mod win {
+    use windows::core::w;
+    use windows::Win32::Foundation::{COLORREF, HWND, LPARAM, LRESULT, WPARAM};
+    use windows::Win32::Graphics::Gdi::{
+        CreateSolidBrush, DeleteObject, GetDC, GetPixel, ReleaseDC, UpdateWindow, CLR_INVALID,
+        HBRUSH,
+    };
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DestroyWindow, RegisterClassW, SetClassLongPtrW,
+        ShowWindow, GCLP_HBRBACKGROUND, SW_SHOWNOACTIVATE, WNDCLASSW, WS_EX_NOACTIVATE,
+        WS_EX_TOOLWINDOW, WS_EX_TOPMOST, WS_POPUP,
+    };
+
+    unsafe extern "system" fn wndproc(
+        hwnd: HWND,
+        msg: u32,
+        wparam: WPARAM,
+        lparam: LPARAM,
+    ) -> LRESULT {
+        DefWindowProcW(hwnd, msg, wparam, lparam)
+    }
+
+    fn colorref([r, g, b]: [u8; 3]) -> COLORREF {
+        COLORREF(r as u32 | ((g as u32) << 8) | ((b as u32) << 16))
+    }
+
+    /// Topmost borderless window filled with one color; destroyed on drop.
+    pub struct Patch {
+        hwnd: HWND,
+        brush: HBRUSH,
+    }
+
+    impl Patch {
+        pub fn show(x: i32, y: i32, rgb: [u8; 3]) -> Result<Patch, String> {
+            unsafe {
+                let instance = GetModuleHandleW(None)
+                    .map_err(|e| format!("Failed to get module handle: {}", e))?;
+                let class = WNDCLASSW {
+                    lpfnWndProc: Some(wndproc),
+                    hInstance: instance.into(),
+                    lpszClassName: w!("PixnibCapturePatch"),
+                    ..Default::default()
+                };
+                // Fails harmlessly once the class exists
+                RegisterClassW(&class);
+                let hwnd = CreateWindowExW(
+                    WS_EX_TOPMOST | WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE,
+                    w!("PixnibCapturePatch"),
+                    w!("Pixnib capture test"),
+                    WS_POPUP,
+                    x,
+                    y,
+                    super::PATCH_SIZE,
+                    super::PATCH_SIZE,
+                    None,
+                    None,
+                    instance,
+                    None,
+                )
+                .map_err(|e| format!("Failed to create test window: {}", e))?;
+                let brush = CreateSolidBrush(colorref(rgb));
+                SetClassLongPtrW(hwnd, GCLP_HBRBACKGROUND, brush.0 as isize);
+                let _ = ShowWindow(hwnd, SW_SHOWNOACTIVATE);
+                let _ = UpdateWindow(hwnd);
+                Ok(Patch { hwnd, brush })
+            }
+        }
+    }
+
+    impl Drop for Patch {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = DestroyWindow(self.hwnd);
+                let _ = DeleteObject(self.brush);
+            }
+        }
+    }
+
+    /// Plain GDI read, without the snapshot and game-mode paths picks take.
+    pub fn get_pixel(x: i32, y: i32) -> Result<[u8; 3], String> {
+        unsafe {
+            let hdc = GetDC(None);
+            if hdc.is_invalid() {
+                return Err("Failed to get device context".to_string());
+            }
+            let color = GetPixel(hdc, x, y);
+            let _ = ReleaseDC(None, hdc);
+            if color == COLORREF(CLR_INVALID) {
+                return Err("GetPixel returned CLR_INVALID".to_string());
+            }
+            let v = color.0;
+            Ok([v as u8, (v >> 8) as u8, (v >> 16) as u8])
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod win {
+    pub struct Patch;
+
+    impl Patch {
+        pub fn show(_x: i32, _y: i32, _rgb: [u8; 3]) -> Result<Patch, String> {
+            Err("The capture self-test is only supported on Windows".to_string())
+        }
+    }
+
+    pub fn get_pixel(_x: i32, _y: i32) -> Result<[u8; 3], String> {
+        Err("GetPixel is only available on Windows".to_string())
+    }
+}
@@ -0,0 +1,136 @@
+use crate::state::AppState;
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
+
+/// Everything a global shortcut can trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action {
+    /// Enter pick mode, or pick when already in it
+    Pick,
+    /// Pick the pixel under the cursor without showing the loupe
+    InstantPick,
+    /// Copy the newest history color again
+    CopyLast,
+    /// Show or hide the main window
+    ToggleWindow,
+    /// Switch to the next copy format
+    CycleFormat,
+    /// Open or close the recent-colors popup
+    QuickPalette,
+}
+
+impl Action {
+    pub const ALL: [Action; 6] = [
+        Action::Pick,
+        Action::InstantPick,
+        Action::CopyLast,
+        Action::ToggleWindow,
+        Action::CycleFormat,
+        Action::QuickPalette,
+    ];
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Binding {
+    pub action: Action,
+    /// Display label of the registered accelerator, absent when unbound
+    pub accelerator: Option<String>,
+}
+
+/// A registered shortcut and the label it was registered under.
+pub type Registration = (Action, Shortcut, String);
+
+/// Parse an accelerator such as `Ctrl+Shift+C`. `Win` is accepted as an
+/// alias for the Super key since that is what Windows users type.
+pub fn parse(accelerator: &str) -> Result<Shortcut, String> {
+    let normalized: Vec<&str> = accelerator
+        .split('+')
+        .map(|token| {
+            let token = token.trim();
+            if token.eq_ignore_ascii_case("win") {
+                "Super"
+            } else {
+                token
+            }
+        })
+        .collect();
+    normalized
+        .join("+")
+        .parse::<Shortcut>()
+        .map_err(|e| format!("Invalid shortcut \"{accelerator}\": {e}"))
+}
+
+pub fn action_for(app: &tauri::AppHandle, shortcut: &Shortcut) -> Option<Action> {
+    app.state::<AppState>()
+        .shortcuts()
+        .iter()
+        .find(|(_, s, _)| s == shortcut)
+        .map(|(action, _, _)| *action)
+}
+
+pub fn label_for(app: &tauri::AppHandle, action: Action) -> Option<String> {
+    app.state::<AppState>()
+        .shortcuts()
+        .iter()
+        .find(|(a, _, _)| *a == action)
+        .map(|(_, _, label)| label.clone())
+}
+
+pub fn bindings(app: &tauri::AppHandle) -> Vec<Binding> {
+    Action::ALL
+        .iter()
+        .map(|&action| Binding {
+            action,
+            accelerator: label_for(app, action),
+        })
+        .collect()
+}
+
+/// Register `accelerator` for `action`, replacing its previous shortcut.
+/// On failure the previous shortcut is restored and an error returned.
+pub fn bind(app: &tauri::AppHandle, action: Action, accelerator: &str) -> Result<String, String> {
+    let shortcut = parse(accelerator)?;
+    let state = app.state::<AppState>();
+    let mut bindings = state.shortcuts();
+    if let Some((other, _, _)) = bindings
+        .iter()
+        .find(|(a, s, _)| *s == shortcut && *a != action)
+    {
+        return Err(format!("{accelerator} is already used by {other:?}"));
+    }
+
+    let previous = bindings
+        .iter()
+        .position(|(a, _, _)| *a == action)
+        .map(|i| bindings.remove(i));
+    if let Some((_, old, _)) = &previous {
+        let _ = app.global_shortcut().unregister(*old);
+    }
+
+    match app.global_shortcut().register(shortcut) {
+        Ok(_) => {
+            bindings.push((action, shortcut, accelerator.to_string()));
+            Ok(accelerator.to_string())
+        }
+        Err(e) => {
+            if let Some((_, old, label)) = previous {
+                if app.global_shortcut().register(old).is_ok() {
+                    bindings.push((action, old, label));
+                }
+            }
+            Err(format!("{accelerator} is unavailable: {e}"))
+        }
+    }
+}
+
+pub fn unbind(app: &tauri::AppHandle, action: Action) {
+    let state = app.state::<AppState>();
+    let mut bindings = state.shortcuts();
+    if let Some(i) = bindings.iter().position(|(a, _, _)| *a == action) {
+        let (_, shortcut, _) = bindings.remove(i);
+        let _ = app.global_shortcut().unregister(shortcut);
+    }
+}
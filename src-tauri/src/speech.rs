@@ -0,0 +1,73 @@
+use crate::{color_math, conversion_cache};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+
+/// Queue feeding the speaker thread, which owns the SAPI voice for the
+/// lifetime of the app. Created on first use.
+static SPEAKER: Mutex<Option<Sender<String>>> = Mutex::new(None);
+
+/// Spoken description of a color, e.g. "hex F F 8 8 0 0, orange, copied".
+/// Hex digits are spaced out so voices read them one by one instead of
+/// guessing at a word.
+pub fn describe(app: &tauri::AppHandle, rgb: [u8; 3], outcome: Option<&str>) -> String {
+    let hex = color_math::to_hex(rgb);
+    let spelled: Vec<String> = hex[1..].chars().map(String::from).collect();
+    let mut text = format!(
+        "hex {}, {}",
+        spelled.join(" "),
+        conversion_cache::name(app, rgb).to_lowercase()
+    );
+    if let Some(outcome) = outcome {
+        text.push_str(", ");
+        text.push_str(outcome);
+    }
+    text
+}
+
+/// Speak `text` without blocking, interrupting any announcement still playing.
+pub fn announce(text: String) {
+    let mut speaker = SPEAKER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(tx) = speaker.as_ref() {
+        if tx.send(text.clone()).is_ok() {
+            return;
+        }
+    }
+    let (tx, rx) = mpsc::channel();
+    let _ = tx.send(text);
+    std::thread::spawn(move || speak_loop(rx));
+    *speaker = Some(tx);
+}
+
+#[cfg(windows)]
+fn speak_loop(rx: mpsc::Receiver<String>) {
+    use windows::core::HSTRING;
+    use windows::Win32::Media::Speech::{
+        ISpVoice, SpVoice, SPF_ASYNC, SPF_IS_NOT_XML, SPF_PURGEBEFORESPEAK,
+    };
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CLSCTX_ALL, COINIT_MULTITHREADED,
+    };
+
+    let voice: ISpVoice = unsafe {
+        let _ = CoInitializeEx(None, COINIT_MULTITHREADED);
+        match CoCreateInstance(&SpVoice, None, CLSCTX_ALL) {
+            Ok(voice) => voice,
+            Err(e) => {
+                eprintln!("Failed to create speech voice: {}", e);
+                return;
+            }
+        }
+    };
+    let flags = (SPF_ASYNC.0 | SPF_PURGEBEFORESPEAK.0 | SPF_IS_NOT_XML.0) as u32;
+    for text in rx {
+        if let Err(e) = unsafe { voice.Speak(&HSTRING::from(text), flags, None) } {
+            eprintln!("Failed to speak: {}", e);
+        }
+    }
+}
+
+#[cfg(not(windows))]
+fn speak_loop(rx: mpsc::Receiver<String>) {
+    // No speech backend on this platform; drain so senders never fail.
+    for _ in rx {}
+}
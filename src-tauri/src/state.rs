@@ -0,0 +1,67 @@
+use crate::conversion_cache::ConversionCache;
+use crate::figma_bridge::FigmaBridge;
+use crate::pick_controller::PickMode;
+use crate::sampling::Sampling;
+use crate::shortcuts::Registration;
+use std::sync::{Mutex, MutexGuard};
+
+/// Runtime state shared by commands and event handlers, managed by Tauri and
+/// reached through `app.state::<AppState>()`. Nothing here is persisted.
+#[derive(Default)]
+pub struct AppState {
+    /// Current pick mode, `None` when not picking. Only changed by
+    /// [`crate::pick_controller::transition`].
+    pick: Mutex<Option<PickMode>>,
+    /// Registered global shortcuts
+    shortcuts: Mutex<Vec<Registration>>,
+    /// Colors collected by the running averaging session, `None` when there
+    /// is none
+    averaging: Mutex<Option<Vec<[u8; 3]>>>,
+    /// Color picks are compared against, mirrored from the saved settings so
+    /// the loupe's capture loop can read it without loading them
+    reference: Mutex<Option<[u8; 3]>>,
+    /// How picks read the pixels around the cursor, mirrored from the saved
+    /// settings
+    sampling: Mutex<Sampling>,
+    /// Conversions the loupe repeats on every frame
+    conversions: Mutex<ConversionCache>,
+    /// Server, pairing and queued colors of the Figma plugin bridge, which
+    /// does its own locking
+    figma: FigmaBridge,
+}
+
+/// Recover from a poisoned lock: every value here stays consistent between
+/// statements, so a panic elsewhere doesn't invalidate it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+impl AppState {
+    pub fn pick(&self) -> MutexGuard<'_, Option<PickMode>> {
+        lock(&self.pick)
+    }
+
+    pub fn shortcuts(&self) -> MutexGuard<'_, Vec<Registration>> {
+        lock(&self.shortcuts)
+    }
+
+    pub fn averaging(&self) -> MutexGuard<'_, Option<Vec<[u8; 3]>>> {
+        lock(&self.averaging)
+    }
+
+    pub fn reference(&self) -> MutexGuard<'_, Option<[u8; 3]>> {
+        lock(&self.reference)
+    }
+
+    pub fn sampling(&self) -> MutexGuard<'_, Sampling> {
+        lock(&self.sampling)
+    }
+
+    pub fn conversions(&self) -> MutexGuard<'_, ConversionCache> {
+        lock(&self.conversions)
+    }
+
+    pub fn figma(&self) -> &FigmaBridge {
+        &self.figma
+    }
+}
@@ -1,17 +1,209 @@
+use crate::activity::ActivityEntry;
+use crate::auto_export::{self, AutoExport};
+use crate::color_names::NameLocale;
+use crate::conversion;
+use crate::copy_format::CopyFormat;
+use crate::encryption::{self, StoreEncryption};
+use crate::events;
+use crate::exporters::TemplateExporter;
+use crate::history;
+use crate::hooks::PickHook;
+use crate::journal;
+use crate::loupe::LoupeSettings;
+use crate::palette_dir;
+use crate::palette_versions::{self, Versions};
+use crate::png_encoder::PngOptions;
+use crate::sampling::Sampling;
+use crate::search;
+use crate::shortcuts::Action;
+use crate::tag_rules::{self, TagRule};
+use crate::timestamps;
+use crate::window_state::WindowGeometry;
+use crate::workspace::Workspace;
 use crate::{BrandKit, ColorEntry, Palette};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tauri::Manager;
 
 const HISTORY_FILE: &str = "color_history.json";
 const SETTINGS_FILE: &str = "settings.json";
 const PALETTES_FILE: &str = "palettes.json";
+const PALETTE_VERSIONS_FILE: &str = "palette_versions.json";
 const BRAND_KIT_FILE: &str = "brand_kit.json";
+const WORKSPACES_FILE: &str = "workspaces.json";
+const EXPORTERS_FILE: &str = "exporters.json";
+const ACTIVITY_FILE: &str = "activity.jsonl";
+const JOURNAL_FILE: &str = "history_journal.jsonl";
+const NATIVE_HOST_DIR: &str = "native-messaging";
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AppSettings {
-    /// Shortcut label the user chose in settings (tried first on startup)
+    /// Pick shortcut chosen before per-action shortcuts existed; only read as
+    /// a fallback when `shortcuts` has no pick entry
     pub preferred_shortcut: Option<String>,
+    /// Accelerator per shortcut action
+    #[serde(default)]
+    pub shortcuts: BTreeMap<Action, String>,
+    /// Workspace whose palettes are shown; `None` is the default workspace
+    #[serde(default)]
+    pub active_workspace: Option<String>,
+    /// Command run after each pick; off unless configured
+    #[serde(default)]
+    pub pick_hook: Option<PickHook>,
+    /// Label new picks with the nearest color name and source app
+    #[serde(default)]
+    pub auto_label: bool,
+    /// Format the backend copies colors in
+    #[serde(default)]
+    pub copy_format: CopyFormat,
+    /// Template for the custom copy format
+    #[serde(default)]
+    pub copy_template: Option<String>,
+    /// Type each picked color into the app that was focused before picking
+    #[serde(default)]
+    pub paste_after_pick: bool,
+    /// Speak each picked or copied color aloud
+    #[serde(default)]
+    pub announce_colors: bool,
+    /// Use the background-adaptive ring instead of the app icon as pick cursor
+    #[serde(default)]
+    pub high_contrast_cursor: bool,
+    /// Sample picks through Desktop Duplication so fullscreen games read correctly
+    #[serde(default)]
+    pub game_mode: bool,
+    /// Also report picks as seen through the monitor's gamma ramp
+    #[serde(default)]
+    pub white_point_report: bool,
+    /// Ignore shortcuts and never pop up the window while another app is fullscreen
+    #[serde(default)]
+    pub quiet_in_fullscreen: bool,
+    /// Toast and announce picks even while Windows Focus Assist is on
+    #[serde(default)]
+    pub ignore_focus_assist: bool,
+    /// Language of color names in labels, the loupe, announcements and exports
+    #[serde(default)]
+    pub color_names: NameLocale,
+    #[serde(default)]
+    pub loupe: LoupeSettings,
+    /// How picks read the pixels around the cursor
+    #[serde(default)]
+    pub sampling: Sampling,
+    /// Delete unpinned history entries older than this many days
+    #[serde(default)]
+    pub history_retention_days: Option<u32>,
+    /// Collapse imported colors within this delta-E of an earlier one
+    #[serde(default)]
+    pub import_dedupe: Option<f64>,
+    /// Color picks are compared against, as `#RRGGBB`
+    #[serde(default)]
+    pub reference_color: Option<String>,
+    /// Encoding of rendered images: exports, QR codes, thumbnails
+    #[serde(default)]
+    pub png: PngOptions,
+    /// Tag new history entries matching these rules
+    #[serde(default)]
+    pub tag_rules: Vec<TagRule>,
+    /// Append every history change to `history_journal.jsonl` for external
+    /// tools; ignored while the store is encrypted
+    #[serde(default)]
+    pub history_journal: bool,
+    /// Directory mirroring every palette as a plain-text file, for keeping
+    /// them in Git; ignored while the store is encrypted
+    #[serde(default)]
+    pub palette_dir: Option<String>,
+    /// Files re-exported whenever their palette changes
+    #[serde(default)]
+    pub auto_exports: Vec<AutoExport>,
+    /// Serve the editor JSON-RPC pipe
+    #[serde(default)]
+    pub editor_rpc: bool,
+    /// Serve the MCP pipe for AI assistants
+    #[serde(default)]
+    pub mcp_server: bool,
+    /// Serve the local HTTP bridge for the Figma plugin
+    #[serde(default)]
+    pub figma_bridge: bool,
+    /// Bearer token of the paired Figma plugin
+    #[serde(default)]
+    pub figma_token: Option<String>,
+    /// Emit `ambient-color` with the screen's average color this often, in
+    /// milliseconds; off when unset
+    #[serde(default)]
+    pub ambient_interval_ms: Option<u64>,
+    /// Encryption at rest for the history and palette stores; the activity
+    /// log is not written while it is on
+    #[serde(default)]
+    pub store_encryption: StoreEncryption,
+    /// Base64 Argon2 salt for passphrase encryption
+    #[serde(default)]
+    pub store_salt: Option<String>,
+    /// Main window position and size when the app last closed
+    #[serde(default)]
+    pub main_window: Option<WindowGeometry>,
+}
+
+static ID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// How long writes to history, palettes and palette versions are held back waiting for more
+/// changes, and the longest a steady stream of changes can delay a write.
+const FLUSH_DEBOUNCE: Duration = Duration::from_millis(400);
+const FLUSH_MAX_DELAY: Duration = Duration::from_secs(3);
+
+/// Write-behind copy of a store. Once loaded, `value` is authoritative and
+/// the file catches up on the next flush.
+struct Cached<T> {
+    value: Option<T>,
+    dirty: bool,
+}
+
+static HISTORY_CACHE: Mutex<Cached<Vec<ColorEntry>>> = Mutex::new(Cached {
+    value: None,
+    dirty: false,
+});
+static PALETTE_CACHE: Mutex<Cached<Vec<Palette>>> = Mutex::new(Cached {
+    value: None,
+    dirty: false,
+});
+static VERSIONS_CACHE: Mutex<Cached<Versions>> = Mutex::new(Cached {
+    value: None,
+    dirty: false,
+});
+
+/// Wakes the flush thread; created with the first change.
+static FLUSHER: Mutex<Option<Sender<()>>> = Mutex::new(None);
+
+/// Serializes journal appends and trims, which run outside the history cache.
+static JOURNAL_LOCK: Mutex<()> = Mutex::new(());
+
+/// Milliseconds since the Unix epoch.
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Id in the same `<millis>-<suffix>` shape the frontend generates, for
+/// records created by the backend.
+pub fn generate_id() -> String {
+    let n = ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64)
+        .unwrap_or(0);
+    let mut suffix = String::new();
+    let mut v = ((seed << 16) ^ n).max(1);
+    while v > 0 && suffix.len() < 9 {
+        let digit = (v % 36) as u32;
+        suffix.push(std::char::from_digit(digit, 36).unwrap_or('0'));
+        v /= 36;
+    }
+    format!("{}-{}", now_millis(), suffix)
 }
 
 fn app_data_file(app: &tauri::AppHandle, name: &str) -> Result<PathBuf, String> {
@@ -31,6 +223,141 @@ fn get_storage_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
     app_data_file(app, HISTORY_FILE)
 }
 
+/// Read a store that may be encrypted (history, palettes).
+fn read_store(path: &Path) -> Result<String, String> {
+    let data =
+        std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    String::from_utf8(encryption::open(&data)?)
+        .map_err(|e| format!("Failed to decode {}: {}", path.display(), e))
+}
+
+/// Write a store in the configured encryption mode.
+fn write_store(app: &tauri::AppHandle, path: &Path, json: &str) -> Result<(), String> {
+    let data = encryption::seal(load_settings(app).store_encryption, json.as_bytes())?;
+    std::fs::write(path, data).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Switch store encryption, re-writing history and palettes under the new
+/// mode. `passphrase` is required for passphrase mode. Every store is
+/// written to a temporary file before any replaces the original, and
+/// settings are saved last, so a failure leaves the old mode in place.
+/// Turning encryption on also stops the plaintext history journal (deleting
+/// it) and palette directory mirror.
+pub fn set_store_encryption(
+    app: &tauri::AppHandle,
+    mode: StoreEncryption,
+    passphrase: Option<&str>,
+) -> Result<(), String> {
+    // Decrypt everything with the current key before switching
+    flush(app)?;
+    let mut stores = Vec::new();
+    for name in [HISTORY_FILE, PALETTES_FILE, PALETTE_VERSIONS_FILE] {
+        let path = app_data_file(app, name)?;
+        if path.exists() {
+            let json = read_store(&path)?;
+            stores.push((name, path, json));
+        }
+    }
+
+    let mut settings = load_settings(app);
+    let previous_mode = settings.store_encryption;
+    let previous_key = encryption::session_key();
+    if mode == StoreEncryption::Passphrase {
+        let passphrase = passphrase
+            .filter(|p| !p.is_empty())
+            .ok_or("A passphrase is required")?;
+        let salt = encryption::new_salt();
+        encryption::unlock(passphrase, &salt)?;
+        settings.store_salt = Some(salt);
+    } else {
+        encryption::lock();
+        settings.store_salt = None;
+    }
+    settings.store_encryption = mode;
+    if mode != StoreEncryption::None {
+        settings.history_journal = false;
+        settings.palette_dir = None;
+    }
+
+    let mut replaced = Vec::new();
+    let result = replace_stores(app, mode, &stores, &mut replaced)
+        .and_then(|()| save_settings(app, &settings));
+    if let Err(e) = result {
+        encryption::restore_key(previous_key);
+        for (path, json) in replaced {
+            let restored = encryption::seal(previous_mode, json.as_bytes())
+                .and_then(|data| std::fs::write(path, data).map_err(|e| e.to_string()));
+            if let Err(e) = restored {
+                eprintln!("Failed to restore {}: {}", path.display(), e);
+            }
+        }
+        return Err(e);
+    }
+
+    if mode != StoreEncryption::None {
+        let journal = journal_path(app)?;
+        if journal.exists() {
+            std::fs::remove_file(&journal)
+                .map_err(|e| format!("Failed to delete history journal: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Seal every store under `mode` into a temporary file, then move them all
+/// into place. Stores already moved are added to `replaced`.
+fn replace_stores<'a>(
+    app: &tauri::AppHandle,
+    mode: StoreEncryption,
+    stores: &'a [(&str, PathBuf, String)],
+    replaced: &mut Vec<(&'a Path, &'a str)>,
+) -> Result<(), String> {
+    let mut staged = Vec::new();
+    let sealed = stores.iter().try_for_each(|(name, path, json)| {
+        let temp = app_data_file(app, &format!("{name}.tmp"))?;
+        staged.push(temp.clone());
+        let data = encryption::seal(mode, json.as_bytes())?;
+        std::fs::write(&temp, data)
+            .map_err(|e| format!("Failed to write {}: {}", temp.display(), e))
+    });
+    if let Err(e) = sealed {
+        for temp in staged {
+            let _ = std::fs::remove_file(temp);
+        }
+        return Err(e);
+    }
+    for ((_, path, json), temp) in stores.iter().zip(&staged) {
+        if let Err(e) = std::fs::rename(temp, path) {
+            for temp in &staged[replaced.len()..] {
+                let _ = std::fs::remove_file(temp);
+            }
+            return Err(format!("Failed to replace {}: {}", path.display(), e));
+        }
+        replaced.push((path.as_path(), json.as_str()));
+    }
+    Ok(())
+}
+
+/// Enter the passphrase for this session. Verified against the stores so a
+/// typo is reported instead of surfacing later as empty palettes.
+pub fn unlock_store(app: &tauri::AppHandle, passphrase: &str) -> Result<(), String> {
+    let salt = load_settings(app)
+        .store_salt
+        .ok_or("Storage is not passphrase-encrypted")?;
+    encryption::unlock(passphrase, &salt)?;
+    for name in [HISTORY_FILE, PALETTES_FILE] {
+        let path = app_data_file(app, name)?;
+        if path.exists() {
+            if let Err(e) = read_store(&path) {
+                encryption::lock();
+                return Err(e);
+            }
+        }
+    }
+    search::invalidate();
+    Ok(())
+}
+
 pub fn save_settings(app: &tauri::AppHandle, settings: &AppSettings) -> Result<(), String> {
     let path = app_data_file(app, SETTINGS_FILE)?;
     let json = serde_json::to_string_pretty(settings)
@@ -47,15 +374,215 @@ pub fn load_settings(app: &tauri::AppHandle) -> AppSettings {
         .unwrap_or_default()
 }
 
+/// Refuse changes that could never be flushed, rather than dropping them later.
+fn ensure_writable(app: &tauri::AppHandle) -> Result<(), String> {
+    if encryption::status(load_settings(app).store_encryption).locked {
+        return Err("Storage is locked; enter the passphrase first".to_string());
+    }
+    Ok(())
+}
+
+/// Replace a cached store and schedule it to be written.
+fn store_later<T>(app: &tauri::AppHandle, cache: &Mutex<Cached<T>>, value: T) {
+    let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+    cache.value = Some(value);
+    cache.dirty = true;
+    drop(cache);
+    schedule_flush(app);
+}
+
+fn schedule_flush(app: &tauri::AppHandle) {
+    let mut flusher = FLUSHER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(tx) = flusher.as_ref() {
+        if tx.send(()).is_ok() {
+            return;
+        }
+    }
+    let (tx, rx) = mpsc::channel::<()>();
+    let _ = tx.send(());
+    let app = app.clone();
+    std::thread::spawn(move || {
+        // Each wake-up starts a burst; write once it has been quiet for
+        // FLUSH_DEBOUNCE, or FLUSH_MAX_DELAY after it started
+        while rx.recv().is_ok() {
+            let started = Instant::now();
+            loop {
+                let left = FLUSH_MAX_DELAY.saturating_sub(started.elapsed());
+                match rx.recv_timeout(FLUSH_DEBOUNCE.min(left)) {
+                    Ok(()) if !left.is_zero() => continue,
+                    _ => break,
+                }
+            }
+            if let Err(e) = flush(&app) {
+                eprintln!("Failed to save: {}", e);
+            }
+        }
+    });
+    *flusher = Some(tx);
+}
+
+/// Write a cached store, passing its JSON through `persisted` first.
+fn flush_cache<T: Serialize>(
+    app: &tauri::AppHandle,
+    cache: &Mutex<Cached<T>>,
+    name: &str,
+    persisted: fn(&mut serde_json::Value),
+) -> Result<(), String> {
+    // Held across the write so an older snapshot can never land last
+    let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+    if !cache.dirty {
+        return Ok(());
+    }
+    if let Some(value) = &cache.value {
+        let mut value = serde_json::to_value(value)
+            .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+        persisted(&mut value);
+        let json = serde_json::to_string_pretty(&value)
+            .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+        write_store(app, &app_data_file(app, name)?, &json)?;
+    }
+    cache.dirty = false;
+    Ok(())
+}
+
+/// Write any pending history, palette and palette version changes now.
+pub fn flush(app: &tauri::AppHandle) -> Result<(), String> {
+    let history = flush_cache(
+        app,
+        &HISTORY_CACHE,
+        HISTORY_FILE,
+        history::strip_representations,
+    );
+    let palettes = flush_cache(app, &PALETTE_CACHE, PALETTES_FILE, |_| {});
+    let versions = flush_cache(app, &VERSIONS_CACHE, PALETTE_VERSIONS_FILE, |_| {});
+    history.and(palettes).and(versions)
+}
+
+/// Flush, then forget the passphrase key along with every decrypted copy
+/// held in memory.
+pub fn lock_store(app: &tauri::AppHandle) -> Result<(), String> {
+    flush(app)?;
+    HISTORY_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .value = None;
+    PALETTE_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .value = None;
+    VERSIONS_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .value = None;
+    encryption::lock();
+    search::invalidate();
+    Ok(())
+}
+
 pub fn save_palettes(app: &tauri::AppHandle, palettes: &[Palette]) -> Result<(), String> {
-    let path = app_data_file(app, PALETTES_FILE)?;
-    let json = serde_json::to_string_pretty(palettes)
-        .map_err(|e| format!("Failed to serialize palettes: {}", e))?;
-    std::fs::write(&path, json).map_err(|e| format!("Failed to write palettes file: {}", e))
+    ensure_writable(app)?;
+    let previous = load_palettes(app);
+    // An unreadable version store is left alone rather than replaced
+    if let Ok(mut versions) = load_palette_versions(app) {
+        if palette_versions::record(&mut versions, &previous, palettes, now_millis()) {
+            store_later(app, &VERSIONS_CACHE, versions);
+        }
+    }
+    store_later(app, &PALETTE_CACHE, palettes.to_vec());
+    search::invalidate();
+    events::emit_palette_changes(app, &previous, palettes);
+    if let Err(e) = mirror_palettes(app, palettes) {
+        eprintln!("Failed to mirror palettes: {}", e);
+    }
+    auto_export::schedule(app, &previous, palettes);
+    Ok(())
+}
+
+/// Write `palettes` to the plain-text palette directory, if one is set and
+/// the store isn't encrypted.
+pub fn mirror_palettes(app: &tauri::AppHandle, palettes: &[Palette]) -> Result<(), String> {
+    let settings = load_settings(app);
+    match settings.palette_dir {
+        Some(dir) if settings.store_encryption == StoreEncryption::None => {
+            palette_dir::write_all(Path::new(&dir), palettes)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Read back palette files added, changed or removed in the palette
+/// directory (e.g. by `git pull`) and save the result. Returns every palette.
+pub fn sync_palette_dir(app: &tauri::AppHandle) -> Result<Vec<Palette>, String> {
+    let settings = load_settings(app);
+    let palettes = load_palettes(app);
+    let dir = match settings.palette_dir {
+        Some(dir) if settings.store_encryption == StoreEncryption::None => dir,
+        _ => return Ok(palettes),
+    };
+    let merged = palette_dir::sync(Path::new(&dir), &palettes)?;
+    if merged == palettes {
+        mirror_palettes(app, &merged)?;
+    } else {
+        save_palettes(app, &merged)?;
+    }
+    Ok(merged)
+}
+
+/// Earlier states of every palette, encrypted like the palette store and
+/// cached like it. Unreadable (e.g. still locked) versions aren't cached.
+pub fn load_palette_versions(app: &tauri::AppHandle) -> Result<Versions, String> {
+    let mut cache = VERSIONS_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(versions) = &cache.value {
+        return Ok(versions.clone());
+    }
+    let path = app_data_file(app, PALETTE_VERSIONS_FILE)?;
+    let versions: Versions = if path.exists() {
+        serde_json::from_str(&read_store(&path)?)
+            .map_err(|e| format!("Failed to parse palette versions file: {}", e))?
+    } else {
+        Versions::new()
+    };
+    cache.value = Some(versions.clone());
+    Ok(versions)
 }
 
 pub fn load_palettes(app: &tauri::AppHandle) -> Vec<Palette> {
-    app_data_file(app, PALETTES_FILE)
+    let mut cache = PALETTE_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(palettes) = &cache.value {
+        return palettes.clone();
+    }
+    let path = match app_data_file(app, PALETTES_FILE) {
+        Ok(path) => path,
+        Err(_) => return Vec::new(),
+    };
+    if !path.exists() {
+        cache.value = Some(Vec::new());
+        return Vec::new();
+    }
+    // Unreadable (e.g. still locked) stores aren't cached so they load once readable
+    match read_store(&path).and_then(|json| {
+        serde_json::from_str::<Vec<Palette>>(&json)
+            .map_err(|e| format!("Failed to parse palettes file: {}", e))
+    }) {
+        Ok(palettes) => {
+            cache.value = Some(palettes.clone());
+            palettes
+        }
+        Err(_) => Vec::new(),
+    }
+}
+
+pub fn save_workspaces(app: &tauri::AppHandle, workspaces: &[Workspace]) -> Result<(), String> {
+    let path = app_data_file(app, WORKSPACES_FILE)?;
+    let json = serde_json::to_string_pretty(workspaces)
+        .map_err(|e| format!("Failed to serialize workspaces: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write workspaces file: {}", e))?;
+    search::invalidate();
+    Ok(())
+}
+
+pub fn load_workspaces(app: &tauri::AppHandle) -> Vec<Workspace> {
+    app_data_file(app, WORKSPACES_FILE)
         .ok()
         .filter(|path| path.exists())
         .and_then(|path| std::fs::read_to_string(path).ok())
@@ -63,6 +590,229 @@ pub fn load_palettes(app: &tauri::AppHandle) -> Vec<Palette> {
         .unwrap_or_default()
 }
 
+/// Size of one entry of the data directory; directories count their contents.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredFile {
+    pub name: String,
+    pub bytes: u64,
+}
+
+/// What the data directory holds, for the storage settings page.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageInfo {
+    pub history_entries: usize,
+    pub palettes: usize,
+    pub workspaces: usize,
+    /// Snapshots across all palettes, including the trash
+    pub palette_versions: usize,
+    pub activity_entries: usize,
+    /// Deleted palettes that can still be restored from their snapshots
+    pub trash_palettes: usize,
+    /// Serialized size of the trash's snapshots (before encryption)
+    pub trash_bytes: u64,
+    pub files: Vec<StoredFile>,
+    pub total_bytes: u64,
+}
+
+fn disk_size(path: &Path) -> u64 {
+    // Links count as themselves, so a linked folder isn't walked or counted
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => std::fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| disk_size(&entry.path()))
+                    .sum()
+            })
+            .unwrap_or(0),
+        Ok(meta) => meta.len(),
+        Err(_) => 0,
+    }
+}
+
+pub async fn storage_info(app: &tauri::AppHandle) -> Result<StorageInfo, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    // Nothing has been saved yet when the directory is missing
+    let mut files: Vec<StoredFile> = std::fs::read_dir(&dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| StoredFile {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            bytes: disk_size(&entry.path()),
+        })
+        .collect();
+    files.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+
+    let palettes = load_palettes(app);
+    let versions = load_palette_versions(app)?;
+    let trash = palette_versions::deleted(&versions, &palettes);
+    let trash_bytes = trash
+        .iter()
+        .filter_map(|id| serde_json::to_string(&versions[*id]).ok())
+        .map(|json| json.len() as u64)
+        .sum();
+    Ok(StorageInfo {
+        history_entries: load_color_history(app).await?.len(),
+        palettes: palettes.len(),
+        workspaces: load_workspaces(app).len(),
+        palette_versions: versions.values().map(Vec::len).sum(),
+        // An unreadable log shouldn't hide the rest of the report
+        activity_entries: load_activity(app).map_or(0, |a| a.len()),
+        trash_palettes: trash.len(),
+        trash_bytes,
+        total_bytes: files.iter().map(|f| f.bytes).sum(),
+        files,
+    })
+}
+
+/// Write pending changes, cut the history journal to its newest lines and
+/// empty the trash: snapshots of deleted palettes are dropped for good. The
+/// activity log is left alone.
+pub fn compact(app: &tauri::AppHandle) -> Result<(), String> {
+    ensure_writable(app)?;
+    flush(app)?;
+    let journal = journal_path(app)?;
+    if journal.exists() {
+        let _guard = JOURNAL_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        trim_journal(&journal)?;
+    }
+    let palettes = load_palettes(app);
+    let mut versions = load_palette_versions(app)?;
+    let trash: Vec<String> = palette_versions::deleted(&versions, &palettes)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    if trash.is_empty() {
+        return Ok(());
+    }
+    for id in &trash {
+        versions.remove(id);
+    }
+    store_later(app, &VERSIONS_CACHE, versions);
+    flush(app)
+}
+
+/// Location of the user exporter config, so the UI can reveal it for editing.
+pub fn exporters_config_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app_data_file(app, EXPORTERS_FILE)
+}
+
+/// User-defined template exporters. A missing file means none; a malformed
+/// one is reported so a typo doesn't silently hide every custom format.
+pub fn load_user_exporters(app: &tauri::AppHandle) -> Result<Vec<TemplateExporter>, String> {
+    let path = exporters_config_path(app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let json = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read exporters file: {}", e))?;
+    serde_json::from_str(&json).map_err(|e| format!("Failed to parse exporters file: {}", e))
+}
+
+pub fn save_user_exporters(
+    app: &tauri::AppHandle,
+    exporters: &[TemplateExporter],
+) -> Result<(), String> {
+    let path = exporters_config_path(app)?;
+    let json = serde_json::to_string_pretty(exporters)
+        .map_err(|e| format!("Failed to serialize exporters: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write exporters file: {}", e))
+}
+
+/// Append one JSON line to the activity log, unless the store is encrypted:
+/// the log is plaintext and would leak what encryption protects.
+pub fn append_activity(app: &tauri::AppHandle, entry: &ActivityEntry) -> Result<(), String> {
+    use std::io::Write;
+
+    if load_settings(app).store_encryption != StoreEncryption::None {
+        return Ok(());
+    }
+    let path = app_data_file(app, ACTIVITY_FILE)?;
+    let mut line =
+        serde_json::to_string(entry).map_err(|e| format!("Failed to serialize activity: {}", e))?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| format!("Failed to write activity log: {}", e))
+}
+
+/// Directory holding the browser native-messaging host manifests.
+pub fn native_host_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app_data_file(app, NATIVE_HOST_DIR)
+}
+
+/// Location of the history journal, which exists once a change was journaled.
+pub fn journal_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app_data_file(app, JOURNAL_FILE)
+}
+
+/// Append one line per entry added, updated or removed between the two
+/// snapshots of history, then drop the oldest lines if the journal has grown
+/// past its cap.
+fn append_journal(
+    app: &tauri::AppHandle,
+    before: &[ColorEntry],
+    after: &[ColorEntry],
+) -> Result<(), String> {
+    use std::io::Write;
+
+    let lines = journal::changes(before, after, now_millis());
+    if lines.is_empty() {
+        return Ok(());
+    }
+    let text = journal::to_jsonl(&lines)?;
+    let path = journal_path(app)?;
+    let _guard = JOURNAL_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to write history journal: {}", e))?;
+    file.write_all(text.as_bytes())
+        .map_err(|e| format!("Failed to write history journal: {}", e))?;
+    if file.metadata().map_or(0, |m| m.len()) > journal::MAX_BYTES {
+        drop(file);
+        trim_journal(&path)?;
+    }
+    Ok(())
+}
+
+/// Keep only the newest lines of the journal. Callers hold [`JOURNAL_LOCK`].
+fn trim_journal(path: &Path) -> Result<(), String> {
+    let full = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read history journal: {}", e))?;
+    let newest = journal::newest_lines(&full);
+    if newest.len() < full.len() {
+        std::fs::write(path, newest)
+            .map_err(|e| format!("Failed to trim history journal: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Every logged activity, oldest first. Unparseable lines (e.g. a write cut
+/// short by a crash) are skipped.
+pub fn load_activity(app: &tauri::AppHandle) -> Result<Vec<ActivityEntry>, String> {
+    let path = app_data_file(app, ACTIVITY_FILE)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let text = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read activity log: {}", e))?;
+    Ok(text
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
 pub fn save_brand_kit(app: &tauri::AppHandle, kit: &BrandKit) -> Result<(), String> {
     let path = app_data_file(app, BRAND_KIT_FILE)?;
     let json = serde_json::to_string_pretty(kit)
@@ -82,27 +832,129 @@ pub async fn save_color_history(
     app: &tauri::AppHandle,
     colors: &[ColorEntry],
 ) -> Result<(), String> {
-    let path = get_storage_path(app)?;
-    let json = serde_json::to_string_pretty(colors)
-        .map_err(|e| format!("Failed to serialize colors: {}", e))?;
-
-    std::fs::write(&path, json).map_err(|e| format!("Failed to write history file: {}", e))?;
+    ensure_writable(app)?;
+    let context = HistoryContext::load(app);
+    let mut cache = HISTORY_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = cached_history(app, &mut cache)
+        .map(|colors| colors.clone())
+        .unwrap_or_default();
+    let mut colors = colors.to_vec();
+    context.prepare(&previous, &mut colors);
+    cache.value = Some(colors.clone());
+    cache.dirty = true;
+    drop(cache);
+    history_saved(app, &context, &previous, &colors);
 
     Ok(())
 }
 
+/// Load, change and save history while holding the cache, so concurrent
+/// updates can't overwrite each other's changes. Nothing is saved when
+/// `update` fails or leaves the entries as they were. Settings and the
+/// activity log are read before taking the cache, and the journal written
+/// after releasing it.
+pub async fn update_color_history<R>(
+    app: &tauri::AppHandle,
+    update: impl FnOnce(&mut Vec<ColorEntry>) -> Result<R, String>,
+) -> Result<R, String> {
+    ensure_writable(app)?;
+    let context = HistoryContext::load(app);
+    let mut cache = HISTORY_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = cached_history(app, &mut cache)?.clone();
+    let mut colors = previous.clone();
+    let result = update(&mut colors)?;
+    if colors != previous {
+        context.prepare(&previous, &mut colors);
+        cache.value = Some(colors.clone());
+        cache.dirty = true;
+        drop(cache);
+        history_saved(app, &context, &previous, &colors);
+    }
+    Ok(result)
+}
+
+/// What a history save needs from settings and the activity log, read up
+/// front so none of it happens under [`HISTORY_CACHE`].
+struct HistoryContext {
+    settings: AppSettings,
+    activity: Vec<ActivityEntry>,
+}
+
+impl HistoryContext {
+    fn load(app: &tauri::AppHandle) -> Self {
+        let settings = load_settings(app);
+        let activity = if tag_rules::needs_source(&settings.tag_rules) {
+            load_activity(app).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        HistoryContext { settings, activity }
+    }
+
+    /// Fill in derived fields of history about to replace `previous`.
+    fn prepare(&self, previous: &[ColorEntry], colors: &mut [ColorEntry]) {
+        // Entries from the frontend may carry stale or no representations
+        for entry in colors.iter_mut() {
+            entry.representations = conversion::representations(entry.rgb);
+        }
+        let rules = &self.settings.tag_rules;
+        if !rules.is_empty() {
+            tag_rules::apply_to_new(rules, previous, colors, &self.activity);
+        }
+        history::stamp_modified(previous, colors, timestamps::now());
+    }
+}
+
+/// Journal a cached history change, schedule its write and announce it.
+fn history_saved(
+    app: &tauri::AppHandle,
+    context: &HistoryContext,
+    previous: &[ColorEntry],
+    colors: &[ColorEntry],
+) {
+    let settings = &context.settings;
+    if settings.history_journal && settings.store_encryption == StoreEncryption::None {
+        if let Err(e) = append_journal(app, previous, colors) {
+            eprintln!("Failed to write history journal: {}", e);
+        }
+    }
+    schedule_flush(app);
+    search::invalidate();
+    events::emit_history_changes(app, previous, colors);
+}
+
 pub async fn load_color_history(app: &tauri::AppHandle) -> Result<Vec<ColorEntry>, String> {
+    let mut cache = HISTORY_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cached_history(app, &mut cache).cloned()
+}
+
+/// History held in `cache`, read from disk first if it isn't there yet.
+fn cached_history<'a>(
+    app: &tauri::AppHandle,
+    cache: &'a mut Cached<Vec<ColorEntry>>,
+) -> Result<&'a Vec<ColorEntry>, String> {
+    if cache.value.is_none() {
+        cache.value = Some(read_color_history(app)?);
+    }
+    Ok(cache.value.get_or_insert_with(Vec::new))
+}
+
+fn read_color_history(app: &tauri::AppHandle) -> Result<Vec<ColorEntry>, String> {
     let path = get_storage_path(app)?;
 
     if !path.exists() {
         return Ok(Vec::new());
     }
 
-    let json = std::fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read history file: {}", e))?;
+    let json = read_store(&path)?;
 
-    let colors: Vec<ColorEntry> =
+    let mut colors: Vec<ColorEntry> =
         serde_json::from_str(&json).map_err(|e| format!("Failed to parse history file: {}", e))?;
+    // Entries saved before modification tracking count as unmodified
+    for entry in &mut colors {
+        entry.modified_at.get_or_insert(entry.created_at);
+        entry.representations = conversion::representations(entry.rgb);
+    }
 
     Ok(colors)
 }
@@ -0,0 +1,121 @@
+//! User rules that tag new history entries as they are saved, e.g. "picked
+//! from figma.exe → `figma`" or "lightness above 0.9 → `background`".
+
+use crate::activity::{self, ActivityEntry};
+use crate::{color_math, ColorEntry};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Comparison {
+    Above,
+    Below,
+}
+
+impl Comparison {
+    fn holds(self, actual: f64, value: f64) -> bool {
+        match self {
+            Comparison::Above => actual > value,
+            Comparison::Below => actual < value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum RuleCondition {
+    /// Executable name of the app the color was picked from, ignoring case
+    SourceApp { app: String },
+    /// HSL lightness from 0 to 1
+    Lightness { op: Comparison, value: f64 },
+    /// HSL saturation from 0 to 1
+    Saturation { op: Comparison, value: f64 },
+    /// Hue in degrees; `from` greater than `to` wraps through red
+    HueRange { from: f64, to: f64 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagRule {
+    /// Assigned by the backend when the rule is added
+    #[serde(default)]
+    pub id: String,
+    pub tag: String,
+    pub condition: RuleCondition,
+    #[serde(default = "enabled")]
+    pub enabled: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl TagRule {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.tag.trim().is_empty() {
+            return Err("Rule tag is empty".to_string());
+        }
+        match &self.condition {
+            RuleCondition::SourceApp { app } if app.trim().is_empty() => {
+                Err("Rule app name is empty".to_string())
+            }
+            RuleCondition::Lightness { value, .. } | RuleCondition::Saturation { value, .. }
+                if !(0.0..=1.0).contains(value) =>
+            {
+                Err(format!("Rule value must be between 0 and 1, got {value}"))
+            }
+            RuleCondition::HueRange { from, to }
+                if !(0.0..=360.0).contains(from) || !(0.0..=360.0).contains(to) =>
+            {
+                Err("Rule hues must be between 0 and 360".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn matches(&self, entry: &ColorEntry, source: Option<&str>) -> bool {
+        let hsl = color_math::rgb_to_hsl(entry.rgb);
+        match &self.condition {
+            RuleCondition::SourceApp { app } => {
+                source.is_some_and(|s| s.eq_ignore_ascii_case(app.trim()))
+            }
+            RuleCondition::Lightness { op, value } => op.holds(hsl.l / 100.0, *value),
+            RuleCondition::Saturation { op, value } => op.holds(hsl.s / 100.0, *value),
+            RuleCondition::HueRange { from, to } if from <= to => (*from..=*to).contains(&hsl.h),
+            RuleCondition::HueRange { from, to } => hsl.h >= *from || hsl.h <= *to,
+        }
+    }
+}
+
+/// Run the enabled `rules` over entries in `entries` that aren't in
+/// `previous`, adding each matching rule's tag once. Source apps are looked up
+/// in `activity`.
+pub fn apply_to_new(
+    rules: &[TagRule],
+    previous: &[ColorEntry],
+    entries: &mut [ColorEntry],
+    activity: &[ActivityEntry],
+) {
+    let known: HashSet<&str> = previous.iter().map(|p| p.id.as_str()).collect();
+    for entry in entries
+        .iter_mut()
+        .filter(|e| !known.contains(e.id.as_str()))
+    {
+        let created = entry.created_at.timestamp_millis().max(0) as u64;
+        let source = activity::pick_source(activity, &entry.hex, created);
+        for rule in rules.iter().filter(|r| r.enabled) {
+            let tag = rule.tag.trim();
+            if rule.matches(entry, source) && !entry.tags.iter().any(|t| t == tag) {
+                entry.tags.push(tag.to_string());
+            }
+        }
+    }
+}
+
+/// Whether any enabled rule needs the activity log to be evaluated.
+pub fn needs_source(rules: &[TagRule]) -> bool {
+    rules
+        .iter()
+        .any(|r| r.enabled && matches!(r.condition, RuleCondition::SourceApp { .. }))
+}
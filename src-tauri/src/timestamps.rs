@@ -0,0 +1,59 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{de, Deserialize, Deserializer, Serializer};
+
+/// Current time, truncated to milliseconds like everything we store.
+pub fn now() -> DateTime<Utc> {
+    from_millis(crate::storage::now_millis())
+}
+
+pub fn from_millis(millis: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(millis as i64).unwrap_or_default()
+}
+
+/// RFC 3339 in UTC with millisecond precision, e.g. `2024-05-01T09:30:00.000Z`.
+pub fn serialize<S: Serializer>(at: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&at.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawTimestamp {
+    /// Milliseconds since the epoch, the format before RFC 3339
+    Millis(u64),
+    Text(String),
+}
+
+/// Accept RFC 3339 with any offset (normalized to UTC) or legacy epoch
+/// milliseconds, so old stores load unchanged.
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DateTime<Utc>, D::Error> {
+    match RawTimestamp::deserialize(deserializer)? {
+        RawTimestamp::Millis(millis) => Ok(from_millis(millis)),
+        RawTimestamp::Text(text) => DateTime::parse_from_rfc3339(&text)
+            .map(|at| at.with_timezone(&Utc))
+            .map_err(de::Error::custom),
+    }
+}
+
+/// [`serialize`]/[`deserialize`] for optional timestamps.
+pub mod option {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        at: &Option<DateTime<Utc>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match at {
+            Some(at) => super::serialize(at, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DateTime<Utc>>, D::Error> {
+        #[derive(Deserialize)]
+        struct Wrapped(#[serde(deserialize_with = "super::deserialize")] DateTime<Utc>);
+        Ok(Option::<Wrapped>::deserialize(deserializer)?.map(|Wrapped(at)| at))
+    }
+}
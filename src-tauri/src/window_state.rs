@@ -0,0 +1,126 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::Manager;
+
+/// Moves and resizes arrive continuously while dragging; save once the
+/// window has been still this long.
+const SAVE_DELAY: Duration = Duration::from_millis(500);
+/// How much of the window's top edge must land on a monitor for a saved
+/// position to be reused, so it can still be grabbed and dragged.
+const MIN_VISIBLE_WIDTH: i64 = 100;
+const MIN_VISIBLE_HEIGHT: i64 = 40;
+
+static SAVE_PENDING: AtomicBool = AtomicBool::new(false);
+static LAST_CHANGE: AtomicU64 = AtomicU64::new(0);
+
+/// Outer position and inner size of a window, in physical pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+fn capture(window: &tauri::WebviewWindow) -> Option<WindowGeometry> {
+    // A minimized window reports a parking position far off-screen
+    if window.is_minimized().unwrap_or(false) {
+        return None;
+    }
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    Some(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Remember where the main window is.
+pub fn save(app: &tauri::AppHandle) -> Result<(), String> {
+    let Some(geometry) = app.get_webview_window("main").and_then(|w| capture(&w)) else {
+        return Ok(());
+    };
+    let mut settings = storage::load_settings(app);
+    if settings.main_window == Some(geometry) {
+        return Ok(());
+    }
+    settings.main_window = Some(geometry);
+    storage::save_settings(app, &settings)
+}
+
+/// Record a move or resize of the main window and save it once the window
+/// settles. Only one waiting thread exists at a time.
+pub fn track(app: &tauri::AppHandle) {
+    LAST_CHANGE.store(storage::now_millis(), Ordering::SeqCst);
+    if SAVE_PENDING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        loop {
+            std::thread::sleep(SAVE_DELAY);
+            let quiet = storage::now_millis().saturating_sub(LAST_CHANGE.load(Ordering::SeqCst));
+            if quiet >= SAVE_DELAY.as_millis() as u64 {
+                break;
+            }
+        }
+        SAVE_PENDING.store(false, Ordering::SeqCst);
+        if let Err(e) = save(&app) {
+            eprintln!("Failed to save window position: {}", e);
+        }
+    });
+}
+
+/// Monitor rectangle as `(x, y, width, height)` in physical pixels.
+type Area = (i32, i32, u32, u32);
+
+/// Whether enough of the window's title area overlaps one of `monitors`.
+fn reachable(geometry: &WindowGeometry, monitors: &[Area]) -> bool {
+    let (left, top) = (geometry.x as i64, geometry.y as i64);
+    let right = left + geometry.width as i64;
+    monitors.iter().any(|&(mx, my, mw, mh)| {
+        let (mx, my) = (mx as i64, my as i64);
+        let overlap_w = right.min(mx + mw as i64) - left.max(mx);
+        // Only the strip the user drags by matters
+        let strip_bottom = top + MIN_VISIBLE_HEIGHT;
+        let overlap_h = strip_bottom.min(my + mh as i64) - top.max(my);
+        overlap_w >= MIN_VISIBLE_WIDTH.min(geometry.width as i64) && overlap_h >= MIN_VISIBLE_HEIGHT
+    })
+}
+
+/// Put the main window back where it was last saved. A saved size is
+/// shrunk to fit the largest monitor; a position that is no longer on any
+/// monitor (unplugged display, changed layout) is dropped, leaving the
+/// window centered where it opened.
+pub fn restore(app: &tauri::AppHandle) {
+    let (Some(window), Some(mut geometry)) = (
+        app.get_webview_window("main"),
+        storage::load_settings(app).main_window,
+    ) else {
+        return;
+    };
+    let monitors: Vec<Area> = window
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(|m| {
+            let (pos, size) = (m.position(), m.size());
+            (pos.x, pos.y, size.width, size.height)
+        })
+        .collect();
+    if let Some(&(_, _, w, h)) = monitors
+        .iter()
+        .max_by_key(|(_, _, w, h)| *w as u64 * *h as u64)
+    {
+        geometry.width = geometry.width.min(w);
+        geometry.height = geometry.height.min(h);
+    }
+    let _ = window.set_size(tauri::PhysicalSize::new(geometry.width, geometry.height));
+    if reachable(&geometry, &monitors) {
+        let _ = window.set_position(tauri::PhysicalPosition::new(geometry.x, geometry.y));
+    }
+}
@@ -14,13 +14,21 @@ import { BrandKit } from "./components/BrandKit";
 import { Onboarding } from "./components/Onboarding";
 import { UpdatePrompt } from "./components/UpdatePrompt";
 import { useColorHistory } from "./hooks/useColorHistory";
-import { ColorInfo, ColorEntry, ColorFormat } from "./types/color";
+import {
+  ColorInfo,
+  ColorEntry,
+  ColorConversion,
+  ColorFormat,
+  FocusAssistState,
+  Oklch,
+  PickError,
+} from "./types/color";
 import { formatColor, getContrastColor } from "./utils/colorConvert";
 
 const appWindow = getCurrentWindow();
 
 function App() {
-  type Swatch = { hex: string; rgb: [number, number, number] };
+  type Swatch = { hex: string; rgb: [number, number, number]; oklch?: Oklch };
   const [displayColor, setDisplayColor] = useState<Swatch | null>(null);
   // The color actually picked; shades are derived from this and it stays
   // restorable even after you preview a shade.
@@ -29,6 +37,7 @@ function App() {
     () => (localStorage.getItem("pixnib-default-format") as ColorFormat) || "hex"
   );
   const [copied, setCopied] = useState(false);
+  const [toastText, setToastText] = useState("Copied to clipboard");
   const [copiedFormat, setCopiedFormat] = useState<string | null>(null);
   const [isMaximized, setIsMaximized] = useState(false);
   const [shortcutLabel, setShortcutLabel] = useState("");
@@ -69,6 +78,60 @@ function App() {
   const handleFormatChange = useCallback((f: ColorFormat) => {
     setFormat(f);
     localStorage.setItem("pixnib-default-format", f);
+    // Keep the backend (copy-last / cycle-format shortcuts) in step
+    if (f === "hex" || f === "rgb" || f === "hsl" || f === "oklch") {
+      invoke("set_copy_format", { format: f }).catch(() => {});
+    }
+  }, []);
+
+  // Color names (labels, loupe, exports) follow the UI language
+  useEffect(() => {
+    invoke("set_color_name_locale", { locale: navigator.language }).catch(
+      () => {}
+    );
+  }, []);
+
+  // A shortcut or system picker pick failed with no command to return to
+  useEffect(() => {
+    const unlisten = listen<PickError>("pick-failed", (event) => {
+      const error = event.payload;
+      setToastText(
+        error.kind === "elevated"
+          ? `${error.app ?? "This window"} runs as administrator; restart Pixnib as administrator to pick from it`
+          : `Pick failed: ${error.message}`
+      );
+      setCopied(true);
+      setTimeout(() => {
+        setCopied(false);
+        setToastText("Copied to clipboard");
+      }, 4000);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  // Cycle-format shortcut: follow the backend's new format and toast it
+  useEffect(() => {
+    const unlisten = listen<{ format: string; text: string | null }>(
+      "copy-format-changed",
+      (event) => {
+        const f = event.payload.format;
+        if (f === "hex" || f === "rgb" || f === "hsl" || f === "oklch") {
+          setFormat(f);
+          localStorage.setItem("pixnib-default-format", f);
+        }
+        setToastText(`Copy format: ${f.toUpperCase()}`);
+        setCopied(true);
+        setTimeout(() => {
+          setCopied(false);
+          setToastText("Copied to clipboard");
+        }, 2000);
+      }
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
   }, []);
   const {
     colors,
@@ -83,13 +146,29 @@ function App() {
   useEffect(() => {
     const unlistenPicked = listen<ColorInfo>("color-picked", async (event) => {
       const color = event.payload;
-      setDisplayColor({ hex: color.hex, rgb: color.rgb });
-      setOriginalColor({ hex: color.hex, rgb: color.rgb });
+      const swatch = { hex: color.hex, rgb: color.rgb, oklch: color.oklch };
+      setDisplayColor(swatch);
+      setOriginalColor(swatch);
       await addColor(color);
 
       // Auto-copy to clipboard
-      const text = formatColor(color.rgb, format);
+      const text = formatColor(color, format);
       await writeText(text);
+      // Focus Assist is holding notifications back; stay quiet too
+      const focus = await invoke<FocusAssistState>(
+        "get_focus_assist_state"
+      ).catch(() => null);
+      if (focus?.muted) return;
+      // Likewise while a fullscreen app is in front, if the user asked for that
+      const fullscreenQuiet = await invoke<boolean>("is_fullscreen_quiet").catch(() => false);
+      if (fullscreenQuiet) return;
+      setToastText(
+        color.colorShift === "nightLight"
+          ? "Copied — Night Light is on, colors on screen look warmer"
+          : color.colorShift === "gammaRamp"
+            ? "Copied — a screen tint is active, colors may look shifted"
+            : "Copied to clipboard"
+      );
       setCopied(true);
       setTimeout(() => setCopied(false), 3000);
     });
@@ -131,14 +210,19 @@ function App() {
   }, []);
 
   const handleSelectFromHistory = useCallback((color: ColorEntry) => {
-    setDisplayColor({ hex: color.hex, rgb: color.rgb });
-    setOriginalColor({ hex: color.hex, rgb: color.rgb });
+    const swatch = { hex: color.hex, rgb: color.rgb, oklch: color.oklch };
+    setDisplayColor(swatch);
+    setOriginalColor(swatch);
   }, []);
 
   // Preview a generated shade as the current color without losing the original
   const handleApplyShade = useCallback(async (hex: string, rgb: [number, number, number]) => {
     setDisplayColor({ hex, rgb });
     await writeText(hex);
+    // Shades are generated here, so ask the backend for their OKLCH
+    invoke<ColorConversion>("convert_color", { color: hex })
+      .then((c) => setDisplayColor((d) => (d?.hex === hex ? { ...d, oklch: c.oklch } : d)))
+      .catch((err) => console.error("Failed to convert shade:", err));
     setCopied(true);
     setTimeout(() => setCopied(false), 2000);
   }, []);
@@ -149,7 +233,7 @@ function App() {
 
   const handleCopyFormat = useCallback(async (f: ColorFormat) => {
     if (displayColor) {
-      const text = formatColor(displayColor.rgb, f);
+      const text = formatColor(displayColor, f);
       await writeText(text);
       setCopiedFormat(f);
       setTimeout(() => setCopiedFormat(null), 1500);
@@ -368,7 +452,7 @@ function App() {
             {/* Tab panels */}
             {detailTab === "values" && (
               <div className="bg-[var(--bg-surface)] rounded-xl border border-[var(--border)] divide-y divide-[var(--border)]">
-                {(["hex", "rgb", "hsl"] as ColorFormat[]).map((f) => (
+                {(["hex", "rgb", "hsl", "oklch"] as ColorFormat[]).map((f) => (
                   <button
                     key={f}
                     onClick={() => handleCopyFormat(f)}
@@ -376,7 +460,7 @@ function App() {
                   >
                     <span className="text-[var(--text-muted)] font-medium uppercase text-[10px] w-8 shrink-0 text-left">{f}</span>
                     <span className="font-mono text-[var(--text-primary)] flex-1 min-w-0 truncate text-center">
-                      {formatColor(displayColor.rgb, f)}
+                      {formatColor(displayColor, f)}
                     </span>
                     {copiedFormat === f ? (
                       <svg className="w-3.5 h-3.5 text-[var(--accent)]" fill="none" viewBox="0 0 24 24" stroke="currentColor" strokeWidth={2.5}>
@@ -424,7 +508,7 @@ function App() {
       {copied && (
         <div className="fixed top-4 right-4 bg-[var(--bg-elevated)] border border-[var(--accent-border)] text-[var(--text-primary)] pl-3 pr-4 py-2.5 rounded-lg shadow-lg shadow-black/20 text-[12px] font-medium animate-fade-in z-50 flex items-center gap-2">
           <div className="w-1.5 h-1.5 rounded-full bg-[var(--accent)]" />
-          Copied to clipboard
+          {toastText}
         </div>
       )}
 
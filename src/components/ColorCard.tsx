@@ -16,7 +16,7 @@ export function ColorCard({ color, onDelete, onSelect }: ColorCardProps) {
   const contrastColor = getContrastColor(color.rgb);
 
   const handleCopy = async (format: ColorFormat = "hex") => {
-    const text = formatColor(color.rgb, format);
+    const text = formatColor(color, format);
     await writeText(text);
     setCopied(true);
     setTimeout(() => setCopied(false), 1000);
@@ -74,7 +74,7 @@ export function ColorCard({ color, onDelete, onSelect }: ColorCardProps) {
             onClick={() => setShowMenu(false)}
           />
           <div className="absolute top-full left-0 mt-1.5 bg-[var(--bg-elevated)] rounded-lg shadow-xl shadow-black/30 z-50 py-1 min-w-[160px] border border-[var(--border-hover)] text-[11px] animate-slide-in">
-            {(["hex", "rgb", "hsl"] as ColorFormat[]).map((f) => (
+            {(["hex", "rgb", "hsl", "oklch"] as ColorFormat[]).map((f) => (
               <button
                 key={f}
                 onClick={() => handleCopy(f)}
@@ -82,7 +82,7 @@ export function ColorCard({ color, onDelete, onSelect }: ColorCardProps) {
               >
                 <span>Copy {f.toUpperCase()}</span>
                 <span className="font-mono text-[10px] text-[var(--text-muted)]">
-                  {formatColor(color.rgb, f)}
+                  {formatColor(color, f)}
                 </span>
               </button>
             ))}
@@ -1,34 +1,37 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { LoupeData } from "../types/color";
-
-const GRID = 11;
-const POLL_MS = 50;
+import { LoupeData, LoupeSettings } from "../types/color";
 
 /**
  * Content of the small always-on-top loupe window that follows the cursor
  * during pick mode. The backend moves the window; this component only polls
  * the pixel grid and renders it. Polling runs solely between
- * pick-mode-started and pick-mode-stopped/color-picked events.
+ * pick-mode-started and pick-mode-stopped/color-picked events, at the
+ * refresh rate from the saved loupe settings.
  */
 export function Loupe() {
   const [data, setData] = useState<LoupeData | null>(null);
+  const [gridLines, setGridLines] = useState(true);
 
   useEffect(() => {
     // The window itself is transparent so our rounded corners show
     document.body.style.background = "transparent";
 
     let timer: number | null = null;
-    const start = () => {
+    const start = async () => {
+      if (timer !== null) return;
+      const settings = await invoke<LoupeSettings>("get_loupe_settings");
+      setGridLines(settings.gridLines);
       if (timer !== null) return;
       timer = window.setInterval(async () => {
         try {
-          setData(await invoke<LoupeData>("capture_loupe", { grid: GRID }));
+          // No grid: the backend sizes it from the saved settings
+          setData(await invoke<LoupeData>("capture_loupe"));
         } catch {
           // Transient capture failure (e.g. secure desktop) — keep last frame
         }
-      }, POLL_MS);
+      }, settings.refreshMs);
     };
     const stop = () => {
       if (timer !== null) {
@@ -49,15 +52,16 @@ export function Loupe() {
     };
   }, []);
 
-  const center = Math.floor((GRID * GRID) / 2);
+  const grid = data ? Math.round(Math.sqrt(data.colors.length)) : 11;
+  const center = Math.floor((grid * grid) / 2);
 
   return (
     <div className="h-screen w-screen overflow-hidden rounded-xl border border-[var(--border-hover)] bg-[var(--bg-base)] flex flex-col select-none">
       <div
-        className="grid flex-1 gap-px bg-[var(--bg-base)] p-1"
-        style={{ gridTemplateColumns: `repeat(${GRID}, 1fr)` }}
+        className={`grid flex-1 ${gridLines ? "gap-px" : ""} bg-[var(--bg-base)] p-1`}
+        style={{ gridTemplateColumns: `repeat(${grid}, 1fr)` }}
       >
-        {(data?.colors ?? Array(GRID * GRID).fill("#131316")).map((hex, i) => (
+        {(data?.colors ?? Array(grid * grid).fill("#131316")).map((hex, i) => (
           <div
             key={i}
             className={i === center ? "outline outline-1 outline-white z-10 rounded-[1px]" : ""}
@@ -73,6 +77,14 @@ export function Loupe() {
         <span className="font-mono text-[11px] text-[var(--text-primary)]">
           {data?.hex ?? "…"}
         </span>
+        {data?.name && (
+          <span className="truncate text-[10px] text-[var(--text-muted)]">{data.name}</span>
+        )}
+        {data?.deltaE !== undefined && (
+          <span className="font-mono text-[10px] text-[var(--text-muted)]">
+            ΔE {data.deltaE.toFixed(1)}
+          </span>
+        )}
       </div>
     </div>
   );
@@ -0,0 +1,45 @@
+import { useEffect, useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { ColorEntry } from "../types/color";
+
+/**
+ * Recent-colors popup opened by the quick palette shortcut. The backend
+ * creates, positions, shows and hides the window and sends the colors with
+ * quick-palette-opened; clicking a swatch copies it and closes the popup.
+ */
+export function QuickPalette() {
+  const [colors, setColors] = useState<ColorEntry[]>([]);
+
+  useEffect(() => {
+    document.body.style.background = "transparent";
+    const unlisten = listen<{ colors: ColorEntry[] }>("quick-palette-opened", (e) =>
+      setColors(e.payload.colors)
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  return (
+    <div className="h-screen w-screen overflow-hidden rounded-xl border border-[var(--border-hover)] bg-[var(--bg-base)] p-2 select-none">
+      {colors.length === 0 ? (
+        <div className="h-full flex items-center justify-center text-[12px] text-[var(--text-muted)]">
+          No colors picked yet
+        </div>
+      ) : (
+        <div className="grid grid-cols-6 gap-1.5">
+          {colors.map((entry) => (
+            <button
+              key={entry.id}
+              title={entry.label ? `${entry.label} ${entry.hex}` : entry.hex}
+              className="aspect-square rounded-md border border-[var(--border-hover)] hover:scale-105 transition-transform duration-100"
+              style={{ backgroundColor: entry.hex }}
+              onClick={() => invoke("quick_palette_copy", { id: entry.id }).catch(() => {})}
+            />
+          ))}
+        </div>
+      )}
+    </div>
+  );
+}
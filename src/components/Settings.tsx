@@ -12,7 +12,7 @@ interface SettingsProps {
   onReplayTour: () => void;
 }
 
-const COPY_FORMATS: ColorFormat[] = ["hex", "rgb", "hsl"];
+const COPY_FORMATS: ColorFormat[] = ["hex", "rgb", "hsl", "oklch"];
 
 /** Slide-over settings panel: pick shortcut, launch-at-startup, default copy format. */
 export function Settings({
@@ -1,8 +1,8 @@
 import { useState, useEffect, useCallback, useRef } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { ColorEntry, ColorInfo } from "../types/color";
-import { generateId } from "../utils/colorConvert";
 
+// Matches the backend limit in add_history_entry
 const MAX_HISTORY = 100;
 
 export function useColorHistory() {
@@ -33,9 +33,12 @@ export function useColorHistory() {
     loadHistory();
   }, []);
 
-  const saveHistory = useCallback(async (newColors: ColorEntry[]) => {
+  // Edits go through targeted commands so the backend applies them to its
+  // own copy; saving this whole list would overwrite usage counts and other
+  // backend-owned fields with whatever this hook last loaded.
+  const runUpdate = useCallback(async (command: string, args: Record<string, unknown>) => {
     try {
-      await invoke("save_color_history", { colors: newColors });
+      await invoke(command, args);
       setError(null);
     } catch (err) {
       console.error("Failed to save history:", err);
@@ -43,53 +46,42 @@ export function useColorHistory() {
     }
   }, []);
 
-  const addColor = useCallback(
-    async (colorInfo: ColorInfo) => {
-      const newEntry: ColorEntry = {
-        id: generateId(),
+  const addColor = useCallback(async (colorInfo: ColorInfo) => {
+    try {
+      const entry = await invoke<ColorEntry>("add_history_entry", {
         hex: colorInfo.hex,
-        rgb: colorInfo.rgb,
-        timestamp: Date.now(),
-      };
-
-      setColors((prevColors) => {
-        const newColors = [newEntry, ...prevColors].slice(0, MAX_HISTORY);
-        // Save in background
-        saveHistory(newColors);
-        return newColors;
+        label: colorInfo.label,
       });
-    },
-    [saveHistory]
-  );
+      setColors((prevColors) => [entry, ...prevColors].slice(0, MAX_HISTORY));
+      setError(null);
+    } catch (err) {
+      console.error("Failed to save history:", err);
+      setError(err instanceof Error ? err.message : String(err));
+    }
+  }, []);
 
   const removeColor = useCallback(
     async (id: string) => {
-      setColors((prevColors) => {
-        const newColors = prevColors.filter((c) => c.id !== id);
-        saveHistory(newColors);
-        return newColors;
-      });
+      setColors((prevColors) => prevColors.filter((c) => c.id !== id));
+      await runUpdate("remove_history_entries", { ids: [id] });
     },
-    [saveHistory]
+    [runUpdate]
   );
 
   const updateLabel = useCallback(
     async (id: string, label: string) => {
-      setColors((prevColors) => {
-        const newColors = prevColors.map((c) =>
-          c.id === id ? { ...c, label: label || undefined } : c
-        );
-        saveHistory(newColors);
-        return newColors;
-      });
+      setColors((prevColors) =>
+        prevColors.map((c) => (c.id === id ? { ...c, label: label || undefined } : c))
+      );
+      await runUpdate("set_entry_label", { id, label });
     },
-    [saveHistory]
+    [runUpdate]
   );
 
   const clearHistory = useCallback(async () => {
     setColors([]);
-    await saveHistory([]);
-  }, [saveHistory]);
+    await runUpdate("clear_color_history", {});
+  }, [runUpdate]);
 
   const reload = useCallback(async () => {
     try {
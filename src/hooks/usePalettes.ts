@@ -34,9 +34,16 @@ function subscribe(listener: () => void) {
 function setPalettes(next: Palette[]) {
   palettes = next;
   emit();
-  invoke("save_palettes", { palettes: next }).catch((err) =>
-    console.error("Failed to save palettes:", err)
-  );
+  invoke("save_palettes", { palettes: next }).catch((err) => {
+    console.error("Failed to save palettes:", err);
+    // A locked palette refused the edit: drop the optimistic update
+    if (err?.kind === "locked") {
+      invoke<Palette[]>("load_palettes").then((p) => {
+        palettes = p;
+        emit();
+      });
+    }
+  });
 }
 
 export function usePalettes() {
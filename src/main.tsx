@@ -3,6 +3,7 @@ import ReactDOM from "react-dom/client";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import App from "./App";
 import { Loupe } from "./components/Loupe";
+import { QuickPalette } from "./components/QuickPalette";
 import { Region } from "./components/Region";
 import "./index.css";
 
@@ -15,6 +16,14 @@ const label = getCurrentWindow().label;
 
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
-    {label === "loupe" ? <Loupe /> : label === "region" ? <Region /> : <App />}
+    {label === "loupe" ? (
+      <Loupe />
+    ) : label === "region" ? (
+      <Region />
+    ) : label === "quick" ? (
+      <QuickPalette />
+    ) : (
+      <App />
+    )}
   </React.StrictMode>
 );
@@ -3,20 +3,102 @@ export interface ColorInfo {
   rgb: [number, number, number];
   x: number;
   y: number;
+  label?: string; // backend auto-label, when enabled
+  deltaE?: number; // CIEDE2000 from the reference color, when one is set
+  whitePoint?: { seen: string; encoded: string }; // through a night-light gamma ramp, and as captured
+  colorShift?: "nightLight" | "gammaRamp"; // screen tint active, pick may look different
+  hsl: Hsl;
+  hsv: Hsv;
+  cmyk: Cmyk;
+  oklch: Oklch;
 }
 
 export interface ColorEntry {
   id: string;
   hex: string;
   rgb: [number, number, number];
-  timestamp: number;
+  createdAt: string; // RFC 3339
+  modifiedAt?: string; // RFC 3339, stamped by the backend
   label?: string;
+  tags?: string[];
+  usageCount?: number;
+  usage?: { copy: number; paste: number; export: number };
+  lastUsed?: number; // ms since epoch
+  pinned?: boolean; // exempt from age-based pruning
+  hsl?: Hsl; // filled in by the backend, recomputed on save
+  hsv?: Hsv;
+  cmyk?: Cmyk;
+  oklch?: Oklch;
+}
+
+// Hue in degrees, every other channel in percent
+export interface Hsl {
+  h: number;
+  s: number;
+  l: number;
+}
+
+export interface Hsv {
+  h: number;
+  s: number;
+  v: number;
+}
+
+export interface Cmyk {
+  c: number;
+  m: number;
+  y: number;
+  k: number;
+}
+
+// Lightness 0-1, hue in degrees
+export interface Oklch {
+  l: number;
+  c: number;
+  h: number;
+}
+
+export interface Oklab {
+  l: number;
+  a: number;
+  b: number;
+}
+
+// Result of `convert_color`
+export interface ColorConversion {
+  hex: string;
+  rgb: [number, number, number];
+  hsl: Hsl;
+  hsv: Hsv;
+  cmyk: Cmyk;
+  oklch: Oklch;
+  oklab: Oklab;
+  clipped: boolean; // OKLab/OKLCH input outside sRGB, hex and rgb clipped
 }
 
 export interface Palette {
   id: string;
   name: string;
   colors: string[]; // hex values
+  darkVariantOf?: string; // id of the light palette a derived dark palette came from
+  workspaceId?: string; // unset = default workspace
+  locked?: boolean; // read-only until unlocked via set_palette_locked
+  export?: PaletteExport; // defaults for export_palette_default
+}
+
+export interface PaletteExport {
+  exporter?: string; // exporter registry id
+  directory?: string;
+  naming?: "kebab" | "snake" | "camel" | "original";
+}
+
+export interface Workspace {
+  id: string;
+  name: string;
+  exportDefaults: {
+    format?: string;
+    directory?: string;
+  };
 }
 
 export interface LoupeData {
@@ -24,9 +106,21 @@ export interface LoupeData {
   hex: string; // center pixel
   x: number;
   y: number;
+  flipLeft: boolean; // loupe placed left of the cursor (right edge)
+  flipUp: boolean; // loupe placed above the cursor (bottom edge)
+  deltaE?: number; // center pixel vs the reference color, when one is set
+  name: string; // nearest CSS name of the center pixel
+  oklch?: Oklch;
 }
 
-export type ColorFormat = "hex" | "rgb" | "rgba" | "hsl" | "css-var";
+export interface LoupeSettings {
+  size: number; // loupe window width, logical px
+  zoom: number; // logical px per captured pixel
+  gridLines: boolean;
+  refreshMs: number;
+}
+
+export type ColorFormat = "hex" | "rgb" | "rgba" | "hsl" | "oklch" | "css-var";
 
 export interface BrandColor {
   role: string; // primary | secondary | accent | neutral | background
@@ -40,3 +134,47 @@ export interface BrandKit {
   bodyFont: string;
   notes: string;
 }
+
+export interface FocusAssistState {
+  state: "off" | "focusSession" | "quietTime" | "presentation";
+  muted: boolean; // toasts and announcements are held back
+}
+
+export type CvdType = "protanopia" | "deuteranopia" | "tritanopia";
+
+export interface CvdColorChange {
+  index: number;
+  from: string;
+  to: string;
+  lightness: number; // OKLCH lightness change
+  hue: number; // degrees
+  deltaE: number; // CIEDE2000 from the original
+}
+
+export interface CvdReport {
+  cvd: CvdType;
+  minDistance: number;
+  colors: string[];
+  changes: CvdColorChange[];
+  unresolved: [number, number][]; // pairs still too close
+  closestBefore: number | null;
+  closestAfter: number | null;
+}
+
+export interface WindowColors {
+  app: string | null; // executable name, or window class on X11
+  title: string;
+  dominant: string;
+  colors: string[]; // most common first
+}
+
+// Payload of `ambient-color`, emitted while the ambient stream is on
+export interface AmbientColor {
+  hex: string;
+  rgb: [number, number, number];
+}
+
+// Error of pick commands and payload of `pick-failed`
+export type PickError =
+  | { kind: "elevated"; app: string | null }
+  | { kind: "other"; message: string };
@@ -1,4 +1,4 @@
-import { ColorFormat } from "../types/color";
+import { ColorFormat, Oklch } from "../types/color";
 
 export function rgbToHsl(
   r: number,
@@ -69,11 +69,14 @@ export function generateScale(
   });
 }
 
-export function formatColor(
-  rgb: [number, number, number],
-  format: ColorFormat
-): string {
-  const [r, g, b] = rgb;
+/** A color to format; `oklch` comes from the backend, never computed here. */
+export interface FormattableColor {
+  rgb: [number, number, number];
+  oklch?: Oklch;
+}
+
+export function formatColor(color: FormattableColor, format: ColorFormat): string {
+  const [r, g, b] = color.rgb;
   const hex = `#${r.toString(16).padStart(2, "0")}${g.toString(16).padStart(2, "0")}${b.toString(16).padStart(2, "0")}`.toUpperCase();
 
   switch (format) {
@@ -87,6 +90,12 @@ export function formatColor(
       const [h, s, l] = rgbToHsl(r, g, b);
       return `hsl(${h}, ${s}%, ${l}%)`;
     }
+    case "oklch": {
+      // Same text as the backend's copy format; grays get hue 0
+      if (!color.oklch) return hex;
+      const { l, c, h } = color.oklch;
+      return `oklch(${(l * 100).toFixed(1)}% ${c.toFixed(3)} ${(c < 0.0005 ? 0 : h).toFixed(1)})`;
+    }
     case "css-var":
       return `--color: ${hex};`;
     default: